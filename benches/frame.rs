@@ -0,0 +1,312 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use entity_table::{ComponentTable, EntityAllocator};
+use entity_table_realtime::scheduler::{
+    process_table_frame, HeapScheduler, IndexedScheduler, LinearScan, Scheduler,
+};
+use entity_table_realtime::{
+    declare_realtime_entity_module, process_entity_frame, ContextContainsRealtimeComponents,
+    Entities, Entity, RealtimeComponent, RealtimeComponentApplyEvent, RealtimeComponentTable,
+    ScheduledRealtimeComponent,
+};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Periodic {
+    period: Duration,
+}
+
+impl RealtimeComponent for Periodic {
+    type Event = ();
+
+    fn tick(&mut self) -> (Self::Event, Duration) {
+        ((), self.period)
+    }
+}
+
+impl RealtimeComponentApplyEvent<Context> for Periodic {
+    fn apply_event(_: (), _: Entity, context: &mut Context) {
+        context.num_events += 1;
+    }
+}
+
+declare_realtime_entity_module! {
+    components[Context] {
+        a: Periodic,
+        b: Periodic,
+        c: Periodic,
+        d: Periodic,
+    }
+}
+
+pub struct Context {
+    components: components::RealtimeComponents,
+    table: RealtimeComponentTable<Periodic>,
+    entities: ComponentTable<()>,
+    num_events: u64,
+}
+
+impl ContextContainsRealtimeComponents for Context {
+    type Components = components::RealtimeComponents;
+    fn components_mut(&mut self) -> &mut Self::Components {
+        &mut self.components
+    }
+    fn realtime_entities(&self) -> Entities<'_> {
+        self.entities.entities()
+    }
+}
+
+fn context(num_entities: usize) -> (Context, Vec<Entity>) {
+    let mut entity_allocator = EntityAllocator::default();
+    let mut context = Context {
+        components: Default::default(),
+        table: Default::default(),
+        entities: Default::default(),
+        num_events: 0,
+    };
+    let mut entities = Vec::new();
+    for i in 0..num_entities {
+        let entity = entity_allocator.alloc();
+        let period = |ms| Periodic {
+            period: Duration::from_millis(ms + (i as u64 % 7)),
+        };
+        context.entities.insert(entity, ());
+        context.components.a.insert(entity, period(5));
+        context.components.b.insert(entity, period(11));
+        context.components.c.insert(entity, period(17));
+        context.components.d.insert(entity, period(23));
+        entities.push(entity);
+    }
+    (context, entities)
+}
+
+/// A context whose single table has a component for each of `num_entities` entities, with
+/// periods spread between 5ms and 25ms
+fn table_context(num_entities: usize) -> Context {
+    table_context_with_periods(num_entities, PeriodDistribution::Spread)
+}
+
+/// How the periods of the components in a benchmarked table are chosen
+#[derive(Clone, Copy)]
+enum PeriodDistribution {
+    /// Every component ticks every 16ms, so they're all due at once
+    Uniform,
+    /// Periods spread between 5ms and 25ms
+    Spread,
+    /// Most components tick every second and one in 16 ticks every 2ms
+    LongTail,
+}
+
+impl PeriodDistribution {
+    const ALL: [Self; 3] = [Self::Uniform, Self::Spread, Self::LongTail];
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Uniform => "uniform",
+            Self::Spread => "spread",
+            Self::LongTail => "long_tail",
+        }
+    }
+
+    fn period(self, i: usize) -> Duration {
+        match self {
+            Self::Uniform => Duration::from_millis(16),
+            Self::Spread => Duration::from_micros(5_000 + i as u64 * 977 % 20_000),
+            Self::LongTail if i.is_multiple_of(16) => Duration::from_millis(2),
+            Self::LongTail => Duration::from_secs(1) + Duration::from_micros(i as u64),
+        }
+    }
+}
+
+fn table_context_with_periods(num_entities: usize, periods: PeriodDistribution) -> Context {
+    let (mut context, _) = context(0);
+    let mut entity_allocator = EntityAllocator::default();
+    for i in 0..num_entities {
+        let period = periods.period(i);
+        // stagger the first ticks so components with the same period aren't all due together
+        let until_next_tick = match periods {
+            PeriodDistribution::Uniform => Duration::ZERO,
+            _ => period * (i % 4) as u32 / 4,
+        };
+        context.table.insert_with_schedule(
+            entity_allocator.alloc(),
+            ScheduledRealtimeComponent::new(Periodic { period }, until_next_tick),
+        );
+    }
+    context
+}
+
+/// Processing with the linear scan of each entity's component schedules
+fn scan(c: &mut Criterion) {
+    let mut group = c.benchmark_group("scan");
+    for num_entities in [10, 100, 1000, 10000] {
+        group.bench_with_input(
+            BenchmarkId::from_parameter(num_entities),
+            &num_entities,
+            |b, &num_entities| {
+                let (mut context, entities) = context(num_entities);
+                b.iter(|| {
+                    for &entity in &entities {
+                        process_entity_frame(entity, Duration::from_millis(16), &mut context);
+                    }
+                });
+            },
+        );
+    }
+    group.finish();
+}
+
+/// The operations the frame loop performs on each schedule (finding the soonest and advancing
+/// every schedule by it), on schedules stored as `Duration`s and as `u64` nanoseconds
+fn schedule_repr(c: &mut Criterion) {
+    let mut group = c.benchmark_group("schedule_repr");
+    for num_schedules in [4, 64, 1024] {
+        let periods = (0..num_schedules as u64)
+            .map(|i| Duration::from_micros(5_000 + i * 977 % 20_000))
+            .collect::<Vec<_>>();
+        group.bench_with_input(
+            BenchmarkId::new("duration", num_schedules),
+            &periods,
+            |b, periods| {
+                let mut schedules = periods.clone();
+                b.iter(|| {
+                    let min = schedules.iter().copied().min().unwrap();
+                    for (schedule, &period) in schedules.iter_mut().zip(periods) {
+                        *schedule = if *schedule == min {
+                            period
+                        } else {
+                            *schedule - min
+                        };
+                    }
+                    black_box(min)
+                });
+            },
+        );
+        group.bench_with_input(
+            BenchmarkId::new("nanos", num_schedules),
+            &periods,
+            |b, periods| {
+                let periods = periods
+                    .iter()
+                    .map(|period| period.as_nanos() as u64)
+                    .collect::<Vec<_>>();
+                let mut schedules = periods.clone();
+                b.iter(|| {
+                    let min = schedules.iter().copied().min().unwrap();
+                    for (schedule, &period) in schedules.iter_mut().zip(&periods) {
+                        *schedule = if *schedule == min {
+                            period
+                        } else {
+                            *schedule - min
+                        };
+                    }
+                    black_box(min)
+                });
+            },
+        );
+    }
+    group.finish();
+}
+
+/// A driver of a single table processing each frame and then asking when the table next ticks,
+/// e.g. to sleep until then. The table is scanned to find its next tick, while the index of an
+/// `IndexedScheduler` stays valid across frames processed with it.
+fn table_min_until_next_tick(c: &mut Criterion) {
+    let mut group = c.benchmark_group("table_min_until_next_tick");
+    let frame = Duration::from_millis(1);
+    for num_components in [16, 256, 4096] {
+        group.bench_with_input(
+            BenchmarkId::new("scan", num_components),
+            &num_components,
+            |b, &num_components| {
+                let mut context = table_context(num_components);
+                b.iter(|| {
+                    process_table_frame(frame, &mut context, &mut LinearScan::new(), |context| {
+                        &mut context.table
+                    });
+                    black_box(context.table.min_until_next_tick())
+                });
+            },
+        );
+        group.bench_with_input(
+            BenchmarkId::new("indexed", num_components),
+            &num_components,
+            |b, &num_components| {
+                let mut context = table_context(num_components);
+                let mut scheduler = IndexedScheduler::new();
+                b.iter(|| {
+                    process_table_frame(frame, &mut context, &mut scheduler, |context| {
+                        &mut context.table
+                    });
+                    black_box(scheduler.min_until_next_tick(&context.table))
+                });
+            },
+        );
+    }
+    group.finish();
+}
+
+/// Processing a whole table's frame with each scheduler, over table sizes and distributions of
+/// component periods
+fn scheduler(c: &mut Criterion) {
+    fn bench(
+        group: &mut criterion::BenchmarkGroup<criterion::measurement::WallTime>,
+        name: &str,
+        periods: PeriodDistribution,
+        num_components: usize,
+        mut scheduler: impl Scheduler<Periodic>,
+    ) {
+        group.bench_with_input(
+            BenchmarkId::new(format!("{}/{}", name, periods.name()), num_components),
+            &num_components,
+            |b, &num_components| {
+                let mut context = table_context_with_periods(num_components, periods);
+                b.iter(|| {
+                    process_table_frame(
+                        Duration::from_millis(16),
+                        &mut context,
+                        &mut scheduler,
+                        |context| &mut context.table,
+                    );
+                    black_box(context.num_events)
+                });
+            },
+        );
+    }
+    let mut group = c.benchmark_group("scheduler");
+    for periods in PeriodDistribution::ALL {
+        for num_components in [16, 256, 4096] {
+            bench(
+                &mut group,
+                "scan",
+                periods,
+                num_components,
+                LinearScan::new(),
+            );
+            bench(
+                &mut group,
+                "indexed",
+                periods,
+                num_components,
+                IndexedScheduler::new(),
+            );
+            bench(
+                &mut group,
+                "heap",
+                periods,
+                num_components,
+                HeapScheduler::new(),
+            );
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    scan,
+    schedule_repr,
+    table_min_until_next_tick,
+    scheduler
+);
+criterion_main!(benches);
@@ -0,0 +1,62 @@
+//! Scheduling audio for the events of realtime components. Events usually occur part way
+//! through a frame, but are only observed once the frame is processed. Playing the sound for an
+//! event as soon as it's observed quantizes its timing to the frame rate, which is audible as
+//! jitter in regular sounds such as footsteps. Instead, cues can be stamped with the offset
+//! into the frame at which their event occurred, plus a fixed latency, and handed to an audio
+//! engine which supports scheduled playback.
+
+use crate::{
+    ContextContainsRealtimeComponents, Entity, RealtimeComponents, RealtimeEntityEvent,
+    RealtimeEntityEvents, RealtimeEventOf,
+};
+use std::time::Duration;
+
+/// A sound to play on behalf of an entity
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AudioCue<A> {
+    pub entity: Entity,
+    pub cue: A,
+    /// When to play the cue, relative to the moment the frame finished being processed
+    pub play_at: Duration,
+}
+
+/// Like `process_entity_frame` but each event is passed to `cue_of` before being applied, and
+/// any cue it returns is appended to `cues`. Each cue is scheduled to play `latency` after its
+/// event occurred. With a latency of at least the longest expected frame duration, the spacing
+/// between cues matches the spacing between their events regardless of the frame rate. Cues
+/// whose time has already passed have a `play_at` of zero.
+pub fn process_entity_frame_with_audio_cues<C, A>(
+    entity: Entity,
+    frame_duration: Duration,
+    latency: Duration,
+    context: &mut C,
+    mut cue_of: impl FnMut(&RealtimeEventOf<C>) -> Option<A>,
+    cues: &mut Vec<AudioCue<A>>,
+) where
+    C: ContextContainsRealtimeComponents,
+{
+    crate::realtime_profile_scope!("process_entity_frame_with_audio_cues");
+    let frame_duration = crate::begin_entity_frame(entity, frame_duration, context);
+    let mut elapsed = Duration::from_micros(0);
+    while elapsed < frame_duration {
+        let (events, until_next_tick) = context
+            .components_mut()
+            .tick_entity(entity, frame_duration - elapsed);
+        elapsed += until_next_tick;
+        if !events.is_empty() {
+            context.before_tick(entity);
+        }
+        events.for_each_event(|event| {
+            if let Some(cue) = cue_of(&event) {
+                cues.push(AudioCue {
+                    entity,
+                    cue,
+                    // the frame finished `frame_duration - elapsed` after the event occurred
+                    play_at: latency.saturating_sub(frame_duration - elapsed),
+                });
+            }
+            event.apply(entity, context);
+        });
+    }
+    context.after_entity_frame(entity);
+}
@@ -0,0 +1,273 @@
+//! Policies limiting how much work is done processing an entity's frame, passed to
+//! `process_entity_frame_with_policy`. Policies compose as tuples, so e.g. a per-entity limit on
+//! simulated time can be combined with a wall-clock deadline for the whole frame:
+//!
+//! ```ignore
+//! let deadline = WallClockDeadline::after(SystemTimeSource::new(), budget);
+//! let mut policy = (MaxFrameDuration(Duration::from_millis(100)), deadline);
+//! for entity in entities {
+//!     process_entity_frame_with_policy(entity, frame_duration, &mut context, &mut policy);
+//! }
+//! ```
+
+use crate::{Entity, SystemTimeSource, TimeSource};
+use std::fmt;
+use std::time::Duration;
+
+/// How far processing of an entity's frame has got, passed to
+/// `FrameBudgetPolicy::continue_frame` before each step
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FrameProgress {
+    pub entity: Entity,
+    /// Steps taken so far. Each step ticks the entity's soonest due components, or advances
+    /// them to the end of the frame.
+    pub steps: usize,
+    /// Components ticked so far. A step ticks every component due at the same instant, so this
+    /// can grow by more than one per step.
+    pub ticks: usize,
+    /// Simulated time processed so far
+    pub simulated: Duration,
+    /// Simulated time remaining in the frame
+    pub remaining: Duration,
+}
+
+/// Decides how much of a frame to process for each entity. Policies are passed by mutable
+/// reference so they can keep state across entities, e.g. a budget shared by a whole frame.
+pub trait FrameBudgetPolicy {
+    /// The simulated time to process for `entity` given the duration of the frame. Time beyond
+    /// this is dropped rather than simulated.
+    fn limit_frame(&mut self, _entity: Entity, frame_duration: Duration) -> Duration {
+        frame_duration
+    }
+
+    /// Called before each step of an entity's frame. Returning `false` stops processing the
+    /// entity, dropping the remainder of its frame.
+    fn continue_frame(&mut self, progress: &FrameProgress) -> bool;
+}
+
+impl<P: FrameBudgetPolicy + ?Sized> FrameBudgetPolicy for &mut P {
+    fn limit_frame(&mut self, entity: Entity, frame_duration: Duration) -> Duration {
+        (**self).limit_frame(entity, frame_duration)
+    }
+
+    fn continue_frame(&mut self, progress: &FrameProgress) -> bool {
+        (**self).continue_frame(progress)
+    }
+}
+
+/// Processes every frame in full
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Unlimited;
+
+impl FrameBudgetPolicy for Unlimited {
+    fn continue_frame(&mut self, _progress: &FrameProgress) -> bool {
+        true
+    }
+}
+
+/// Processes at most the given duration of each entity's frame
+#[derive(Debug, Clone, Copy)]
+pub struct MaxFrameDuration(pub Duration);
+
+impl FrameBudgetPolicy for MaxFrameDuration {
+    fn limit_frame(&mut self, _entity: Entity, frame_duration: Duration) -> Duration {
+        frame_duration.min(self.0)
+    }
+
+    fn continue_frame(&mut self, _progress: &FrameProgress) -> bool {
+        true
+    }
+}
+
+/// Takes at most the given number of steps for each entity
+#[derive(Debug, Clone, Copy)]
+pub struct MaxStepsPerEntity(pub usize);
+
+impl FrameBudgetPolicy for MaxStepsPerEntity {
+    fn continue_frame(&mut self, progress: &FrameProgress) -> bool {
+        progress.steps < self.0
+    }
+}
+
+/// Takes steps for each entity until the given number of its components have ticked.
+/// Components due at the same instant tick in the same step, so the final step can take the
+/// number of ticks over the limit.
+#[derive(Debug, Clone, Copy)]
+pub struct MaxTicksPerEntity(pub usize);
+
+impl FrameBudgetPolicy for MaxTicksPerEntity {
+    fn continue_frame(&mut self, progress: &FrameProgress) -> bool {
+        progress.ticks < self.0
+    }
+}
+
+/// A number of steps shared by every entity processed with the policy
+#[derive(Debug, Clone, Copy)]
+pub struct StepBudget {
+    remaining: usize,
+}
+
+impl StepBudget {
+    pub fn new(max_steps: usize) -> Self {
+        Self {
+            remaining: max_steps,
+        }
+    }
+
+    pub fn remaining(&self) -> usize {
+        self.remaining
+    }
+}
+
+impl FrameBudgetPolicy for StepBudget {
+    fn continue_frame(&mut self, _progress: &FrameProgress) -> bool {
+        if self.remaining == 0 {
+            return false;
+        }
+        self.remaining -= 1;
+        true
+    }
+}
+
+/// Stops processing entities once a wall-clock deadline has passed, according to a
+/// `TimeSource`. The time source is read before every step.
+#[derive(Debug, Clone, Copy)]
+pub struct WallClockDeadline<S: TimeSource = SystemTimeSource> {
+    time_source: S,
+    deadline: Duration,
+}
+
+impl<S: TimeSource> WallClockDeadline<S> {
+    /// A deadline at the time source's time `deadline`
+    pub fn new(time_source: S, deadline: Duration) -> Self {
+        Self {
+            time_source,
+            deadline,
+        }
+    }
+
+    /// A deadline `budget` from the time source's current time
+    pub fn after(time_source: S, budget: Duration) -> Self {
+        let deadline = time_source.now().saturating_add(budget);
+        Self::new(time_source, deadline)
+    }
+
+    pub fn deadline(&self) -> Duration {
+        self.deadline
+    }
+
+    pub fn time_source(&self) -> &S {
+        &self.time_source
+    }
+
+    pub fn time_source_mut(&mut self) -> &mut S {
+        &mut self.time_source
+    }
+}
+
+impl<S: TimeSource> FrameBudgetPolicy for WallClockDeadline<S> {
+    fn continue_frame(&mut self, _progress: &FrameProgress) -> bool {
+        self.time_source.now() < self.deadline
+    }
+}
+
+/// Both policies apply. The second policy is only consulted about a step if the first allows
+/// it.
+impl<A: FrameBudgetPolicy, B: FrameBudgetPolicy> FrameBudgetPolicy for (A, B) {
+    fn limit_frame(&mut self, entity: Entity, frame_duration: Duration) -> Duration {
+        let frame_duration = self.0.limit_frame(entity, frame_duration);
+        self.1.limit_frame(entity, frame_duration)
+    }
+
+    fn continue_frame(&mut self, progress: &FrameProgress) -> bool {
+        self.0.continue_frame(progress) && self.1.continue_frame(progress)
+    }
+}
+
+/// All three policies apply, as with pairs of policies
+impl<A: FrameBudgetPolicy, B: FrameBudgetPolicy, C: FrameBudgetPolicy> FrameBudgetPolicy
+    for (A, B, C)
+{
+    fn limit_frame(&mut self, entity: Entity, frame_duration: Duration) -> Duration {
+        let frame_duration = self.0.limit_frame(entity, frame_duration);
+        let frame_duration = self.1.limit_frame(entity, frame_duration);
+        self.2.limit_frame(entity, frame_duration)
+    }
+
+    fn continue_frame(&mut self, progress: &FrameProgress) -> bool {
+        self.0.continue_frame(progress)
+            && self.1.continue_frame(progress)
+            && self.2.continue_frame(progress)
+    }
+}
+
+/// The whole-frame step ceiling of a `FrameCeiling` was reached
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FrameCeilingExceeded {
+    pub max_steps: usize,
+    /// The entity being processed when the ceiling was reached
+    pub entity: Entity,
+}
+
+impl fmt::Display for FrameCeilingExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "realtime frame exceeded its ceiling of {} steps while processing {:?}",
+            self.max_steps, self.entity
+        )
+    }
+}
+
+impl std::error::Error for FrameCeilingExceeded {}
+
+/// A ceiling on the number of steps taken across every entity processed with the policy during
+/// a frame, guaranteeing that frame processing finishes however components misbehave (e.g. by
+/// returning zero durations from `tick`). Once the ceiling is reached, no more steps are taken
+/// for any entity until the policy is reset, and the first entity affected is reported by
+/// `exceeded`.
+#[derive(Debug, Clone, Copy)]
+pub struct FrameCeiling {
+    max_steps: usize,
+    steps: usize,
+    exceeded: Option<FrameCeilingExceeded>,
+}
+
+impl FrameCeiling {
+    pub fn new(max_steps: usize) -> Self {
+        Self {
+            max_steps,
+            steps: 0,
+            exceeded: None,
+        }
+    }
+
+    /// Steps taken since the policy was created or reset
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+
+    pub fn exceeded(&self) -> Option<FrameCeilingExceeded> {
+        self.exceeded
+    }
+
+    /// Start counting steps for a new frame
+    pub fn reset(&mut self) {
+        self.steps = 0;
+        self.exceeded = None;
+    }
+}
+
+impl FrameBudgetPolicy for FrameCeiling {
+    fn continue_frame(&mut self, progress: &FrameProgress) -> bool {
+        if self.steps >= self.max_steps {
+            self.exceeded.get_or_insert(FrameCeilingExceeded {
+                max_steps: self.max_steps,
+                entity: progress.entity,
+            });
+            return false;
+        }
+        self.steps += 1;
+        true
+    }
+}
@@ -0,0 +1,94 @@
+use std::time::{Duration, Instant};
+
+/// Source of monotonic time used by `RealtimeClock` to measure frame durations
+pub trait TimeSource {
+    /// Time elapsed since some fixed point in the past. Must never decrease.
+    fn now(&self) -> Duration;
+}
+
+/// Measures time with the system's monotonic clock
+#[derive(Debug, Clone, Copy)]
+pub struct SystemTimeSource {
+    start: Instant,
+}
+
+impl SystemTimeSource {
+    pub fn new() -> Self {
+        Self {
+            start: Instant::now(),
+        }
+    }
+}
+
+impl Default for SystemTimeSource {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TimeSource for SystemTimeSource {
+    fn now(&self) -> Duration {
+        self.start.elapsed()
+    }
+}
+
+/// Time source which only advances when told to, for deterministic tests
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ManualTimeSource {
+    now: Duration,
+}
+
+impl ManualTimeSource {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn advance(&mut self, by: Duration) {
+        self.now += by;
+    }
+}
+
+impl TimeSource for ManualTimeSource {
+    fn now(&self) -> Duration {
+        self.now
+    }
+}
+
+/// Measures the duration of each frame according to a `TimeSource`
+#[derive(Debug, Clone)]
+pub struct RealtimeClock<S: TimeSource = SystemTimeSource> {
+    time_source: S,
+    last_frame: Duration,
+}
+
+impl<S: TimeSource> RealtimeClock<S> {
+    pub fn new(time_source: S) -> Self {
+        let last_frame = time_source.now();
+        Self {
+            time_source,
+            last_frame,
+        }
+    }
+
+    /// The time since the previous call (or since the clock was created)
+    pub fn frame_duration(&mut self) -> Duration {
+        let now = self.time_source.now();
+        let frame_duration = now.saturating_sub(self.last_frame);
+        self.last_frame = now;
+        frame_duration
+    }
+
+    pub fn time_source(&self) -> &S {
+        &self.time_source
+    }
+
+    pub fn time_source_mut(&mut self) -> &mut S {
+        &mut self.time_source
+    }
+}
+
+impl Default for RealtimeClock<SystemTimeSource> {
+    fn default() -> Self {
+        Self::new(SystemTimeSource::new())
+    }
+}
@@ -0,0 +1,295 @@
+//! Adapters which compose realtime components into more complex behaviour, e.g. playing one
+//! effect after another, or repeating an effect several times. Adapters tick the components
+//! they wrap with the same method they're ticked with (`tick`, `tick_with` or `tick_with_rng`),
+//! passing on their own `TickCtx`.
+
+use crate::{InnerTick, RealtimeComponent, TickCtx};
+#[cfg(feature = "serialize")]
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// A realtime component which eventually finishes. A component is finished once it has
+/// produced its last event, and the duration returned by the tick which produced that event is
+/// the time until the component ends. Finished components shouldn't be ticked again.
+pub trait FiniteRealtimeComponent: RealtimeComponent {
+    fn is_finished(&self) -> bool;
+}
+
+impl<T: FiniteRealtimeComponent + ?Sized> FiniteRealtimeComponent for Box<T> {
+    fn is_finished(&self) -> bool {
+        (**self).is_finished()
+    }
+}
+
+/// Event of a combinator of two components
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CombinedEvent<A, B> {
+    First(A),
+    Second(B),
+}
+
+/// Runs `A` until it finishes, then runs `B`
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Chain<A, B> {
+    pub first: A,
+    pub second: B,
+}
+
+impl<A, B> Chain<A, B> {
+    pub fn new(first: A, second: B) -> Self {
+        Self { first, second }
+    }
+}
+
+impl<A: FiniteRealtimeComponent, B: RealtimeComponent> Chain<A, B> {
+    fn tick_inner(
+        &mut self,
+        inner: &mut InnerTick,
+    ) -> (CombinedEvent<A::Event, B::Event>, Duration) {
+        if self.first.is_finished() {
+            let (event, until_next_tick) = inner.tick(&mut self.second);
+            (CombinedEvent::Second(event), until_next_tick)
+        } else {
+            let (event, until_next_tick) = inner.tick(&mut self.first);
+            (CombinedEvent::First(event), until_next_tick)
+        }
+    }
+}
+
+impl<A: FiniteRealtimeComponent, B: RealtimeComponent> RealtimeComponent for Chain<A, B> {
+    type Event = CombinedEvent<A::Event, B::Event>;
+
+    fn tick(&mut self) -> (Self::Event, Duration) {
+        self.tick_inner(&mut InnerTick::Tick)
+    }
+
+    fn tick_with(&mut self, context: &TickCtx) -> (Self::Event, Duration) {
+        self.tick_inner(&mut InnerTick::TickWith(context))
+    }
+
+    #[cfg(feature = "rand")]
+    fn tick_with_rng(
+        &mut self,
+        context: &TickCtx,
+        rng: &mut dyn rand_core::RngCore,
+    ) -> (Self::Event, Duration) {
+        self.tick_inner(&mut InnerTick::TickWithRng(context, rng))
+    }
+
+    fn on_insert(&mut self) -> Option<Self::Event> {
+        if self.first.is_finished() {
+            self.second.on_insert().map(CombinedEvent::Second)
+        } else {
+            self.first.on_insert().map(CombinedEvent::First)
+        }
+    }
+
+    fn on_remove(&mut self) -> Option<Self::Event> {
+        if self.first.is_finished() {
+            self.second.on_remove().map(CombinedEvent::Second)
+        } else {
+            self.first.on_remove().map(CombinedEvent::First)
+        }
+    }
+
+    fn on_ticks_dropped(&mut self, dropped: u64) -> Option<Self::Event> {
+        if self.first.is_finished() {
+            self.second
+                .on_ticks_dropped(dropped)
+                .map(CombinedEvent::Second)
+        } else {
+            self.first
+                .on_ticks_dropped(dropped)
+                .map(CombinedEvent::First)
+        }
+    }
+}
+
+impl<A: FiniteRealtimeComponent, B: FiniteRealtimeComponent> FiniteRealtimeComponent
+    for Chain<A, B>
+{
+    fn is_finished(&self) -> bool {
+        self.first.is_finished() && self.second.is_finished()
+    }
+}
+
+/// Runs a component a fixed number of times, starting each repetition from a copy of the
+/// original component once the previous repetition finishes
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Repeat<A> {
+    original: A,
+    current: A,
+    repetitions_remaining: u32,
+}
+
+impl<A: Clone> Repeat<A> {
+    /// Run `component` `times` times in total. `times` of zero runs it once.
+    pub fn new(component: A, times: u32) -> Self {
+        Self {
+            current: component.clone(),
+            original: component,
+            repetitions_remaining: times.saturating_sub(1),
+        }
+    }
+
+    /// The number of repetitions after the current one
+    pub fn repetitions_remaining(&self) -> u32 {
+        self.repetitions_remaining
+    }
+}
+
+impl<A: FiniteRealtimeComponent + Clone> Repeat<A> {
+    fn tick_inner(&mut self, inner: &mut InnerTick) -> (A::Event, Duration) {
+        let (event, until_next_tick) = inner.tick(&mut self.current);
+        if self.current.is_finished() && self.repetitions_remaining > 0 {
+            self.current = self.original.clone();
+            self.repetitions_remaining -= 1;
+        }
+        (event, until_next_tick)
+    }
+}
+
+impl<A: FiniteRealtimeComponent + Clone> RealtimeComponent for Repeat<A> {
+    type Event = A::Event;
+
+    fn tick(&mut self) -> (Self::Event, Duration) {
+        self.tick_inner(&mut InnerTick::Tick)
+    }
+
+    fn tick_with(&mut self, context: &TickCtx) -> (Self::Event, Duration) {
+        self.tick_inner(&mut InnerTick::TickWith(context))
+    }
+
+    #[cfg(feature = "rand")]
+    fn tick_with_rng(
+        &mut self,
+        context: &TickCtx,
+        rng: &mut dyn rand_core::RngCore,
+    ) -> (Self::Event, Duration) {
+        self.tick_inner(&mut InnerTick::TickWithRng(context, rng))
+    }
+
+    fn on_insert(&mut self) -> Option<Self::Event> {
+        self.current.on_insert()
+    }
+
+    fn on_remove(&mut self) -> Option<Self::Event> {
+        self.current.on_remove()
+    }
+
+    fn on_ticks_dropped(&mut self, dropped: u64) -> Option<Self::Event> {
+        self.current.on_ticks_dropped(dropped)
+    }
+}
+
+impl<A: FiniteRealtimeComponent + Clone> FiniteRealtimeComponent for Repeat<A> {
+    fn is_finished(&self) -> bool {
+        self.current.is_finished() && self.repetitions_remaining == 0
+    }
+}
+
+/// Runs `A` until it finishes, then `B` until it finishes, then a fresh copy of `A`, and so on
+/// forever
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Alternate<A, B> {
+    first: A,
+    second: B,
+    current: Running<A, B>,
+}
+
+/// The copy of one of the components of an `Alternate` which is currently running
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+enum Running<A, B> {
+    First(A),
+    Second(B),
+}
+
+impl<A: Clone, B> Alternate<A, B> {
+    /// Starts with `first`
+    pub fn new(first: A, second: B) -> Self {
+        Self {
+            current: Running::First(first.clone()),
+            first,
+            second,
+        }
+    }
+}
+
+impl<A, B> Alternate<A, B>
+where
+    A: FiniteRealtimeComponent + Clone,
+    B: FiniteRealtimeComponent + Clone,
+{
+    fn tick_inner(
+        &mut self,
+        inner: &mut InnerTick,
+    ) -> (CombinedEvent<A::Event, B::Event>, Duration) {
+        match &mut self.current {
+            Running::First(first) => {
+                let (event, until_next_tick) = inner.tick(first);
+                if first.is_finished() {
+                    self.current = Running::Second(self.second.clone());
+                }
+                (CombinedEvent::First(event), until_next_tick)
+            }
+            Running::Second(second) => {
+                let (event, until_next_tick) = inner.tick(second);
+                if second.is_finished() {
+                    self.current = Running::First(self.first.clone());
+                }
+                (CombinedEvent::Second(event), until_next_tick)
+            }
+        }
+    }
+}
+
+impl<A, B> RealtimeComponent for Alternate<A, B>
+where
+    A: FiniteRealtimeComponent + Clone,
+    B: FiniteRealtimeComponent + Clone,
+{
+    type Event = CombinedEvent<A::Event, B::Event>;
+
+    fn tick(&mut self) -> (Self::Event, Duration) {
+        self.tick_inner(&mut InnerTick::Tick)
+    }
+
+    fn tick_with(&mut self, context: &TickCtx) -> (Self::Event, Duration) {
+        self.tick_inner(&mut InnerTick::TickWith(context))
+    }
+
+    #[cfg(feature = "rand")]
+    fn tick_with_rng(
+        &mut self,
+        context: &TickCtx,
+        rng: &mut dyn rand_core::RngCore,
+    ) -> (Self::Event, Duration) {
+        self.tick_inner(&mut InnerTick::TickWithRng(context, rng))
+    }
+
+    fn on_insert(&mut self) -> Option<Self::Event> {
+        match &mut self.current {
+            Running::First(first) => first.on_insert().map(CombinedEvent::First),
+            Running::Second(second) => second.on_insert().map(CombinedEvent::Second),
+        }
+    }
+
+    fn on_remove(&mut self) -> Option<Self::Event> {
+        match &mut self.current {
+            Running::First(first) => first.on_remove().map(CombinedEvent::First),
+            Running::Second(second) => second.on_remove().map(CombinedEvent::Second),
+        }
+    }
+
+    fn on_ticks_dropped(&mut self, dropped: u64) -> Option<Self::Event> {
+        match &mut self.current {
+            Running::First(first) => first.on_ticks_dropped(dropped).map(CombinedEvent::First),
+            Running::Second(second) => second.on_ticks_dropped(dropped).map(CombinedEvent::Second),
+        }
+    }
+}
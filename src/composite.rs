@@ -0,0 +1,161 @@
+//! Many components multiplexed through a single table entry and schedule, for entities with
+//! many cheap effects where the overhead of a table per effect would dominate
+
+use crate::{
+    duration_to_nanos, Entity, InnerTick, RealtimeComponent, RealtimeComponentApplyEvent,
+    ScheduledRealtimeComponent, TickCtx,
+};
+#[cfg(feature = "serialize")]
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// A list of components sharing one schedule. Each tick of the composite ticks whichever part is
+/// due, and its events are applied as if the part were in a table of its own. Parts can be
+/// boxed trait objects to combine different types of component. Create composites with
+/// `Composite::scheduled` and add parts with `ScheduledRealtimeComponent::push_part` so the
+/// shared schedule stays in sync with the parts. Parts are ticked with the same method as the
+/// composite, and are passed the composite's `TickCtx`.
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct Composite<T> {
+    parts: Vec<Part<T>>,
+}
+
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+struct Part<T> {
+    /// Time between the composite's next tick and the part's next tick
+    #[cfg_attr(
+        feature = "serialize",
+        serde(rename = "after_composite", with = "crate::serialize_nanos")
+    )]
+    after_composite_nanos: u64,
+    component: T,
+}
+
+impl<T: RealtimeComponent> Composite<T> {
+    /// A composite of parts with the given times until their first ticks, scheduled to tick
+    /// when the first part is due. A composite with no parts is scheduled to never tick.
+    pub fn scheduled(parts: Vec<(Duration, T)>) -> ScheduledRealtimeComponent<Self> {
+        let mut scheduled =
+            ScheduledRealtimeComponent::new(Self { parts: Vec::new() }, Duration::MAX);
+        for (until_next_tick, part) in parts {
+            scheduled.push_part(part, until_next_tick);
+        }
+        scheduled
+    }
+
+    pub fn len(&self) -> usize {
+        self.parts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parts.is_empty()
+    }
+
+    pub fn parts(&self) -> impl '_ + Iterator<Item = &T> {
+        self.parts.iter().map(|part| &part.component)
+    }
+
+    pub fn parts_mut(&mut self) -> impl '_ + Iterator<Item = &mut T> {
+        self.parts.iter_mut().map(|part| &mut part.component)
+    }
+}
+
+impl<T: RealtimeComponent> ScheduledRealtimeComponent<Composite<T>> {
+    /// Add a part which first ticks after `until_next_tick`, bringing the composite's next tick
+    /// forward if necessary
+    pub fn push_part(&mut self, part: T, until_next_tick: Duration) {
+        let part_nanos = duration_to_nanos(until_next_tick);
+        let composite_nanos = self.until_next_tick_nanos();
+        if part_nanos >= composite_nanos {
+            self.component.parts.push(Part {
+                after_composite_nanos: part_nanos - composite_nanos,
+                component: part,
+            });
+        } else {
+            let earlier_by = composite_nanos - part_nanos;
+            for existing in self.component.parts.iter_mut() {
+                existing.after_composite_nanos += earlier_by;
+            }
+            self.component.parts.push(Part {
+                after_composite_nanos: 0,
+                component: part,
+            });
+            self.set_until_next_tick(until_next_tick);
+        }
+    }
+}
+
+impl<T: RealtimeComponent> Composite<T> {
+    fn tick_inner(&mut self, inner: &mut InnerTick) -> (T::Event, Duration) {
+        let part = self
+            .parts
+            .iter_mut()
+            .find(|part| part.after_composite_nanos == 0)
+            .expect("ticked a composite with no parts");
+        let (event, until_next_tick) = inner.tick(&mut part.component);
+        part.after_composite_nanos = duration_to_nanos(until_next_tick);
+        let until_next_tick = self
+            .parts
+            .iter()
+            .map(|part| part.after_composite_nanos)
+            .min()
+            .unwrap_or(0);
+        for part in self.parts.iter_mut() {
+            part.after_composite_nanos -= until_next_tick;
+        }
+        (event, Duration::from_nanos(until_next_tick))
+    }
+}
+
+impl<T: RealtimeComponent> RealtimeComponent for Composite<T> {
+    type Event = T::Event;
+
+    /// Panics if the composite has no parts
+    fn tick(&mut self) -> (Self::Event, Duration) {
+        self.tick_inner(&mut InnerTick::Tick)
+    }
+
+    /// Panics if the composite has no parts
+    fn tick_with(&mut self, context: &TickCtx) -> (Self::Event, Duration) {
+        self.tick_inner(&mut InnerTick::TickWith(context))
+    }
+
+    /// Panics if the composite has no parts
+    #[cfg(feature = "rand")]
+    fn tick_with_rng(
+        &mut self,
+        context: &TickCtx,
+        rng: &mut dyn rand_core::RngCore,
+    ) -> (Self::Event, Duration) {
+        self.tick_inner(&mut InnerTick::TickWithRng(context, rng))
+    }
+
+    /// Only one event can be returned, so this is the event of the first part which has one
+    fn on_insert(&mut self) -> Option<Self::Event> {
+        self.parts
+            .iter_mut()
+            .find_map(|part| part.component.on_insert())
+    }
+
+    /// Only one event can be returned, so this is the event of the first part which has one
+    fn on_remove(&mut self) -> Option<Self::Event> {
+        self.parts
+            .iter_mut()
+            .find_map(|part| part.component.on_remove())
+    }
+
+    /// Only one event can be returned, so this is the event of the first part which has one
+    fn on_ticks_dropped(&mut self, dropped: u64) -> Option<Self::Event> {
+        self.parts
+            .iter_mut()
+            .find_map(|part| part.component.on_ticks_dropped(dropped))
+    }
+}
+
+impl<C, T: RealtimeComponentApplyEvent<C>> RealtimeComponentApplyEvent<C> for Composite<T> {
+    fn apply_event(event: T::Event, entity: Entity, context: &mut C) {
+        T::apply_event(event, entity, context)
+    }
+}
@@ -0,0 +1,216 @@
+use crate::{Entity, RealtimeComponentsDyn, RealtimeFrameHooks};
+use std::any::Any;
+use std::marker::PhantomData;
+use std::time::Duration;
+
+/// Identifies a module registered with a `RealtimeCoordinator`
+pub struct RealtimeModuleId<T> {
+    index: usize,
+    phantom: PhantomData<fn() -> T>,
+}
+
+impl<T> Clone for RealtimeModuleId<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for RealtimeModuleId<T> {}
+
+/// Ticks entities across several sets of realtime components (e.g. the types generated by
+/// `declare_realtime_entity_module!` in different crates or plugins) as if they were one. All
+/// modules advance in lockstep, so events are applied in the order they occur regardless of
+/// which module produced them. Events occurring at the same time are applied in the order the
+/// modules were registered.
+pub struct RealtimeCoordinator<C: ?Sized> {
+    modules: Vec<Box<dyn RealtimeComponentsDyn<C>>>,
+}
+
+impl<C: ?Sized> Default for RealtimeCoordinator<C> {
+    fn default() -> Self {
+        Self {
+            modules: Vec::new(),
+        }
+    }
+}
+
+impl<C: ?Sized> RealtimeCoordinator<C> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn register<T: RealtimeComponentsDyn<C> + 'static>(
+        &mut self,
+        module: T,
+    ) -> RealtimeModuleId<T> {
+        self.modules.push(Box::new(module));
+        RealtimeModuleId {
+            index: self.modules.len() - 1,
+            phantom: PhantomData,
+        }
+    }
+
+    pub fn get<T: Any>(&self, id: RealtimeModuleId<T>) -> &T {
+        self.modules[id.index]
+            .as_any()
+            .downcast_ref()
+            .expect("module id from a different coordinator")
+    }
+
+    pub fn get_mut<T: Any>(&mut self, id: RealtimeModuleId<T>) -> &mut T {
+        self.modules[id.index]
+            .as_any_mut()
+            .downcast_mut()
+            .expect("module id from a different coordinator")
+    }
+
+    pub fn len(&self) -> usize {
+        self.modules.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.modules.is_empty()
+    }
+
+    /// Returns true if the entity has components in any module, whether or not they're enabled
+    pub fn contains_entity(&self, entity: Entity) -> bool {
+        self.modules
+            .iter()
+            .any(|module| module.contains_entity_dyn(entity))
+    }
+
+    /// Remove all components for a given entity from every module
+    pub fn remove_entity(&mut self, entity: Entity) {
+        for module in &mut self.modules {
+            module.remove_entity_dyn(entity);
+        }
+    }
+
+    /// Remove all components for all entities from every module
+    pub fn clear(&mut self) {
+        for module in &mut self.modules {
+            module.clear_dyn();
+        }
+    }
+
+    /// Prepare to process an entity's frame across all modules, returning the duration of the
+    /// frame to simulate. See `begin_entity_frame`.
+    fn begin_entity_frame(
+        &mut self,
+        entity: Entity,
+        frame_duration: Duration,
+        context: &mut C,
+    ) -> Duration
+    where
+        C: RealtimeFrameHooks,
+    {
+        let frame_duration = crate::entity_frame_duration(entity, frame_duration, context);
+        if frame_duration.is_zero() {
+            return frame_duration;
+        }
+        let frame_duration = context
+            .realtime_catch_up_mode(entity)
+            .clamp(frame_duration, || {
+                self.modules
+                    .iter()
+                    .filter_map(|module| module.min_period_dyn(entity))
+                    .min()
+            });
+        for module in &mut self.modules {
+            if let Some(events) = module.drop_excess_ticks_dyn(entity, frame_duration) {
+                events.apply_dyn(entity, context);
+            }
+        }
+        frame_duration
+    }
+
+    /// Advance every module to the soonest tick of any of an entity's components within the
+    /// remaining time, not counting components held back by an event cap, applying the
+    /// resulting events. Returns the time advanced and the number
+    /// of components which ticked.
+    fn step_entity(
+        &mut self,
+        entity: Entity,
+        frame_remaining: Duration,
+        context: &mut C,
+    ) -> (Duration, usize)
+    where
+        C: RealtimeFrameHooks,
+    {
+        let until_next_step = self
+            .modules
+            .iter()
+            .map(|module| module.until_next_step_dyn(entity, frame_remaining))
+            .fold(frame_remaining, Duration::min);
+        let mut advanced = until_next_step;
+        let mut num_ticks = 0;
+        for module in &mut self.modules {
+            let (events, module_advanced) = module.tick_entity_dyn(entity, until_next_step);
+            advanced = advanced.min(module_advanced);
+            if let Some(events) = events {
+                if num_ticks == 0 {
+                    context.before_tick(entity);
+                }
+                num_ticks += events.ticked_dyn().len();
+                events.apply_dyn(entity, context);
+            }
+        }
+        (advanced, num_ticks)
+    }
+
+    /// Like `process_entity_frame` but across all modules. Returns `false` without doing
+    /// anything if the entity has no components in any module.
+    pub fn process_entity_frame(
+        &mut self,
+        entity: Entity,
+        frame_duration: Duration,
+        context: &mut C,
+    ) -> bool
+    where
+        C: RealtimeFrameHooks,
+    {
+        if !self.contains_entity(entity) {
+            return false;
+        }
+        let mut frame_remaining = self.begin_entity_frame(entity, frame_duration, context);
+        while frame_remaining > Duration::from_micros(0) {
+            let (advanced, step_ticks) = self.step_entity(entity, frame_remaining, context);
+            if advanced.is_zero() && step_ticks == 0 {
+                // every due component is held back by its table's event cap
+                break;
+            }
+            frame_remaining -= advanced;
+        }
+        context.after_entity_frame(entity);
+        true
+    }
+
+    /// Like `process_entity_frame_with_budget` but across all modules, which share the budget of
+    /// `max_ticks`. Returns the number of components which ticked.
+    pub fn process_entity_frame_with_budget(
+        &mut self,
+        entity: Entity,
+        frame_duration: Duration,
+        context: &mut C,
+        max_ticks: usize,
+    ) -> usize
+    where
+        C: RealtimeFrameHooks,
+    {
+        if !self.contains_entity(entity) {
+            return 0;
+        }
+        let mut frame_remaining = self.begin_entity_frame(entity, frame_duration, context);
+        let mut num_ticks = 0;
+        while frame_remaining > Duration::from_micros(0) && num_ticks < max_ticks {
+            let (advanced, step_ticks) = self.step_entity(entity, frame_remaining, context);
+            if advanced.is_zero() && step_ticks == 0 {
+                break;
+            }
+            frame_remaining -= advanced;
+            num_ticks += step_ticks;
+        }
+        context.after_entity_frame(entity);
+        num_ticks
+    }
+}
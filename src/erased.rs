@@ -0,0 +1,307 @@
+//! Type-erased processing of realtime components. Rather than a generated module with code
+//! specialized for every component type, tables are stored as trait objects and processed by a
+//! single frame loop. This trades a little dispatch cost for much less monomorphized code. Every
+//! event is boxed into an `ErasedEvent` on its way from its table to the context, which costs a
+//! heap allocation per event whose type has a non-zero size, so prefer the generated modules for
+//! components which tick many times per frame.
+//!
+//! Each table's event cap and maximum period apply here as they do to every other way of
+//! processing its frames. Call `ErasedRealtimeComponents::advance_simulated_time` once per frame
+//! after processing every entity so the event caps start counting again.
+
+use crate::{
+    Entity, RealtimeComponent, RealtimeComponentApplyEvent, RealtimeComponentTable,
+    RealtimeFrameHooks,
+};
+use std::any::Any;
+use std::marker::PhantomData;
+use std::time::Duration;
+
+/// Applies an event produced by a type-erased table to a context. Creating one allocates unless
+/// the event has a zero size.
+pub type ErasedEvent<C> = Box<dyn FnOnce(Entity, &mut C)>;
+
+/// Object-safe interface to a `RealtimeComponentTable` whose events apply to contexts of type
+/// `C`
+pub trait ErasedRealtimeComponentTable<C> {
+    /// The time until the component of an entity next ticks, or `None` if the entity has no
+    /// component or its component is disabled
+    fn until_next_tick(&self, entity: Entity) -> Option<Duration>;
+
+    /// The duration returned by the most recent tick of the component of an entity
+    fn period(&self, entity: Entity) -> Option<Duration>;
+
+    /// Returns true if the component of an entity is due within `frame_remaining` but the
+    /// table's event cap has been reached, so it won't tick this frame
+    fn is_capped(&self, entity: Entity, frame_remaining: Duration) -> bool;
+
+    /// Advance the component of an entity by `duration`, ticking it if it becomes due and the
+    /// table's event cap allows it. Returns a function which applies the resulting event.
+    /// Components held back by the event cap are left due.
+    fn advance(&mut self, entity: Entity, duration: Duration) -> Option<ErasedEvent<C>>;
+
+    /// Drop the ticks of the component of an entity which would leave it further behind than
+    /// its `max_missed_periods` during a frame of `frame_duration`. Returns a function which
+    /// applies its `on_ticks_dropped` event.
+    fn drop_excess_ticks(
+        &mut self,
+        entity: Entity,
+        frame_duration: Duration,
+    ) -> Option<ErasedEvent<C>>;
+
+    /// End the table's frame. See `RealtimeComponentTable::advance_simulated_time`.
+    fn advance_simulated_time(&mut self, duration: Duration);
+
+    fn remove_entity(&mut self, entity: Entity);
+    fn clear(&mut self);
+    fn as_any(&self) -> &dyn Any;
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+
+impl<C, T> ErasedRealtimeComponentTable<C> for RealtimeComponentTable<T>
+where
+    T: RealtimeComponentApplyEvent<C> + 'static,
+    T::Event: 'static,
+{
+    fn until_next_tick(&self, entity: Entity) -> Option<Duration> {
+        self.time_to_due(entity)
+    }
+
+    fn period(&self, entity: Entity) -> Option<Duration> {
+        self.get_with_schedule(entity)
+            .map(|scheduled| scheduled.period())
+    }
+
+    fn is_capped(&self, entity: Entity, frame_remaining: Duration) -> bool {
+        RealtimeComponentTable::is_capped(self, entity, frame_remaining)
+    }
+
+    fn advance(&mut self, entity: Entity, duration: Duration) -> Option<ErasedEvent<C>> {
+        if self.is_capped(entity, duration) {
+            self.report_capped(entity);
+            self.advance_until_due_nanos(entity, crate::duration_to_nanos(duration));
+            return None;
+        }
+        let scheduled = self
+            .get_with_schedule_mut(entity)
+            .filter(|scheduled| scheduled.is_enabled())?;
+        let duration = crate::duration_to_nanos(duration);
+        if scheduled.until_next_tick_nanos() <= duration {
+            let event = scheduled.tick(entity);
+            self.after_tick(entity);
+            Some(Box::new(move |entity, context| {
+                T::apply_event(event, entity, context)
+            }))
+        } else {
+            scheduled.advance_nanos(duration);
+            None
+        }
+    }
+
+    fn drop_excess_ticks(
+        &mut self,
+        entity: Entity,
+        frame_duration: Duration,
+    ) -> Option<ErasedEvent<C>> {
+        let event = self
+            .get_with_schedule_mut(entity)?
+            .drop_excess_ticks(frame_duration)?;
+        Some(Box::new(move |entity, context| {
+            T::apply_event(event, entity, context)
+        }))
+    }
+
+    fn advance_simulated_time(&mut self, duration: Duration) {
+        RealtimeComponentTable::advance_simulated_time(self, duration);
+    }
+
+    fn remove_entity(&mut self, entity: Entity) {
+        self.remove_with_schedule(entity);
+    }
+
+    fn clear(&mut self) {
+        RealtimeComponentTable::clear(self);
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+/// Identifies a table registered with `ErasedRealtimeComponents`
+pub struct ErasedTableId<T> {
+    index: usize,
+    phantom: PhantomData<fn() -> T>,
+}
+
+impl<T> Clone for ErasedTableId<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for ErasedTableId<T> {}
+
+/// Collection of type-erased tables of realtime components whose events apply to contexts of
+/// type `C`
+pub struct ErasedRealtimeComponents<C> {
+    tables: Vec<Box<dyn ErasedRealtimeComponentTable<C>>>,
+}
+
+impl<C> Default for ErasedRealtimeComponents<C> {
+    fn default() -> Self {
+        Self { tables: Vec::new() }
+    }
+}
+
+impl<C: 'static> ErasedRealtimeComponents<C> {
+    pub fn register<T>(&mut self, table: RealtimeComponentTable<T>) -> ErasedTableId<T>
+    where
+        T: RealtimeComponentApplyEvent<C> + 'static,
+        T::Event: 'static,
+    {
+        self.tables.push(Box::new(table));
+        ErasedTableId {
+            index: self.tables.len() - 1,
+            phantom: PhantomData,
+        }
+    }
+
+    pub fn get<T: RealtimeComponent + 'static>(
+        &self,
+        id: ErasedTableId<T>,
+    ) -> &RealtimeComponentTable<T> {
+        self.tables[id.index]
+            .as_any()
+            .downcast_ref()
+            .expect("table id from a different collection")
+    }
+
+    pub fn get_mut<T: RealtimeComponent + 'static>(
+        &mut self,
+        id: ErasedTableId<T>,
+    ) -> &mut RealtimeComponentTable<T> {
+        self.tables[id.index]
+            .as_any_mut()
+            .downcast_mut()
+            .expect("table id from a different collection")
+    }
+}
+
+impl<C> ErasedRealtimeComponents<C> {
+    /// Remove all components for a given entity
+    pub fn remove_entity(&mut self, entity: Entity) {
+        for table in &mut self.tables {
+            table.remove_entity(entity);
+        }
+    }
+
+    /// Remove all components for all entities
+    pub fn clear(&mut self) {
+        for table in &mut self.tables {
+            table.clear();
+        }
+    }
+
+    /// End the frame of every table, once every entity's frame has been processed. See
+    /// `RealtimeComponentTable::advance_simulated_time`.
+    pub fn advance_simulated_time(&mut self, duration: Duration) {
+        for table in &mut self.tables {
+            table.advance_simulated_time(duration);
+        }
+    }
+
+    /// How far to advance an entity's components given `frame_remaining`, leaving out
+    /// components held back by their table's event cap
+    fn until_next_tick(&self, entity: Entity, frame_remaining: Duration) -> Duration {
+        self.tables
+            .iter()
+            .filter(|table| !table.is_capped(entity, frame_remaining))
+            .filter_map(|table| table.until_next_tick(entity))
+            .fold(frame_remaining, Duration::min)
+    }
+
+    fn contains_entity(&self, entity: Entity) -> bool {
+        self.tables
+            .iter()
+            .any(|table| table.period(entity).is_some())
+    }
+
+    fn min_period(&self, entity: Entity) -> Option<Duration> {
+        self.tables
+            .iter()
+            .filter_map(|table| table.period(entity))
+            .filter(|period| !period.is_zero())
+            .min()
+    }
+}
+
+pub trait ContextContainsErasedRealtimeComponents: Sized {
+    fn erased_components_mut(&mut self) -> &mut ErasedRealtimeComponents<Self>;
+}
+
+/// Prepare to process an entity's frame, returning the duration of the frame to simulate. See
+/// `begin_entity_frame`.
+fn begin_entity_frame_erased<C: ContextContainsErasedRealtimeComponents + RealtimeFrameHooks>(
+    entity: Entity,
+    frame_duration: Duration,
+    context: &mut C,
+) -> Duration {
+    let frame_duration = crate::entity_frame_duration(entity, frame_duration, context);
+    if frame_duration.is_zero() {
+        return frame_duration;
+    }
+    let frame_duration = context
+        .realtime_catch_up_mode(entity)
+        .clamp(frame_duration, || {
+            context.erased_components_mut().min_period(entity)
+        });
+    let num_tables = context.erased_components_mut().tables.len();
+    for i in 0..num_tables {
+        if let Some(apply) =
+            context.erased_components_mut().tables[i].drop_excess_ticks(entity, frame_duration)
+        {
+            apply(entity, context);
+        }
+    }
+    frame_duration
+}
+
+/// Equivalent of `process_entity_frame` for type-erased tables. Entities with no components
+/// are skipped.
+pub fn process_entity_frame_erased<
+    C: ContextContainsErasedRealtimeComponents + RealtimeFrameHooks,
+>(
+    entity: Entity,
+    frame_duration: Duration,
+    context: &mut C,
+) {
+    if !context.erased_components_mut().contains_entity(entity) {
+        return;
+    }
+    let mut frame_remaining = begin_entity_frame_erased(entity, frame_duration, context);
+    while frame_remaining > Duration::from_micros(0) {
+        let until_next_tick = context
+            .erased_components_mut()
+            .until_next_tick(entity, frame_remaining);
+        let num_tables = context.erased_components_mut().tables.len();
+        let mut ticked = false;
+        for i in 0..num_tables {
+            if let Some(apply) =
+                context.erased_components_mut().tables[i].advance(entity, until_next_tick)
+            {
+                if !ticked {
+                    context.before_tick(entity);
+                    ticked = true;
+                }
+                apply(entity, context);
+            }
+        }
+        frame_remaining -= until_next_tick;
+    }
+    context.after_entity_frame(entity);
+}
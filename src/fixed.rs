@@ -0,0 +1,385 @@
+//! A realtime component table with a fixed maximum number of entities, chosen at compile time,
+//! whose storage is inline so it never allocates. This is for embedded targets and
+//! latency-critical servers which forbid allocation at runtime. Tables which can't be used with
+//! `declare_realtime_entity_module!` are processed with `process_fixed_entity_frame` and
+//! `process_fixed_table_frame`. Events are passed straight from each tick to `apply_event`
+//! without being boxed or buffered, so processing frames doesn't allocate either, provided the
+//! components' `tick` and `apply_event` methods don't. Profiling with the `puffin` feature is
+//! the exception.
+
+use crate::{
+    duration_to_nanos, liveness, Entity, EventCapExceeded, PeriodClamped, RealtimeComponent,
+    RealtimeComponentApplyEvent, RealtimeFrameHooks, ScheduledRealtimeComponent, TableLimits,
+};
+#[cfg(feature = "serialize")]
+use serde::{Deserialize, Serialize};
+use smallvec::SmallVec;
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Table associating at most `N` entities with realtime components. Lookups scan the table, so
+/// it's intended for small values of `N`.
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    feature = "serialize",
+    serde(
+        try_from = "UncheckedFixedRealtimeComponentTable<T>",
+        bound(serialize = "T: Serialize", deserialize = "T: Deserialize<'de>")
+    )
+)]
+#[derive(Debug, Clone)]
+pub struct FixedRealtimeComponentTable<T: RealtimeComponent, const N: usize> {
+    #[cfg_attr(feature = "serialize", serde(serialize_with = "serialize_entries"))]
+    entries: SmallVec<[(Entity, ScheduledRealtimeComponent<T>); N]>,
+    #[cfg_attr(feature = "serialize", serde(with = "crate::serialize_nanos"))]
+    simulated_time: u64,
+    /// Event cap and maximum period, which are configuration rather than state so aren't
+    /// serialized
+    #[cfg_attr(feature = "serialize", serde(skip))]
+    limits: TableLimits,
+}
+
+/// A component couldn't be inserted into a `FixedRealtimeComponentTable` because it was full
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FixedTableFull<T>(pub T);
+
+impl<T> fmt::Display for FixedTableFull<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "fixed-capacity realtime component table is full")
+    }
+}
+
+impl<T: fmt::Debug> std::error::Error for FixedTableFull<T> {}
+
+impl<T: RealtimeComponent, const N: usize> Default for FixedRealtimeComponentTable<T, N> {
+    fn default() -> Self {
+        Self {
+            entries: SmallVec::new(),
+            simulated_time: 0,
+            limits: Default::default(),
+        }
+    }
+}
+
+impl<T: RealtimeComponent, const N: usize> FixedRealtimeComponentTable<T, N> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The maximum number of entities the table can hold
+    pub fn capacity(&self) -> usize {
+        N
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.entries.len() >= N
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    fn index_of(&self, entity: Entity) -> Option<usize> {
+        self.entries.iter().position(|(e, _)| *e == entity)
+    }
+
+    /// Returns the component which was replaced, if any. Components of entities which aren't
+    /// already in the table are returned as an error if the table is full.
+    pub fn insert_with_schedule(
+        &mut self,
+        entity: Entity,
+        data: ScheduledRealtimeComponent<T>,
+    ) -> Result<Option<ScheduledRealtimeComponent<T>>, FixedTableFull<ScheduledRealtimeComponent<T>>>
+    {
+        liveness::debug_assert_live(entity, ::std::any::type_name::<T>());
+        if let Some(index) = self.index_of(entity) {
+            return Ok(Some(std::mem::replace(&mut self.entries[index].1, data)));
+        }
+        if self.is_full() {
+            return Err(FixedTableFull(data));
+        }
+        self.entries.push((entity, data));
+        Ok(None)
+    }
+
+    /// Like `RealtimeComponentTable::insert`, but components of entities which aren't already in
+    /// the table are returned as an error if the table is full
+    pub fn insert(&mut self, entity: Entity, data: T) -> Result<Option<T>, FixedTableFull<T>> {
+        self.insert_with_schedule(
+            entity,
+            ScheduledRealtimeComponent::new(data, Duration::from_millis(0)),
+        )
+        .map(|replaced| replaced.map(|c| c.component))
+        .map_err(|FixedTableFull(c)| FixedTableFull(c.component))
+    }
+
+    pub fn contains(&self, entity: Entity) -> bool {
+        self.index_of(entity).is_some()
+    }
+
+    pub fn remove_with_schedule(
+        &mut self,
+        entity: Entity,
+    ) -> Option<ScheduledRealtimeComponent<T>> {
+        self.index_of(entity)
+            .map(|index| self.entries.swap_remove(index).1)
+    }
+
+    pub fn remove(&mut self, entity: Entity) -> Option<T> {
+        self.remove_with_schedule(entity).map(|c| c.component)
+    }
+
+    pub fn get_with_schedule(&self, entity: Entity) -> Option<&ScheduledRealtimeComponent<T>> {
+        liveness::debug_assert_live(entity, ::std::any::type_name::<T>());
+        self.entries
+            .iter()
+            .find(|(e, _)| *e == entity)
+            .map(|(_, scheduled)| scheduled)
+    }
+
+    pub fn get_with_schedule_mut(
+        &mut self,
+        entity: Entity,
+    ) -> Option<&mut ScheduledRealtimeComponent<T>> {
+        liveness::debug_assert_live(entity, ::std::any::type_name::<T>());
+        self.entries
+            .iter_mut()
+            .find(|(e, _)| *e == entity)
+            .map(|(_, scheduled)| scheduled)
+    }
+
+    pub fn get(&self, entity: Entity) -> Option<&T> {
+        self.get_with_schedule(entity).map(|c| &c.component)
+    }
+
+    pub fn get_mut(&mut self, entity: Entity) -> Option<&mut T> {
+        self.get_with_schedule_mut(entity).map(|c| &mut c.component)
+    }
+
+    pub fn iter(&self) -> impl '_ + Iterator<Item = (Entity, &T)> {
+        self.entries
+            .iter()
+            .map(|(entity, scheduled)| (*entity, &scheduled.component))
+    }
+
+    pub fn iter_mut(&mut self) -> impl '_ + Iterator<Item = (Entity, &mut T)> {
+        self.entries
+            .iter_mut()
+            .map(|(entity, scheduled)| (*entity, &mut scheduled.component))
+    }
+
+    pub fn entities(&self) -> impl '_ + Iterator<Item = Entity> {
+        self.entries.iter().map(|(entity, _)| *entity)
+    }
+
+    /// Total simulated time the table has been advanced by since it was created
+    pub fn simulated_time(&self) -> Duration {
+        Duration::from_nanos(self.simulated_time)
+    }
+
+    pub fn advance_simulated_time(&mut self, duration: Duration) {
+        self.simulated_time = self
+            .simulated_time
+            .saturating_add(duration_to_nanos(duration));
+        self.limits.end_frame();
+    }
+
+    /// Like `RealtimeComponentTable::set_event_cap`. The callback is allocated when it's set,
+    /// not while processing frames.
+    pub fn set_event_cap(
+        &mut self,
+        max_events_per_frame: u32,
+        on_overflow: impl Fn(EventCapExceeded) + Send + Sync + 'static,
+    ) {
+        self.limits
+            .set_event_cap(max_events_per_frame, Arc::new(on_overflow));
+    }
+
+    pub fn clear_event_cap(&mut self) {
+        self.limits.clear_event_cap();
+    }
+
+    pub fn event_cap(&self) -> Option<u32> {
+        self.limits.max_events_per_frame
+    }
+
+    /// The number of events the table's components have produced since the last call to
+    /// `advance_simulated_time`. This is only counted while an event cap is set.
+    pub fn events_this_frame(&self) -> u32 {
+        self.limits.events_this_frame
+    }
+
+    /// Like `RealtimeComponentTable::set_max_period`. The callback is allocated when it's set,
+    /// not while processing frames.
+    pub fn set_max_period(
+        &mut self,
+        max_period: Duration,
+        on_clamped: impl Fn(PeriodClamped) + Send + Sync + 'static,
+    ) {
+        self.limits.set_max_period(max_period, Arc::new(on_clamped));
+    }
+
+    pub fn clear_max_period(&mut self) {
+        self.limits.clear_max_period();
+    }
+
+    pub fn max_period(&self) -> Option<Duration> {
+        self.limits.max_period_nanos.map(Duration::from_nanos)
+    }
+
+    /// The enabled component of `entity` along with the table's limits
+    fn get_enabled_with_limits_mut(
+        &mut self,
+        entity: Entity,
+    ) -> Option<(&mut ScheduledRealtimeComponent<T>, &mut TableLimits)> {
+        let scheduled = self
+            .entries
+            .iter_mut()
+            .find(|(e, _)| *e == entity)
+            .map(|(_, scheduled)| scheduled)
+            .filter(|scheduled| scheduled.is_enabled())?;
+        Some((scheduled, &mut self.limits))
+    }
+}
+
+#[cfg(feature = "serialize")]
+fn serialize_entries<S, T, const N: usize>(
+    entries: &SmallVec<[(Entity, ScheduledRealtimeComponent<T>); N]>,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+    T: RealtimeComponent + Serialize,
+{
+    entries.as_slice().serialize(serializer)
+}
+
+#[cfg(feature = "serialize")]
+#[derive(Deserialize)]
+struct UncheckedFixedRealtimeComponentTable<T: RealtimeComponent> {
+    entries: Vec<(Entity, ScheduledRealtimeComponent<T>)>,
+    #[serde(with = "crate::serialize_nanos")]
+    simulated_time: u64,
+}
+
+#[cfg(feature = "serialize")]
+impl<T: RealtimeComponent, const N: usize> TryFrom<UncheckedFixedRealtimeComponentTable<T>>
+    for FixedRealtimeComponentTable<T, N>
+{
+    type Error = FixedTableFull<usize>;
+
+    /// The error contains the number of entries which were deserialized
+    fn try_from(table: UncheckedFixedRealtimeComponentTable<T>) -> Result<Self, Self::Error> {
+        if table.entries.len() > N {
+            return Err(FixedTableFull(table.entries.len()));
+        }
+        Ok(Self {
+            entries: table.entries.into_iter().collect(),
+            simulated_time: table.simulated_time,
+            limits: Default::default(),
+        })
+    }
+}
+
+/// Simulate the component of an entity in the table returned by `table` for the duration of a
+/// frame, applying each event to the context as it occurs. Returns `false` without doing
+/// anything if the entity has no component in the table.
+pub fn process_fixed_entity_frame<C, T, const N: usize>(
+    entity: Entity,
+    frame_duration: Duration,
+    context: &mut C,
+    table: impl Fn(&mut C) -> &mut FixedRealtimeComponentTable<T, N>,
+) -> bool
+where
+    C: RealtimeFrameHooks,
+    T: RealtimeComponentApplyEvent<C>,
+{
+    crate::realtime_profile_scope!("process_fixed_entity_frame");
+    if !table(context).contains(entity) {
+        return false;
+    }
+    let frame_duration = begin_fixed_entity_frame(entity, frame_duration, context, &table);
+    let mut frame_remaining = duration_to_nanos(frame_duration);
+    while let Some((scheduled, limits)) = table(context).get_enabled_with_limits_mut(entity) {
+        if scheduled.until_next_tick_nanos() > frame_remaining {
+            scheduled.advance_nanos(frame_remaining);
+            break;
+        }
+        if limits.cap_reached() {
+            // held back by the event cap, so left due rather than falling behind
+            scheduled.advance_nanos(scheduled.until_next_tick_nanos());
+            limits.report_capped::<T>(entity);
+            break;
+        }
+        frame_remaining -= scheduled.until_next_tick_nanos();
+        let event = scheduled.tick(entity);
+        limits.after_tick(entity, scheduled);
+        context.before_tick(entity);
+        T::apply_event(event, entity, context);
+    }
+    context.after_entity_frame(entity);
+    true
+}
+
+/// Prepare to process an entity's frame, returning the duration of the frame to simulate. See
+/// `begin_entity_frame`.
+fn begin_fixed_entity_frame<C, T, const N: usize>(
+    entity: Entity,
+    frame_duration: Duration,
+    context: &mut C,
+    table: impl Fn(&mut C) -> &mut FixedRealtimeComponentTable<T, N>,
+) -> Duration
+where
+    C: RealtimeFrameHooks,
+    T: RealtimeComponentApplyEvent<C>,
+{
+    let frame_duration = crate::entity_frame_duration(entity, frame_duration, context);
+    if frame_duration.is_zero() {
+        return frame_duration;
+    }
+    let frame_duration = context
+        .realtime_catch_up_mode(entity)
+        .clamp(frame_duration, || {
+            table(context)
+                .get_with_schedule(entity)
+                .map(|scheduled| scheduled.period())
+                .filter(|period| !period.is_zero())
+        });
+    if let Some(event) = table(context)
+        .get_with_schedule_mut(entity)
+        .and_then(|scheduled| scheduled.drop_excess_ticks(frame_duration))
+    {
+        T::apply_event(event, entity, context);
+    }
+    frame_duration
+}
+
+/// Simulate every component in the table returned by `table` for the duration of a frame, then
+/// advance the table's simulated time. Entities are processed in the order they are stored.
+/// Removing entities from the table while applying events changes that order, so an entity may
+/// be skipped for the rest of the frame.
+pub fn process_fixed_table_frame<C, T, const N: usize>(
+    frame_duration: Duration,
+    context: &mut C,
+    table: impl Fn(&mut C) -> &mut FixedRealtimeComponentTable<T, N>,
+) where
+    C: RealtimeFrameHooks,
+    T: RealtimeComponentApplyEvent<C>,
+{
+    crate::realtime_profile_scope!("process_fixed_table_frame");
+    let mut index = 0;
+    while let Some(&(entity, _)) = table(context).entries.get(index) {
+        process_fixed_entity_frame(entity, frame_duration, context, &table);
+        index += 1;
+    }
+    table(context).advance_simulated_time(frame_duration);
+}
@@ -0,0 +1,57 @@
+#[cfg(feature = "serialize")]
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+const NANOS_PER_SEC: u64 = 1_000_000_000;
+
+/// The period of something which happens `hz` times per second, rounded to the nearest
+/// nanosecond. Panics if `hz` isn't positive and finite.
+pub fn period_from_hz(hz: f64) -> Duration {
+    Frequency::from_hz(hz).period()
+}
+
+/// The period of a frame at `fps` frames per second, rounded to the nearest nanosecond. Panics
+/// if `fps` is zero.
+pub fn period_from_fps(fps: u32) -> Duration {
+    assert!(fps > 0, "frame rate must be positive");
+    let fps = fps as u64;
+    Duration::from_nanos((NANOS_PER_SEC + fps / 2) / fps)
+}
+
+/// A number of occurrences per second, e.g. the rate at which a component should tick
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Frequency {
+    hz: f64,
+}
+
+impl Frequency {
+    /// Panics if `hz` isn't positive and finite
+    pub fn from_hz(hz: f64) -> Self {
+        assert!(
+            hz > 0.0 && hz.is_finite(),
+            "frequency must be positive and finite (got {})",
+            hz
+        );
+        Self { hz }
+    }
+
+    /// The frequency of something which happens once every `period`. Panics if `period` is
+    /// zero.
+    pub fn from_period(period: Duration) -> Self {
+        assert!(!period.is_zero(), "period must be non-zero");
+        Self::from_hz(1.0 / period.as_secs_f64())
+    }
+
+    pub fn hz(self) -> f64 {
+        self.hz
+    }
+
+    /// Time between occurrences, rounded to the nearest nanosecond. Frequencies above 1GHz
+    /// have a period of 1ns rather than zero, since a component returning a zero duration from
+    /// `tick` would tick forever.
+    pub fn period(self) -> Duration {
+        let nanos = (NANOS_PER_SEC as f64 / self.hz).round();
+        Duration::from_nanos((nanos.min(u64::MAX as f64) as u64).max(1))
+    }
+}
@@ -0,0 +1,126 @@
+//! Entry points for fuzzing the frame loop. Not part of the public API.
+
+use crate::budget::FrameCeiling;
+use crate::{
+    process_entity_frame_with_policy, ContextContainsRealtimeComponents, Entities, Entity,
+    RealtimeComponent, RealtimeComponentApplyEvent,
+};
+use entity_table::{ComponentTable, EntityAllocator};
+#[cfg(feature = "serialize")]
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Reference component which cycles through a list of periods, keeping track of the total
+/// time it has scheduled so it can be compared with the total time simulated
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct FuzzComponent {
+    periods: Vec<Duration>,
+    index: usize,
+    total_scheduled: Duration,
+}
+
+impl RealtimeComponent for FuzzComponent {
+    type Event = ();
+
+    fn tick(&mut self) -> (Self::Event, Duration) {
+        let period = self.periods[self.index % self.periods.len()];
+        self.index += 1;
+        self.total_scheduled += period;
+        ((), period)
+    }
+}
+
+impl RealtimeComponentApplyEvent<FuzzContext> for FuzzComponent {
+    fn apply_event(_: (), _: Entity, context: &mut FuzzContext) {
+        context.num_events += 1;
+    }
+}
+
+crate::declare_realtime_entity_module! {
+    components[FuzzContext] {
+        a: FuzzComponent,
+        b: FuzzComponent,
+    }
+}
+
+pub struct FuzzContext {
+    components: components::RealtimeComponents,
+    entities: ComponentTable<()>,
+    num_events: u64,
+}
+
+impl ContextContainsRealtimeComponents for FuzzContext {
+    type Components = components::RealtimeComponents;
+    fn components_mut(&mut self) -> &mut Self::Components {
+        &mut self.components
+    }
+    fn realtime_entities(&self) -> Entities<'_> {
+        self.entities.entities()
+    }
+}
+
+/// Interpret each 4 bytes of `data` as a number of microseconds
+fn durations(data: &[u8]) -> impl '_ + Iterator<Item = Duration> {
+    data.chunks_exact(4).map(|chunk| {
+        Duration::from_micros(u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]) as u64)
+    })
+}
+
+/// Derive component periods and frame durations from `data`, process the frames, and check
+/// that every component's schedule agrees with the total simulated time. Periods may be zero,
+/// so the frames are processed with a `FrameCeiling`, and the check is skipped once a frame is
+/// cut short by it.
+pub fn frame_loop(data: &[u8]) {
+    let (&num_periods, data) = match data.split_first() {
+        Some(split) => split,
+        None => return,
+    };
+    let num_periods = (num_periods as usize % 8) + 1;
+    let (period_data, frame_data) = data.split_at((num_periods * 4).min(data.len()));
+    let periods = durations(period_data).collect::<Vec<_>>();
+    if periods.is_empty() {
+        return;
+    }
+    let mut entity_allocator = EntityAllocator::default();
+    let mut context = FuzzContext {
+        components: Default::default(),
+        entities: Default::default(),
+        num_events: 0,
+    };
+    let entity = entity_allocator.alloc();
+    context.entities.insert(entity, ());
+    let component = FuzzComponent {
+        periods: periods.clone(),
+        index: 0,
+        total_scheduled: Duration::from_millis(0),
+    };
+    context.components.a.insert(entity, component.clone());
+    context.components.b.insert(
+        entity,
+        FuzzComponent {
+            index: 1,
+            ..component
+        },
+    );
+    let mut total_simulated = Duration::from_millis(0);
+    // limit the total number of ticks so inputs can't make the fuzzer time out
+    let mut ceiling = FrameCeiling::new(1 << 16);
+    for frame_duration in durations(frame_data) {
+        process_entity_frame_with_policy(entity, frame_duration, &mut context, &mut ceiling);
+        if ceiling.exceeded().is_some() {
+            return;
+        }
+        total_simulated += frame_duration;
+    }
+    for scheduled in [
+        context.components.a.get_with_schedule(entity),
+        context.components.b.get_with_schedule(entity),
+    ] {
+        let scheduled = scheduled.unwrap();
+        assert_eq!(
+            scheduled.component.total_scheduled,
+            total_simulated + scheduled.until_next_tick()
+        );
+    }
+}
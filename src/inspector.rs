@@ -0,0 +1,62 @@
+//! An egui widget for inspecting realtime components while a game is running
+
+use crate::{Entity, RealtimeDebugSummary};
+
+/// Action requested by interacting with a `RealtimeInspector`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InspectorAction {
+    /// The pause or resume button of a component was clicked
+    TogglePause { entity: Entity, component: String },
+}
+
+/// Renders a `RealtimeDebugSummary` as a table of entities and their realtime components, with
+/// progress bars showing the time until each component's next tick
+pub struct RealtimeInspector<'a> {
+    summary: &'a RealtimeDebugSummary,
+}
+
+impl<'a> RealtimeInspector<'a> {
+    pub fn new(summary: &'a RealtimeDebugSummary) -> Self {
+        Self { summary }
+    }
+
+    /// Draw the inspector, returning the actions requested by the user this frame
+    pub fn show(self, ui: &mut egui::Ui) -> Vec<InspectorAction> {
+        let mut actions = Vec::new();
+        egui::Grid::new("realtime_inspector")
+            .striped(true)
+            .num_columns(4)
+            .show(ui, |ui| {
+                ui.strong("Entity");
+                ui.strong("Component");
+                ui.strong("Until next tick");
+                ui.strong("State");
+                ui.end_row();
+                for entity_summary in &self.summary.entities {
+                    for component in &entity_summary.components {
+                        ui.label(format!("{:?}", entity_summary.entity));
+                        ui.label(&component.name);
+                        let progress = if component.period.is_zero() {
+                            1.
+                        } else {
+                            1. - component.until_next_tick.as_secs_f32()
+                                / component.period.as_secs_f32()
+                        };
+                        ui.add(
+                            egui::ProgressBar::new(progress.clamp(0., 1.))
+                                .text(format!("{:?}", component.until_next_tick)),
+                        );
+                        let label = if component.enabled { "Pause" } else { "Resume" };
+                        if ui.button(label).clicked() {
+                            actions.push(InspectorAction::TogglePause {
+                                entity: entity_summary.entity,
+                                component: component.name.clone(),
+                            });
+                        }
+                        ui.end_row();
+                    }
+                }
+            });
+        actions
+    }
+}
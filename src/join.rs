@@ -0,0 +1,66 @@
+//! Iterating over the entities which have components in several tables at once, like the joins
+//! of an ECS, so that logic coordinating components doesn't need nested lookups
+
+use crate::{ComponentTable, Entity, RealtimeComponent, RealtimeComponentTable};
+
+/// Iterate over the entities with components in both `a` and `b`, along with both components.
+/// Only the smaller of the two tables is iterated over, looking up each of its entities in the
+/// other table.
+pub fn iter_join<'a, A: RealtimeComponent, B: RealtimeComponent>(
+    a: &'a RealtimeComponentTable<A>,
+    b: &'a RealtimeComponentTable<B>,
+) -> impl 'a + Iterator<Item = (Entity, &'a A, &'a B)> {
+    let (a_entities, b_entities) = if a.len() <= b.len() {
+        (Some(a.entities()), None)
+    } else {
+        (None, Some(b.entities()))
+    };
+    a_entities
+        .into_iter()
+        .flatten()
+        .chain(b_entities.into_iter().flatten())
+        .filter_map(move |entity| Some((entity, a.get(entity)?, b.get(entity)?)))
+}
+
+/// Iterate over the entities with components in both a realtime table and a plain component
+/// table, e.g. to read the position of each entity with a realtime effect. Only the smaller of
+/// the two tables is iterated over.
+pub fn iter_join_component_table<'a, T: RealtimeComponent, U>(
+    realtime: &'a RealtimeComponentTable<T>,
+    table: &'a ComponentTable<U>,
+) -> impl 'a + Iterator<Item = (Entity, &'a T, &'a U)> {
+    let (realtime_entities, table_entities) = if realtime.len() <= table.len() {
+        (Some(realtime.entities()), None)
+    } else {
+        (None, Some(table.entities()))
+    };
+    realtime_entities
+        .into_iter()
+        .flatten()
+        .chain(table_entities.into_iter().flatten())
+        .filter_map(move |entity| Some((entity, realtime.get(entity)?, table.get(entity)?)))
+}
+
+/// Like `iter_join_component_table` but the components of the plain table can be modified, e.g.
+/// to write the appearance of each entity with a realtime effect. The plain table is always the
+/// one iterated over.
+pub fn iter_join_component_table_mut<'a, T: RealtimeComponent, U>(
+    realtime: &'a RealtimeComponentTable<T>,
+    table: &'a mut ComponentTable<U>,
+) -> impl 'a + Iterator<Item = (Entity, &'a T, &'a mut U)> {
+    table
+        .iter_mut()
+        .filter_map(move |(entity, data)| Some((entity, realtime.get(entity)?, data)))
+}
+
+/// Like `iter_join_component_table` but the realtime components can be modified, e.g. to
+/// retarget effects based on the position of their entity. The realtime table is always the one
+/// iterated over.
+pub fn iter_mut_join_component_table<'a, T: RealtimeComponent, U>(
+    realtime: &'a mut RealtimeComponentTable<T>,
+    table: &'a ComponentTable<U>,
+) -> impl 'a + Iterator<Item = (Entity, &'a mut T, &'a U)> {
+    realtime
+        .iter_mut()
+        .filter_map(move |(entity, component)| Some((entity, component, table.get(entity)?)))
+}
@@ -1,10 +1,97 @@
 use entity_table::ComponentTable;
-pub use entity_table::{ComponentTableIter, ComponentTableIterMut, Entities, Entity};
+pub use entity_table::{
+    ComponentTableIter, ComponentTableIterMut, Entities, Entity, EntityAllocator,
+};
+#[cfg(feature = "puffin")]
+#[doc(hidden)]
+pub use puffin; // Re-export puffin so it can be referenced in macro body
+#[cfg(feature = "rand")]
+pub use rand_core; // Re-export rand_core so it can be referenced in macro body
+#[cfg(feature = "rayon")]
+#[doc(hidden)]
+pub use rayon; // Re-export rayon so it can be referenced in macro body
 #[cfg(feature = "serialize")]
 pub use serde; // Re-export serde so it can be referenced in macro body
 #[cfg(feature = "serialize")]
 use serde::{Deserialize, Serialize};
+use smallvec::SmallVec;
+use std::any::Any;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::ops::ControlFlow;
+#[cfg(feature = "rayon")]
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::Arc;
 use std::time::Duration;
+/// Re-export typetag so that traits of dynamic components can be made serializable without
+/// depending on it directly. Bring it into scope with `use entity_table_realtime::typetag;`
+/// before using `#[typetag::serde]`. For example, a family of boxed components which all
+/// produce the same type of event can be stored in a single table and serialized. Note that the
+/// types generated by `declare_realtime_entity_module!` derive `Debug` and `Clone`, so the boxed
+/// trait objects must implement them too.
+///
+/// ```ignore
+/// use entity_table_realtime::typetag;
+///
+/// #[typetag::serde]
+/// trait Effect: RealtimeComponent<Event = EffectEvent> {}
+///
+/// #[typetag::serde]
+/// impl Effect for Flicker {}
+///
+/// impl RealtimeComponentApplyEvent<Context> for Box<dyn Effect> {
+///     fn apply_event(event: EffectEvent, entity: Entity, context: &mut Context) {
+///         // ...
+///     }
+/// }
+///
+/// declare_realtime_entity_module! {
+///     components[Context] {
+///         effect: Box<dyn Effect>,
+///     }
+/// }
+/// ```
+#[cfg(feature = "typetag")]
+pub use typetag;
+
+pub mod audio;
+pub mod budget;
+mod clock;
+pub mod combinators;
+pub mod composite;
+mod coordinator;
+pub mod erased;
+pub mod fixed;
+mod frequency;
+#[cfg(feature = "egui")]
+pub mod inspector;
+pub mod join;
+pub mod liveness;
+pub mod movement;
+pub mod prediction;
+pub mod rate_limit;
+pub mod replay;
+pub mod replication;
+pub mod scheduler;
+#[cfg(feature = "rhai")]
+pub mod scripted;
+pub mod seconds;
+pub mod sharded;
+pub mod shared;
+pub mod state_machine;
+pub mod stats;
+pub mod testing;
+pub mod timeline;
+pub mod watchdog;
+
+#[doc(hidden)]
+pub mod fuzz;
+
+pub use budget::{FrameBudgetPolicy, FrameProgress};
+pub use clock::{ManualTimeSource, RealtimeClock, SystemTimeSource, TimeSource};
+pub use coordinator::{RealtimeCoordinator, RealtimeModuleId};
+pub use frequency::{period_from_fps, period_from_hz, Frequency};
 
 /// A component of an entity which can produce realtime events
 pub trait RealtimeComponent {
@@ -13,6 +100,169 @@ pub trait RealtimeComponent {
 
     /// Generate an event, along with the time until the next tick should take place
     fn tick(&mut self) -> (Self::Event, Duration);
+
+    /// Called instead of `tick` when the component is ticked as part of a table, with the
+    /// entity it belongs to and how much time has passed. Implement this rather than `tick`
+    /// for components which need either. The default implementation calls `tick`.
+    fn tick_with(&mut self, context: &TickCtx) -> (Self::Event, Duration) {
+        let _ = context;
+        self.tick()
+    }
+
+    /// Called instead of `tick_with` when frames are processed with a random number generator
+    /// (e.g. by `process_entity_frame_with_rng`). Components which need randomness should draw
+    /// it from `rng` rather than owning a generator, so that a game seeded with a single
+    /// generator behaves deterministically. The default implementation calls `tick_with`.
+    #[cfg(feature = "rand")]
+    fn tick_with_rng(
+        &mut self,
+        context: &TickCtx,
+        rng: &mut dyn rand_core::RngCore,
+    ) -> (Self::Event, Duration) {
+        let _ = rng;
+        self.tick_with(context)
+    }
+
+    /// An event to apply when the component is inserted into its table with
+    /// `insert_with_event`, `insert_entity_data_with_events` or `insert_realtime_component`,
+    /// before its first tick, for setting up state in the context (e.g. registering a light
+    /// source). The default implementation returns `None`.
+    fn on_insert(&mut self) -> Option<Self::Event> {
+        None
+    }
+
+    /// An event to apply when the component is removed from its table with
+    /// `remove_with_event`, `remove_entity_with_events` or `remove_realtime_entity`, for
+    /// cleaning up state in the context (e.g. stopping a looping sound). Components which
+    /// remove themselves while applying their events should do so with one of these too. The
+    /// default implementation returns `None`.
+    fn on_remove(&mut self) -> Option<Self::Event> {
+        None
+    }
+
+    /// An event to apply when ticks of the component are dropped because it fell further behind
+    /// than its schedule's `max_missed_periods` allows, e.g. to spawn a single burst of
+    /// particles in place of the dropped ones. `dropped` is the number of ticks dropped. The
+    /// default implementation returns `None`.
+    fn on_ticks_dropped(&mut self, _dropped: u64) -> Option<Self::Event> {
+        None
+    }
+
+    /// Called instead of `tick` when time is skipped without generating events. `missed` is
+    /// the time that has passed since the component was due to tick. Returns the time until the
+    /// next tick. The default implementation ticks the component, discarding the events, until
+    /// it has caught up.
+    fn skip(&mut self, missed: Duration) -> Duration {
+        let mut missed = missed;
+        loop {
+            let (_, until_next_tick) = self.tick();
+            if until_next_tick > missed {
+                return until_next_tick - missed;
+            }
+            if until_next_tick == Duration::from_millis(0) {
+                return until_next_tick;
+            }
+            missed -= until_next_tick;
+        }
+    }
+}
+
+/// Allows tables of boxed components, such as `Box<dyn Trait>` where `Trait` has
+/// `RealtimeComponent` as a supertrait with a fixed event type
+impl<T: RealtimeComponent + ?Sized> RealtimeComponent for Box<T> {
+    type Event = T::Event;
+
+    fn tick(&mut self) -> (Self::Event, Duration) {
+        (**self).tick()
+    }
+
+    fn tick_with(&mut self, context: &TickCtx) -> (Self::Event, Duration) {
+        (**self).tick_with(context)
+    }
+
+    #[cfg(feature = "rand")]
+    fn tick_with_rng(
+        &mut self,
+        context: &TickCtx,
+        rng: &mut dyn rand_core::RngCore,
+    ) -> (Self::Event, Duration) {
+        (**self).tick_with_rng(context, rng)
+    }
+
+    fn skip(&mut self, missed: Duration) -> Duration {
+        (**self).skip(missed)
+    }
+
+    fn on_insert(&mut self) -> Option<Self::Event> {
+        (**self).on_insert()
+    }
+
+    fn on_remove(&mut self) -> Option<Self::Event> {
+        (**self).on_remove()
+    }
+
+    fn on_ticks_dropped(&mut self, dropped: u64) -> Option<Self::Event> {
+        (**self).on_ticks_dropped(dropped)
+    }
+}
+
+/// Passed to `RealtimeComponent::tick_with`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TickCtx {
+    /// The entity the component belongs to
+    pub entity: Entity,
+    /// Simulated time since the component last ticked, or since it was inserted if this is its
+    /// first tick. This is usually the duration returned by the previous tick, but can differ
+    /// if the component was rescheduled in the meantime, and excludes time spent disabled.
+    pub elapsed_since_last_tick: Duration,
+    /// Simulated time since the component was inserted, excluding time spent disabled
+    pub total_simulated_time: Duration,
+}
+
+/// The method an adapter was ticked with, for ticking the components it wraps the same way
+pub(crate) enum InnerTick<'a> {
+    Tick,
+    TickWith(&'a TickCtx),
+    #[cfg(feature = "rand")]
+    TickWithRng(&'a TickCtx, &'a mut dyn rand_core::RngCore),
+}
+
+impl InnerTick<'_> {
+    pub(crate) fn tick<T: RealtimeComponent + ?Sized>(
+        &mut self,
+        component: &mut T,
+    ) -> (T::Event, Duration) {
+        match self {
+            Self::Tick => component.tick(),
+            Self::TickWith(context) => component.tick_with(context),
+            #[cfg(feature = "rand")]
+            Self::TickWithRng(context, rng) => component.tick_with_rng(context, &mut **rng),
+        }
+    }
+}
+
+/// Optional random number generator passed from generated code to ticks. Without the `rand`
+/// feature this is always empty.
+#[doc(hidden)]
+#[derive(Default)]
+pub struct TickRng<'a> {
+    #[cfg(feature = "rand")]
+    rng: Option<&'a mut dyn rand_core::RngCore>,
+    lifetime: std::marker::PhantomData<&'a mut ()>,
+}
+
+impl<'a> TickRng<'a> {
+    pub fn none() -> Self {
+        Self::default()
+    }
+
+    #[cfg(feature = "rand")]
+    pub fn new(rng: &'a mut dyn rand_core::RngCore) -> Self {
+        Self {
+            rng: Some(rng),
+            lifetime: std::marker::PhantomData,
+        }
+    }
 }
 
 pub trait RealtimeComponentApplyEvent<C>: RealtimeComponent {
@@ -21,211 +271,2907 @@ pub trait RealtimeComponentApplyEvent<C>: RealtimeComponent {
     fn apply_event(event: <Self as RealtimeComponent>::Event, entity: Entity, context: &mut C);
 }
 
+/// Parse the literals used to specify initial delays in `declare_realtime_entity_module!`: an
+/// integer followed by one of the units `ns`, `us`, `ms` or `s` (e.g. `100ms`)
+#[doc(hidden)]
+pub const fn duration_from_literal(literal: &str) -> Duration {
+    const fn unit_is(bytes: &[u8], start: usize, unit: &[u8]) -> bool {
+        if bytes.len() - start != unit.len() {
+            return false;
+        }
+        let mut i = 0;
+        while i < unit.len() {
+            if bytes[start + i] != unit[i] {
+                return false;
+            }
+            i += 1;
+        }
+        true
+    }
+    let bytes = literal.as_bytes();
+    let mut value: u64 = 0;
+    let mut i = 0;
+    while i < bytes.len() && (bytes[i].is_ascii_digit() || bytes[i] == b'_') {
+        if bytes[i] != b'_' {
+            value = value * 10 + (bytes[i] - b'0') as u64;
+        }
+        i += 1;
+    }
+    if i == 0 {
+        panic!("initial delay must start with an integer");
+    }
+    if unit_is(bytes, i, b"ns") {
+        Duration::from_nanos(value)
+    } else if unit_is(bytes, i, b"us") {
+        Duration::from_micros(value)
+    } else if unit_is(bytes, i, b"ms") {
+        Duration::from_millis(value)
+    } else if unit_is(bytes, i, b"s") {
+        Duration::from_secs(value)
+    } else {
+        panic!("initial delay must end with one of the units ns, us, ms or s");
+    }
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! realtime_initial_delay {
+    () => {
+        ::std::time::Duration::from_nanos(0)
+    };
+    ($delay:literal) => {
+        $crate::duration_from_literal(stringify!($delay))
+    };
+}
+
+/// Convert a duration to whole nanoseconds, saturating at `u64::MAX` (about 584 years)
+#[doc(hidden)]
+pub fn duration_to_nanos(duration: Duration) -> u64 {
+    duration.as_nanos().min(u64::MAX as u128) as u64
+}
+
+/// Check, in builds with debug assertions, that a step of an entity's frame kept a component's
+/// schedule consistent. `before` and `after` are the time until the component's next tick
+/// before and after a step simulating `step` nanoseconds, and `ticked` is whether the component
+/// produced an event. Components must tick exactly when their schedule reaches zero, and
+/// schedules of components which didn't tick must decrease by exactly the simulated time.
+#[doc(hidden)]
+#[inline]
+#[track_caller]
+pub fn debug_assert_schedule_step(
+    component: &'static str,
+    entity: Entity,
+    before: Option<u64>,
+    after: Option<u64>,
+    ticked: bool,
+    step: u64,
+) {
+    #[cfg(debug_assertions)]
+    if let Some(before) = before {
+        assert!(
+            before >= step,
+            "schedule of {} of {:?} underflowed: {}ns until its next tick but stepped {}ns",
+            component,
+            entity,
+            before,
+            step
+        );
+        if ticked {
+            assert!(
+                before == step,
+                "{} of {:?} ticked with {}ns until its next tick",
+                component,
+                entity,
+                before - step
+            );
+        } else {
+            assert!(
+                before != step,
+                "{} of {:?} was due but didn't tick",
+                component,
+                entity
+            );
+            assert!(
+                after == Some(before - step),
+                "schedule of {} of {:?} went from {}ns to {:?}ns over a {}ns step",
+                component,
+                entity,
+                before,
+                after,
+                step
+            );
+        }
+    }
+    let _ = (component, entity, before, after, ticked, step);
+}
+
+/// Serialize `u64` nanosecond fields as `Duration`s so the serialized format doesn't depend on
+/// the internal representation
+#[cfg(feature = "serialize")]
+mod serialize_nanos {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use std::time::Duration;
+
+    pub fn serialize<S: Serializer>(nanos: &u64, serializer: S) -> Result<S::Ok, S::Error> {
+        Duration::from_nanos(*nanos).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u64, D::Error> {
+        Duration::deserialize(deserializer).map(super::duration_to_nanos)
+    }
+}
+
+/// A component along with its schedule. Schedules are stored as `u64` nanoseconds rather
+/// than `Duration`s since the frame loop spends most of its time comparing and subtracting
+/// them, which is cheaper on plain integers, and it makes each entry in a table smaller.
+///
+/// Breaking change in 0.3.0: the schedule was previously the public field
+/// `until_next_tick: Duration`. Construct components with `new` rather than a struct literal,
+/// and use `until_next_tick` and `set_until_next_tick` in place of the field. Tables
+/// serialized by 0.2 still deserialize.
 #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 #[derive(Debug, Clone)]
 pub struct ScheduledRealtimeComponent<T: RealtimeComponent> {
     pub component: T,
-    pub until_next_tick: Duration,
+    #[cfg_attr(
+        feature = "serialize",
+        serde(rename = "until_next_tick", with = "serialize_nanos")
+    )]
+    until_next_tick_nanos: u64,
+    #[cfg_attr(
+        feature = "serialize",
+        serde(rename = "period", with = "serialize_nanos", default)
+    )]
+    period_nanos: u64,
+    #[cfg_attr(feature = "serialize", serde(default = "enabled_default"))]
+    enabled: bool,
+    #[cfg_attr(
+        feature = "serialize",
+        serde(rename = "since_last_tick", with = "serialize_nanos", default)
+    )]
+    since_last_tick_nanos: u64,
+    #[cfg_attr(
+        feature = "serialize",
+        serde(rename = "age", with = "serialize_nanos", default)
+    )]
+    age_nanos: u64,
+    #[cfg_attr(feature = "serialize", serde(default))]
+    max_missed_periods: Option<u64>,
+    #[cfg(feature = "stats")]
+    #[cfg_attr(feature = "serialize", serde(skip))]
+    stats: stats::ComponentStats,
+}
+
+#[cfg(feature = "serialize")]
+fn enabled_default() -> bool {
+    true
+}
+
+impl<T: RealtimeComponent> ScheduledRealtimeComponent<T> {
+    pub fn new(component: T, until_next_tick: Duration) -> Self {
+        Self {
+            component,
+            until_next_tick_nanos: duration_to_nanos(until_next_tick),
+            period_nanos: 0,
+            enabled: true,
+            since_last_tick_nanos: 0,
+            age_nanos: 0,
+            max_missed_periods: None,
+            #[cfg(feature = "stats")]
+            stats: Default::default(),
+        }
+    }
+
+    /// Counters of the work done by the component. Always zero without the `stats` feature.
+    #[cfg(feature = "stats")]
+    pub fn stats(&self) -> stats::ComponentStats {
+        self.stats
+    }
+
+    /// Counters of the work done by the component. Always zero without the `stats` feature.
+    #[cfg(not(feature = "stats"))]
+    pub fn stats(&self) -> stats::ComponentStats {
+        Default::default()
+    }
+
+    fn record_elapsed(&mut self, nanos: u64) {
+        self.since_last_tick_nanos = self.since_last_tick_nanos.saturating_add(nanos);
+        self.age_nanos = self.age_nanos.saturating_add(nanos);
+        #[cfg(feature = "stats")]
+        {
+            self.stats.simulated_time += Duration::from_nanos(nanos);
+        }
+    }
+
+    /// Simulated time since the component was inserted, excluding time spent disabled
+    pub fn age(&self) -> Duration {
+        Duration::from_nanos(self.age_nanos)
+    }
+
+    /// Disabled components don't tick and their schedules don't advance, so they resume with
+    /// the same time until their next tick once enabled
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    pub fn until_next_tick(&self) -> Duration {
+        Duration::from_nanos(self.until_next_tick_nanos)
+    }
+
+    pub fn until_next_tick_nanos(&self) -> u64 {
+        self.until_next_tick_nanos
+    }
+
+    pub fn set_until_next_tick(&mut self, until_next_tick: Duration) {
+        self.until_next_tick_nanos = duration_to_nanos(until_next_tick);
+    }
+
+    /// The duration returned by the most recent tick of the component
+    pub fn period(&self) -> Duration {
+        Duration::from_nanos(self.period_nanos)
+    }
+
+    /// Drop the ticks of a frame which would leave the component behind by more than
+    /// `max_missed_periods` periods, e.g. to prevent a storm of particles when a game window is
+    /// restored. The component's `on_ticks_dropped` event is applied in place of the dropped
+    /// ticks.
+    pub fn with_max_missed_periods(mut self, max_missed_periods: u64) -> Self {
+        self.max_missed_periods = Some(max_missed_periods);
+        self
+    }
+
+    pub fn set_max_missed_periods(&mut self, max_missed_periods: Option<u64>) {
+        self.max_missed_periods = max_missed_periods;
+    }
+
+    pub fn max_missed_periods(&self) -> Option<u64> {
+        self.max_missed_periods
+    }
+
+    /// Delay the component's next tick by whole periods so that it ticks at most
+    /// `max_missed_periods + 1` times during a frame of `frame_duration`, returning its
+    /// `on_ticks_dropped` event if any ticks were dropped. Components which are disabled, have
+    /// no limit or haven't ticked yet are left alone.
+    pub fn drop_excess_ticks(&mut self, frame_duration: Duration) -> Option<T::Event> {
+        let max_missed_periods = self.max_missed_periods?;
+        if !self.enabled || self.period_nanos == 0 {
+            return None;
+        }
+        let behind = duration_to_nanos(frame_duration).checked_sub(self.until_next_tick_nanos)?;
+        let dropped = (behind / self.period_nanos).checked_sub(max_missed_periods)?;
+        if dropped == 0 {
+            return None;
+        }
+        self.until_next_tick_nanos = self
+            .until_next_tick_nanos
+            .saturating_add(dropped.saturating_mul(self.period_nanos));
+        #[cfg(feature = "log")]
+        log::warn!(
+            "dropping {} ticks of {} which fell behind",
+            dropped,
+            ::std::any::type_name::<T>()
+        );
+        self.component.on_ticks_dropped(dropped)
+    }
+
+    /// Bring the next tick `nanos` nanoseconds closer. Panics in debug builds if `nanos`
+    /// exceeds the time until the next tick.
+    pub fn advance_nanos(&mut self, nanos: u64) {
+        debug_assert!(
+            nanos <= self.until_next_tick_nanos,
+            "advanced {}ns past the next tick of {}",
+            nanos - self.until_next_tick_nanos,
+            ::std::any::type_name::<T>()
+        );
+        self.until_next_tick_nanos -= nanos;
+        self.record_elapsed(nanos);
+    }
+
+    /// Tick the component on behalf of `entity` and schedule its next tick
+    pub fn tick(&mut self, entity: Entity) -> T::Event {
+        self.tick_with_tick_rng(entity, &mut TickRng::none())
+    }
+
+    /// Like `tick` but the component may draw random numbers from `rng`
+    #[cfg(feature = "rand")]
+    pub fn tick_with_rng(&mut self, entity: Entity, rng: &mut dyn rand_core::RngCore) -> T::Event {
+        self.tick_with_tick_rng(entity, &mut TickRng::new(rng))
+    }
+
+    #[doc(hidden)]
+    pub fn tick_with_tick_rng(&mut self, entity: Entity, rng: &mut TickRng) -> T::Event {
+        crate::realtime_profile_scope!("tick", ::std::any::type_name::<T>());
+        stats::record_tick();
+        self.record_elapsed(self.until_next_tick_nanos);
+        #[cfg(feature = "stats")]
+        {
+            self.stats.ticks += 1;
+            self.stats.last_tick = Some(::std::time::Instant::now());
+        }
+        let context = TickCtx {
+            entity,
+            elapsed_since_last_tick: Duration::from_nanos(self.since_last_tick_nanos),
+            total_simulated_time: self.age(),
+        };
+        self.since_last_tick_nanos = 0;
+        let component = &mut self.component;
+        let (event, until_next_tick) = watchdog::watch(
+            watchdog::TickPhase::Tick,
+            ::std::any::type_name::<T>(),
+            Some(entity),
+            || {
+                #[cfg(feature = "rand")]
+                if let Some(rng) = rng.rng.as_deref_mut() {
+                    return component.tick_with_rng(&context, rng);
+                }
+                let _ = rng;
+                component.tick_with(&context)
+            },
+        );
+        #[cfg(feature = "log")]
+        if until_next_tick.is_zero() {
+            log::warn!(
+                "{} returned a zero duration from tick so it will tick again immediately",
+                ::std::any::type_name::<T>()
+            );
+        }
+        self.until_next_tick_nanos = duration_to_nanos(until_next_tick);
+        self.period_nanos = self.until_next_tick_nanos;
+        event
+    }
+
+    /// Advance the schedule by `duration` without generating events. If the component would
+    /// have ticked during that time it is told how much time it missed.
+    pub fn skip(&mut self, duration: Duration) {
+        if !self.enabled {
+            return;
+        }
+        let nanos = duration_to_nanos(duration);
+        self.record_elapsed(nanos);
+        if self.until_next_tick_nanos > nanos {
+            self.until_next_tick_nanos -= nanos;
+        } else {
+            let missed = Duration::from_nanos(nanos - self.until_next_tick_nanos);
+            self.until_next_tick_nanos = duration_to_nanos(self.component.skip(missed));
+        }
+    }
+}
+
+/// Details of a table exceeding its event cap, passed to the callback given to
+/// `RealtimeComponentTable::set_event_cap`
+#[derive(Debug, Clone, Copy)]
+pub struct EventCapExceeded {
+    /// Type name of the table's component
+    pub component: &'static str,
+    /// The entity whose component was the first to be deferred this frame
+    pub entity: Entity,
+    pub max_events_per_frame: u32,
+}
+
+/// Details of a component returning a duration from `tick` longer than its table's maximum
+/// period, passed to the callback given to `RealtimeComponentTable::set_max_period`
+#[derive(Debug, Clone, Copy)]
+pub struct PeriodClamped {
+    /// Type name of the table's component
+    pub component: &'static str,
+    pub entity: Entity,
+    /// The duration returned by `tick`
+    pub returned: Duration,
+    pub max_period: Duration,
+}
+
+/// Callback of `RealtimeComponentTable::set_event_cap`
+type OnEventCapExceeded = Arc<dyn Fn(EventCapExceeded) + Send + Sync>;
+
+/// Callback of `RealtimeComponentTable::set_max_period`
+type OnPeriodClamped = Arc<dyn Fn(PeriodClamped) + Send + Sync>;
+
+/// The event cap and maximum period of a table, along with the events counted towards the cap
+/// this frame
+#[derive(Clone, Default)]
+struct TableLimits {
+    max_events_per_frame: Option<u32>,
+    on_overflow: Option<OnEventCapExceeded>,
+    events_this_frame: u32,
+    overflowed: bool,
+    max_period_nanos: Option<u64>,
+    on_period_clamped: Option<OnPeriodClamped>,
+}
+
+impl fmt::Debug for TableLimits {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("TableLimits")
+            .field("max_events_per_frame", &self.max_events_per_frame)
+            .field("events_this_frame", &self.events_this_frame)
+            .field("overflowed", &self.overflowed)
+            .field("max_period_nanos", &self.max_period_nanos)
+            .finish_non_exhaustive()
+    }
+}
+
+impl TableLimits {
+    fn set_event_cap(&mut self, max_events_per_frame: u32, on_overflow: OnEventCapExceeded) {
+        self.max_events_per_frame = Some(max_events_per_frame);
+        self.on_overflow = Some(on_overflow);
+    }
+
+    fn clear_event_cap(&mut self) {
+        self.max_events_per_frame = None;
+        self.on_overflow = None;
+    }
+
+    fn set_max_period(&mut self, max_period: Duration, on_clamped: OnPeriodClamped) {
+        self.max_period_nanos = Some(duration_to_nanos(max_period));
+        self.on_period_clamped = Some(on_clamped);
+    }
+
+    fn clear_max_period(&mut self) {
+        self.max_period_nanos = None;
+        self.on_period_clamped = None;
+    }
+
+    /// Returns true if no more events may be produced this frame
+    fn cap_reached(&self) -> bool {
+        self.max_events_per_frame
+            .is_some_and(|max_events_per_frame| self.events_this_frame >= max_events_per_frame)
+    }
+
+    /// Returns true if `scheduled` is due within `frame_remaining` nanoseconds but the event
+    /// cap has been reached, so it won't tick this frame
+    fn is_capped<T: RealtimeComponent>(
+        &self,
+        scheduled: &ScheduledRealtimeComponent<T>,
+        frame_remaining: u64,
+    ) -> bool {
+        self.cap_reached() && scheduled.until_next_tick_nanos <= frame_remaining
+    }
+
+    /// Record that the component of `entity` was held back by the event cap, calling the
+    /// overflow callback the first time this happens each frame
+    fn report_capped<T: RealtimeComponent>(&mut self, entity: Entity) {
+        if self.overflowed {
+            return;
+        }
+        self.overflowed = true;
+        if let (Some(max_events_per_frame), Some(on_overflow)) =
+            (self.max_events_per_frame, self.on_overflow.as_ref())
+        {
+            on_overflow(EventCapExceeded {
+                component: ::std::any::type_name::<T>(),
+                entity,
+                max_events_per_frame,
+            });
+        }
+    }
+
+    /// Called after the component of `entity` ticks to count its event towards the event cap
+    /// and clamp its period, returning true if its period was clamped
+    fn after_tick<T: RealtimeComponent>(
+        &mut self,
+        entity: Entity,
+        scheduled: &mut ScheduledRealtimeComponent<T>,
+    ) -> bool {
+        if self.max_events_per_frame.is_some() {
+            self.events_this_frame += 1;
+        }
+        self.clamp_period(entity, scheduled)
+    }
+
+    /// Start counting events towards the event cap for a new frame
+    fn end_frame(&mut self) {
+        self.events_this_frame = 0;
+        self.overflowed = false;
+    }
+
+    /// Clamp the period the component of `entity` just returned from `tick` to the maximum
+    /// period, returning true if it was clamped
+    fn clamp_period<T: RealtimeComponent>(
+        &self,
+        entity: Entity,
+        scheduled: &mut ScheduledRealtimeComponent<T>,
+    ) -> bool {
+        let max_period_nanos = match self.max_period_nanos {
+            Some(max_period_nanos) => max_period_nanos,
+            None => return false,
+        };
+        if scheduled.period_nanos <= max_period_nanos {
+            return false;
+        }
+        let clamped = PeriodClamped {
+            component: ::std::any::type_name::<T>(),
+            entity,
+            returned: scheduled.period(),
+            max_period: Duration::from_nanos(max_period_nanos),
+        };
+        scheduled.period_nanos = max_period_nanos;
+        scheduled.until_next_tick_nanos = scheduled.until_next_tick_nanos.min(max_period_nanos);
+        #[cfg(feature = "log")]
+        log::warn!(
+            "{} returned {:?} from tick for {:?}, clamping to {:?}",
+            clamped.component,
+            clamped.returned,
+            entity,
+            clamped.max_period
+        );
+        if let Some(on_period_clamped) = self.on_period_clamped.as_ref() {
+            on_period_clamped(clamped);
+        }
+        true
+    }
+}
+
+/// The limits of a table whose components are being ticked in parallel, shared between the
+/// threads ticking them. Which components are deferred once the event cap is reached depends
+/// on how the threads are scheduled.
+#[cfg(feature = "rayon")]
+#[doc(hidden)]
+#[derive(Debug)]
+pub struct ParallelTableLimits {
+    limits: TableLimits,
+    events_this_frame: AtomicU32,
+    overflowed: AtomicBool,
+}
+
+#[cfg(feature = "rayon")]
+impl ParallelTableLimits {
+    /// Returns true if the component of `entity` may tick, counting its event towards the
+    /// event cap. Otherwise it must be deferred to the next frame.
+    pub fn try_tick<T: RealtimeComponent>(&self, entity: Entity) -> bool {
+        let max_events_per_frame = match self.limits.max_events_per_frame {
+            Some(max_events_per_frame) => max_events_per_frame,
+            None => return true,
+        };
+        let reserved = self
+            .events_this_frame
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |events| {
+                (events < max_events_per_frame).then_some(events + 1)
+            })
+            .is_ok();
+        if !reserved && !self.overflowed.swap(true, Ordering::Relaxed) {
+            if let Some(on_overflow) = self.limits.on_overflow.as_ref() {
+                on_overflow(EventCapExceeded {
+                    component: ::std::any::type_name::<T>(),
+                    entity,
+                    max_events_per_frame,
+                });
+            }
+        }
+        reserved
+    }
+
+    /// Called after the component of `entity` ticks to apply the table's maximum period
+    pub fn after_tick<T: RealtimeComponent>(
+        &self,
+        entity: Entity,
+        scheduled: &mut ScheduledRealtimeComponent<T>,
+    ) {
+        self.limits.clamp_period(entity, scheduled);
+    }
+
+    /// Record the events counted towards the event cap in the table
+    pub fn finish<T: RealtimeComponent>(self, table: &mut RealtimeComponentTable<T>) {
+        table.limits.events_this_frame = self.events_this_frame.into_inner();
+        table.limits.overflowed = self.overflowed.into_inner();
+    }
+}
+
+/// How `RealtimeComponentTable::insert` schedules the first tick of the components it inserts
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum SchedulePolicy {
+    /// Components tick as soon as the entity is next processed
+    #[default]
+    Immediate,
+    /// Components first tick after a fixed delay, e.g. their period
+    Delay(Duration),
+    /// Components first tick after a delay less than the given duration, chosen by hashing the
+    /// entity. Components spawned together don't tick in unison, but the delay for an entity is
+    /// deterministic.
+    Spread(Duration),
+}
+
+impl SchedulePolicy {
+    /// The time until the first tick of a component of `entity` inserted under this policy
+    pub fn until_first_tick(self, entity: Entity) -> Duration {
+        match self {
+            Self::Immediate => Duration::from_nanos(0),
+            Self::Delay(delay) => delay,
+            Self::Spread(range) => {
+                let range = duration_to_nanos(range);
+                if range == 0 {
+                    return Duration::from_nanos(0);
+                }
+                let mut hasher = std::collections::hash_map::DefaultHasher::new();
+                entity.hash(&mut hasher);
+                Duration::from_nanos(hasher.finish() % range)
+            }
+        }
+    }
 }
 
+/// The most times `RealtimeComponentTable::fast_forward` ticks a component at the same instant
+/// before giving up on it for the rest of the fast-forward
+pub const MAX_TICKS_PER_INSTANT: u32 = 1024;
+
 #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 #[derive(Debug, Clone)]
-pub struct RealtimeComponentTable<T: RealtimeComponent>(
-    ComponentTable<ScheduledRealtimeComponent<T>>,
-);
+pub struct RealtimeComponentTable<T: RealtimeComponent> {
+    components: ComponentTable<ScheduledRealtimeComponent<T>>,
+    /// Total simulated time the table has been advanced by, in nanoseconds
+    #[cfg_attr(feature = "serialize", serde(with = "serialize_nanos", default))]
+    simulated_time: u64,
+    /// Event cap and maximum period, which are configuration rather than state so aren't
+    /// serialized
+    #[cfg_attr(feature = "serialize", serde(skip))]
+    limits: TableLimits,
+    /// Policy for scheduling the first tick of inserted components
+    #[cfg_attr(feature = "serialize", serde(default))]
+    schedule_policy: SchedulePolicy,
+    /// Incremented whenever a component is inserted or removed or a schedule may have been
+    /// modified, for indexes of the table's schedules
+    #[cfg_attr(feature = "serialize", serde(skip))]
+    schedule_generation: u64,
+}
 
 impl<T: RealtimeComponent> Default for RealtimeComponentTable<T> {
     fn default() -> Self {
-        Self(Default::default())
+        Self {
+            components: Default::default(),
+            simulated_time: 0,
+            limits: Default::default(),
+            schedule_policy: Default::default(),
+            schedule_generation: 0,
+        }
     }
 }
 
 impl<T: RealtimeComponent> RealtimeComponentTable<T> {
     pub fn clear(&mut self) {
-        self.0.clear();
+        self.schedule_generation += 1;
+        self.components.clear();
     }
     pub fn is_empty(&self) -> bool {
-        self.0.is_empty()
+        self.components.is_empty()
     }
     pub fn len(&self) -> usize {
-        self.0.len()
+        self.components.len()
     }
     pub fn insert_with_schedule(
         &mut self,
         entity: Entity,
         data: ScheduledRealtimeComponent<T>,
     ) -> Option<ScheduledRealtimeComponent<T>> {
-        self.0.insert(entity, data)
+        liveness::debug_assert_live(entity, ::std::any::type_name::<T>());
+        self.schedule_generation += 1;
+        self.components.insert(entity, data)
     }
+    /// Insert a component scheduled according to the table's schedule policy. Returns the
+    /// component which was replaced, if any.
     pub fn insert(&mut self, entity: Entity, data: T) -> Option<T> {
+        let until_first_tick = self.schedule_policy.until_first_tick(entity);
         self.insert_with_schedule(
             entity,
-            ScheduledRealtimeComponent {
-                component: data,
-                until_next_tick: Duration::from_millis(0),
-            },
+            ScheduledRealtimeComponent::new(data, until_first_tick),
+        )
+        .map(|c| c.component)
+    }
+    /// Set how `insert` schedules the first tick of the components it inserts
+    pub fn with_schedule_policy(mut self, schedule_policy: SchedulePolicy) -> Self {
+        self.schedule_policy = schedule_policy;
+        self
+    }
+    pub fn set_schedule_policy(&mut self, schedule_policy: SchedulePolicy) {
+        self.schedule_policy = schedule_policy;
+    }
+    pub fn schedule_policy(&self) -> SchedulePolicy {
+        self.schedule_policy
+    }
+    /// Insert a component along with the event returned by its `on_insert`. Returns the
+    /// component which was replaced, if any.
+    pub fn insert_with_event(
+        &mut self,
+        entity: Entity,
+        mut data: T,
+    ) -> (Option<T>, Option<T::Event>) {
+        let event = data.on_insert();
+        (self.insert(entity, data), event)
+    }
+    /// Insert a component whose first tick is `base_delay` plus a random duration less than
+    /// `jitter_range`, so that components spawned together don't tick in unison
+    #[cfg(feature = "rand")]
+    pub fn insert_with_jitter(
+        &mut self,
+        entity: Entity,
+        data: T,
+        base_delay: Duration,
+        jitter_range: Duration,
+        rng: &mut impl rand_core::RngCore,
+    ) -> Option<T> {
+        // scale a random u64 into the range rather than taking a remainder to avoid bias
+        let jitter_nanos =
+            ((rng.next_u64() as u128 * duration_to_nanos(jitter_range) as u128) >> 64) as u64;
+        self.insert_with_schedule(
+            entity,
+            ScheduledRealtimeComponent::new(data, base_delay + Duration::from_nanos(jitter_nanos)),
         )
         .map(|c| c.component)
     }
+    /// Insert a batch of components with their first ticks spread evenly across `period`, so
+    /// that components spawned together don't all tick in the same frame. The `i`th of `n`
+    /// components first ticks after `period * i / n`. Components replaced by the insertion are
+    /// dropped.
+    pub fn insert_staggered<I>(&mut self, components: I, period: Duration)
+    where
+        I: IntoIterator<Item = (Entity, T)>,
+        I::IntoIter: ExactSizeIterator,
+    {
+        let components = components.into_iter();
+        let n = components.len() as u128;
+        let period_nanos = duration_to_nanos(period) as u128;
+        for (i, (entity, data)) in components.enumerate() {
+            let delay_nanos = (period_nanos * i as u128 / n) as u64;
+            self.insert_with_schedule(
+                entity,
+                ScheduledRealtimeComponent::new(data, Duration::from_nanos(delay_nanos)),
+            );
+        }
+    }
     pub fn contains(&self, entity: Entity) -> bool {
-        self.0.contains(entity)
+        self.components.contains(entity)
     }
     pub fn remove_with_schedule(
         &mut self,
         entity: Entity,
     ) -> Option<ScheduledRealtimeComponent<T>> {
-        self.0.remove(entity)
+        self.schedule_generation += 1;
+        self.components.remove(entity)
+    }
+    pub fn remove(&mut self, entity: Entity) -> Option<T> {
+        let removed = self.remove_with_schedule(entity).map(|c| c.component);
+        #[cfg(feature = "log")]
+        if removed.is_none() {
+            log::warn!(
+                "attempted to remove absent {} from {:?}",
+                ::std::any::type_name::<T>(),
+                entity
+            );
+        }
+        removed
+    }
+    /// Remove the component of an entity along with the event returned by its `on_remove`
+    pub fn remove_with_event(&mut self, entity: Entity) -> Option<(T, Option<T::Event>)> {
+        self.remove_with_schedule(entity).map(|scheduled| {
+            let mut component = scheduled.component;
+            let event = component.on_remove();
+            (component, event)
+        })
+    }
+    pub fn get_with_schedule(&self, entity: Entity) -> Option<&ScheduledRealtimeComponent<T>> {
+        liveness::debug_assert_live(entity, ::std::any::type_name::<T>());
+        self.components.get(entity)
+    }
+    pub fn get_with_schedule_mut(
+        &mut self,
+        entity: Entity,
+    ) -> Option<&mut ScheduledRealtimeComponent<T>> {
+        self.schedule_generation += 1;
+        self.get_with_schedule_mut_untracked(entity)
+    }
+    /// Like `get_with_schedule_mut` but leaves the schedule generation unchanged, for
+    /// schedulers which update their own index
+    pub(crate) fn get_with_schedule_mut_untracked(
+        &mut self,
+        entity: Entity,
+    ) -> Option<&mut ScheduledRealtimeComponent<T>> {
+        liveness::debug_assert_live(entity, ::std::any::type_name::<T>());
+        self.components.get_mut(entity)
+    }
+    /// Bring the next tick of every enabled component other than those of `excluded` `nanos`
+    /// nanoseconds closer, leaving the schedule generation unchanged, for schedulers which
+    /// account for the excluded components themselves
+    pub(crate) fn advance_enabled_nanos(&mut self, nanos: u64, excluded: &[Entity]) {
+        for (entity, scheduled) in self.components.iter_mut() {
+            if scheduled.enabled && !excluded.contains(&entity) {
+                scheduled.advance_nanos(nanos);
+            }
+        }
+    }
+    /// Replace the table's components and their schedules, and its simulated time, with those
+    /// of `other`, e.g. a copy received from a server. The table's event cap, maximum period
+    /// and schedule policy are configuration rather than state, so they're kept.
+    pub fn restore_state(&mut self, other: Self) {
+        self.components = other.components;
+        self.simulated_time = other.simulated_time;
+        self.schedule_generation += 1;
+    }
+    /// Counter which changes whenever a component is inserted or removed, or a schedule may
+    /// have been modified other than by advancing every enabled component together. Indexes of
+    /// the table's schedules (e.g. `scheduler::IndexedScheduler`) compare it to tell when they
+    /// need rebuilding. Not serialized.
+    pub fn schedule_generation(&self) -> u64 {
+        self.schedule_generation
+    }
+    pub fn get(&self, entity: Entity) -> Option<&T> {
+        self.get_with_schedule(entity).map(|c| &c.component)
+    }
+    /// Counters of the work done by the component of an entity. Always zero without the `stats`
+    /// feature.
+    pub fn component_stats(&self, entity: Entity) -> Option<stats::ComponentStats> {
+        self.get_with_schedule(entity).map(|c| c.stats())
+    }
+    /// Enable or disable the component of an entity without affecting its schedule. Returns
+    /// false if the entity has no component in this table.
+    pub fn set_enabled(&mut self, entity: Entity, enabled: bool) -> bool {
+        if let Some(scheduled) = self.get_with_schedule_mut(entity) {
+            scheduled.set_enabled(enabled);
+            true
+        } else {
+            false
+        }
+    }
+    pub fn get_mut(&mut self, entity: Entity) -> Option<&mut T> {
+        self.get_with_schedule_mut_untracked(entity)
+            .map(|c| &mut c.component)
+    }
+    pub fn iter_with_schedule(&self) -> ComponentTableIter<ScheduledRealtimeComponent<T>> {
+        self.components.iter()
+    }
+    pub fn iter_with_schedule_mut(
+        &mut self,
+    ) -> ComponentTableIterMut<ScheduledRealtimeComponent<T>> {
+        self.schedule_generation += 1;
+        self.components.iter_mut()
+    }
+    pub fn iter(&self) -> RealtimeComponentTableIter<T> {
+        RealtimeComponentTableIter(self.components.iter())
+    }
+    pub fn iter_mut(&mut self) -> RealtimeComponentTableIterMut<T> {
+        RealtimeComponentTableIterMut(self.components.iter_mut())
+    }
+    pub fn entities(&self) -> impl '_ + Iterator<Item = Entity> {
+        self.iter().map(|(entity, _)| entity)
+    }
+    /// Remove the components of entities which are no longer allocated by `allocator`, e.g.
+    /// because they were despawned without removing their realtime components. The removed
+    /// components' `on_remove` events are discarded.
+    pub fn retain_live(&mut self, allocator: &EntityAllocator) {
+        let dead = self
+            .entities()
+            .filter(|&entity| !allocator.exists(entity))
+            .collect::<Vec<_>>();
+        for entity in dead {
+            self.remove_with_schedule(entity);
+        }
+    }
+    /// The time until the soonest tick of any enabled component in the table, or `None` if
+    /// there are no enabled components. Tables aren't indexed by schedule, so this visits every
+    /// component. Drivers which process a single table's frames with
+    /// `scheduler::process_table_frame` and query its next tick every frame can process it with
+    /// a `scheduler::IndexedScheduler` and ask that instead, in O(log n) time.
+    pub fn min_until_next_tick(&self) -> Option<Duration> {
+        self.components
+            .iter()
+            .filter(|(_, scheduled)| scheduled.enabled)
+            .map(|(_, scheduled)| scheduled.until_next_tick_nanos)
+            .min()
+            .map(Duration::from_nanos)
+    }
+    /// The time until the component of an entity next ticks, or `None` if the entity has no
+    /// component or its component is disabled
+    pub fn time_to_due(&self, entity: Entity) -> Option<Duration> {
+        self.get_with_schedule(entity)
+            .filter(|scheduled| scheduled.enabled)
+            .map(|scheduled| scheduled.until_next_tick())
+    }
+    /// Returns true if the component of an entity would tick during a frame of
+    /// `frame_duration`, e.g. for acting just before an emitter's next burst
+    pub fn is_due(&self, entity: Entity, frame_duration: Duration) -> bool {
+        self.get_with_schedule(entity).is_some_and(|scheduled| {
+            scheduled.enabled
+                && scheduled.until_next_tick_nanos <= duration_to_nanos(frame_duration)
+        })
+    }
+    /// Replace the time until each component next ticks with the result of calling `f` with
+    /// its entity and current time until next tick, e.g. doubling every schedule for a
+    /// slow-time spell. Disabled components are included.
+    pub fn map_schedules(&mut self, mut f: impl FnMut(Entity, Duration) -> Duration) {
+        self.schedule_generation += 1;
+        for (entity, scheduled) in self.components.iter_mut() {
+            scheduled.until_next_tick_nanos =
+                duration_to_nanos(f(entity, scheduled.until_next_tick()));
+        }
+    }
+    /// The components sorted by their time until next tick, soonest first. Components due at
+    /// the same time are in the order `iter` yields them. This collects and sorts the whole
+    /// table, taking O(n log n) time. Tables processed with `scheduler::process_table_frame`
+    /// and a `scheduler::IndexedScheduler` can visit their enabled components in order without
+    /// sorting each time, with the scheduler's `iter_by_schedule`.
+    pub fn sorted_by_schedule(&self) -> Vec<(Entity, &ScheduledRealtimeComponent<T>)> {
+        let mut by_schedule = self.iter_with_schedule().collect::<Vec<_>>();
+        by_schedule.sort_by_key(|(_, scheduled)| scheduled.until_next_tick_nanos);
+        by_schedule
+    }
+    /// Iterate over only the components for which `predicate` returns true
+    pub fn iter_filtered<'a, F>(
+        &'a self,
+        mut predicate: F,
+    ) -> impl 'a + Iterator<Item = (Entity, &'a T)>
+    where
+        F: 'a + FnMut(Entity, &T) -> bool,
+    {
+        self.iter()
+            .filter(move |&(entity, component)| predicate(entity, component))
+    }
+    /// Like `iter_filtered` but yields mutable references to the components
+    pub fn iter_filtered_mut<'a, F>(
+        &'a mut self,
+        mut predicate: F,
+    ) -> impl 'a + Iterator<Item = (Entity, &'a mut T)>
+    where
+        F: 'a + FnMut(Entity, &T) -> bool,
+    {
+        self.iter_mut()
+            .filter(move |(entity, component)| predicate(*entity, component))
+    }
+    /// Iterate over the time until the next tick of each component, without accessing the
+    /// components themselves
+    pub fn iter_schedules(&self) -> impl '_ + Iterator<Item = (Entity, Duration)> {
+        self.components
+            .iter()
+            .map(|(entity, scheduled)| (entity, scheduled.until_next_tick()))
+    }
+    /// Like `iter_mut` but also yields the time until each component's next tick. The schedules
+    /// themselves can't be modified through this iterator.
+    pub fn iter_mut_with_until_next_tick(
+        &mut self,
+    ) -> impl '_ + Iterator<Item = (Entity, &mut T, Duration)> {
+        self.components.iter_mut().map(|(entity, scheduled)| {
+            let until_next_tick = scheduled.until_next_tick();
+            (entity, &mut scheduled.component, until_next_tick)
+        })
+    }
+    /// Total simulated time the table has been advanced by since it was created. This only
+    /// increases, and isn't reset by `clear`.
+    pub fn simulated_time(&self) -> Duration {
+        Duration::from_nanos(self.simulated_time)
+    }
+    /// Add `duration` to the simulated time without affecting any components. Frame processors
+    /// call this once per frame after processing every entity.
+    pub fn advance_simulated_time(&mut self, duration: Duration) {
+        self.simulated_time = self
+            .simulated_time
+            .saturating_add(duration_to_nanos(duration));
+        self.limits.end_frame();
+    }
+    /// Limit the number of events the table's components produce per frame, to protect frames
+    /// where many components are due at once (e.g. a chain of explosions) from applying an
+    /// unbounded number of events. Once the limit is reached, components which are due are
+    /// deferred to the next frame rather than ticked, and `on_overflow` is called the first
+    /// time this happens each frame. Deferred components are left due, so they tick as soon as
+    /// the next frame starts rather than falling behind. A frame ends when
+    /// `advance_simulated_time` is called. The cap applies to every way of processing the
+    /// table's frames.
+    pub fn set_event_cap(
+        &mut self,
+        max_events_per_frame: u32,
+        on_overflow: impl Fn(EventCapExceeded) + Send + Sync + 'static,
+    ) {
+        self.limits
+            .set_event_cap(max_events_per_frame, Arc::new(on_overflow));
+    }
+    pub fn clear_event_cap(&mut self) {
+        self.limits.clear_event_cap();
+    }
+    pub fn event_cap(&self) -> Option<u32> {
+        self.limits.max_events_per_frame
+    }
+    /// The number of events the table's components have produced since the last call to
+    /// `advance_simulated_time`. This is only counted while an event cap is set.
+    pub fn events_this_frame(&self) -> u32 {
+        self.limits.events_this_frame
+    }
+    /// Returns true if the component of `entity` is due within `frame_remaining` but the
+    /// table's event cap has been reached, so it won't tick this frame
+    #[doc(hidden)]
+    pub fn is_capped(&self, entity: Entity, frame_remaining: Duration) -> bool {
+        self.components.get(entity).is_some_and(|scheduled| {
+            self.limits
+                .is_capped(scheduled, duration_to_nanos(frame_remaining))
+        })
+    }
+    /// Like `get_with_schedule_mut` but returns `None` if the component is due within
+    /// `frame_remaining` and the table's event cap has been reached
+    #[doc(hidden)]
+    pub fn get_with_schedule_mut_capped(
+        &mut self,
+        entity: Entity,
+        frame_remaining: Duration,
+    ) -> Option<&mut ScheduledRealtimeComponent<T>> {
+        if self.is_capped(entity, frame_remaining) {
+            self.limits.report_capped::<T>(entity);
+            return None;
+        }
+        self.get_with_schedule_mut(entity)
+    }
+    /// Bring the next tick of the component of `entity` up to `nanos` nanoseconds closer without
+    /// ticking it, leaving it due if its tick would have passed, e.g. because it was held back
+    /// by the event cap
+    #[doc(hidden)]
+    pub fn advance_until_due_nanos(&mut self, entity: Entity, nanos: u64) {
+        if let Some(scheduled) = self.components.get_mut(entity) {
+            if scheduled.enabled {
+                scheduled.advance_nanos(scheduled.until_next_tick_nanos.min(nanos));
+                self.schedule_generation += 1;
+            }
+        }
+    }
+    /// Like `advance_until_due_nanos` but for every enabled component other than those of
+    /// `excluded`
+    pub(crate) fn advance_enabled_until_due_nanos(&mut self, nanos: u64, excluded: &[Entity]) {
+        self.schedule_generation += 1;
+        self.advance_enabled_until_due_nanos_untracked(nanos, excluded);
+    }
+    /// Like `advance_enabled_until_due_nanos` but leaves the schedule generation unchanged, for
+    /// schedulers which account for the components left due themselves
+    pub(crate) fn advance_enabled_until_due_nanos_untracked(
+        &mut self,
+        nanos: u64,
+        excluded: &[Entity],
+    ) {
+        for (entity, scheduled) in self.components.iter_mut() {
+            if scheduled.enabled && !excluded.contains(&entity) {
+                scheduled.advance_nanos(scheduled.until_next_tick_nanos.min(nanos));
+            }
+        }
+    }
+    /// Limit the durations the table's components may return from `tick`. Longer durations,
+    /// such as `Duration::MAX` returned by mistake, would leave a component frozen, so they are
+    /// clamped to `max_period` and reported to `on_clamped` (and logged with the `log`
+    /// feature). The limit applies to every way of processing the table's frames.
+    pub fn set_max_period(
+        &mut self,
+        max_period: Duration,
+        on_clamped: impl Fn(PeriodClamped) + Send + Sync + 'static,
+    ) {
+        self.limits.set_max_period(max_period, Arc::new(on_clamped));
+    }
+    pub fn clear_max_period(&mut self) {
+        self.limits.clear_max_period();
+    }
+    pub fn max_period(&self) -> Option<Duration> {
+        self.limits.max_period_nanos.map(Duration::from_nanos)
+    }
+    /// Called after the component of `entity` ticks to apply the table's limits
+    #[doc(hidden)]
+    pub fn after_tick(&mut self, entity: Entity) {
+        if let Some(scheduled) = self.components.get_mut(entity) {
+            if self.limits.after_tick(entity, scheduled) {
+                self.schedule_generation += 1;
+            }
+        }
+    }
+    /// Like `after_tick` but leaves the schedule generation unchanged, for callers which keep
+    /// their index of the table's schedules up to date themselves
+    pub(crate) fn after_tick_untracked(
+        &mut self,
+        entity: Entity,
+    ) -> Option<&ScheduledRealtimeComponent<T>> {
+        let scheduled = self.components.get_mut(entity)?;
+        self.limits.after_tick(entity, scheduled);
+        Some(scheduled)
+    }
+    /// Record that the component of `entity` was held back by the event cap
+    pub(crate) fn report_capped(&mut self, entity: Entity) {
+        self.limits.report_capped::<T>(entity);
+    }
+    /// Returns true if the table's components may not produce any more events this frame
+    pub(crate) fn event_cap_reached(&self) -> bool {
+        self.limits.cap_reached()
+    }
+    /// The table's limits, to be applied to components ticked in parallel. Apply the result
+    /// back to the table with `ParallelTableLimits::finish`.
+    #[cfg(feature = "rayon")]
+    #[doc(hidden)]
+    pub fn parallel_limits(&self) -> ParallelTableLimits {
+        ParallelTableLimits {
+            limits: self.limits.clone(),
+            events_this_frame: AtomicU32::new(self.limits.events_this_frame),
+            overflowed: AtomicBool::new(self.limits.overflowed),
+        }
+    }
+    /// Advance every component by `duration` without generating events
+    pub fn advance_silently(&mut self, duration: Duration) {
+        self.schedule_generation += 1;
+        for (_, scheduled) in self.components.iter_mut() {
+            scheduled.skip(duration);
+        }
+        self.advance_simulated_time(duration);
+    }
+    /// Advance every component by `duration`, ticking each as many times as it would have
+    /// ticked had the time been processed normally. Returns the resulting events in the order
+    /// they would have occurred, each with the time since the start of the fast-forward at
+    /// which it occurred. A component which keeps returning zero durations from `tick` would
+    /// never let the fast-forward end, so after `MAX_TICKS_PER_INSTANT` ticks at the same
+    /// instant it stops ticking and is left due at that instant. The fast-forward counts as a
+    /// single frame for the table's event cap, and components held back by the cap are left due
+    /// too.
+    pub fn fast_forward(&mut self, duration: Duration) -> Vec<(Duration, Entity, T::Event)> {
+        let mut events = Vec::new();
+        let duration_nanos = duration_to_nanos(duration);
+        self.schedule_generation += 1;
+        let limits = &mut self.limits;
+        for (entity, scheduled) in self.components.iter_mut() {
+            if !scheduled.enabled {
+                continue;
+            }
+            let mut elapsed = 0;
+            let mut ticks_this_instant = 0;
+            while scheduled.until_next_tick_nanos <= duration_nanos - elapsed {
+                if scheduled.until_next_tick_nanos > 0 {
+                    ticks_this_instant = 0;
+                } else if ticks_this_instant == MAX_TICKS_PER_INSTANT {
+                    break;
+                }
+                if limits.cap_reached() {
+                    limits.report_capped::<T>(entity);
+                    break;
+                }
+                ticks_this_instant += 1;
+                elapsed += scheduled.until_next_tick_nanos;
+                let event = scheduled.tick(entity);
+                limits.after_tick(entity, scheduled);
+                events.push((Duration::from_nanos(elapsed), entity, event));
+            }
+            scheduled
+                .advance_nanos((duration_nanos - elapsed).min(scheduled.until_next_tick_nanos));
+        }
+        self.advance_simulated_time(duration);
+        events.sort_by_key(|&(time, _, _)| time);
+        events
+    }
+}
+
+pub struct RealtimeComponentTableIter<'a, T: RealtimeComponent>(
+    ComponentTableIter<'a, ScheduledRealtimeComponent<T>>,
+);
+
+pub struct RealtimeComponentTableIterMut<'a, T: RealtimeComponent>(
+    ComponentTableIterMut<'a, ScheduledRealtimeComponent<T>>,
+);
+
+impl<'a, T: RealtimeComponent> Iterator for RealtimeComponentTableIter<'a, T> {
+    type Item = (Entity, &'a T);
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|(entity, c)| (entity, &c.component))
+    }
+}
+
+impl<'a, T: RealtimeComponent> Iterator for RealtimeComponentTableIterMut<'a, T> {
+    type Item = (Entity, &'a mut T);
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|(entity, c)| (entity, &mut c.component))
+    }
+}
+
+/// How a component makes up for time it missed, such as during a fast-forward or a frame which
+/// took longer than expected
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum CatchUpPolicy {
+    /// Replay every missed tick, applying each of their events
+    #[default]
+    Burst,
+    /// Jump to the present without generating events
+    Skip,
+}
+
+/// What happens when a frame is much longer than the periods of the components being processed,
+/// e.g. after the game was paused in a debugger or the machine slept. This applies to every
+/// component of an entity and is chosen by the context with
+/// `ContextContainsRealtimeComponents::realtime_catch_up_mode`, whereas a component's `CatchUpPolicy` only
+/// applies where a maximum frame duration is given explicitly.
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum CatchUpMode {
+    /// Simulate the entire frame, replaying every tick
+    #[default]
+    ReplayAll,
+    /// Simulate at most the shortest period of an entity's components (the durations returned
+    /// by their most recent ticks). Components which haven't ticked yet are ignored, as are
+    /// entities whose components are all yet to tick.
+    OnePeriod,
+    /// Simulate at most the given duration of each entity's frame
+    ClampTotal(Duration),
+}
+
+impl CatchUpMode {
+    /// The duration of a frame to simulate for an entity whose components' shortest non-zero
+    /// period is returned by `min_period`
+    pub fn clamp(
+        self,
+        frame_duration: Duration,
+        min_period: impl FnOnce() -> Option<Duration>,
+    ) -> Duration {
+        match self {
+            Self::ReplayAll => frame_duration,
+            Self::OnePeriod => {
+                min_period().map_or(frame_duration, |min_period| frame_duration.min(min_period))
+            }
+            Self::ClampTotal(max) => frame_duration.min(max),
+        }
+    }
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! realtime_group {
+    () => {
+        ::std::option::Option::None
+    };
+    ($group:ident) => {
+        ::std::option::Option::Some(stringify!($group))
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! realtime_catch_up_policy {
+    () => {
+        $crate::CatchUpPolicy::Burst
+    };
+    (burst) => {
+        $crate::CatchUpPolicy::Burst
+    };
+    (skip) => {
+        $crate::CatchUpPolicy::Skip
+    };
+}
+
+/// Expands to the given items only if the `rayon` feature is enabled
+#[cfg(feature = "rayon")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! realtime_rayon {
+    ($($item:tt)*) => {
+        $($item)*
+    };
+}
+
+#[cfg(not(feature = "rayon"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! realtime_rayon {
+    ($($item:tt)*) => {};
+}
+
+/// Expands to the given items only if the `rand` feature is enabled
+#[cfg(feature = "rand")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! realtime_rand {
+    ($($item:tt)*) => {
+        $($item)*
+    };
+}
+
+#[cfg(not(feature = "rand"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! realtime_rand {
+    ($($item:tt)*) => {};
+}
+
+/// Open a puffin profiling scope lasting until the end of the enclosing block. Expands to
+/// nothing unless the `puffin` feature is enabled.
+#[cfg(feature = "puffin")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! realtime_profile_scope {
+    ($($arg:tt)*) => {
+        $crate::puffin::profile_scope!($($arg)*);
+    };
+}
+
+#[cfg(not(feature = "puffin"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! realtime_profile_scope {
+    ($($arg:tt)*) => {};
+}
+
+/// Lane in which an entity is processed when a frame's tick budget can't cover every entity.
+/// Entities in higher lanes are processed first.
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub enum RealtimePriority {
+    Ambient,
+    #[default]
+    Normal,
+    Critical,
+}
+
+/// Serializable summary of the schedules of all realtime components, suitable for dumping to
+/// an external inspector
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Default)]
+pub struct RealtimeDebugSummary {
+    pub entities: Vec<RealtimeEntityDebugSummary>,
+    #[cfg_attr(feature = "serialize", serde(skip))]
+    index: HashMap<Entity, usize>,
+}
+
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct RealtimeEntityDebugSummary {
+    pub entity: Entity,
+    pub components: Vec<RealtimeComponentDebugSummary>,
+}
+
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct RealtimeComponentDebugSummary {
+    /// Name of the field containing the component
+    pub name: String,
+    pub until_next_tick: Duration,
+    pub period: Duration,
+    #[cfg_attr(feature = "serialize", serde(default = "enabled_default"))]
+    pub enabled: bool,
+}
+
+impl RealtimeDebugSummary {
+    /// Add the schedule of a component to the summary of its entity
+    pub fn add_component<T: RealtimeComponent>(
+        &mut self,
+        entity: Entity,
+        name: &str,
+        scheduled: &ScheduledRealtimeComponent<T>,
+    ) {
+        let index = *self.index.entry(entity).or_insert_with(|| {
+            self.entities.push(RealtimeEntityDebugSummary {
+                entity,
+                components: Vec::new(),
+            });
+            self.entities.len() - 1
+        });
+        self.entities[index]
+            .components
+            .push(RealtimeComponentDebugSummary {
+                name: name.to_string(),
+                until_next_tick: scheduled.until_next_tick(),
+                period: scheduled.period(),
+                enabled: scheduled.is_enabled(),
+            });
+    }
+
+    /// Hash of every component's schedule. The hash is stable across runs of the same build so
+    /// it can be compared between peers to detect divergence.
+    pub fn schedule_hash(&self) -> u64 {
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        for entity_summary in &self.entities {
+            entity_summary.entity.hash(&mut hasher);
+            for component in &entity_summary.components {
+                component.name.hash(&mut hasher);
+                component.until_next_tick.hash(&mut hasher);
+                component.period.hash(&mut hasher);
+            }
+        }
+        hasher.finish()
+    }
+
+    pub fn get(&self, entity: Entity) -> Option<&RealtimeEntityDebugSummary> {
+        if self.index.len() == self.entities.len() {
+            self.index.get(&entity).map(|&index| &self.entities[index])
+        } else {
+            // the index is not serialized
+            self.entities.iter().find(|e| e.entity == entity)
+        }
+    }
+}
+
+impl RealtimeEntityDebugSummary {
+    pub fn get(&self, name: &str) -> Option<&RealtimeComponentDebugSummary> {
+        self.components.iter().find(|c| c.name == name)
+    }
+}
+
+/// Bounded history of schedule summaries, recorded once per frame, for inspecting what a
+/// component was doing some number of frames ago
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct ScheduleHistory {
+    capacity: usize,
+    frames: VecDeque<RealtimeDebugSummary>,
+}
+
+impl ScheduleHistory {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            frames: VecDeque::with_capacity(capacity),
+        }
+    }
+
+    /// Record the summary of the current frame, discarding the oldest frame if the history is
+    /// full
+    pub fn record(&mut self, summary: RealtimeDebugSummary) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.frames.len() == self.capacity {
+            self.frames.pop_front();
+        }
+        self.frames.push_back(summary);
+    }
+
+    /// The summary recorded `frames_ago` frames before the most recent one
+    pub fn frames_ago(&self, frames_ago: usize) -> Option<&RealtimeDebugSummary> {
+        self.frames
+            .len()
+            .checked_sub(frames_ago + 1)
+            .and_then(|index| self.frames.get(index))
+    }
+
+    /// The schedule of an entity's component `frames_ago` frames before the most recent one
+    pub fn component(
+        &self,
+        frames_ago: usize,
+        entity: Entity,
+        name: &str,
+    ) -> Option<&RealtimeComponentDebugSummary> {
+        self.frames_ago(frames_ago)?.get(entity)?.get(name)
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+    pub fn clear(&mut self) {
+        self.frames.clear();
+    }
+}
+
+/// Record of the frame durations and per-frame state hashes of a live run, used to check that
+/// re-simulating the same frames produces the same states
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Default)]
+pub struct DeterminismAudit {
+    frames: Vec<AuditFrame>,
+}
+
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AuditFrame {
+    pub frame_duration: Duration,
+    /// Hash of the state after processing the frame (e.g. `RealtimeDebugSummary::schedule_hash`)
+    pub hash: u64,
+}
+
+/// The first frame at which a re-simulation diverged from the recorded run
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Divergence {
+    pub frame_index: usize,
+    pub expected_hash: u64,
+    pub actual_hash: u64,
+}
+
+impl DeterminismAudit {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a frame of the live run
+    pub fn record(&mut self, frame_duration: Duration, hash: u64) {
+        self.frames.push(AuditFrame {
+            frame_duration,
+            hash,
+        });
+    }
+
+    pub fn frames(&self) -> &[AuditFrame] {
+        &self.frames
+    }
+
+    pub fn clear(&mut self) {
+        self.frames.clear();
+    }
+
+    /// Re-simulate each recorded frame by calling `resimulate` with its duration. The function
+    /// should advance a clone of the state (e.g. a clone of the generated `RealtimeComponents`
+    /// taken at the start of the recording) by the frame and return the hash of the result.
+    /// Returns the first frame whose hash doesn't match the recording.
+    pub fn audit(&self, mut resimulate: impl FnMut(Duration) -> u64) -> Option<Divergence> {
+        self.frames
+            .iter()
+            .enumerate()
+            .find_map(|(frame_index, frame)| {
+                let actual_hash = resimulate(frame.frame_duration);
+                if actual_hash == frame.hash {
+                    None
+                } else {
+                    Some(Divergence {
+                        frame_index,
+                        expected_hash: frame.hash,
+                        actual_hash,
+                    })
+                }
+            })
+    }
+}
+
+pub trait ContextContainsRealtimeComponents {
+    type Components: RealtimeComponents<Self>;
+    fn components_mut(&mut self) -> &mut Self::Components;
+    fn realtime_entities(&self) -> Entities;
+
+    /// The priority lane of an entity, consulted by budget-limited processing
+    fn realtime_priority(&self, _entity: Entity) -> RealtimePriority {
+        RealtimePriority::Normal
+    }
+
+    /// The maximum simulated time an entity may advance in a single frame, regardless of the
+    /// frame's duration. Time beyond the limit is dropped rather than simulated, so entities
+    /// whose schedules have a large backlog (e.g. because they were just streamed in) don't
+    /// replay it all at once.
+    fn realtime_max_frame_duration(&self, _entity: Entity) -> Option<Duration> {
+        None
+    }
+
+    /// How fast time passes for an entity's realtime components relative to the frame, queried
+    /// once per frame. The default is 1.0. Gameplay systems in the context can dilate an
+    /// entity's realtime behaviour, e.g. 0.5 in a bullet-time field or 0.0 in a stasis zone.
+    /// Negative and NaN scales are treated as 0.0. The scaled frame is then limited by
+    /// `realtime_max_frame_duration`.
+    fn realtime_time_scale(&self, _entity: Entity) -> f64 {
+        1.0
+    }
+
+    /// Returns true if an entity is dormant, e.g. because it's off-screen. Frames of dormant
+    /// entities are dropped rather than simulated, so their schedules freeze until the entity
+    /// stops being dormant, and they don't fast-forward through the time they were dormant.
+    /// Queried once per frame.
+    fn realtime_dormant(&self, _entity: Entity) -> bool {
+        false
+    }
+
+    /// What happens when an entity's frame is much longer than the periods of its components.
+    /// Applied after the time scale and maximum frame duration.
+    fn realtime_catch_up_mode(&self, _entity: Entity) -> CatchUpMode {
+        CatchUpMode::ReplayAll
+    }
+
+    /// Called by `process_entity_frame` and its variants once an entity's frame is complete and
+    /// its events have been applied, e.g. to flush render commands or audio batched up while
+    /// applying the entity's events.
+    fn after_entity_frame(&mut self, _entity: Entity) {}
+
+    /// Called by `process_entity_frame` and its variants each time some of an entity's
+    /// components tick, before their events are applied, e.g. to look up the entity's position
+    /// once per tick rather than in each component's `apply_event`. `defer_entity_frame` calls
+    /// it as the events are deferred instead.
+    fn before_tick(&mut self, _entity: Entity) {}
+}
+
+/// The hooks of `ContextContainsRealtimeComponents`, for frame processors whose components
+/// aren't found through a `ContextContainsRealtimeComponents`, such as `RealtimeCoordinator`,
+/// `process_table_frame` and fixed-capacity tables. It's implemented for every
+/// `ContextContainsRealtimeComponents` by forwarding to its hooks. Other contexts implement it
+/// directly, with an empty block if they don't need any hooks.
+pub trait RealtimeFrameHooks {
+    /// See `ContextContainsRealtimeComponents::realtime_max_frame_duration`
+    fn realtime_max_frame_duration(&self, _entity: Entity) -> Option<Duration> {
+        None
+    }
+
+    /// See `ContextContainsRealtimeComponents::realtime_time_scale`
+    fn realtime_time_scale(&self, _entity: Entity) -> f64 {
+        1.0
+    }
+
+    /// See `ContextContainsRealtimeComponents::realtime_dormant`
+    fn realtime_dormant(&self, _entity: Entity) -> bool {
+        false
+    }
+
+    /// See `ContextContainsRealtimeComponents::realtime_catch_up_mode`
+    fn realtime_catch_up_mode(&self, _entity: Entity) -> CatchUpMode {
+        CatchUpMode::ReplayAll
+    }
+
+    /// See `ContextContainsRealtimeComponents::after_entity_frame`
+    fn after_entity_frame(&mut self, _entity: Entity) {}
+
+    /// See `ContextContainsRealtimeComponents::before_tick`
+    fn before_tick(&mut self, _entity: Entity) {}
+}
+
+impl<C: ContextContainsRealtimeComponents + ?Sized> RealtimeFrameHooks for C {
+    fn realtime_max_frame_duration(&self, entity: Entity) -> Option<Duration> {
+        ContextContainsRealtimeComponents::realtime_max_frame_duration(self, entity)
+    }
+
+    fn realtime_time_scale(&self, entity: Entity) -> f64 {
+        ContextContainsRealtimeComponents::realtime_time_scale(self, entity)
+    }
+
+    fn realtime_dormant(&self, entity: Entity) -> bool {
+        ContextContainsRealtimeComponents::realtime_dormant(self, entity)
+    }
+
+    fn realtime_catch_up_mode(&self, entity: Entity) -> CatchUpMode {
+        ContextContainsRealtimeComponents::realtime_catch_up_mode(self, entity)
+    }
+
+    fn after_entity_frame(&mut self, entity: Entity) {
+        ContextContainsRealtimeComponents::after_entity_frame(self, entity)
+    }
+
+    fn before_tick(&mut self, entity: Entity) {
+        ContextContainsRealtimeComponents::before_tick(self, entity)
+    }
+}
+
+/// Multiply `duration` by `scale`, rounding to the nearest nanosecond
+fn scale_duration(duration: Duration, scale: f64) -> Duration {
+    if scale == 1.0 {
+        return duration;
+    }
+    seconds::duration_from_secs(duration.as_secs_f64() * scale)
+}
+
+/// The duration of an entity's frame to simulate before applying the context's catch-up mode,
+/// which depends on the entity's components so is applied by each frame processor. This is
+/// zero for dormant entities. Otherwise this applies the context's time scale and maximum frame
+/// duration.
+pub(crate) fn entity_frame_duration<C: RealtimeFrameHooks + ?Sized>(
+    entity: Entity,
+    frame_duration: Duration,
+    context: &C,
+) -> Duration {
+    if context.realtime_dormant(entity) {
+        return Duration::ZERO;
+    }
+    let frame_duration = scale_duration(frame_duration, context.realtime_time_scale(entity));
+    context
+        .realtime_max_frame_duration(entity)
+        .map_or(frame_duration, |max| frame_duration.min(max))
+}
+
+/// Prepare to process an entity's frame, returning the duration of the frame to simulate. This
+/// is zero for dormant entities. Otherwise this applies the context's time scale, maximum frame
+/// duration and catch-up mode, and drops the ticks of components which have fallen too far
+/// behind.
+pub(crate) fn begin_entity_frame<C: ContextContainsRealtimeComponents>(
+    entity: Entity,
+    frame_duration: Duration,
+    context: &mut C,
+) -> Duration {
+    let frame_duration = entity_frame_duration(entity, frame_duration, context);
+    begin_scaled_entity_frame(entity, frame_duration, context)
+}
+
+/// The rest of `begin_entity_frame` once the time scale and maximum frame duration have been
+/// applied to the frame
+fn begin_scaled_entity_frame<C: ContextContainsRealtimeComponents>(
+    entity: Entity,
+    frame_duration: Duration,
+    context: &mut C,
+) -> Duration {
+    if frame_duration.is_zero() {
+        return frame_duration;
+    }
+    let frame_duration = context
+        .realtime_catch_up_mode(entity)
+        .clamp(frame_duration, || {
+            context.components_mut().min_period(entity)
+        });
+    let events = context
+        .components_mut()
+        .drop_excess_ticks(entity, frame_duration);
+    events.apply(entity, context);
+    frame_duration
+}
+
+/// Like `begin_entity_frame` but for components stored outside the context
+pub(crate) fn begin_entity_frame_dyn<C: RealtimeFrameHooks + ?Sized>(
+    entity: Entity,
+    frame_duration: Duration,
+    components: &mut dyn RealtimeComponentsDyn<C>,
+    context: &mut C,
+) -> Duration {
+    let frame_duration = entity_frame_duration(entity, frame_duration, context);
+    if frame_duration.is_zero() {
+        return frame_duration;
+    }
+    let frame_duration = context
+        .realtime_catch_up_mode(entity)
+        .clamp(frame_duration, || components.min_period_dyn(entity));
+    if let Some(events) = components.drop_excess_ticks_dyn(entity, frame_duration) {
+        events.apply_dyn(entity, context);
+    }
+    frame_duration
+}
+
+/// A single event produced by one of an entity's realtime components
+pub trait RealtimeEntityEvent<C: ?Sized> {
+    fn apply(self, entity: Entity, context: &mut C);
+}
+
+pub trait RealtimeEntityEvents<C: ?Sized> {
+    type Event: RealtimeEntityEvent<C>;
+
+    /// Names of the fields of the module's components, in the order of their indices in
+    /// `TickedComponents`
+    const COMPONENT_NAMES: &'static [&'static str];
+
+    fn apply(self, entity: Entity, context: &mut C);
+
+    /// Split into the individual events, in the order `apply` would apply them
+    fn for_each_event(self, f: impl FnMut(Self::Event));
+
+    /// Like `apply` but catches panics from `apply_event`. Events are applied in order until
+    /// one panics, after which the remaining events are dropped.
+    fn apply_catch_unwind(self, entity: Entity, context: &mut C) -> Result<(), RealtimePanic>;
+
+    /// The components which produced an event, as opposed to those whose schedules were only
+    /// brought closer to their next tick
+    fn ticked(&self) -> TickedComponents;
+
+    /// Returns true if no component produced an event
+    fn is_empty(&self) -> bool;
+}
+
+/// Set of components of an entity, identified by their indices in the `component_index` module
+/// generated by `declare_realtime_entity_module!`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct TickedComponents(u64);
+
+impl TickedComponents {
+    /// The maximum number of components which can be declared in a single module
+    pub const MAX_COMPONENTS: usize = 64;
+
+    pub const fn empty() -> Self {
+        Self(0)
+    }
+
+    pub fn insert(&mut self, index: usize) {
+        self.0 |= 1 << index;
+    }
+
+    pub const fn contains(self, index: usize) -> bool {
+        self.0 & (1 << index) != 0
+    }
+
+    pub const fn len(self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// The bitset, where bit `i` is set if the component with index `i` is present
+    pub const fn bits(self) -> u64 {
+        self.0
+    }
+
+    /// The indices of the components in ascending order
+    pub fn iter(self) -> impl Iterator<Item = usize> {
+        (0..Self::MAX_COMPONENTS).filter(move |&index| self.contains(index))
+    }
+}
+
+/// A panic caught while applying the event of a realtime component
+pub struct RealtimePanic {
+    pub entity: Entity,
+    /// Name of the field of the component whose event was being applied
+    pub component: &'static str,
+    /// The value the panic was raised with
+    pub payload: Box<dyn Any + Send>,
+}
+
+impl RealtimePanic {
+    /// Continue unwinding with the original panic payload
+    pub fn resume_unwind(self) -> ! {
+        std::panic::resume_unwind(self.payload)
+    }
+}
+
+impl fmt::Debug for RealtimePanic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("RealtimePanic")
+            .field("entity", &self.entity)
+            .field("component", &self.component)
+            .finish_non_exhaustive()
+    }
+}
+
+pub trait RealtimeComponents<C: ?Sized> {
+    type EntityEvents: RealtimeEntityEvents<C>;
+
+    fn tick_entity(
+        &mut self,
+        entity: Entity,
+        frame_remaining: Duration,
+    ) -> (Self::EntityEvents, Duration);
+
+    /// Remove all components for a given entity
+    fn remove_entity(&mut self, entity: Entity);
+
+    /// Remove all components for a given entity, returning the events returned by their
+    /// `on_remove` methods
+    fn remove_entity_with_events(&mut self, entity: Entity) -> Self::EntityEvents;
+
+    /// Drop the ticks of an entity's components which would leave them further behind than
+    /// their `max_missed_periods` during a frame of `frame_duration`, returning their
+    /// `on_ticks_dropped` events
+    fn drop_excess_ticks(&mut self, entity: Entity, frame_duration: Duration)
+        -> Self::EntityEvents;
+
+    /// Remove all components for all entities
+    fn clear(&mut self);
+
+    /// Add `duration` to the simulated time of every table. Call once per frame after
+    /// processing every entity.
+    fn advance_simulated_time(&mut self, duration: Duration);
+
+    /// The time until the soonest tick of any of an entity's enabled components, or `None` if
+    /// the entity has no enabled components
+    fn until_next_tick(&self, entity: Entity) -> Option<Duration>;
+
+    /// How far `tick_entity` would advance an entity's components given `frame_remaining`:
+    /// the time until the soonest tick of its enabled components which aren't held back by
+    /// their table's event cap, limited to `frame_remaining`. The default implementation
+    /// ignores event caps.
+    fn until_next_step(&self, entity: Entity, frame_remaining: Duration) -> Duration {
+        self.until_next_tick(entity)
+            .map_or(frame_remaining, |until_next_tick| {
+                until_next_tick.min(frame_remaining)
+            })
+    }
+
+    /// Returns true if the entity has any components, whether or not they're enabled
+    fn contains_entity(&self, entity: Entity) -> bool;
+
+    /// The shortest non-zero period of an entity's components, used by
+    /// `CatchUpMode::OnePeriod`. The default implementation returns `None`, so the mode has no
+    /// effect.
+    fn min_period(&self, _entity: Entity) -> Option<Duration> {
+        None
+    }
+
+    /// Like `tick_entity` but only considers components whose catch-up policy is
+    /// `CatchUpPolicy::Burst`
+    fn tick_entity_burst(
+        &mut self,
+        entity: Entity,
+        frame_remaining: Duration,
+    ) -> (Self::EntityEvents, Duration);
+
+    /// Like `tick_entity` but ignores components in the given group
+    fn tick_entity_excluding_group(
+        &mut self,
+        group: &str,
+        entity: Entity,
+        frame_remaining: Duration,
+    ) -> (Self::EntityEvents, Duration);
+
+    /// Advance the components of an entity whose catch-up policy is `CatchUpPolicy::Skip` by
+    /// `duration` without generating events
+    fn skip_entity(&mut self, entity: Entity, duration: Duration);
+
+    /// Like `tick_entity` but passes each event to `f` as it is produced rather than collecting
+    /// them into a `Self::EntityEvents`
+    fn tick_entity_with(
+        &mut self,
+        entity: Entity,
+        frame_remaining: Duration,
+        f: impl FnMut(<Self::EntityEvents as RealtimeEntityEvents<C>>::Event),
+    ) -> Duration;
+
+    /// Like `tick_entity` but components may draw random numbers from `rng`. The default
+    /// implementation ignores `rng`.
+    #[cfg(feature = "rand")]
+    fn tick_entity_with_rng(
+        &mut self,
+        entity: Entity,
+        frame_remaining: Duration,
+        rng: &mut dyn rand_core::RngCore,
+    ) -> (Self::EntityEvents, Duration) {
+        let _ = rng;
+        self.tick_entity(entity, frame_remaining)
+    }
+}
+
+/// Object-safe counterpart of `RealtimeEntityEvents`
+pub trait RealtimeEntityEventsDyn<C: ?Sized> {
+    fn apply_dyn(self: Box<Self>, entity: Entity, context: &mut C);
+
+    /// Like `RealtimeEntityEvents::ticked`
+    fn ticked_dyn(&self) -> TickedComponents;
+}
+
+impl<C: ?Sized, E: RealtimeEntityEvents<C>> RealtimeEntityEventsDyn<C> for E {
+    fn apply_dyn(self: Box<Self>, entity: Entity, context: &mut C) {
+        (*self).apply(entity, context);
+    }
+
+    fn ticked_dyn(&self) -> TickedComponents {
+        self.ticked()
+    }
+}
+
+/// Object-safe counterpart of `RealtimeComponents`, implemented for every type implementing
+/// `RealtimeComponents`. This allows the types generated by several invocations of
+/// `declare_realtime_entity_module!` (e.g. for the core game and for mods) to be stored as
+/// `Box<dyn RealtimeComponentsDyn<C>>` and processed uniformly.
+pub trait RealtimeComponentsDyn<C: ?Sized> {
+    /// Like `RealtimeComponents::tick_entity` but events are boxed, and `None` is returned in
+    /// place of events if no component ticked
+    fn tick_entity_dyn(
+        &mut self,
+        entity: Entity,
+        frame_remaining: Duration,
+    ) -> (Option<Box<dyn RealtimeEntityEventsDyn<C>>>, Duration);
+
+    fn remove_entity_dyn(&mut self, entity: Entity);
+    fn clear_dyn(&mut self);
+    fn until_next_tick_dyn(&self, entity: Entity) -> Option<Duration>;
+    fn until_next_step_dyn(&self, entity: Entity, frame_remaining: Duration) -> Duration;
+    fn contains_entity_dyn(&self, entity: Entity) -> bool;
+    fn min_period_dyn(&self, entity: Entity) -> Option<Duration>;
+
+    /// Like `RealtimeComponents::drop_excess_ticks` but events are boxed, and `None` is
+    /// returned in place of events if no ticks were dropped
+    fn drop_excess_ticks_dyn(
+        &mut self,
+        entity: Entity,
+        frame_duration: Duration,
+    ) -> Option<Box<dyn RealtimeEntityEventsDyn<C>>>;
+
+    fn as_any(&self) -> &dyn Any;
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+
+impl<C: ?Sized, T> RealtimeComponentsDyn<C> for T
+where
+    T: RealtimeComponents<C> + Any,
+    T::EntityEvents: 'static,
+{
+    fn tick_entity_dyn(
+        &mut self,
+        entity: Entity,
+        frame_remaining: Duration,
+    ) -> (Option<Box<dyn RealtimeEntityEventsDyn<C>>>, Duration) {
+        let (events, until_next_tick) = self.tick_entity(entity, frame_remaining);
+        if events.is_empty() {
+            (None, until_next_tick)
+        } else {
+            (Some(Box::new(events)), until_next_tick)
+        }
+    }
+
+    fn remove_entity_dyn(&mut self, entity: Entity) {
+        self.remove_entity(entity);
+    }
+
+    fn clear_dyn(&mut self) {
+        self.clear();
+    }
+
+    fn until_next_tick_dyn(&self, entity: Entity) -> Option<Duration> {
+        self.until_next_tick(entity)
+    }
+
+    fn until_next_step_dyn(&self, entity: Entity, frame_remaining: Duration) -> Duration {
+        self.until_next_step(entity, frame_remaining)
+    }
+
+    fn contains_entity_dyn(&self, entity: Entity) -> bool {
+        self.contains_entity(entity)
+    }
+
+    fn min_period_dyn(&self, entity: Entity) -> Option<Duration> {
+        self.min_period(entity)
+    }
+
+    fn drop_excess_ticks_dyn(
+        &mut self,
+        entity: Entity,
+        frame_duration: Duration,
+    ) -> Option<Box<dyn RealtimeEntityEventsDyn<C>>> {
+        let events = self.drop_excess_ticks(entity, frame_duration);
+        if events.is_empty() {
+            None
+        } else {
+            Some(Box::new(events))
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+/// Like `process_entity_frame` but for components stored outside the context behind a
+/// `RealtimeComponentsDyn`. Since the components aren't part of the context, applying an
+/// event can't affect them.
+pub fn process_entity_frame_dyn<C: RealtimeFrameHooks + ?Sized>(
+    entity: Entity,
+    frame_duration: Duration,
+    components: &mut dyn RealtimeComponentsDyn<C>,
+    context: &mut C,
+) -> bool {
+    realtime_profile_scope!("process_entity_frame_dyn");
+    if !components.contains_entity_dyn(entity) {
+        return false;
+    }
+    let mut frame_remaining = begin_entity_frame_dyn(entity, frame_duration, components, context);
+    while frame_remaining > Duration::from_micros(0) {
+        let (events, until_next_tick) = components.tick_entity_dyn(entity, frame_remaining);
+        if let Some(events) = events {
+            context.before_tick(entity);
+            events.apply_dyn(entity, context);
+        }
+        frame_remaining -= until_next_tick;
+    }
+    context.after_entity_frame(entity);
+    true
+}
+
+/// Simulate an entity's realtime components for the duration of a frame, applying each event
+/// to the context as it occurs. Returns `false` without doing anything if the entity has no
+/// realtime components, so callers can prune entities from their lists.
+pub fn process_entity_frame<C: ContextContainsRealtimeComponents>(
+    entity: Entity,
+    frame_duration: Duration,
+    context: &mut C,
+) -> bool {
+    realtime_profile_scope!("process_entity_frame");
+    let processed = process_entity_frame_unscoped(
+        entity,
+        frame_duration,
+        context,
+        &mut scheduler::LinearScan::new(),
+    );
+    #[cfg(feature = "log")]
+    if !processed {
+        log::warn!(
+            "processing frame for {:?} which has no realtime components",
+            entity
+        );
+    }
+    processed
+}
+
+fn process_entity_frame_unscoped<C: ContextContainsRealtimeComponents>(
+    entity: Entity,
+    frame_duration: Duration,
+    context: &mut C,
+    scheduler: &mut impl scheduler::EntityScheduler<C>,
+) -> bool {
+    process_entity_frame_with_policy_unscoped(
+        entity,
+        frame_duration,
+        context,
+        budget::Unlimited,
+        scheduler,
+    )
+    .is_some()
+}
+
+/// Like `process_entity_frame` but `scheduler` takes each step of the entity's frame
+pub fn process_entity_frame_with_scheduler<C: ContextContainsRealtimeComponents>(
+    entity: Entity,
+    frame_duration: Duration,
+    context: &mut C,
+    scheduler: &mut impl scheduler::EntityScheduler<C>,
+) -> bool {
+    realtime_profile_scope!("process_entity_frame_with_scheduler");
+    let processed = process_entity_frame_unscoped(entity, frame_duration, context, scheduler);
+    #[cfg(feature = "log")]
+    if !processed {
+        log::warn!(
+            "processing frame for {:?} which has no realtime components",
+            entity
+        );
+    }
+    processed
+}
+
+/// Like `process_entity_frame` but `policy` decides how much of the frame to process. Returns
+/// the progress made through the entity's frame, or `None` without doing anything if the
+/// entity has no realtime components.
+pub fn process_entity_frame_with_policy<C: ContextContainsRealtimeComponents>(
+    entity: Entity,
+    frame_duration: Duration,
+    context: &mut C,
+    policy: impl FrameBudgetPolicy,
+) -> Option<FrameProgress> {
+    realtime_profile_scope!("process_entity_frame_with_policy");
+    process_entity_frame_with_policy_unscoped(
+        entity,
+        frame_duration,
+        context,
+        policy,
+        &mut scheduler::LinearScan::new(),
+    )
+}
+
+fn process_entity_frame_with_policy_unscoped<C: ContextContainsRealtimeComponents>(
+    entity: Entity,
+    frame_duration: Duration,
+    context: &mut C,
+    policy: impl FrameBudgetPolicy,
+    scheduler: &mut impl scheduler::EntityScheduler<C>,
+) -> Option<FrameProgress> {
+    if !context.components_mut().contains_entity(entity) {
+        return None;
+    }
+    let frame_duration = begin_entity_frame(entity, frame_duration, context);
+    Some(process_entity_frame_inner(
+        entity,
+        frame_duration,
+        context,
+        policy,
+        scheduler,
+    ))
+}
+
+/// Process an entity's frame whose duration has already been prepared by `begin_entity_frame`
+fn process_entity_frame_inner<C: ContextContainsRealtimeComponents>(
+    entity: Entity,
+    frame_duration: Duration,
+    context: &mut C,
+    mut policy: impl FrameBudgetPolicy,
+    scheduler: &mut impl scheduler::EntityScheduler<C>,
+) -> FrameProgress {
+    let mut progress = FrameProgress {
+        entity,
+        steps: 0,
+        ticks: 0,
+        simulated: Duration::ZERO,
+        remaining: policy.limit_frame(entity, frame_duration),
+    };
+    while progress.remaining > Duration::from_micros(0) && policy.continue_frame(&progress) {
+        let (events, until_next_tick) =
+            scheduler.step(entity, context.components_mut(), progress.remaining);
+        if !events.is_empty() {
+            context.before_tick(entity);
+        }
+        progress.ticks += events.ticked().len();
+        events.apply(entity, context);
+        progress.remaining -= until_next_tick;
+        progress.simulated += until_next_tick;
+        progress.steps += 1;
+    }
+    context.after_entity_frame(entity);
+    progress
+}
+
+/// Simulate the realtime components of each of a batch of entities for the duration of a frame,
+/// for games which already track which entities have active effects. Returns the number of
+/// entities which had realtime components. Entities appearing more than once are processed
+/// each time they appear; see `process_entities_frame_dedup`.
+pub fn process_entities_frame<C: ContextContainsRealtimeComponents>(
+    entities: &[Entity],
+    frame_duration: Duration,
+    context: &mut C,
+) -> usize {
+    realtime_profile_scope!("process_entities_frame");
+    entities
+        .iter()
+        .filter(|&&entity| {
+            process_entity_frame_unscoped(
+                entity,
+                frame_duration,
+                context,
+                &mut scheduler::LinearScan::new(),
+            )
+        })
+        .count()
+}
+
+/// Like `process_entities_frame` but each entity is processed at most once, even if it appears
+/// several times in `entities`
+pub fn process_entities_frame_dedup<C: ContextContainsRealtimeComponents>(
+    entities: &[Entity],
+    frame_duration: Duration,
+    context: &mut C,
+) -> usize {
+    realtime_profile_scope!("process_entities_frame_dedup");
+    let mut seen = HashSet::with_capacity(entities.len());
+    entities
+        .iter()
+        .filter(|&&entity| {
+            seen.insert(entity)
+                && process_entity_frame_unscoped(
+                    entity,
+                    frame_duration,
+                    context,
+                    &mut scheduler::LinearScan::new(),
+                )
+        })
+        .count()
+}
+
+/// Insert a component into the table returned by `table`, applying the event returned by its
+/// `on_insert` method to the context. Returns the component which was replaced, if any.
+pub fn insert_realtime_component<C, T>(
+    entity: Entity,
+    component: T,
+    context: &mut C,
+    table: impl FnOnce(&mut C) -> &mut RealtimeComponentTable<T>,
+) -> Option<T>
+where
+    T: RealtimeComponentApplyEvent<C>,
+{
+    let (replaced, event) = table(context).insert_with_event(entity, component);
+    if let Some(event) = event {
+        T::apply_event(event, entity, context);
+    }
+    replaced
+}
+
+/// Remove all of an entity's realtime components, applying the events returned by their
+/// `on_remove` methods to the context
+pub fn remove_realtime_entity<C: ContextContainsRealtimeComponents>(
+    entity: Entity,
+    context: &mut C,
+) {
+    let events = context.components_mut().remove_entity_with_events(entity);
+    events.apply(entity, context);
+}
+
+/// Like `process_entity_frame` but components may draw random numbers from `rng` as they tick
+/// (see `RealtimeComponent::tick_with_rng`)
+#[cfg(feature = "rand")]
+pub fn process_entity_frame_with_rng<C: ContextContainsRealtimeComponents>(
+    entity: Entity,
+    frame_duration: Duration,
+    context: &mut C,
+    rng: &mut impl rand_core::RngCore,
+) -> bool {
+    realtime_profile_scope!("process_entity_frame_with_rng");
+    if !context.components_mut().contains_entity(entity) {
+        return false;
     }
-    pub fn remove(&mut self, entity: Entity) -> Option<T> {
-        self.remove_with_schedule(entity).map(|c| c.component)
+    let mut frame_remaining = begin_entity_frame(entity, frame_duration, context);
+    while frame_remaining > Duration::from_micros(0) {
+        let (events, until_next_tick) =
+            context
+                .components_mut()
+                .tick_entity_with_rng(entity, frame_remaining, rng);
+        if !events.is_empty() {
+            context.before_tick(entity);
+        }
+        events.apply(entity, context);
+        frame_remaining -= until_next_tick;
     }
-    pub fn get_with_schedule(&self, entity: Entity) -> Option<&ScheduledRealtimeComponent<T>> {
-        self.0.get(entity)
+    context.after_entity_frame(entity);
+    true
+}
+
+/// The type of individual events produced by the realtime components of a context
+pub type RealtimeEventOf<C> = <<<C as ContextContainsRealtimeComponents>::Components as RealtimeComponents<C>>::EntityEvents as RealtimeEntityEvents<C>>::Event;
+
+/// Apply events buffered by e.g. `par_tick_all_sharded`, in the order they're given
+pub fn apply_buffered_events<C: ?Sized, E: RealtimeEntityEvents<C>>(
+    events: impl IntoIterator<Item = (Entity, E)>,
+    context: &mut C,
+) {
+    realtime_profile_scope!("apply_buffered_events");
+    for (entity, events) in events {
+        events.apply(entity, context);
     }
-    pub fn get_with_schedule_mut(
-        &mut self,
-        entity: Entity,
-    ) -> Option<&mut ScheduledRealtimeComponent<T>> {
-        self.0.get_mut(entity)
+}
+
+/// Buffer of events whose application has been deferred. Storage for the first `N` events is
+/// inline, so frames which produce few events don't allocate.
+pub struct DeferredRealtimeEvents<E, const N: usize = 8> {
+    events: SmallVec<[(Entity, E); N]>,
+}
+
+impl<E, const N: usize> Default for DeferredRealtimeEvents<E, N> {
+    fn default() -> Self {
+        Self {
+            events: SmallVec::new(),
+        }
     }
-    pub fn get(&self, entity: Entity) -> Option<&T> {
-        self.get_with_schedule(entity).map(|c| &c.component)
+}
+
+impl<E, const N: usize> DeferredRealtimeEvents<E, N> {
+    pub fn new() -> Self {
+        Self::default()
     }
-    pub fn get_mut(&mut self, entity: Entity) -> Option<&mut T> {
-        self.get_with_schedule_mut(entity).map(|c| &mut c.component)
+
+    pub fn push(&mut self, entity: Entity, event: E) {
+        self.events.push((entity, event));
     }
-    pub fn iter_with_schedule(&self) -> ComponentTableIter<ScheduledRealtimeComponent<T>> {
-        self.0.iter()
+
+    pub fn len(&self) -> usize {
+        self.events.len()
     }
-    pub fn iter_with_schedule_mut(
-        &mut self,
-    ) -> ComponentTableIterMut<ScheduledRealtimeComponent<T>> {
-        self.0.iter_mut()
+
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
     }
-    pub fn iter(&self) -> RealtimeComponentTableIter<T> {
-        RealtimeComponentTableIter(self.0.iter())
+
+    /// Returns true if the events no longer fit in the inline storage
+    pub fn spilled(&self) -> bool {
+        self.events.spilled()
     }
-    pub fn iter_mut(&mut self) -> RealtimeComponentTableIterMut<T> {
-        RealtimeComponentTableIterMut(self.0.iter_mut())
+
+    pub fn clear(&mut self) {
+        self.events.clear();
     }
-    pub fn entities(&self) -> impl '_ + Iterator<Item = Entity> {
-        self.iter().map(|(entity, _)| entity)
+
+    pub fn iter(&self) -> impl Iterator<Item = (Entity, &E)> {
+        self.events.iter().map(|(entity, event)| (*entity, event))
+    }
+
+    /// Apply the buffered events in the order they were produced, leaving the buffer empty
+    pub fn apply<C: ?Sized>(&mut self, context: &mut C)
+    where
+        E: RealtimeEntityEvent<C>,
+    {
+        for (entity, event) in self.events.drain(..) {
+            event.apply(entity, context);
+        }
     }
 }
 
-pub struct RealtimeComponentTableIter<'a, T: RealtimeComponent>(
-    ComponentTableIter<'a, ScheduledRealtimeComponent<T>>,
-);
+/// Like `process_entity_frame` but rather than applying events, they are appended to
+/// `deferred` to be applied later. Since the events aren't applied, they can't influence the
+/// entity's components during the frame. The context's hooks are called as usual, except that
+/// `before_tick` is called once each time some of the entity's components tick and their events
+/// are deferred, rather than before the events are applied.
+pub fn defer_entity_frame<C: ContextContainsRealtimeComponents, const N: usize>(
+    entity: Entity,
+    frame_duration: Duration,
+    context: &mut C,
+    deferred: &mut DeferredRealtimeEvents<RealtimeEventOf<C>, N>,
+) {
+    realtime_profile_scope!("defer_entity_frame");
+    let mut frame_remaining = begin_entity_frame(entity, frame_duration, context);
+    while frame_remaining > Duration::from_micros(0) {
+        let num_deferred = deferred.len();
+        frame_remaining -=
+            context
+                .components_mut()
+                .tick_entity_with(entity, frame_remaining, |event| {
+                    deferred.push(entity, event)
+                });
+        if deferred.len() > num_deferred {
+            context.before_tick(entity);
+        }
+    }
+    context.after_entity_frame(entity);
+}
 
-pub struct RealtimeComponentTableIterMut<'a, T: RealtimeComponent>(
-    ComponentTableIterMut<'a, ScheduledRealtimeComponent<T>>,
-);
+/// Description of a single step taken by `step_entity`
+#[derive(Debug, Clone)]
+pub struct StepInfo {
+    /// Simulated time of the entity that passed before the step's components ticked
+    pub elapsed: Duration,
+    /// Duration of the context's frame which the step amounts to, i.e. `elapsed` undone by the
+    /// entity's time scale
+    pub frame_duration: Duration,
+    /// The components which ticked
+    pub ticked: TickedComponents,
+    /// Names of the fields of the entity's components, indexed as in `ticked`
+    pub component_names: &'static [&'static str],
+}
 
-impl<'a, T: RealtimeComponent> Iterator for RealtimeComponentTableIter<'a, T> {
-    type Item = (Entity, &'a T);
-    fn next(&mut self) -> Option<Self::Item> {
-        self.0.next().map(|(entity, c)| (entity, &c.component))
+impl StepInfo {
+    /// Names of the fields of the components which ticked
+    pub fn ticked_names(&self) -> impl '_ + Iterator<Item = &'static str> {
+        self.ticked.iter().map(|index| self.component_names[index])
     }
 }
 
-impl<'a, T: RealtimeComponent> Iterator for RealtimeComponentTableIterMut<'a, T> {
-    type Item = (Entity, &'a mut T);
-    fn next(&mut self) -> Option<Self::Item> {
-        self.0.next().map(|(entity, c)| (entity, &mut c.component))
+/// Advance an entity to the tick of its soonest due component(s), tick them and apply their
+/// events. Intended for stepping through realtime behaviour one event at a time while debugging.
+/// Each step is processed as a frame just long enough for the soonest due components to tick:
+/// dormant entities and entities whose time is stopped don't advance, the context's maximum
+/// frame duration and catch-up mode apply, and its `before_tick` and `after_entity_frame` hooks
+/// are called. The event caps of the context's tables are reset after each step as they are
+/// after each frame. Returns `None` if the entity has no realtime components. Otherwise the
+/// step's `ticked` is empty if nothing ticked, e.g. because the entity is dormant or its
+/// components are disabled or held back by an event cap.
+pub fn step_entity<C: ContextContainsRealtimeComponents>(
+    entity: Entity,
+    context: &mut C,
+) -> Option<StepInfo> {
+    step_entity_inspect(entity, context, |_, _| ())
+}
+
+/// Like `step_entity` but `inspect` is called with the events of the components which ticked,
+/// before they're applied to the context, e.g. to print them in a debug console
+pub fn step_entity_inspect<C: ContextContainsRealtimeComponents>(
+    entity: Entity,
+    context: &mut C,
+    inspect: impl FnOnce(&<C::Components as RealtimeComponents<C>>::EntityEvents, &C),
+) -> Option<StepInfo> {
+    realtime_profile_scope!("step_entity");
+    if !context.components_mut().contains_entity(entity) {
+        return None;
+    }
+    let mut step = StepInfo {
+        elapsed: Duration::ZERO,
+        frame_duration: Duration::ZERO,
+        ticked: TickedComponents::default(),
+        component_names: <C::Components as RealtimeComponents<C>>::EntityEvents::COMPONENT_NAMES,
+    };
+    let time_scale = context.realtime_time_scale(entity);
+    if !context.realtime_dormant(entity) && time_scale > 0.0 {
+        let components = context.components_mut();
+        // components held back by an event cap are only advanced until they're due
+        let until_next_tick = components.until_next_tick(entity).unwrap_or_default();
+        let until_next_step = components.until_next_step(entity, until_next_tick);
+        let frame_duration = context
+            .realtime_max_frame_duration(entity)
+            .map_or(until_next_step, |max| until_next_step.min(max));
+        let frame_duration = begin_scaled_entity_frame(entity, frame_duration, context);
+        let (events, elapsed) = context.components_mut().tick_entity(entity, frame_duration);
+        step.elapsed = elapsed;
+        step.frame_duration = scale_duration(elapsed, time_scale.recip());
+        step.ticked = events.ticked();
+        if !events.is_empty() {
+            context.before_tick(entity);
+        }
+        inspect(&events, context);
+        events.apply(entity, context);
     }
+    context.after_entity_frame(entity);
+    context
+        .components_mut()
+        .advance_simulated_time(Duration::ZERO);
+    Some(step)
 }
 
-pub trait ContextContainsRealtimeComponents {
-    type Components: RealtimeComponents<Self>;
-    fn components_mut(&mut self) -> &mut Self::Components;
-    fn realtime_entities(&self) -> Entities;
+/// Like `process_entity_frame` but catches panics raised while applying events. Component
+/// tables are left consistent since all schedules are updated before any event is applied. On
+/// panic, the remainder of the frame is not simulated for this entity, though the context's
+/// `after_entity_frame` hook is still called. Returns `Ok(false)` without doing anything if the
+/// entity has no realtime components.
+pub fn process_entity_frame_catch_unwind<C: ContextContainsRealtimeComponents>(
+    entity: Entity,
+    frame_duration: Duration,
+    context: &mut C,
+) -> Result<bool, RealtimePanic> {
+    realtime_profile_scope!("process_entity_frame_catch_unwind");
+    process_entity_frame_catch_unwind_unscoped(
+        entity,
+        frame_duration,
+        context,
+        &mut scheduler::LinearScan::new(),
+    )
 }
 
-pub trait RealtimeEntityEvents<C: ?Sized> {
-    fn apply(self, entity: Entity, context: &mut C);
+fn process_entity_frame_catch_unwind_unscoped<C: ContextContainsRealtimeComponents>(
+    entity: Entity,
+    frame_duration: Duration,
+    context: &mut C,
+    scheduler: &mut impl scheduler::EntityScheduler<C>,
+) -> Result<bool, RealtimePanic> {
+    if !context.components_mut().contains_entity(entity) {
+        return Ok(false);
+    }
+    let mut frame_remaining = begin_entity_frame(entity, frame_duration, context);
+    while frame_remaining > Duration::from_micros(0) {
+        let (events, until_next_tick) =
+            scheduler.step(entity, context.components_mut(), frame_remaining);
+        frame_remaining -= until_next_tick;
+        if !events.is_empty() {
+            context.before_tick(entity);
+        }
+        if let Err(panic) = events.apply_catch_unwind(entity, context) {
+            context.after_entity_frame(entity);
+            return Err(panic);
+        }
+    }
+    context.after_entity_frame(entity);
+    Ok(true)
 }
 
-pub trait RealtimeComponents<C: ?Sized> {
-    type EntityEvents: RealtimeEntityEvents<C>;
+/// Like `process_entity_frame` but stops once `max_ticks` of the entity's components have
+/// ticked, returning the number of components which ticked. Components due at the same instant
+/// tick together, so the final step can take the count over `max_ticks`. If the budget runs out
+/// before the end of the frame, the remainder of the frame is not simulated for this entity.
+pub fn process_entity_frame_with_budget<C: ContextContainsRealtimeComponents>(
+    entity: Entity,
+    frame_duration: Duration,
+    context: &mut C,
+    max_ticks: usize,
+) -> usize {
+    realtime_profile_scope!("process_entity_frame_with_budget");
+    process_entity_frame_with_budget_unscoped(
+        entity,
+        frame_duration,
+        context,
+        max_ticks,
+        &mut scheduler::LinearScan::new(),
+    )
+}
 
-    fn tick_entity(
-        &mut self,
-        entity: Entity,
-        frame_remaining: Duration,
-    ) -> (Self::EntityEvents, Duration);
+fn process_entity_frame_with_budget_unscoped<C: ContextContainsRealtimeComponents>(
+    entity: Entity,
+    frame_duration: Duration,
+    context: &mut C,
+    max_ticks: usize,
+    scheduler: &mut impl scheduler::EntityScheduler<C>,
+) -> usize {
+    let progress = match process_entity_frame_with_policy_unscoped(
+        entity,
+        frame_duration,
+        context,
+        budget::MaxTicksPerEntity(max_ticks),
+        scheduler,
+    ) {
+        Some(progress) => progress,
+        None => return 0,
+    };
+    #[cfg(feature = "log")]
+    if progress.remaining > Duration::from_micros(0) {
+        log::warn!(
+            "tick budget of {} exhausted for {:?}, dropping the remaining {:?} of the frame",
+            max_ticks,
+            entity,
+            progress.remaining
+        );
+    }
+    progress.ticks
 }
 
-pub fn process_entity_frame<C: ContextContainsRealtimeComponents>(
+/// Like `process_entity_frame` but components in the given group are left untouched, e.g. to
+/// skip render-only effects on a dedicated server
+pub fn process_entity_frame_excluding_group<C: ContextContainsRealtimeComponents>(
     entity: Entity,
     frame_duration: Duration,
+    group: &str,
     context: &mut C,
 ) {
-    let mut frame_remaining = frame_duration;
+    realtime_profile_scope!("process_entity_frame_excluding_group");
+    let mut frame_remaining = begin_entity_frame(entity, frame_duration, context);
     while frame_remaining > Duration::from_micros(0) {
-        let (events, until_next_tick) = context
-            .components_mut()
-            .tick_entity(entity, frame_remaining);
+        let (events, until_next_tick) =
+            context
+                .components_mut()
+                .tick_entity_excluding_group(group, entity, frame_remaining);
+        if !events.is_empty() {
+            context.before_tick(entity);
+        }
         events.apply(entity, context);
         frame_remaining -= until_next_tick;
     }
+    context.after_entity_frame(entity);
+}
+
+/// Like `process_entity_frame` but if the frame is longer than `max_frame_duration`, components
+/// whose catch-up policy is `CatchUpPolicy::Skip` skip the excess time rather than replaying
+/// it. Components whose catch-up policy is `CatchUpPolicy::Burst` simulate the entire frame.
+pub fn process_entity_frame_with_catch_up<C: ContextContainsRealtimeComponents>(
+    entity: Entity,
+    frame_duration: Duration,
+    max_frame_duration: Duration,
+    context: &mut C,
+) {
+    realtime_profile_scope!("process_entity_frame_with_catch_up");
+    process_entity_frame_with_catch_up_unscoped(
+        entity,
+        frame_duration,
+        max_frame_duration,
+        context,
+        &mut scheduler::LinearScan::new(),
+    );
+}
+
+fn process_entity_frame_with_catch_up_unscoped<C: ContextContainsRealtimeComponents>(
+    entity: Entity,
+    frame_duration: Duration,
+    max_frame_duration: Duration,
+    context: &mut C,
+    scheduler: &mut impl scheduler::EntityScheduler<C>,
+) {
+    if !context.components_mut().contains_entity(entity) {
+        return;
+    }
+    let frame_duration = begin_entity_frame(entity, frame_duration, context);
+    if frame_duration > max_frame_duration {
+        let excess = frame_duration - max_frame_duration;
+        #[cfg(feature = "log")]
+        log::warn!(
+            "frame of {:?} for {:?} exceeds {:?}, skipping {:?} for components which don't catch up",
+            frame_duration,
+            entity,
+            max_frame_duration,
+            excess
+        );
+        let mut excess_remaining = excess;
+        while excess_remaining > Duration::from_micros(0) {
+            let (events, until_next_tick) = context
+                .components_mut()
+                .tick_entity_burst(entity, excess_remaining);
+            if !events.is_empty() {
+                context.before_tick(entity);
+            }
+            events.apply(entity, context);
+            excess_remaining -= until_next_tick;
+        }
+        context.components_mut().skip_entity(entity, excess);
+        process_entity_frame_inner(
+            entity,
+            max_frame_duration,
+            context,
+            budget::Unlimited,
+            scheduler,
+        );
+    } else {
+        process_entity_frame_inner(
+            entity,
+            frame_duration,
+            context,
+            budget::Unlimited,
+            scheduler,
+        );
+    }
 }
 
 #[cfg(not(feature = "serialize"))]
 #[macro_export]
 macro_rules! declare_realtime_entity_module_types {
-    { $($component_name:ident: $component_type:ty,)* } => {
+    { #[serde(crate = $serde_crate:literal)] $components:ident $entity_data:ident $components_delta:ident $($component_name:ident: $component_type:ty,)* } => {
         /// Struct where each field contains a table associating entities with data
         /// (ie. components)
         #[derive(Debug, Clone)]
-        pub struct RealtimeComponents {
+        pub struct $components {
             $(pub $component_name: $crate::RealtimeComponentTable<$component_type>,)*
         }
 
         /// Struct holding all components for a single entity
         #[derive(Debug, Clone)]
-        pub struct RealtimeEntityData {
+        pub struct $entity_data {
             $(pub $component_name: Option<$component_type>,)*
         }
+
+        /// The changes to each table between two points in time, for replication
+        #[derive(Debug, Clone)]
+        pub struct $components_delta {
+            $(pub $component_name: $crate::replication::RealtimeComponentTableDelta<$component_type>,)*
+        }
     }
 }
 
 #[cfg(feature = "serialize")]
 #[macro_export]
 macro_rules! declare_realtime_entity_module_types {
-    { $($component_name:ident: $component_type:ty,)* } => {
+    { #[serde(crate = $serde_crate:literal)] $components:ident $entity_data:ident $components_delta:ident $($component_name:ident: $component_type:ty,)* } => {
         /// Struct where each field contains a table associating entities with data
         /// (ie. components)
         #[derive(Debug, Clone, $crate::serde::Serialize, $crate::serde::Deserialize)]
-        pub struct RealtimeComponents {
+        #[serde(crate = $serde_crate)]
+        pub struct $components {
             $(pub $component_name: $crate::RealtimeComponentTable<$component_type>,)*
         }
 
-        /// Struct holding all components for a single entity
+        /// Struct holding all components for a single entity. Components missing when
+        /// deserializing are absent, so entity templates in data files need only list the
+        /// components they have.
         #[derive(Debug, Clone, $crate::serde::Serialize, $crate::serde::Deserialize)]
-        pub struct RealtimeEntityData {
+        #[serde(crate = $serde_crate, default)]
+        pub struct $entity_data {
             $(pub $component_name: Option<$component_type>,)*
         }
+
+        /// The changes to each table between two points in time, for replication
+        #[derive(Debug, Clone, $crate::serde::Serialize, $crate::serde::Deserialize)]
+        #[serde(crate = $serde_crate)]
+        pub struct $components_delta {
+            $(pub $component_name: $crate::replication::RealtimeComponentTableDelta<$component_type>,)*
+        }
     }
 }
 
+/// Declare a module containing types for storing and processing the realtime components of
+/// a context.
+///
+/// Items from this crate and from `entity_table` are referred to via `$crate`, so the macro
+/// works when this crate is re-exported by a facade crate. The exception is the serde derive
+/// used by the `serialize` feature, which expects a crate named `serde` to be available. This
+/// can be overridden with a leading `#[serde(crate = "...")]` attribute, as with serde's own
+/// derive:
+///
+/// ```ignore
+/// declare_realtime_entity_module! {
+///     #[serde(crate = "engine::serde")]
+///     components[Context] {
+///         flicker: Flicker,
+///     }
+/// }
+/// ```
+///
+/// With the `serialize` feature, the generated `RealtimeEntityData` is also serializable, so
+/// entity templates can be stored in data files listing only the components they have.
+///
+/// A component can be given a delay before it first ticks after being inserted with
+/// `insert_entity_data` or `update_entity_data`, written as an integer with a unit of `ns`,
+/// `us`, `ms` or `s`:
+///
+/// ```ignore
+/// declare_realtime_entity_module! {
+///     components[Context] {
+///         flicker: Flicker = 100ms,
+///         particle_emitter: ParticleEmitter,
+///     }
+/// }
+/// ```
+///
+/// Components can be given a catch-up policy of `burst` (the default) or `skip` with a
+/// `#[catch_up(...)]` attribute, which determines how they make up for time during
+/// `fast_forward` and `process_entity_frame_with_catch_up`:
+///
+/// ```ignore
+/// declare_realtime_entity_module! {
+///     components[Context] {
+///         #[catch_up(skip)]
+///         flicker: Flicker,
+///         #[catch_up(burst)]
+///         poison: PoisonTimer,
+///     }
+/// }
+/// ```
+///
+/// Components can be assigned to a named group with a `#[group(...)]` attribute (after any
+/// `#[catch_up(...)]` attribute). The generated `tick_entity_group` and
+/// `tick_entity_excluding_group` methods, and `process_entity_frame_excluding_group`, tick
+/// components selectively by group:
+///
+/// ```ignore
+/// declare_realtime_entity_module! {
+///     components[Context] {
+///         #[catch_up(skip)]
+///         #[group(render)]
+///         flicker: Flicker,
+///         poison: PoisonTimer,
+///     }
+/// }
+/// ```
+///
+/// Contexts can have generic parameters (lifetimes, types and consts), which are declared after
+/// the module name, along with an optional where clause after the context:
+///
+/// ```ignore
+/// declare_realtime_entity_module! {
+///     components<'a, R>[Context<'a, R>] where R: Rng {
+///         flicker: Flicker,
+///     }
+/// }
+///
+/// declare_realtime_entity_module! {
+///     grid_components<'a, const W: usize, const H: usize>[GridContext<'a, W, H>] {
+///         flicker: Flicker,
+///     }
+/// }
+/// ```
+///
+/// Generic parameters must not have defaults, since they are used to declare impls.
+///
+/// The generated types can be renamed with a `#[names(...)]` attribute (after any serde
+/// attribute), which allows the types generated by several invocations to be re-exported from
+/// a single module. The `RealtimeEntityViewMut` and `RealtimeComponentsDelta` names may be left
+/// out, keeping their defaults:
+///
+/// ```ignore
+/// declare_realtime_entity_module! {
+///     #[names(
+///         RealtimeComponents = EnemyComponents,
+///         RealtimeEntityData = EnemyEntityData,
+///         RealtimeEntityEvents = EnemyEntityEvents,
+///         RealtimeEntityEvent = EnemyEntityEvent,
+///         RealtimeEntityViewMut = EnemyEntityViewMut,
+///         RealtimeComponentsDelta = EnemyComponentsDelta,
+///     )]
+///     enemy_components[Context] {
+///         flicker: Flicker,
+///     }
+/// }
+/// ```
 #[macro_export]
 macro_rules! declare_realtime_entity_module {
-    { $module_name:ident[$context:ty] { $($component_name:ident: $component_type:ty,)* } } => {
-        $crate::declare_realtime_entity_module! { $module_name<>[$context] { $($component_name: $component_type,)* } }
-    };
-    { $module_name:ident<$lt:lifetime>[$context:ty] { $($component_name:ident: $component_type:ty,)* } } => {
-        $crate::declare_realtime_entity_module! { $module_name<$lt,>[$context] { $($component_name: $component_type,)* } }
-    };
-    { $module_name:ident<$($lt:lifetime),* $(,)?>[$context:ty] { $($component_name:ident: $component_type:ty,)* } } => {
+    {
+        @inner
+        serde_crate[$serde_crate:literal $($_default_serde_crate:literal)?]
+        names[
+            [$components:ident $entity_data:ident $entity_events:ident $entity_event:ident $($_default_name:ident)*]
+            [$entity_view_mut:ident $($_default_view_mut:ident)?]
+            [$components_delta:ident $($_default_components_delta:ident)?]
+        ]
+        $module_name:ident[$($generics:tt)*][$context:ty][$($where_clause:tt)*]
+        {
+            $(
+                $(#[catch_up($catch_up:ident)])?
+                $(#[group($group:ident)])?
+                $component_name:ident: $component_type:ty $(= $initial_delay:literal)?,
+            )*
+        }
+    } => {
+        // The generated types refer to component types through `ComponentTypes`, which is only
+        // as visible as the module itself
+        #[allow(private_interfaces)]
         mod $module_name {
-            #[allow(unused_imports)]
-            use super::*;
+            /// Associates each component name with its type. Implemented outside the module so
+            /// that component types are resolved in the scope where the macro was invoked.
+            #[doc(hidden)]
+            #[allow(non_camel_case_types)]
+            pub(super) trait ComponentTypes {
+                $(type $component_name: $crate::RealtimeComponent;)*
+            }
+
+            #[doc(hidden)]
+            pub(super) enum Types {}
+
+            #[allow(non_camel_case_types)]
+            mod component_type {
+                $(pub type $component_name = <super::Types as super::ComponentTypes>::$component_name;)*
+            }
+
+            /// The delay before each component first ticks after being inserted by
+            /// `insert_entity_data` or `update_entity_data`
+            #[allow(non_upper_case_globals, unused)]
+            pub mod initial_delay {
+                $(pub const $component_name: ::std::time::Duration = $crate::realtime_initial_delay!($($initial_delay)?);)*
+            }
+
+            /// The catch-up policy of each component
+            #[allow(non_upper_case_globals, unused)]
+            pub mod catch_up_policy {
+                $(pub const $component_name: $crate::CatchUpPolicy = $crate::realtime_catch_up_policy!($($catch_up)?);)*
+            }
+
+            /// The name of the group each component belongs to, if any
+            #[allow(non_upper_case_globals, unused)]
+            pub mod group {
+                $(pub const $component_name: ::std::option::Option<&str> = $crate::realtime_group!($($group)?);)*
+            }
+
+            /// The index of each component in a `TickedComponents`
+            #[allow(non_upper_case_globals, unused)]
+            pub mod component_index {
+                #[allow(non_camel_case_types)]
+                enum Index {
+                    $($component_name,)*
+                }
+                $(pub const $component_name: usize = Index::$component_name as usize;)*
+                const COUNT: usize = {
+                    let indices: &[Index] = &[$(Index::$component_name),*];
+                    indices.len()
+                };
+                const _: () = assert!(
+                    COUNT <= $crate::TickedComponents::MAX_COMPONENTS,
+                    "too many components in realtime entity module",
+                );
+            }
 
             $crate::declare_realtime_entity_module_types! {
-                $($component_name: $component_type,)*
+                #[serde(crate = $serde_crate)]
+                $components $entity_data $components_delta
+                $($component_name: component_type::$component_name,)*
             }
 
-            impl Default for RealtimeComponents {
+            impl Default for $components {
                 fn default() -> Self {
                     Self {
                         $($component_name: Default::default(),)*
@@ -233,89 +3179,562 @@ macro_rules! declare_realtime_entity_module {
                 }
             }
 
-            impl Default for RealtimeEntityData {
+            impl Default for $entity_data {
                 fn default() -> Self {
                     Self {
                         $($component_name: None,)*
                     }
                 }
-            }
+            }
+
+            impl Default for $components_delta {
+                fn default() -> Self {
+                    Self {
+                        $($component_name: Default::default(),)*
+                    }
+                }
+            }
+
+            impl $components_delta {
+                /// Returns true if no table changed.
+                #[allow(unused)]
+                pub fn is_empty(&self) -> bool {
+                    true $(&& self.$component_name.is_empty())*
+                }
+            }
+
+            impl $entity_data {
+                /// Returns true if no component is present.
+                #[allow(unused)]
+                pub fn is_empty(&self) -> bool {
+                    true $(&& self.$component_name.is_none())*
+                }
+
+                /// Combine with another `RealtimeEntityData`, e.g. to apply a variant on top of a
+                /// base archetype. Components present in `other` replace those in `self`.
+                #[allow(unused)]
+                pub fn merge(self, other: Self) -> Self {
+                    Self {
+                        $($component_name: other.$component_name.or(self.$component_name),)*
+                    }
+                }
+            }
+
+            /// Struct holding events associated with components for a given entity
+            pub struct $entity_events {
+                $(pub $component_name: Option<<component_type::$component_name as $crate::RealtimeComponent>::Event>,)*
+            }
+
+            /// Mutable references to every component of a single entity
+            pub struct $entity_view_mut<'a> {
+                $(pub $component_name: Option<&'a mut component_type::$component_name>,)*
+            }
+
+            /// A single event produced by one of the components of an entity
+            #[allow(non_camel_case_types)]
+            pub enum $entity_event {
+                $($component_name(<component_type::$component_name as $crate::RealtimeComponent>::Event),)*
+            }
+
+            impl $entity_events {
+                /// Returns true if no component produced an event.
+                #[allow(unused)]
+                pub fn is_empty(&self) -> bool {
+                    true $(&& self.$component_name.is_none())*
+                }
+
+            }
+
+            impl ::std::fmt::Debug for $entity_events
+            where
+                $(for<'a> <component_type::$component_name as $crate::RealtimeComponent>::Event: ::std::fmt::Debug,)*
+            {
+                fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+                    let mut debug_struct = f.debug_struct(stringify!($entity_events));
+                    $(if let Some(event) = self.$component_name.as_ref() {
+                        debug_struct.field(stringify!($component_name), event);
+                    })*
+                    debug_struct.finish()
+                }
+            }
+
+            $crate::realtime_rayon! {
+                #[derive(Default)]
+                struct ParallelEntityComponents<'a> {
+                    $($component_name: Option<&'a mut $crate::ScheduledRealtimeComponent<component_type::$component_name>>,)*
+                }
+
+                struct ParallelLimits {
+                    $($component_name: $crate::ParallelTableLimits,)*
+                }
+
+                /// Which of an entity's components have been deferred by their table's event cap
+                #[derive(Default)]
+                struct ParallelEntityDeferred {
+                    $($component_name: bool,)*
+                }
+
+                impl $components {
+                    /// The enabled components of every entity, in the order the entities are
+                    /// first found in the tables
+                    fn parallel_entity_components(&mut self) -> Vec<($crate::Entity, ParallelEntityComponents<'_>)> {
+                        let mut index = ::std::collections::HashMap::new();
+                        let mut entities: Vec<($crate::Entity, ParallelEntityComponents)> = Vec::new();
+                        $(for (entity, scheduled) in self.$component_name.iter_with_schedule_mut() {
+                            if scheduled.is_enabled() {
+                                let i = *index.entry(entity).or_insert_with(|| {
+                                    entities.push((entity, ParallelEntityComponents::default()));
+                                    entities.len() - 1
+                                });
+                                entities[i].1.$component_name = Some(scheduled);
+                            }
+                        })*
+                        entities
+                    }
+
+                    /// The limits of every table, shared between the threads ticking them
+                    fn parallel_limits(&self) -> ParallelLimits {
+                        ParallelLimits {
+                            $($component_name: self.$component_name.parallel_limits(),)*
+                        }
+                    }
+
+                    /// Tick the components of an entity for the duration of a frame, appending
+                    /// their events to `events`. Components which are due once their table's
+                    /// event cap is reached are deferred to the next frame, and left due.
+                    fn tick_parallel_entity(
+                        entity: $crate::Entity,
+                        components: &mut ParallelEntityComponents<'_>,
+                        limits: &ParallelLimits,
+                        frame_duration: ::std::time::Duration,
+                        events: &mut Vec<($crate::Entity, $entity_events)>,
+                    ) {
+                        let mut frame_remaining = $crate::duration_to_nanos(frame_duration);
+                        let mut deferred = ParallelEntityDeferred::default();
+                        while frame_remaining > 0 {
+                            let mut until_next_tick = frame_remaining;
+                            $(if let Some(scheduled_component) = components.$component_name.as_ref() {
+                                if !deferred.$component_name {
+                                    until_next_tick = until_next_tick.min(scheduled_component.until_next_tick_nanos());
+                                }
+                            })*
+                            $(let $component_name = if let Some(scheduled_component) = components.$component_name.as_mut() {
+                                if deferred.$component_name {
+                                    let until_due = scheduled_component.until_next_tick_nanos();
+                                    scheduled_component.advance_nanos(until_next_tick.min(until_due));
+                                    None
+                                } else if until_next_tick != scheduled_component.until_next_tick_nanos() {
+                                    scheduled_component.advance_nanos(until_next_tick);
+                                    None
+                                } else if limits.$component_name.try_tick::<component_type::$component_name>(entity) {
+                                    let event = scheduled_component.tick(entity);
+                                    limits.$component_name.after_tick(entity, scheduled_component);
+                                    Some(event)
+                                } else {
+                                    scheduled_component.advance_nanos(until_next_tick);
+                                    deferred.$component_name = true;
+                                    None
+                                }
+                            } else {
+                                None
+                            };)*
+                            let entity_events = $entity_events {
+                                $($component_name,)*
+                            };
+                            if !entity_events.is_empty() {
+                                events.push((entity, entity_events));
+                            }
+                            frame_remaining -= until_next_tick;
+                        }
+                    }
+
+                    /// Tick every entity for the duration of a frame, processing entities in
+                    /// parallel on the rayon thread pool. Events are buffered rather than
+                    /// applied, so they can't influence any components during the frame. Each
+                    /// table's event cap and maximum period apply, though which components are
+                    /// deferred by the event cap depends on how the entities are scheduled.
+                    /// Returns the events of each entity in the order they occurred, grouped by
+                    /// entity. Apply them afterwards with `RealtimeEntityEvents::apply`.
+                    #[allow(unused, private_bounds)]
+                    pub fn par_tick_all(
+                        &mut self,
+                        frame_duration: ::std::time::Duration,
+                    ) -> Vec<($crate::Entity, $entity_events)>
+                    where
+                        $(for<'a> component_type::$component_name: ::std::marker::Send,)*
+                        $(for<'a> <component_type::$component_name as $crate::RealtimeComponent>::Event: ::std::marker::Send,)*
+                    {
+                        use $crate::rayon::prelude::*;
+                        let limits = self.parallel_limits();
+                        let events = self.parallel_entity_components()
+                            .par_iter_mut()
+                            .map(|(entity, components)| {
+                                let mut events = Vec::new();
+                                Self::tick_parallel_entity(*entity, components, &limits, frame_duration, &mut events);
+                                events
+                            })
+                            .collect::<Vec<_>>()
+                            .into_iter()
+                            .flatten()
+                            .collect();
+                        $(limits.$component_name.finish(&mut self.$component_name);)*
+                        events
+                    }
+
+                    /// Like `par_tick_all` but entities are partitioned into at most
+                    /// `num_shards` shards of adjacent entities, each ticked on its own thread
+                    /// into its own event buffer. This is coarser than `par_tick_all`, so suits
+                    /// worlds with many entities which each do little work. The buffers are
+                    /// merged in entity order, so the returned events don't depend on how the
+                    /// shards were scheduled. Apply them afterwards with
+                    /// `apply_buffered_events`. Panics if `num_shards` is zero.
+                    #[allow(unused, private_bounds)]
+                    pub fn par_tick_all_sharded(
+                        &mut self,
+                        frame_duration: ::std::time::Duration,
+                        num_shards: usize,
+                    ) -> Vec<($crate::Entity, $entity_events)>
+                    where
+                        $(for<'a> component_type::$component_name: ::std::marker::Send,)*
+                        $(for<'a> <component_type::$component_name as $crate::RealtimeComponent>::Event: ::std::marker::Send,)*
+                    {
+                        use $crate::rayon::prelude::*;
+                        assert!(num_shards > 0, "num_shards must be positive");
+                        let limits = self.parallel_limits();
+                        let mut entities = self.parallel_entity_components();
+                        entities.sort_unstable_by_key(|(entity, _)| *entity);
+                        let shard_size = entities.len().div_ceil(num_shards).max(1);
+                        let events = entities
+                            .par_chunks_mut(shard_size)
+                            .map(|shard| {
+                                let mut events = Vec::new();
+                                for (entity, components) in shard {
+                                    Self::tick_parallel_entity(*entity, components, &limits, frame_duration, &mut events);
+                                }
+                                events
+                            })
+                            .collect::<Vec<_>>()
+                            .into_iter()
+                            .flatten()
+                            .collect();
+                        drop(entities);
+                        $(limits.$component_name.finish(&mut self.$component_name);)*
+                        events
+                    }
+                }
+            }
+
+            impl $components {
+
+                /// Remove all components for all entities.
+                #[allow(unused)]
+                pub fn clear(&mut self) {
+                    $(self.$component_name.clear();)*
+                }
+
+                /// The changes to every table which turn `previous` into `self`. See
+                /// `RealtimeComponentTable::delta_from`.
+                #[allow(unused, private_bounds)]
+                pub fn delta_from(&self, previous: &Self) -> $components_delta
+                where
+                    $(for<'a> component_type::$component_name: ::std::clone::Clone + ::std::cmp::PartialEq,)*
+                {
+                    $components_delta {
+                        $($component_name: self.$component_name.delta_from(&previous.$component_name),)*
+                    }
+                }
+
+                /// Bring a copy of every table up to date with the changes in `delta`
+                #[allow(unused)]
+                pub fn apply_delta(&mut self, delta: $components_delta) {
+                    $(self.$component_name.apply_delta(delta.$component_name);)*
+                }
+
+                /// Summarize the schedules of every component of every entity.
+                #[allow(unused)]
+                pub fn debug_summary(&self) -> $crate::RealtimeDebugSummary {
+                    let mut summary = $crate::RealtimeDebugSummary::default();
+                    $(for (entity, scheduled) in self.$component_name.iter_with_schedule() {
+                        summary.add_component(entity, stringify!($component_name), scheduled);
+                    })*
+                    summary
+                }
+
+                /// Toggle whether the component with the given field name is enabled for an entity,
+                /// e.g. in response to `InspectorAction::TogglePause`. Returns whether the component
+                /// is now enabled, or `None` if the entity has no such component.
+                #[allow(unused)]
+                pub fn toggle_enabled(&mut self, entity: $crate::Entity, component: &str) -> Option<bool> {
+                    $(if component == stringify!($component_name) {
+                        let scheduled_component = self.$component_name.get_with_schedule_mut(entity)?;
+                        scheduled_component.set_enabled(!scheduled_component.is_enabled());
+                        return Some(scheduled_component.is_enabled());
+                    })*
+                    None
+                }
+
+                /// Add `duration` to the simulated time of every table without affecting any
+                /// components. Call once per frame after processing every entity.
+                #[allow(unused)]
+                pub fn advance_simulated_time(&mut self, duration: ::std::time::Duration) {
+                    $(self.$component_name.advance_simulated_time(duration);)*
+                }
+
+                /// Advance every component of every entity by `duration` without generating events.
+                #[allow(unused)]
+                pub fn advance_silently(&mut self, duration: ::std::time::Duration) {
+                    $(self.$component_name.advance_silently(duration);)*
+                }
+
+                /// Advance every component of every entity by `duration`. Components whose catch-up
+                /// policy is `CatchUpPolicy::Burst` tick as many times as they would have ticked had
+                /// the time been processed normally, while those whose policy is
+                /// `CatchUpPolicy::Skip` skip the time without generating events. Returns the
+                /// resulting events in the order they would have occurred, each with the time since
+                /// the start of the fast-forward at which it occurred. After ticking
+                /// `MAX_TICKS_PER_INSTANT` times at the same instant an entity stops ticking and its
+                /// components are left due, and components held back by an event cap are left due
+                /// rather than falling behind.
+                #[allow(unused)]
+                pub fn fast_forward(
+                    &mut self,
+                    duration: ::std::time::Duration,
+                ) -> Vec<(::std::time::Duration, $crate::Entity, $entity_events)> {
+                    let mut entities = Vec::new();
+                    let mut seen = ::std::collections::HashSet::new();
+                    $(for entity in self.$component_name.entities() {
+                        if seen.insert(entity) {
+                            entities.push(entity);
+                        }
+                    })*
+                    let mut events = Vec::new();
+                    for entity in entities {
+                        let mut elapsed = ::std::time::Duration::from_millis(0);
+                        let mut steps_this_instant = 0;
+                        while elapsed < duration {
+                            let (entity_events, until_next_tick) =
+                                self.tick_entity_burst(entity, duration - elapsed);
+                            elapsed += until_next_tick;
+                            if !entity_events.is_empty() {
+                                events.push((elapsed, entity, entity_events));
+                            }
+                            if !until_next_tick.is_zero() {
+                                steps_this_instant = 0;
+                            }
+                            steps_this_instant += 1;
+                            if steps_this_instant == $crate::MAX_TICKS_PER_INSTANT {
+                                break;
+                            }
+                        }
+                        if elapsed < duration {
+                            // the entity's components kept ticking without time passing, so
+                            // they skip the rest of the time and are left due
+                            let remaining = $crate::duration_to_nanos(duration - elapsed);
+                            $(if catch_up_policy::$component_name == $crate::CatchUpPolicy::Burst {
+                                self.$component_name.advance_until_due_nanos(entity, remaining);
+                            })*
+                        }
+                    }
+                    $(if catch_up_policy::$component_name == $crate::CatchUpPolicy::Skip {
+                        self.$component_name.advance_silently(duration);
+                    } else {
+                        self.$component_name.advance_simulated_time(duration);
+                    })*
+                    events.sort_by_key(|&(time, _, _)| time);
+                    events
+                }
 
-            /// Struct holding events associated with components for a given entity
-            pub struct RealtimeEntityEvents {
-                $(pub $component_name: Option<<$component_type as $crate::RealtimeComponent>::Event>,)*
-            }
+                /// The time until the soonest tick of any of an entity's enabled components, or
+                /// `None` if the entity has no enabled components. The same as `time_to_due`.
+                #[allow(unused)]
+                pub fn until_next_tick(&self, entity: $crate::Entity) -> Option<::std::time::Duration> {
+                    self.time_to_due(entity)
+                }
 
-            impl RealtimeEntityEvents {
-                /// Update a context by applying all the events.
+                /// How far `tick_entity` would advance an entity's components given
+                /// `frame_remaining`, leaving out components held back by their table's event
+                /// cap.
                 #[allow(unused)]
-                pub fn apply<$($lt,)*>(
-                    self,
+                pub fn until_next_step(
+                    &self,
                     entity: $crate::Entity,
-                    context: &mut $context,
-                ) {
-                    $(if let Some(event) = self.$component_name {
-                        <$component_type as $crate::RealtimeComponentApplyEvent<$context>>::apply_event(
-                            event,
-                            entity,
-                            context,
-                        );
+                    frame_remaining: ::std::time::Duration,
+                ) -> ::std::time::Duration {
+                    let mut until_next_step = frame_remaining;
+                    $(if !self.$component_name.is_capped(entity, frame_remaining) {
+                        if let Some(scheduled_component) = self.$component_name.get_with_schedule(entity) {
+                            if scheduled_component.is_enabled() {
+                                until_next_step = until_next_step.min(scheduled_component.until_next_tick());
+                            }
+                        }
                     })*
+                    until_next_step
                 }
-            }
 
-            impl<$($lt,)*> $crate::RealtimeEntityEvents<$context> for RealtimeEntityEvents {
-                fn apply(self, entity: $crate::Entity, context: &mut $context) {
-                    RealtimeEntityEvents::apply(self, entity, context);
+                /// Returns true if the entity has any components, whether or not they're enabled.
+                #[allow(unused)]
+                pub fn contains_entity(&self, entity: $crate::Entity) -> bool {
+                    $(self.$component_name.contains(entity) ||)* false
                 }
-            }
 
-            impl RealtimeComponents {
+                /// The time until any of an entity's enabled components next ticks, or `None` if
+                /// it has no enabled components.
+                #[allow(unused)]
+                pub fn time_to_due(&self, entity: $crate::Entity) -> Option<::std::time::Duration> {
+                    let mut time_to_due: Option<::std::time::Duration> = None;
+                    $(if let Some(component_time_to_due) = self.$component_name.time_to_due(entity) {
+                        time_to_due = Some(time_to_due.map_or(component_time_to_due, |time_to_due| {
+                            time_to_due.min(component_time_to_due)
+                        }));
+                    })*
+                    time_to_due
+                }
 
-                /// Remove all components for all entities.
+                /// Returns true if any of an entity's components would tick during a frame of
+                /// `frame_duration`.
                 #[allow(unused)]
-                pub fn clear(&mut self) {
-                    $(self.$component_name.clear();)*
+                pub fn is_due(&self, entity: $crate::Entity, frame_duration: ::std::time::Duration) -> bool {
+                    $(self.$component_name.is_due(entity, frame_duration) ||)* false
+                }
+
+                /// Replace the time until each component of every entity next ticks with the
+                /// result of calling `f` with its entity and current time until next tick, e.g.
+                /// adding a fixed offset after a cutscene.
+                #[allow(unused)]
+                pub fn map_schedules(&mut self, mut f: impl FnMut($crate::Entity, ::std::time::Duration) -> ::std::time::Duration) {
+                    $(self.$component_name.map_schedules(&mut f);)*
+                }
+
+                /// The shortest non-zero period of an entity's components, or `None` if none of
+                /// them have ticked.
+                #[allow(unused)]
+                pub fn min_period(&self, entity: $crate::Entity) -> Option<::std::time::Duration> {
+                    let mut min_period: Option<::std::time::Duration> = None;
+                    $(if let Some(scheduled_component) = self.$component_name.get_with_schedule(entity) {
+                        let period = scheduled_component.period();
+                        if !period.is_zero() {
+                            min_period = Some(min_period.map_or(period, |min_period| min_period.min(period)));
+                        }
+                    })*
+                    min_period
                 }
 
                 /// Remove all components for a given entity.
                 #[allow(unused)]
                 pub fn remove_entity(&mut self, entity: $crate::Entity) {
-                    $(self.$component_name.remove(entity);)*
+                    $(self.$component_name.remove_with_schedule(entity);)*
+                }
+
+                /// Remove all components for a given entity, returning the events returned by
+                /// their `on_remove` methods.
+                #[allow(unused)]
+                pub fn remove_entity_with_events(&mut self, entity: $crate::Entity) -> $entity_events {
+                    $entity_events {
+                        $($component_name: self.$component_name.remove_with_event(entity).and_then(|(_, event)| event),)*
+                    }
+                }
+
+                /// Drop the ticks of an entity's components which would leave them further behind
+                /// than their `max_missed_periods` during a frame of `frame_duration`, returning
+                /// their `on_ticks_dropped` events.
+                #[allow(unused)]
+                pub fn drop_excess_ticks(&mut self, entity: $crate::Entity, frame_duration: ::std::time::Duration) -> $entity_events {
+                    $entity_events {
+                        $($component_name: self.$component_name.get_with_schedule_mut(entity).and_then(|scheduled| scheduled.drop_excess_ticks(frame_duration)),)*
+                    }
+                }
+
+                /// Remove the components of entities which are no longer allocated by
+                /// `allocator` from every table.
+                #[allow(unused)]
+                pub fn retain_live(&mut self, allocator: &$crate::EntityAllocator) {
+                    $(self.$component_name.retain_live(allocator);)*
+                }
+
+                /// Borrow every component of an entity mutably at the same time.
+                #[allow(unused)]
+                pub fn entity_view_mut(&mut self, entity: $crate::Entity) -> $entity_view_mut<'_> {
+                    $entity_view_mut {
+                        $($component_name: self.$component_name.get_mut(entity),)*
+                    }
+                }
+
+                /// Call `f` once for each entity with at least one component, with mutable access to
+                /// all of its components at the same time.
+                #[allow(unused)]
+                pub fn for_each_entity_mut(&mut self, mut f: impl FnMut($crate::Entity, $entity_view_mut<'_>)) {
+                    let mut entities = Vec::new();
+                    let mut seen = ::std::collections::HashSet::new();
+                    $(for entity in self.$component_name.entities() {
+                        if seen.insert(entity) {
+                            entities.push(entity);
+                        }
+                    })*
+                    for entity in entities {
+                        f(entity, self.entity_view_mut(entity));
+                    }
                 }
 
                 /// Clone each component of an entity into a `RealtimeEntityData`.
                 #[allow(unused)]
-                pub fn clone_entity_data(&self, entity: $crate::Entity) -> RealtimeEntityData {
-                    RealtimeEntityData {
+                pub fn clone_entity_data(&self, entity: $crate::Entity) -> $entity_data {
+                    $entity_data {
                         $($component_name: self.$component_name.get(entity).cloned(),)*
                     }
                 }
 
                 /// Remove each component of an entity into a `RealtimeEntityData`.
                 #[allow(unused)]
-                pub fn remove_entity_data(&mut self, entity: $crate::Entity) -> RealtimeEntityData {
-                    RealtimeEntityData {
-                        $($component_name: self.$component_name.remove(entity),)*
+                pub fn remove_entity_data(&mut self, entity: $crate::Entity) -> $entity_data {
+                    $entity_data {
+                        $($component_name: self.$component_name.remove_with_schedule(entity).map(|c| c.component),)*
                     }
                 }
 
-                /// Insert each component in a `RealtimeEntityData` for an entity.
+                /// Insert each component in a `RealtimeEntityData` for an entity. Returns the
+                /// components which were replaced.
                 #[allow(unused)]
-                pub fn insert_entity_data(&mut self, entity: $crate::Entity, entity_data: RealtimeEntityData) {
-                    $(if let Some(field) = entity_data.$component_name {
-                        self.$component_name.insert(entity, field);
-                    })*
+                pub fn insert_entity_data(&mut self, entity: $crate::Entity, entity_data: $entity_data) -> $entity_data {
+                    $entity_data {
+                        $($component_name: entity_data.$component_name.and_then(|field| {
+                            self.$component_name
+                                .insert_with_schedule(
+                                    entity,
+                                    $crate::ScheduledRealtimeComponent::new(field, initial_delay::$component_name),
+                                )
+                                .map(|c| c.component)
+                        }),)*
+                    }
+                }
+
+                /// Like `insert_entity_data` but also returns the events returned by the
+                /// `on_insert` methods of the inserted components.
+                #[allow(unused)]
+                pub fn insert_entity_data_with_events(
+                    &mut self,
+                    entity: $crate::Entity,
+                    mut entity_data: $entity_data,
+                ) -> ($entity_data, $entity_events) {
+                    $(let $component_name = entity_data
+                        .$component_name
+                        .as_mut()
+                        .and_then($crate::RealtimeComponent::on_insert);)*
+                    (self.insert_entity_data(entity, entity_data), $entity_events {
+                        $($component_name,)*
+                    })
                 }
 
                 /// Update all components of an entity to match a `RealtimeEntityData` (removing
                 /// components that are absent from the `RealtimeEntityData`).
                 #[allow(unused)]
-                pub fn update_entity_data(&mut self, entity: $crate::Entity, entity_data: RealtimeEntityData) {
+                pub fn update_entity_data(&mut self, entity: $crate::Entity, entity_data: $entity_data) {
                     $(if let Some(field) = entity_data.$component_name {
-                        self.$component_name.insert(entity, field);
+                        self.$component_name.insert_with_schedule(
+                            entity,
+                            $crate::ScheduledRealtimeComponent::new(field, initial_delay::$component_name),
+                        );
                     } else {
-                        self.$component_name.remove(entity);
+                        self.$component_name.remove_with_schedule(entity);
                     })*
                 }
 
@@ -325,67 +3744,724 @@ macro_rules! declare_realtime_entity_module {
                 pub fn tick_entity(
                     &mut self,
                     entity: $crate::Entity,
-                    frame_remaining: std::time::Duration,
-                ) -> (RealtimeEntityEvents, std::time::Duration) {
+                    frame_remaining: ::std::time::Duration,
+                ) -> ($entity_events, ::std::time::Duration) {
+                    self.tick_entity_components(entity, frame_remaining, |_, _| true, &mut $crate::TickRng::none())
+                }
+
+                $crate::realtime_rand! {
+                    /// Like `tick_entity` but components may draw random numbers from `rng`.
+                    #[allow(unused)]
+                    pub fn tick_entity_with_rng(
+                        &mut self,
+                        entity: $crate::Entity,
+                        frame_remaining: ::std::time::Duration,
+                        rng: &mut dyn $crate::rand_core::RngCore,
+                    ) -> ($entity_events, ::std::time::Duration) {
+                        self.tick_entity_components(entity, frame_remaining, |_, _| true, &mut $crate::TickRng::new(rng))
+                    }
+                }
+
+                /// Like `tick_entity` but only considers components whose catch-up policy is
+                /// `CatchUpPolicy::Burst`.
+                #[allow(unused)]
+                pub fn tick_entity_burst(
+                    &mut self,
+                    entity: $crate::Entity,
+                    frame_remaining: ::std::time::Duration,
+                ) -> ($entity_events, ::std::time::Duration) {
+                    self.tick_entity_components(entity, frame_remaining, |catch_up_policy, _| {
+                        catch_up_policy == $crate::CatchUpPolicy::Burst
+                    }, &mut $crate::TickRng::none())
+                }
+
+                /// Like `tick_entity` but only considers components in the given group.
+                #[allow(unused)]
+                pub fn tick_entity_group(
+                    &mut self,
+                    group: &str,
+                    entity: $crate::Entity,
+                    frame_remaining: ::std::time::Duration,
+                ) -> ($entity_events, ::std::time::Duration) {
+                    self.tick_entity_components(entity, frame_remaining, |_, component_group| {
+                        component_group == Some(group)
+                    }, &mut $crate::TickRng::none())
+                }
+
+                /// Like `tick_entity` but ignores components in the given group.
+                #[allow(unused)]
+                pub fn tick_entity_excluding_group(
+                    &mut self,
+                    group: &str,
+                    entity: $crate::Entity,
+                    frame_remaining: ::std::time::Duration,
+                ) -> ($entity_events, ::std::time::Duration) {
+                    self.tick_entity_components(entity, frame_remaining, |_, component_group| {
+                        component_group != Some(group)
+                    }, &mut $crate::TickRng::none())
+                }
+
+                /// Advance the components of an entity whose catch-up policy is
+                /// `CatchUpPolicy::Skip` by `duration` without generating events.
+                #[allow(unused)]
+                pub fn skip_entity(&mut self, entity: $crate::Entity, duration: ::std::time::Duration) {
+                    $(if catch_up_policy::$component_name == $crate::CatchUpPolicy::Skip {
+                        if let Some(scheduled_component) = self.$component_name.get_with_schedule_mut(entity) {
+                            scheduled_component.skip(duration);
+                        }
+                    })*
+                }
+
+                fn tick_entity_components(
+                    &mut self,
+                    entity: $crate::Entity,
+                    frame_remaining: ::std::time::Duration,
+                    include: impl Fn($crate::CatchUpPolicy, Option<&str>) -> bool,
+                    rng: &mut $crate::TickRng,
+                ) -> ($entity_events, ::std::time::Duration) {
                     struct RealtimeEntityComponentsMut<'a> {
-                        $($component_name: Option<&'a mut $crate::ScheduledRealtimeComponent<$component_type>>,)*
+                        $($component_name: Option<&'a mut $crate::ScheduledRealtimeComponent<component_type::$component_name>>,)*
+                    }
+                    struct RealtimeEntitySchedules {
+                        $($component_name: Option<u64>,)*
                     }
+                    struct RealtimeEntityCapped {
+                        $($component_name: bool,)*
+                    }
+                    let capped = RealtimeEntityCapped {
+                        $($component_name: include(catch_up_policy::$component_name, group::$component_name)
+                            && self.$component_name.is_capped(entity, frame_remaining),)*
+                    };
                     let mut components = RealtimeEntityComponentsMut {
-                        $($component_name: self.$component_name.get_with_schedule_mut(entity),)*
+                        $($component_name: if include(catch_up_policy::$component_name, group::$component_name) {
+                            self.$component_name.get_with_schedule_mut_capped(entity, frame_remaining).filter(|c| c.is_enabled())
+                        } else {
+                            None
+                        },)*
                     };
-                    let mut until_next_tick = frame_remaining;
+                    let schedules_before = RealtimeEntitySchedules {
+                        $($component_name: components.$component_name.as_ref().map(|c| c.until_next_tick_nanos()),)*
+                    };
+                    let mut until_next_tick = $crate::duration_to_nanos(frame_remaining);
+                    let mut num_schedules = 0;
                     $(if let Some(event) = components.$component_name.as_ref() {
-                        until_next_tick = until_next_tick.min(event.until_next_tick);
+                        until_next_tick = until_next_tick.min(event.until_next_tick_nanos());
+                        num_schedules += 1;
                     })*
+                    $crate::stats::record_schedule_scan(num_schedules);
                     $(let $component_name = if let Some(scheduled_component) = components.$component_name.as_mut() {
-                        if until_next_tick == scheduled_component.until_next_tick {
-                            use $crate::RealtimeComponent;
-                            let (event, until_next_tick) = scheduled_component.component.tick();
-                            scheduled_component.until_next_tick = until_next_tick;
-                            Some(event)
+                        if until_next_tick == scheduled_component.until_next_tick_nanos() {
+                            Some(scheduled_component.tick_with_tick_rng(entity, rng))
                         } else {
-                            scheduled_component.until_next_tick -= until_next_tick;
+                            scheduled_component.advance_nanos(until_next_tick);
                             None
                         }
                     } else {
                         None
                     };)*
-                    (RealtimeEntityEvents {
+                    $($crate::debug_assert_schedule_step(
+                        stringify!($component_name),
+                        entity,
+                        schedules_before.$component_name,
+                        components.$component_name.as_ref().map(|c| c.until_next_tick_nanos()),
+                        $component_name.is_some(),
+                        until_next_tick,
+                    );)*
+                    $(if $component_name.is_some() {
+                        self.$component_name.after_tick(entity);
+                    })*
+                    $(if capped.$component_name {
+                        self.$component_name.advance_until_due_nanos(entity, until_next_tick);
+                    })*
+                    ($entity_events {
                         $($component_name,)*
-                    }, until_next_tick)
+                    }, ::std::time::Duration::from_nanos(until_next_tick))
+                }
+
+                /// Like `tick_entity` but passes each event to `f` as it is produced rather than
+                /// collecting them into a `RealtimeEntityEvents`.
+                #[allow(unused)]
+                pub fn tick_entity_with(
+                    &mut self,
+                    entity: $crate::Entity,
+                    frame_remaining: ::std::time::Duration,
+                    mut f: impl FnMut($entity_event),
+                ) -> ::std::time::Duration {
+                    struct RealtimeEntityComponentsMut<'a> {
+                        $($component_name: Option<&'a mut $crate::ScheduledRealtimeComponent<component_type::$component_name>>,)*
+                    }
+                    struct RealtimeEntitySchedules {
+                        $($component_name: Option<u64>,)*
+                    }
+                    struct RealtimeEntityCapped {
+                        $($component_name: bool,)*
+                    }
+                    let capped = RealtimeEntityCapped {
+                        $($component_name: self.$component_name.is_capped(entity, frame_remaining),)*
+                    };
+                    let mut components = RealtimeEntityComponentsMut {
+                        $($component_name: self.$component_name.get_with_schedule_mut_capped(entity, frame_remaining).filter(|c| c.is_enabled()),)*
+                    };
+                    let schedules_before = RealtimeEntitySchedules {
+                        $($component_name: components.$component_name.as_ref().map(|c| c.until_next_tick_nanos()),)*
+                    };
+                    let mut until_next_tick = $crate::duration_to_nanos(frame_remaining);
+                    let mut num_schedules = 0;
+                    $(if let Some(event) = components.$component_name.as_ref() {
+                        until_next_tick = until_next_tick.min(event.until_next_tick_nanos());
+                        num_schedules += 1;
+                    })*
+                    $crate::stats::record_schedule_scan(num_schedules);
+                    $(let $component_name = if let Some(scheduled_component) = components.$component_name.as_mut() {
+                        if until_next_tick == scheduled_component.until_next_tick_nanos() {
+                            f($entity_event::$component_name(scheduled_component.tick(entity)));
+                            true
+                        } else {
+                            scheduled_component.advance_nanos(until_next_tick);
+                            false
+                        }
+                    } else {
+                        false
+                    };)*
+                    $($crate::debug_assert_schedule_step(
+                        stringify!($component_name),
+                        entity,
+                        schedules_before.$component_name,
+                        components.$component_name.as_ref().map(|c| c.until_next_tick_nanos()),
+                        $component_name,
+                        until_next_tick,
+                    );)*
+                    $(if $component_name {
+                        self.$component_name.after_tick(entity);
+                    })*
+                    $(if capped.$component_name {
+                        self.$component_name.advance_until_due_nanos(entity, until_next_tick);
+                    })*
+                    ::std::time::Duration::from_nanos(until_next_tick)
+                }
+            }
+        }
+
+        impl $module_name::ComponentTypes for $module_name::Types {
+            $(type $component_name = $component_type;)*
+        }
+
+        impl<$($generics)*> $crate::RealtimeEntityEvent<$context> for $module_name::$entity_event
+        $($where_clause)*
+        {
+            fn apply(self, entity: $crate::Entity, context: &mut $context) {
+                match self {
+                    $($module_name::$entity_event::$component_name(event) => {
+                        $crate::realtime_profile_scope!("apply_event", stringify!($component_name));
+                        $crate::watchdog::watch(
+                            $crate::watchdog::TickPhase::ApplyEvent,
+                            ::std::any::type_name::<$component_type>(),
+                            ::std::option::Option::Some(entity),
+                            || <$component_type as $crate::RealtimeComponentApplyEvent<$context>>::apply_event(
+                                event,
+                                entity,
+                                context,
+                            ),
+                        );
+                    })*
                 }
             }
+        }
+
+        impl $module_name::$entity_events {
+            /// Update a context by applying all the events.
+            #[allow(unused)]
+            pub fn apply<$($generics)*>(
+                self,
+                entity: $crate::Entity,
+                context: &mut $context,
+            )
+            $($where_clause)*
+            {
+                $(if let ::std::option::Option::Some(event) = self.$component_name {
+                    $crate::realtime_profile_scope!("apply_event", stringify!($component_name));
+                    $crate::watchdog::watch(
+                        $crate::watchdog::TickPhase::ApplyEvent,
+                        ::std::any::type_name::<$component_type>(),
+                        ::std::option::Option::Some(entity),
+                        || <$component_type as $crate::RealtimeComponentApplyEvent<$context>>::apply_event(
+                            event,
+                            entity,
+                            context,
+                        ),
+                    );
+                })*
+            }
+        }
+
+        impl<$($generics)*> $crate::RealtimeEntityEvents<$context> for $module_name::$entity_events
+        $($where_clause)*
+        {
+            type Event = $module_name::$entity_event;
+
+            const COMPONENT_NAMES: &'static [&'static str] = &[$(stringify!($component_name)),*];
+
+            fn apply(self, entity: $crate::Entity, context: &mut $context) {
+                $module_name::$entity_events::apply(self, entity, context);
+            }
+
+            fn for_each_event(self, mut f: impl FnMut($module_name::$entity_event)) {
+                $(if let ::std::option::Option::Some(event) = self.$component_name {
+                    f($module_name::$entity_event::$component_name(event));
+                })*
+            }
+
+            fn apply_catch_unwind(
+                self,
+                entity: $crate::Entity,
+                context: &mut $context,
+            ) -> ::std::result::Result<(), $crate::RealtimePanic> {
+                $(if let ::std::option::Option::Some(event) = self.$component_name {
+                    ::std::panic::catch_unwind(::std::panic::AssertUnwindSafe(|| {
+                        $crate::realtime_profile_scope!("apply_event", stringify!($component_name));
+                        $crate::watchdog::watch(
+                            $crate::watchdog::TickPhase::ApplyEvent,
+                            ::std::any::type_name::<$component_type>(),
+                            ::std::option::Option::Some(entity),
+                            || <$component_type as $crate::RealtimeComponentApplyEvent<$context>>::apply_event(
+                                event,
+                                entity,
+                                context,
+                            ),
+                        );
+                    }))
+                    .map_err(|payload| $crate::RealtimePanic {
+                        entity,
+                        component: stringify!($component_name),
+                        payload,
+                    })?;
+                })*
+                ::std::result::Result::Ok(())
+            }
+
+            fn is_empty(&self) -> bool {
+                $module_name::$entity_events::is_empty(self)
+            }
+
+            fn ticked(&self) -> $crate::TickedComponents {
+                let mut ticked = $crate::TickedComponents::empty();
+                $(if self.$component_name.is_some() {
+                    ticked.insert($module_name::component_index::$component_name);
+                })*
+                ticked
+            }
+        }
+
+        impl<$($generics)*> $crate::RealtimeComponents<$context> for $module_name::$components
+        $($where_clause)*
+        {
+            type EntityEvents = $module_name::$entity_events;
+
+            fn tick_entity(
+                &mut self,
+                entity: $crate::Entity,
+                frame_remaining: ::std::time::Duration,
+            ) -> (Self::EntityEvents, ::std::time::Duration) {
+                $module_name::$components::tick_entity(self, entity, frame_remaining)
+            }
+
+            fn remove_entity(&mut self, entity: $crate::Entity) {
+                $module_name::$components::remove_entity(self, entity)
+            }
+
+            fn remove_entity_with_events(&mut self, entity: $crate::Entity) -> Self::EntityEvents {
+                $module_name::$components::remove_entity_with_events(self, entity)
+            }
+
+            fn drop_excess_ticks(&mut self, entity: $crate::Entity, frame_duration: ::std::time::Duration) -> Self::EntityEvents {
+                $module_name::$components::drop_excess_ticks(self, entity, frame_duration)
+            }
+
+            fn clear(&mut self) {
+                $module_name::$components::clear(self)
+            }
+
+            fn advance_simulated_time(&mut self, duration: ::std::time::Duration) {
+                $module_name::$components::advance_simulated_time(self, duration)
+            }
+
+            fn until_next_tick(&self, entity: $crate::Entity) -> ::std::option::Option<::std::time::Duration> {
+                $module_name::$components::until_next_tick(self, entity)
+            }
+
+            fn until_next_step(
+                &self,
+                entity: $crate::Entity,
+                frame_remaining: ::std::time::Duration,
+            ) -> ::std::time::Duration {
+                $module_name::$components::until_next_step(self, entity, frame_remaining)
+            }
+
+            fn contains_entity(&self, entity: $crate::Entity) -> bool {
+                $module_name::$components::contains_entity(self, entity)
+            }
+
+            fn min_period(&self, entity: $crate::Entity) -> ::std::option::Option<::std::time::Duration> {
+                $module_name::$components::min_period(self, entity)
+            }
+
+            fn tick_entity_burst(
+                &mut self,
+                entity: $crate::Entity,
+                frame_remaining: ::std::time::Duration,
+            ) -> (Self::EntityEvents, ::std::time::Duration) {
+                $module_name::$components::tick_entity_burst(self, entity, frame_remaining)
+            }
+
+            fn tick_entity_excluding_group(
+                &mut self,
+                group: &str,
+                entity: $crate::Entity,
+                frame_remaining: ::std::time::Duration,
+            ) -> (Self::EntityEvents, ::std::time::Duration) {
+                $module_name::$components::tick_entity_excluding_group(self, group, entity, frame_remaining)
+            }
+
+            fn skip_entity(&mut self, entity: $crate::Entity, duration: ::std::time::Duration) {
+                $module_name::$components::skip_entity(self, entity, duration)
+            }
 
-            impl<$($lt,)*> $crate::RealtimeComponents<$context> for RealtimeComponents {
-                type EntityEvents = RealtimeEntityEvents;
+            fn tick_entity_with(
+                &mut self,
+                entity: $crate::Entity,
+                frame_remaining: ::std::time::Duration,
+                f: impl FnMut($module_name::$entity_event),
+            ) -> ::std::time::Duration {
+                $module_name::$components::tick_entity_with(self, entity, frame_remaining, f)
+            }
 
-                fn tick_entity(
+            $crate::realtime_rand! {
+                fn tick_entity_with_rng(
                     &mut self,
                     entity: $crate::Entity,
-                    frame_remaining: std::time::Duration,
-                ) -> (Self::EntityEvents, std::time::Duration) {
-                    RealtimeComponents::tick_entity(self, entity, frame_remaining)
+                    frame_remaining: ::std::time::Duration,
+                    rng: &mut dyn $crate::rand_core::RngCore,
+                ) -> (Self::EntityEvents, ::std::time::Duration) {
+                    $module_name::$components::tick_entity_with_rng(self, entity, frame_remaining, rng)
                 }
             }
         }
     };
+    // Generic parameters and where clauses are collected a token at a time, since they can't be
+    // matched by fragment specifiers without ambiguity
+    { @start $settings:tt < $($rest:tt)* } => {
+        $crate::declare_realtime_entity_module! { @generics $settings [] $($rest)* }
+    };
+    { @start $settings:tt [$context:ty] $($rest:tt)* } => {
+        $crate::declare_realtime_entity_module! { @where_clause $settings [] [$context] [] $($rest)* }
+    };
+    { @generics $settings:tt [$($generics:tt)*] > [$context:ty] $($rest:tt)* } => {
+        $crate::declare_realtime_entity_module! { @where_clause $settings [$($generics)*] [$context] [] $($rest)* }
+    };
+    { @generics $settings:tt [$($generics:tt)*] $next:tt $($rest:tt)* } => {
+        $crate::declare_realtime_entity_module! { @generics $settings [$($generics)* $next] $($rest)* }
+    };
+    {
+        @where_clause [serde_crate $serde_crate:tt names $names:tt $module_name:ident]
+        $generics:tt $context:tt $where_clause:tt { $($components:tt)* }
+    } => {
+        $crate::declare_realtime_entity_module! {
+            @inner
+            serde_crate $serde_crate
+            names $names
+            $module_name $generics $context $where_clause
+            { $($components)* }
+        }
+    };
+    { @where_clause $settings:tt $generics:tt $context:tt [$($where_clause:tt)*] $next:tt $($rest:tt)* } => {
+        $crate::declare_realtime_entity_module! { @where_clause $settings $generics $context [$($where_clause)* $next] $($rest)* }
+    };
+    {
+        $(#[serde(crate = $serde_crate:literal)])?
+        $(#[names(
+            RealtimeComponents = $components:ident,
+            RealtimeEntityData = $entity_data:ident,
+            RealtimeEntityEvents = $entity_events:ident,
+            RealtimeEntityEvent = $entity_event:ident
+            $(, RealtimeEntityViewMut = $entity_view_mut:ident)?
+            $(, RealtimeComponentsDelta = $components_delta:ident)? $(,)?
+        )])?
+        $module_name:ident $($rest:tt)*
+    } => {
+        $crate::declare_realtime_entity_module! {
+            @start [
+                serde_crate[$($serde_crate)? "serde"]
+                names[
+                    [
+                        $($components $entity_data $entity_events $entity_event)?
+                        RealtimeComponents RealtimeEntityData RealtimeEntityEvents RealtimeEntityEvent
+                    ]
+                    [$($($entity_view_mut)?)? RealtimeEntityViewMut]
+                    [$($($components_delta)?)? RealtimeComponentsDelta]
+                ]
+                $module_name
+            ]
+            $($rest)*
+        }
+    };
+}
+
+/// Construct a `RealtimeEntityData` from just the components which are present, leaving the
+/// rest as `None`. The path to the generated `RealtimeEntityData` comes before the fields, so the
+/// macro works wherever it's invoked:
+///
+/// ```ignore
+/// let data = realtime_entity_data! {
+///     components::RealtimeEntityData {
+///         flicker: Flicker::new(),
+///         particle: Particle::new(),
+///     }
+/// };
+/// ```
+#[macro_export]
+macro_rules! realtime_entity_data {
+    { $entity_data:path { $($component_name:ident: $value:expr),* $(,)? } } => {{
+        type EntityData = $entity_data;
+        EntityData {
+            $($component_name: ::std::option::Option::Some($value),)*
+            ..::std::default::Default::default()
+        }
+    }};
 }
 
+/// Processes the frames of every entity of a context. Each step of an entity's frame is taken
+/// by the `scheduler::EntityScheduler` of type `S`, which by default scans the schedules of the
+/// entity's components. The scheduler isn't serialized.
 #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    feature = "serialize",
+    serde(bound(serialize = "", deserialize = "S: Default"))
+)]
 #[derive(Debug, Clone, Default)]
-pub struct AnimationContext {
+pub struct AnimationContext<S = scheduler::LinearScan> {
     realtime_entities: Vec<Entity>,
+    #[cfg_attr(feature = "serialize", serde(skip))]
+    scheduler: S,
 }
 
 impl AnimationContext {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<S> AnimationContext<S> {
+    /// An `AnimationContext` whose `scheduler` takes each step of each entity's frame
+    pub fn with_scheduler(scheduler: S) -> Self {
+        Self {
+            realtime_entities: Vec::new(),
+            scheduler,
+        }
+    }
+
+    pub fn scheduler(&self) -> &S {
+        &self.scheduler
+    }
+
+    pub fn scheduler_mut(&mut self) -> &mut S {
+        &mut self.scheduler
+    }
+
     pub fn tick<C: ContextContainsRealtimeComponents>(
         &mut self,
         mut context: C,
         frame_duration: Duration,
-    ) {
+    ) where
+        S: scheduler::EntityScheduler<C>,
+    {
+        self.realtime_entities.extend(context.realtime_entities());
+        for entity in self.realtime_entities.drain(..) {
+            process_entity_frame_with_scheduler(
+                entity,
+                frame_duration,
+                &mut context,
+                &mut self.scheduler,
+            );
+        }
+        context
+            .components_mut()
+            .advance_simulated_time(frame_duration);
+    }
+
+    /// Like `tick` but only processes entities which have a component in the table returned by
+    /// `table`, e.g. a table marking entities which are visible. The cost of finding the entities
+    /// to process is proportional to the size of that table rather than the number of entities.
+    pub fn tick_joined<C: ContextContainsRealtimeComponents, U>(
+        &mut self,
+        mut context: C,
+        frame_duration: Duration,
+        table: impl FnOnce(&C) -> &ComponentTable<U>,
+    ) where
+        S: scheduler::EntityScheduler<C>,
+    {
+        realtime_profile_scope!("tick_joined");
+        self.realtime_entities.extend(table(&context).entities());
+        for entity in self.realtime_entities.drain(..) {
+            process_entity_frame_unscoped(
+                entity,
+                frame_duration,
+                &mut context,
+                &mut self.scheduler,
+            );
+        }
+        context
+            .components_mut()
+            .advance_simulated_time(frame_duration);
+    }
+
+    /// Like `tick` but at most `max_steps` steps are taken across all entities, so that the
+    /// frame finishes however components misbehave. If the ceiling is reached, the remainder of
+    /// the frame is dropped for the entities which haven't been processed, and an error
+    /// describing where the ceiling was reached is returned. The table's simulated time is
+    /// advanced either way.
+    pub fn tick_with_ceiling<C: ContextContainsRealtimeComponents>(
+        &mut self,
+        mut context: C,
+        frame_duration: Duration,
+        max_steps: usize,
+    ) -> Result<(), budget::FrameCeilingExceeded>
+    where
+        S: scheduler::EntityScheduler<C>,
+    {
+        let mut ceiling = budget::FrameCeiling::new(max_steps);
+        self.realtime_entities.extend(context.realtime_entities());
+        for entity in self.realtime_entities.drain(..) {
+            if ceiling.exceeded().is_some() {
+                break;
+            }
+            process_entity_frame_with_policy_unscoped(
+                entity,
+                frame_duration,
+                &mut context,
+                &mut ceiling,
+                &mut self.scheduler,
+            );
+        }
+        context
+            .components_mut()
+            .advance_simulated_time(frame_duration);
+        match ceiling.exceeded() {
+            Some(exceeded) => {
+                #[cfg(feature = "log")]
+                log::error!("{}", exceeded);
+                Err(exceeded)
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// Like `tick` but catches panics raised while applying events. Each panic is passed to
+    /// `on_panic` which decides whether to continue processing the remaining entities.
+    pub fn tick_catch_unwind<C: ContextContainsRealtimeComponents>(
+        &mut self,
+        mut context: C,
+        frame_duration: Duration,
+        mut on_panic: impl FnMut(RealtimePanic) -> ControlFlow<()>,
+    ) where
+        S: scheduler::EntityScheduler<C>,
+    {
+        self.realtime_entities.extend(context.realtime_entities());
+        for entity in self.realtime_entities.drain(..) {
+            if let Err(panic) = process_entity_frame_catch_unwind_unscoped(
+                entity,
+                frame_duration,
+                &mut context,
+                &mut self.scheduler,
+            ) {
+                if on_panic(panic).is_break() {
+                    break;
+                }
+            }
+        }
+        context
+            .components_mut()
+            .advance_simulated_time(frame_duration);
+    }
+
+    /// Like `tick` but components whose catch-up policy is `CatchUpPolicy::Skip` only simulate
+    /// up to `max_frame_duration` of the frame. See `process_entity_frame_with_catch_up`.
+    pub fn tick_with_catch_up<C: ContextContainsRealtimeComponents>(
+        &mut self,
+        mut context: C,
+        frame_duration: Duration,
+        max_frame_duration: Duration,
+    ) where
+        S: scheduler::EntityScheduler<C>,
+    {
         self.realtime_entities.extend(context.realtime_entities());
         for entity in self.realtime_entities.drain(..) {
-            process_entity_frame(entity, frame_duration, &mut context);
+            process_entity_frame_with_catch_up_unscoped(
+                entity,
+                frame_duration,
+                max_frame_duration,
+                &mut context,
+                &mut self.scheduler,
+            );
         }
+        context
+            .components_mut()
+            .advance_simulated_time(frame_duration);
+    }
+
+    /// Like `tick` but stops once `max_ticks` components have ticked across all entities (see
+    /// `process_entity_frame_with_budget`). Entities are processed in order of their priority
+    /// lane so that critical entities are processed before ambient ones when the budget can't
+    /// cover everything.
+    pub fn tick_with_budget<C: ContextContainsRealtimeComponents>(
+        &mut self,
+        mut context: C,
+        frame_duration: Duration,
+        max_ticks: usize,
+    ) where
+        S: scheduler::EntityScheduler<C>,
+    {
+        self.realtime_entities.extend(context.realtime_entities());
+        self.realtime_entities
+            .sort_by_key(|&entity| std::cmp::Reverse(context.realtime_priority(entity)));
+        let mut ticks_remaining = max_ticks;
+        for entity in self.realtime_entities.drain(..) {
+            if ticks_remaining == 0 {
+                break;
+            }
+            ticks_remaining =
+                ticks_remaining.saturating_sub(process_entity_frame_with_budget_unscoped(
+                    entity,
+                    frame_duration,
+                    &mut context,
+                    ticks_remaining,
+                    &mut self.scheduler,
+                ));
+        }
+        context
+            .components_mut()
+            .advance_simulated_time(frame_duration);
+    }
+}
+
+/// Restore the realtime components of a context from `snapshot`, then replay `frames`,
+/// processing every entity each frame as `AnimationContext::tick` does. Schedules are kept in
+/// whole nanoseconds, so replaying the same frames from the same snapshot applies the same
+/// events in the same order, making this the core of rollback netcode. Any other state of the
+/// context which events modify must be restored by the caller beforehand.
+pub fn resimulate<C>(snapshot: &C::Components, frames: &[Duration], context: &mut C)
+where
+    C: ContextContainsRealtimeComponents,
+    C::Components: Clone,
+{
+    context.components_mut().clone_from(snapshot);
+    let mut entities = Vec::new();
+    for &frame_duration in frames {
+        entities.extend(context.realtime_entities());
+        for entity in entities.drain(..) {
+            process_entity_frame(entity, frame_duration, context);
+        }
+        context
+            .components_mut()
+            .advance_simulated_time(frame_duration);
     }
 }
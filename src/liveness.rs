@@ -0,0 +1,47 @@
+//! Debug assertions that entities are live when their components are inserted or looked up,
+//! enabled by the `liveness` feature in builds with debug assertions. These catch stale
+//! `Entity`s which refer to despawned entities whose slots have since been reused. Without the
+//! feature the installed check is never called.
+//!
+//! ```ignore
+//! let allocator = Rc::new(RefCell::new(EntityAllocator::default()));
+//! let check = allocator.clone();
+//! liveness::install(move |entity| check.borrow().exists(entity));
+//! ```
+
+use entity_table::Entity;
+use std::cell::RefCell;
+
+type IsLive = Box<dyn Fn(Entity) -> bool>;
+
+thread_local! {
+    static CHECK: RefCell<Option<IsLive>> = const { RefCell::new(None) };
+}
+
+/// Use `is_live` to check entities accessed in tables on the current thread, replacing any
+/// previously installed check
+pub fn install(is_live: impl Fn(Entity) -> bool + 'static) {
+    CHECK.with(|check| *check.borrow_mut() = Some(Box::new(is_live)));
+}
+
+/// Stop checking entities on the current thread
+pub fn uninstall() {
+    CHECK.with(|check| *check.borrow_mut() = None);
+}
+
+#[doc(hidden)]
+#[inline]
+pub fn debug_assert_live(entity: Entity, component: &'static str) {
+    #[cfg(all(feature = "liveness", debug_assertions))]
+    CHECK.with(|check| {
+        if let Some(is_live) = check.borrow().as_ref() {
+            assert!(
+                is_live(entity),
+                "accessed {} of {:?} which is not live",
+                component,
+                entity
+            );
+        }
+    });
+    let _ = (entity, component);
+}
@@ -0,0 +1,225 @@
+//! Realtime components which move entities around a grid, at speeds measured in cells per
+//! second
+
+use crate::combinators::FiniteRealtimeComponent;
+use crate::{Frequency, RealtimeComponent, ScheduledRealtimeComponent};
+#[cfg(feature = "serialize")]
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// A speed of movement in grid cells per second. Cells generally can't all take the same
+/// whole number of nanoseconds to cross (e.g. at 3 cells per second), so the period of each
+/// cell is rounded such that the total time taken to cross any number of cells is as close as
+/// possible to exact, rather than rounding every cell the same way and accumulating error.
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Speed(Frequency);
+
+impl Speed {
+    /// Panics if `cells_per_second` isn't positive and finite
+    pub fn cells_per_second(cells_per_second: f64) -> Self {
+        Self(Frequency::from_hz(cells_per_second))
+    }
+
+    /// The speed at which one cell is crossed every `period`. Panics if `period` is zero.
+    pub fn from_cell_period(period: Duration) -> Self {
+        Self(Frequency::from_period(period))
+    }
+
+    pub fn as_cells_per_second(self) -> f64 {
+        self.0.hz()
+    }
+
+    pub fn frequency(self) -> Frequency {
+        self.0
+    }
+
+    /// Time taken to cross `cells` cells, rounded to the nearest nanosecond
+    pub fn duration_of_cells(self, cells: u64) -> Duration {
+        let nanos = (cells as f64 * 1e9 / self.0.hz()).round();
+        Duration::from_nanos(nanos.min(u64::MAX as f64) as u64)
+    }
+
+    /// Time taken to cross the cell with the given index, counting from zero, when moving
+    /// continuously at this speed. Summing the periods of cells `0..n` gives
+    /// `duration_of_cells(n)` exactly. Cells are crossed in at least 1ns.
+    pub fn period_of_cell(self, index: u64) -> Duration {
+        let period =
+            self.duration_of_cells(index.saturating_add(1)) - self.duration_of_cells(index);
+        period.max(Duration::from_nanos(1))
+    }
+
+    /// The average time taken to cross a cell, rounded to the nearest nanosecond
+    pub fn cell_period(self) -> Duration {
+        self.0.period()
+    }
+}
+
+/// Moves an entity one cell in a fixed direction each time it ticks, at a given speed. Each
+/// event is the offset by which the entity should be moved.
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct GridMovement {
+    step: (i32, i32),
+    speed: Speed,
+    /// Cells crossed since the speed was last set, for rounding periods consistently
+    cells_moved: u64,
+}
+
+impl GridMovement {
+    pub fn new(step: (i32, i32), speed: Speed) -> Self {
+        Self {
+            step,
+            speed,
+            cells_moved: 0,
+        }
+    }
+
+    /// Schedule the movement such that the first step occurs after the first cell has been
+    /// crossed, rather than immediately
+    pub fn scheduled(self) -> ScheduledRealtimeComponent<Self> {
+        let until_first_step = self.speed.period_of_cell(self.cells_moved);
+        ScheduledRealtimeComponent::new(self, until_first_step)
+    }
+
+    pub fn step(&self) -> (i32, i32) {
+        self.step
+    }
+
+    pub fn set_step(&mut self, step: (i32, i32)) {
+        self.step = step;
+    }
+
+    pub fn speed(&self) -> Speed {
+        self.speed
+    }
+
+    /// Takes effect after the step which is currently scheduled
+    pub fn set_speed(&mut self, speed: Speed) {
+        self.speed = speed;
+        self.cells_moved = 0;
+    }
+}
+
+impl RealtimeComponent for GridMovement {
+    type Event = (i32, i32);
+
+    fn tick(&mut self) -> (Self::Event, Duration) {
+        self.cells_moved += 1;
+        (self.step, self.speed.period_of_cell(self.cells_moved))
+    }
+}
+
+/// Event of a `RealtimeMovement`
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RealtimeMovementEvent {
+    /// The entity should be moved to the given cell
+    MovedTo((i32, i32)),
+    /// The entity should be moved to the given cell, which is the last cell of the path
+    Arrived((i32, i32)),
+    /// The entity should be moved to the given cell, after which it has taken the maximum
+    /// number of steps
+    Expired((i32, i32)),
+}
+
+impl RealtimeMovementEvent {
+    pub fn cell(self) -> (i32, i32) {
+        match self {
+            Self::MovedTo(cell) | Self::Arrived(cell) | Self::Expired(cell) => cell,
+        }
+    }
+
+    /// Whether the movement has finished. The component should be removed when applying a
+    /// terminal event, otherwise it continues to repeat the event.
+    pub fn is_terminal(self) -> bool {
+        !matches!(self, Self::MovedTo(_))
+    }
+}
+
+/// Moves an entity along a precomputed path of cells at a given speed, e.g. a projectile
+/// following a line. The path doesn't include the cell where the entity starts.
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct RealtimeMovement {
+    path: Vec<(i32, i32)>,
+    speed: Speed,
+    max_steps: Option<usize>,
+    /// Index into the path of the next cell to move to
+    next: usize,
+}
+
+impl RealtimeMovement {
+    /// Panics if `path` is empty
+    pub fn new(path: Vec<(i32, i32)>, speed: Speed) -> Self {
+        assert!(!path.is_empty(), "path must not be empty");
+        Self {
+            path,
+            speed,
+            max_steps: None,
+            next: 0,
+        }
+    }
+
+    /// Stop after `max_steps` steps with an `Expired` event if the end of the path hasn't been
+    /// reached by then, e.g. to limit the range of a projectile. Panics if `max_steps` is zero.
+    pub fn with_max_steps(self, max_steps: usize) -> Self {
+        assert!(max_steps > 0, "max_steps must be positive");
+        Self {
+            max_steps: Some(max_steps),
+            ..self
+        }
+    }
+
+    /// Schedule the movement such that the first step occurs after the first cell has been
+    /// crossed, rather than immediately
+    pub fn scheduled(self) -> ScheduledRealtimeComponent<Self> {
+        let until_first_step = self.speed.period_of_cell(self.next as u64);
+        ScheduledRealtimeComponent::new(self, until_first_step)
+    }
+
+    pub fn path(&self) -> &[(i32, i32)] {
+        &self.path
+    }
+
+    pub fn speed(&self) -> Speed {
+        self.speed
+    }
+
+    /// The number of steps taken so far
+    pub fn steps_taken(&self) -> usize {
+        self.next
+    }
+
+    /// Index into the path of the cell where the movement finishes
+    fn last_index(&self) -> usize {
+        self.max_steps.unwrap_or(usize::MAX).min(self.path.len()) - 1
+    }
+}
+
+impl RealtimeComponent for RealtimeMovement {
+    type Event = RealtimeMovementEvent;
+
+    fn tick(&mut self) -> (Self::Event, Duration) {
+        // once finished, the terminal step is repeated
+        let index = self.next.min(self.last_index());
+        let cell = self.path[index];
+        let steps_taken = index + 1;
+        let event = if steps_taken == self.path.len() {
+            RealtimeMovementEvent::Arrived(cell)
+        } else if self.max_steps == Some(steps_taken) {
+            RealtimeMovementEvent::Expired(cell)
+        } else {
+            RealtimeMovementEvent::MovedTo(cell)
+        };
+        self.next = steps_taken;
+        (event, self.speed.period_of_cell(steps_taken as u64))
+    }
+}
+
+/// Finished once an `Arrived` or `Expired` event has been emitted
+impl FiniteRealtimeComponent for RealtimeMovement {
+    fn is_finished(&self) -> bool {
+        self.next > self.last_index()
+    }
+}
@@ -0,0 +1,89 @@
+//! Client-side prediction of realtime components. A client inserts components locally in
+//! response to its own actions (e.g. a muzzle flash when firing) without waiting for the server,
+//! tagging them as predicted. When the server's authoritative copy of the table arrives, the
+//! client's table is rolled back to it and re-simulated through the frames the server hadn't yet
+//! seen. Predicted components which the server hasn't confirmed yet are carried across.
+
+use crate::{Entity, RealtimeComponent, RealtimeComponentTable};
+use std::collections::{HashSet, VecDeque};
+use std::time::Duration;
+
+/// Identifies a frame simulated by the client, for the server to acknowledge
+pub type FrameId = u64;
+
+/// Records the frames simulated by a client and which of the components in a single table were
+/// predicted. Use a separate `RealtimePrediction` for each table.
+#[derive(Debug, Clone, Default)]
+pub struct RealtimePrediction {
+    next_frame: FrameId,
+    frames: VecDeque<(FrameId, Duration)>,
+    predicted: HashSet<Entity>,
+}
+
+impl RealtimePrediction {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record the duration of a frame the client simulated, returning its id
+    pub fn record_frame(&mut self, frame_duration: Duration) -> FrameId {
+        let frame = self.next_frame;
+        self.next_frame += 1;
+        self.frames.push_back((frame, frame_duration));
+        frame
+    }
+
+    /// Tag the component of an entity as locally predicted
+    pub fn predict(&mut self, entity: Entity) {
+        self.predicted.insert(entity);
+    }
+
+    pub fn is_predicted(&self, entity: Entity) -> bool {
+        self.predicted.contains(&entity)
+    }
+
+    /// The number of frames simulated since the most recently acknowledged one
+    pub fn num_unacknowledged_frames(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Replace the components of `table` with those of `authoritative`, the server's copy of
+    /// the table as of the end of frame `acknowledged`, then re-simulate the frames recorded after it. Events produced
+    /// while re-simulating are discarded, since their effects were already shown when the
+    /// frames were first simulated. Predicted components absent from `authoritative` are kept
+    /// as they are, while those which are present are no longer considered predicted. The
+    /// table's own limits and schedule policy are kept, as with `restore_state`.
+    pub fn reconcile<T: RealtimeComponent>(
+        &mut self,
+        table: &mut RealtimeComponentTable<T>,
+        authoritative: RealtimeComponentTable<T>,
+        acknowledged: FrameId,
+    ) {
+        let mut unconfirmed = Vec::new();
+        self.predicted.retain(|&entity| {
+            if authoritative.contains(entity) {
+                return false;
+            }
+            // predicted components which have since been removed locally are forgotten
+            if let Some(scheduled) = table.remove_with_schedule(entity) {
+                unconfirmed.push((entity, scheduled));
+                true
+            } else {
+                false
+            }
+        });
+        table.restore_state(authoritative);
+        while let Some(&(frame, _)) = self.frames.front() {
+            if frame > acknowledged {
+                break;
+            }
+            self.frames.pop_front();
+        }
+        for &(_, frame_duration) in &self.frames {
+            table.fast_forward(frame_duration);
+        }
+        for (entity, scheduled) in unconfirmed {
+            table.insert_with_schedule(entity, scheduled);
+        }
+    }
+}
@@ -0,0 +1,208 @@
+//! Adapters which limit the rate at which a component's events are applied, for noisy
+//! components whose events are expensive to apply. Each tick of an adapter produces an event
+//! which is `None` if no event of the inner component should be applied. The inner component is
+//! ticked with the same method as the adapter, and is passed the adapter's `TickCtx`.
+
+use crate::{
+    duration_to_nanos, Entity, InnerTick, RealtimeComponent, RealtimeComponentApplyEvent, TickCtx,
+};
+#[cfg(feature = "serialize")]
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Drops events of the inner component which occur less than `min_interval` after the last
+/// event which wasn't dropped
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct Throttle<T> {
+    pub inner: T,
+    min_interval_nanos: u64,
+    /// Time between the last event which wasn't dropped and the previous tick
+    since_emitted_nanos: Option<u64>,
+    /// The duration returned by the previous tick
+    period_nanos: u64,
+}
+
+impl<T> Throttle<T> {
+    pub fn new(inner: T, min_interval: Duration) -> Self {
+        Self {
+            inner,
+            min_interval_nanos: duration_to_nanos(min_interval),
+            since_emitted_nanos: None,
+            period_nanos: 0,
+        }
+    }
+
+    pub fn min_interval(&self) -> Duration {
+        Duration::from_nanos(self.min_interval_nanos)
+    }
+}
+
+impl<T: RealtimeComponent> Throttle<T> {
+    fn tick_inner(&mut self, inner: &mut InnerTick) -> (Option<T::Event>, Duration) {
+        let since_emitted = self
+            .since_emitted_nanos
+            .map(|since_emitted| since_emitted.saturating_add(self.period_nanos));
+        let (event, until_next_tick) = inner.tick(&mut self.inner);
+        self.period_nanos = duration_to_nanos(until_next_tick);
+        match since_emitted {
+            Some(since_emitted) if since_emitted < self.min_interval_nanos => {
+                self.since_emitted_nanos = Some(since_emitted);
+                (None, until_next_tick)
+            }
+            _ => {
+                self.since_emitted_nanos = Some(0);
+                (Some(event), until_next_tick)
+            }
+        }
+    }
+}
+
+impl<T: RealtimeComponent> RealtimeComponent for Throttle<T> {
+    type Event = Option<T::Event>;
+
+    fn tick(&mut self) -> (Self::Event, Duration) {
+        self.tick_inner(&mut InnerTick::Tick)
+    }
+
+    fn tick_with(&mut self, context: &TickCtx) -> (Self::Event, Duration) {
+        self.tick_inner(&mut InnerTick::TickWith(context))
+    }
+
+    #[cfg(feature = "rand")]
+    fn tick_with_rng(
+        &mut self,
+        context: &TickCtx,
+        rng: &mut dyn rand_core::RngCore,
+    ) -> (Self::Event, Duration) {
+        self.tick_inner(&mut InnerTick::TickWithRng(context, rng))
+    }
+
+    fn on_insert(&mut self) -> Option<Self::Event> {
+        self.inner.on_insert().map(Some)
+    }
+
+    /// The inner component's event is never dropped
+    fn on_remove(&mut self) -> Option<Self::Event> {
+        self.inner.on_remove().map(Some)
+    }
+
+    fn on_ticks_dropped(&mut self, dropped: u64) -> Option<Self::Event> {
+        self.inner.on_ticks_dropped(dropped).map(Some)
+    }
+}
+
+impl<C, T: RealtimeComponentApplyEvent<C>> RealtimeComponentApplyEvent<C> for Throttle<T> {
+    fn apply_event(event: Option<T::Event>, entity: Entity, context: &mut C) {
+        if let Some(event) = event {
+            T::apply_event(event, entity, context);
+        }
+    }
+}
+
+/// Holds back each event of the inner component until the inner component has gone `quiet`
+/// without producing another event, then emits only the most recent event. A component which
+/// ticks more often than `quiet` never has its events emitted.
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    feature = "serialize",
+    serde(bound(
+        serialize = "T: Serialize, T::Event: Serialize",
+        deserialize = "T: Deserialize<'de>, T::Event: Deserialize<'de>"
+    ))
+)]
+#[derive(Debug, Clone)]
+pub struct Debounce<T: RealtimeComponent> {
+    pub inner: T,
+    quiet_nanos: u64,
+    /// Time between the next tick of the adapter and the next tick of the inner component
+    inner_after_next_nanos: u64,
+    /// The most recent event of the inner component, with the time between the next tick of
+    /// the adapter and when the event should be emitted
+    pending: Option<(u64, T::Event)>,
+}
+
+impl<T: RealtimeComponent> Debounce<T> {
+    pub fn new(inner: T, quiet: Duration) -> Self {
+        Self {
+            inner,
+            quiet_nanos: duration_to_nanos(quiet),
+            inner_after_next_nanos: 0,
+            pending: None,
+        }
+    }
+
+    pub fn quiet(&self) -> Duration {
+        Duration::from_nanos(self.quiet_nanos)
+    }
+}
+
+impl<T: RealtimeComponent> Debounce<T> {
+    fn tick_inner(&mut self, inner: &mut InnerTick) -> (Option<T::Event>, Duration) {
+        // a pending event due at the same time as the inner component's tick has been quiet for
+        // long enough, so it's emitted first
+        let event = match self.pending.take() {
+            Some((0, event)) => Some(event),
+            pending => {
+                self.pending = pending;
+                if self.inner_after_next_nanos == 0 {
+                    let (event, until_next_tick) = inner.tick(&mut self.inner);
+                    self.inner_after_next_nanos = duration_to_nanos(until_next_tick);
+                    self.pending = Some((self.quiet_nanos, event));
+                }
+                None
+            }
+        };
+        let until_next_tick = match &self.pending {
+            Some((until_emitted, _)) => self.inner_after_next_nanos.min(*until_emitted),
+            None => self.inner_after_next_nanos,
+        };
+        self.inner_after_next_nanos -= until_next_tick;
+        if let Some((until_emitted, _)) = self.pending.as_mut() {
+            *until_emitted -= until_next_tick;
+        }
+        (event, Duration::from_nanos(until_next_tick))
+    }
+}
+
+impl<T: RealtimeComponent> RealtimeComponent for Debounce<T> {
+    type Event = Option<T::Event>;
+
+    fn tick(&mut self) -> (Self::Event, Duration) {
+        self.tick_inner(&mut InnerTick::Tick)
+    }
+
+    fn tick_with(&mut self, context: &TickCtx) -> (Self::Event, Duration) {
+        self.tick_inner(&mut InnerTick::TickWith(context))
+    }
+
+    #[cfg(feature = "rand")]
+    fn tick_with_rng(
+        &mut self,
+        context: &TickCtx,
+        rng: &mut dyn rand_core::RngCore,
+    ) -> (Self::Event, Duration) {
+        self.tick_inner(&mut InnerTick::TickWithRng(context, rng))
+    }
+
+    fn on_insert(&mut self) -> Option<Self::Event> {
+        self.inner.on_insert().map(Some)
+    }
+
+    /// Any pending event is discarded in favour of the inner component's event
+    fn on_remove(&mut self) -> Option<Self::Event> {
+        self.inner.on_remove().map(Some)
+    }
+
+    fn on_ticks_dropped(&mut self, dropped: u64) -> Option<Self::Event> {
+        self.inner.on_ticks_dropped(dropped).map(Some)
+    }
+}
+
+impl<C, T: RealtimeComponentApplyEvent<C>> RealtimeComponentApplyEvent<C> for Debounce<T> {
+    fn apply_event(event: Option<T::Event>, entity: Entity, context: &mut C) {
+        if let Some(event) = event {
+            T::apply_event(event, entity, context);
+        }
+    }
+}
@@ -0,0 +1,199 @@
+//! Replay logs of the frames processed by a game and the events which occurred during them,
+//! for storing on disk, attaching to bug reports and playing back. Events are recorded as a
+//! type chosen by the game (e.g. a serializable description of the event) rather than the
+//! events themselves, so that logs remain readable after the components change. With the
+//! `serialize` feature, logs are serialized along with a format version which is checked when
+//! they are deserialized.
+
+use crate::{
+    ContextContainsRealtimeComponents, Entity, RealtimeComponents, RealtimeEntityEvent,
+    RealtimeEntityEvents, RealtimeEventOf,
+};
+#[cfg(feature = "serialize")]
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::time::Duration;
+
+/// The version of the replay log format written by this version of the crate
+pub const REPLAY_FORMAT_VERSION: u32 = 1;
+
+/// An event which occurred during a frame
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReplayEvent<E> {
+    /// Time since the start of the frame at which the event occurred
+    pub offset: Duration,
+    pub entity: Entity,
+    pub event: E,
+}
+
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReplayFrame<E> {
+    pub frame_duration: Duration,
+    pub events: Vec<ReplayEvent<E>>,
+}
+
+/// A recording of a sequence of frames
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    feature = "serialize",
+    serde(
+        try_from = "UncheckedReplayLog<E>",
+        bound(deserialize = "E: Deserialize<'de>")
+    )
+)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReplayLog<E> {
+    version: u32,
+    frames: Vec<ReplayFrame<E>>,
+}
+
+impl<E> Default for ReplayLog<E> {
+    fn default() -> Self {
+        Self {
+            version: REPLAY_FORMAT_VERSION,
+            frames: Vec::new(),
+        }
+    }
+}
+
+impl<E> ReplayLog<E> {
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+
+    pub fn frames(&self) -> &[ReplayFrame<E>] {
+        &self.frames
+    }
+
+    /// The duration of each frame, e.g. for passing to `resimulate`
+    pub fn frame_durations(&self) -> impl '_ + Iterator<Item = Duration> {
+        self.frames.iter().map(|frame| frame.frame_duration)
+    }
+
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+}
+
+/// A replay log was written with a version of the format this version of the crate can't read
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UnsupportedReplayVersion(pub u32);
+
+impl fmt::Display for UnsupportedReplayVersion {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "unsupported replay format version {} (expected at most {})",
+            self.0, REPLAY_FORMAT_VERSION
+        )
+    }
+}
+
+impl std::error::Error for UnsupportedReplayVersion {}
+
+#[cfg(feature = "serialize")]
+#[derive(Deserialize)]
+struct UncheckedReplayLog<E> {
+    version: u32,
+    frames: Vec<ReplayFrame<E>>,
+}
+
+#[cfg(feature = "serialize")]
+impl<E> TryFrom<UncheckedReplayLog<E>> for ReplayLog<E> {
+    type Error = UnsupportedReplayVersion;
+
+    fn try_from(log: UncheckedReplayLog<E>) -> Result<Self, Self::Error> {
+        if log.version > REPLAY_FORMAT_VERSION {
+            return Err(UnsupportedReplayVersion(log.version));
+        }
+        Ok(Self {
+            version: log.version,
+            frames: log.frames,
+        })
+    }
+}
+
+/// Builds a `ReplayLog` one frame at a time
+#[derive(Debug, Clone)]
+pub struct ReplayWriter<E> {
+    log: ReplayLog<E>,
+}
+
+impl<E> Default for ReplayWriter<E> {
+    fn default() -> Self {
+        Self {
+            log: ReplayLog::default(),
+        }
+    }
+}
+
+impl<E> ReplayWriter<E> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start recording a frame. Events recorded until the next call belong to this frame.
+    pub fn begin_frame(&mut self, frame_duration: Duration) {
+        self.log.frames.push(ReplayFrame {
+            frame_duration,
+            events: Vec::new(),
+        });
+    }
+
+    /// Record an event in the current frame. Panics if no frame has begun.
+    pub fn record_event(&mut self, offset: Duration, entity: Entity, event: E) {
+        self.log
+            .frames
+            .last_mut()
+            .expect("no frame has begun")
+            .events
+            .push(ReplayEvent {
+                offset,
+                entity,
+                event,
+            });
+    }
+
+    pub fn finish(self) -> ReplayLog<E> {
+        self.log
+    }
+}
+
+/// Like `process_entity_frame` but each event is passed to `record` before being applied, and
+/// anything it returns is recorded in the current frame of `writer`. Call
+/// `ReplayWriter::begin_frame` before processing the frame's entities.
+pub fn process_entity_frame_recorded<C, E>(
+    entity: Entity,
+    frame_duration: Duration,
+    context: &mut C,
+    mut record: impl FnMut(&RealtimeEventOf<C>) -> Option<E>,
+    writer: &mut ReplayWriter<E>,
+) where
+    C: ContextContainsRealtimeComponents,
+{
+    crate::realtime_profile_scope!("process_entity_frame_recorded");
+    let frame_duration = crate::begin_entity_frame(entity, frame_duration, context);
+    let mut elapsed = Duration::from_micros(0);
+    while elapsed < frame_duration {
+        let (events, until_next_tick) = context
+            .components_mut()
+            .tick_entity(entity, frame_duration - elapsed);
+        elapsed += until_next_tick;
+        if !events.is_empty() {
+            context.before_tick(entity);
+        }
+        events.for_each_event(|event| {
+            if let Some(recorded) = record(&event) {
+                writer.record_event(elapsed, entity, recorded);
+            }
+            event.apply(entity, context);
+        });
+    }
+    context.after_entity_frame(entity);
+}
@@ -0,0 +1,119 @@
+//! Replicating realtime components over a network. The authority keeps a copy of each table as
+//! of the last sync, and sends the delta between it and the current table. Clients apply the
+//! delta to their own copy of the table to bring it up to date.
+
+use crate::{
+    duration_to_nanos, Entity, RealtimeComponent, RealtimeComponentTable,
+    ScheduledRealtimeComponent,
+};
+#[cfg(feature = "serialize")]
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// The changes to a `RealtimeComponentTable` between two points in time
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct RealtimeComponentTableDelta<T: RealtimeComponent> {
+    /// Components which were inserted, or whose data or enabled state changed, along with
+    /// their schedules
+    pub inserted: Vec<(Entity, ScheduledRealtimeComponent<T>)>,
+    pub removed: Vec<Entity>,
+    /// Components which are otherwise unchanged but whose schedules changed
+    pub rescheduled: Vec<(Entity, ScheduleState)>,
+}
+
+/// The schedule of a component, without the component itself
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScheduleState {
+    pub until_next_tick: Duration,
+    /// The duration returned by the most recent tick of the component
+    pub period: Duration,
+    /// Simulated time since the component last ticked
+    pub since_last_tick: Duration,
+    /// Simulated time since the component was inserted, excluding time spent disabled
+    pub age: Duration,
+    pub max_missed_periods: Option<u64>,
+}
+
+impl ScheduleState {
+    pub fn of<T: RealtimeComponent>(scheduled: &ScheduledRealtimeComponent<T>) -> Self {
+        Self {
+            until_next_tick: scheduled.until_next_tick(),
+            period: scheduled.period(),
+            since_last_tick: Duration::from_nanos(scheduled.since_last_tick_nanos),
+            age: scheduled.age(),
+            max_missed_periods: scheduled.max_missed_periods(),
+        }
+    }
+
+    /// Replace the schedule of `scheduled` with this one
+    pub fn apply<T: RealtimeComponent>(&self, scheduled: &mut ScheduledRealtimeComponent<T>) {
+        scheduled.until_next_tick_nanos = duration_to_nanos(self.until_next_tick);
+        scheduled.period_nanos = duration_to_nanos(self.period);
+        scheduled.since_last_tick_nanos = duration_to_nanos(self.since_last_tick);
+        scheduled.age_nanos = duration_to_nanos(self.age);
+        scheduled.max_missed_periods = self.max_missed_periods;
+    }
+}
+
+impl<T: RealtimeComponent> Default for RealtimeComponentTableDelta<T> {
+    fn default() -> Self {
+        Self {
+            inserted: Vec::new(),
+            removed: Vec::new(),
+            rescheduled: Vec::new(),
+        }
+    }
+}
+
+impl<T: RealtimeComponent> RealtimeComponentTableDelta<T> {
+    pub fn is_empty(&self) -> bool {
+        self.inserted.is_empty() && self.removed.is_empty() && self.rescheduled.is_empty()
+    }
+}
+
+impl<T: RealtimeComponent + Clone + PartialEq> RealtimeComponentTable<T> {
+    /// The changes which turn `previous` into `self`
+    pub fn delta_from(&self, previous: &Self) -> RealtimeComponentTableDelta<T> {
+        let mut delta = RealtimeComponentTableDelta::default();
+        for (entity, scheduled) in self.iter_with_schedule() {
+            match previous.get_with_schedule(entity) {
+                Some(previous_scheduled)
+                    if previous_scheduled.component == scheduled.component
+                        && previous_scheduled.is_enabled() == scheduled.is_enabled() =>
+                {
+                    let schedule = ScheduleState::of(scheduled);
+                    if ScheduleState::of(previous_scheduled) != schedule {
+                        delta.rescheduled.push((entity, schedule));
+                    }
+                }
+                _ => delta.inserted.push((entity, scheduled.clone())),
+            }
+        }
+        for entity in previous.entities() {
+            if !self.contains(entity) {
+                delta.removed.push(entity);
+            }
+        }
+        delta
+    }
+}
+
+impl<T: RealtimeComponent> RealtimeComponentTable<T> {
+    /// Bring a copy of a table up to date with the changes in `delta`. Rescheduled entities
+    /// which are absent from this table are ignored.
+    pub fn apply_delta(&mut self, delta: RealtimeComponentTableDelta<T>) {
+        for entity in delta.removed {
+            self.remove_with_schedule(entity);
+        }
+        for (entity, scheduled) in delta.inserted {
+            self.insert_with_schedule(entity, scheduled);
+        }
+        for (entity, schedule) in delta.rescheduled {
+            if let Some(scheduled) = self.get_with_schedule_mut(entity) {
+                schedule.apply(scheduled);
+            }
+        }
+    }
+}
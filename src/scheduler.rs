@@ -0,0 +1,553 @@
+//! Strategies for finding which component in a table is due next, for processing a whole
+//! table's frame in the order its components tick with `process_table_frame`. The default
+//! strategy, `LinearScan`, scans every schedule in the table. `IndexedScheduler` keeps the
+//! table's schedules ordered by when they're due, so drivers which process a single table with
+//! `process_table_frame` can also find its next tick without visiting every component.
+//! `HeapScheduler` does the same with a binary heap, which is cheaper to update but holds stale
+//! entries. Other schedulers (e.g. a timing wheel) can be plugged in by implementing
+//! `Scheduler`, without changing how frames are processed. The `frame` benchmarks compare them.
+//!
+//! Frames processed an entity at a time, by `AnimationContext` and
+//! `process_entity_frame_with_scheduler`, take each step with an `EntityScheduler`, which is
+//! also `LinearScan` by default.
+
+use crate::{
+    duration_to_nanos, ContextContainsRealtimeComponents, Entity, RealtimeComponent,
+    RealtimeComponentApplyEvent, RealtimeComponentTable, RealtimeComponents, RealtimeFrameHooks,
+    ScheduledRealtimeComponent,
+};
+use std::cmp::Reverse;
+use std::collections::{BTreeSet, BinaryHeap, HashMap};
+use std::time::Duration;
+
+/// Decides the order in which the components of a table tick
+pub trait Scheduler<T: RealtimeComponent> {
+    /// The entity whose enabled component is due soonest, along with the time until it's due,
+    /// if any is due within `within`
+    fn next_due(
+        &mut self,
+        table: &RealtimeComponentTable<T>,
+        within: Duration,
+    ) -> Option<(Entity, Duration)>;
+
+    /// Bring the next tick of every enabled component in the table `duration` closer. No
+    /// component will be due sooner than `duration`.
+    fn advance(&mut self, table: &mut RealtimeComponentTable<T>, duration: Duration);
+
+    /// Bring the next tick of every enabled component in the table up to `duration` closer,
+    /// leaving those due sooner than `duration` due now. This ends frames in which the table's
+    /// event cap held components back.
+    fn advance_until_due(&mut self, table: &mut RealtimeComponentTable<T>, duration: Duration);
+
+    /// Called after the component of `entity` ticks, with its new schedule. This also ends its
+    /// exclusion.
+    fn reschedule(&mut self, entity: Entity, scheduled: &ScheduledRealtimeComponent<T>);
+
+    /// Leave the component of `entity` out of `next_due`, `advance` and `advance_until_due`
+    /// until it's rescheduled or `end_exclusions` is called, because its frame is simulated
+    /// separately
+    fn exclude(&mut self, entity: Entity);
+
+    /// End the exclusion of every component which hasn't been rescheduled since it was excluded
+    fn end_exclusions(&mut self, table: &RealtimeComponentTable<T>);
+}
+
+/// Finds the next due component by checking every schedule in the table. Also the default
+/// `EntityScheduler`, checking the schedule of each of an entity's components.
+#[derive(Debug, Clone, Default)]
+pub struct LinearScan {
+    excluded: Vec<Entity>,
+}
+
+impl LinearScan {
+    pub const fn new() -> Self {
+        Self {
+            excluded: Vec::new(),
+        }
+    }
+}
+
+impl<T: RealtimeComponent> Scheduler<T> for LinearScan {
+    fn next_due(
+        &mut self,
+        table: &RealtimeComponentTable<T>,
+        within: Duration,
+    ) -> Option<(Entity, Duration)> {
+        let within = duration_to_nanos(within);
+        table
+            .iter_with_schedule()
+            .filter(|(entity, scheduled)| {
+                scheduled.is_enabled()
+                    && scheduled.until_next_tick_nanos() <= within
+                    && !self.excluded.contains(entity)
+            })
+            .min_by_key(|(_, scheduled)| scheduled.until_next_tick_nanos())
+            .map(|(entity, scheduled)| (entity, scheduled.until_next_tick()))
+    }
+
+    fn advance(&mut self, table: &mut RealtimeComponentTable<T>, duration: Duration) {
+        let nanos = duration_to_nanos(duration);
+        for (entity, scheduled) in table.iter_with_schedule_mut() {
+            if scheduled.is_enabled() && !self.excluded.contains(&entity) {
+                scheduled.advance_nanos(nanos);
+            }
+        }
+    }
+
+    fn advance_until_due(&mut self, table: &mut RealtimeComponentTable<T>, duration: Duration) {
+        table.advance_enabled_until_due_nanos(duration_to_nanos(duration), &self.excluded);
+    }
+
+    fn reschedule(&mut self, entity: Entity, _scheduled: &ScheduledRealtimeComponent<T>) {
+        self.excluded.retain(|&excluded| excluded != entity);
+    }
+
+    fn exclude(&mut self, entity: Entity) {
+        self.excluded.push(entity);
+    }
+
+    fn end_exclusions(&mut self, _table: &RealtimeComponentTable<T>) {
+        self.excluded.clear();
+    }
+}
+
+/// Takes each step of an entity's frame for `process_entity_frame_with_scheduler` and
+/// `AnimationContext`, whose frame processing functions such as `tick` and `tick_with_budget`
+/// use the scheduler they're created with. A step ticks the entity's components which are due
+/// soonest, or advances to the end of the frame if none are due. Schedulers of whole tables, such
+/// as `IndexedScheduler`, plug into `process_table_frame` instead.
+pub trait EntityScheduler<C: ContextContainsRealtimeComponents + ?Sized> {
+    /// Take the next step of the frame of `entity` given the `frame_remaining`, returning the
+    /// events of the components which ticked and how far the step advanced
+    fn step(
+        &mut self,
+        entity: Entity,
+        components: &mut C::Components,
+        frame_remaining: Duration,
+    ) -> (
+        <C::Components as RealtimeComponents<C>>::EntityEvents,
+        Duration,
+    );
+}
+
+impl<C: ContextContainsRealtimeComponents + ?Sized> EntityScheduler<C> for LinearScan {
+    fn step(
+        &mut self,
+        entity: Entity,
+        components: &mut C::Components,
+        frame_remaining: Duration,
+    ) -> (
+        <C::Components as RealtimeComponents<C>>::EntityEvents,
+        Duration,
+    ) {
+        components.tick_entity(entity, frame_remaining)
+    }
+}
+
+/// Keeps the enabled components of a single table ordered by when they're due. Finding the next
+/// due component takes O(log n) time, as does rescheduling a component after it ticks.
+/// Advancing still visits every component, since the table stores the time until each
+/// component's next tick. The index only stays valid while the table's frames are processed by
+/// `process_table_frame` with this scheduler. Anything else which changes the table's
+/// `schedule_generation` makes the next use of the index rebuild it in O(n log n) time. That
+/// includes processing the table with `process_entity_frame`, the coordinator or `fast_forward`,
+/// inserting, removing, enabling or disabling components, and modifying schedules with
+/// `get_with_schedule_mut`. Each table needs its own `IndexedScheduler`.
+#[derive(Debug, Clone, Default)]
+pub struct IndexedScheduler {
+    /// Entities of enabled components keyed by the time they're due, measured from when the
+    /// index was built
+    by_due: BTreeSet<(u64, Entity)>,
+    due: HashMap<Entity, u64>,
+    /// Time the index has been advanced by since it was built. Components due before this were
+    /// held back by the table's event cap and are due now.
+    elapsed: u64,
+    /// Schedule generation of the table when the index was built
+    generation: Option<u64>,
+    /// Entities whose components are left out of the index while their frames are simulated
+    /// separately
+    excluded: Vec<Entity>,
+}
+
+impl IndexedScheduler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn sync<T: RealtimeComponent>(&mut self, table: &RealtimeComponentTable<T>) {
+        if self.generation == Some(table.schedule_generation()) {
+            return;
+        }
+        self.by_due.clear();
+        self.due.clear();
+        self.elapsed = 0;
+        for (entity, scheduled) in table.iter_with_schedule() {
+            if scheduled.is_enabled() && !self.excluded.contains(&entity) {
+                self.by_due
+                    .insert((scheduled.until_next_tick_nanos(), entity));
+                self.due.insert(entity, scheduled.until_next_tick_nanos());
+            }
+        }
+        self.generation = Some(table.schedule_generation());
+    }
+
+    /// The time until the soonest tick of any enabled component in `table`, or `None` if there
+    /// are no enabled components. This is O(log n) unless the index needs rebuilding.
+    pub fn min_until_next_tick<T: RealtimeComponent>(
+        &mut self,
+        table: &RealtimeComponentTable<T>,
+    ) -> Option<Duration> {
+        self.sync(table);
+        self.by_due
+            .first()
+            .map(|&(due, _)| Duration::from_nanos(due.saturating_sub(self.elapsed)))
+    }
+
+    /// Iterate over the enabled components of `table` in order of their time until next tick,
+    /// soonest first, yielding each entity with the time until its component next ticks.
+    /// Components due at the same time are yielded in order of their entities. This doesn't
+    /// allocate unless the index needs rebuilding.
+    pub fn iter_by_schedule<T: RealtimeComponent>(
+        &mut self,
+        table: &RealtimeComponentTable<T>,
+    ) -> impl '_ + Iterator<Item = (Entity, Duration)> {
+        self.sync(table);
+        let elapsed = self.elapsed;
+        self.by_due
+            .iter()
+            .map(move |&(due, entity)| (entity, Duration::from_nanos(due.saturating_sub(elapsed))))
+    }
+}
+
+impl<T: RealtimeComponent> Scheduler<T> for IndexedScheduler {
+    fn next_due(
+        &mut self,
+        table: &RealtimeComponentTable<T>,
+        within: Duration,
+    ) -> Option<(Entity, Duration)> {
+        self.sync(table);
+        let &(due, entity) = self.by_due.first()?;
+        let until_due = due.saturating_sub(self.elapsed);
+        (until_due <= duration_to_nanos(within)).then(|| (entity, Duration::from_nanos(until_due)))
+    }
+
+    fn advance(&mut self, table: &mut RealtimeComponentTable<T>, duration: Duration) {
+        self.sync(table);
+        let nanos = duration_to_nanos(duration);
+        table.advance_enabled_nanos(nanos, &self.excluded);
+        self.elapsed += nanos;
+    }
+
+    fn advance_until_due(&mut self, table: &mut RealtimeComponentTable<T>, duration: Duration) {
+        self.sync(table);
+        let nanos = duration_to_nanos(duration);
+        table.advance_enabled_until_due_nanos_untracked(nanos, &self.excluded);
+        self.elapsed += nanos;
+    }
+
+    fn reschedule(&mut self, entity: Entity, scheduled: &ScheduledRealtimeComponent<T>) {
+        self.excluded.retain(|&excluded| excluded != entity);
+        if let Some(due) = self.due.remove(&entity) {
+            self.by_due.remove(&(due, entity));
+        }
+        if scheduled.is_enabled() {
+            let due = self.elapsed + scheduled.until_next_tick_nanos();
+            self.by_due.insert((due, entity));
+            self.due.insert(entity, due);
+        }
+    }
+
+    fn exclude(&mut self, entity: Entity) {
+        if let Some(due) = self.due.remove(&entity) {
+            self.by_due.remove(&(due, entity));
+        }
+        self.excluded.push(entity);
+    }
+
+    fn end_exclusions(&mut self, table: &RealtimeComponentTable<T>) {
+        for entity in std::mem::take(&mut self.excluded) {
+            if let Some(scheduled) = table.get_with_schedule(entity) {
+                self.reschedule(entity, scheduled);
+            }
+        }
+    }
+}
+
+/// Keeps the enabled components of a single table in a binary heap ordered by when they're due.
+/// Components tick in the same order as with `IndexedScheduler`, and the heap stays valid under
+/// the same conditions as its index. Rescheduling a component pushes a new entry rather than
+/// removing the old one, which is skipped once it reaches the top of the heap, so updates are
+/// cheaper but the heap grows with stale entries. It's compacted when they outnumber the
+/// components. Each table needs its own `HeapScheduler`.
+#[derive(Debug, Clone, Default)]
+pub struct HeapScheduler {
+    /// Entities of enabled components keyed by the time they're due, measured from when the
+    /// heap was built, including stale entries of rescheduled components
+    heap: BinaryHeap<Reverse<(u64, Entity)>>,
+    due: HashMap<Entity, u64>,
+    /// Time the heap has been advanced by since it was built. Components due before this were
+    /// held back by the table's event cap and are due now.
+    elapsed: u64,
+    /// Schedule generation of the table when the heap was built
+    generation: Option<u64>,
+    /// Entities whose components are left out of the heap while their frames are simulated
+    /// separately
+    excluded: Vec<Entity>,
+}
+
+impl HeapScheduler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn sync<T: RealtimeComponent>(&mut self, table: &RealtimeComponentTable<T>) {
+        if self.generation == Some(table.schedule_generation()) {
+            return;
+        }
+        self.due.clear();
+        self.elapsed = 0;
+        for (entity, scheduled) in table.iter_with_schedule() {
+            if scheduled.is_enabled() && !self.excluded.contains(&entity) {
+                self.due.insert(entity, scheduled.until_next_tick_nanos());
+            }
+        }
+        self.rebuild_heap();
+        self.generation = Some(table.schedule_generation());
+    }
+
+    fn rebuild_heap(&mut self) {
+        let mut entries = std::mem::take(&mut self.heap).into_vec();
+        entries.clear();
+        entries.extend(
+            self.due
+                .iter()
+                .map(|(&entity, &due)| Reverse((due, entity))),
+        );
+        self.heap = BinaryHeap::from(entries);
+    }
+
+    /// Remove stale entries from the top of the heap, returning the soonest current entry
+    fn peek(&mut self) -> Option<(u64, Entity)> {
+        while let Some(&Reverse((due, entity))) = self.heap.peek() {
+            if self.due.get(&entity) == Some(&due) {
+                return Some((due, entity));
+            }
+            self.heap.pop();
+        }
+        None
+    }
+
+    /// The time until the soonest tick of any enabled component in `table`, or `None` if there
+    /// are no enabled components. This is O(log n) unless the heap needs rebuilding.
+    pub fn min_until_next_tick<T: RealtimeComponent>(
+        &mut self,
+        table: &RealtimeComponentTable<T>,
+    ) -> Option<Duration> {
+        self.sync(table);
+        self.peek()
+            .map(|(due, _)| Duration::from_nanos(due.saturating_sub(self.elapsed)))
+    }
+}
+
+impl<T: RealtimeComponent> Scheduler<T> for HeapScheduler {
+    fn next_due(
+        &mut self,
+        table: &RealtimeComponentTable<T>,
+        within: Duration,
+    ) -> Option<(Entity, Duration)> {
+        self.sync(table);
+        let (due, entity) = self.peek()?;
+        let until_due = due.saturating_sub(self.elapsed);
+        (until_due <= duration_to_nanos(within)).then(|| (entity, Duration::from_nanos(until_due)))
+    }
+
+    fn advance(&mut self, table: &mut RealtimeComponentTable<T>, duration: Duration) {
+        self.sync(table);
+        let nanos = duration_to_nanos(duration);
+        table.advance_enabled_nanos(nanos, &self.excluded);
+        self.elapsed += nanos;
+    }
+
+    fn advance_until_due(&mut self, table: &mut RealtimeComponentTable<T>, duration: Duration) {
+        self.sync(table);
+        let nanos = duration_to_nanos(duration);
+        table.advance_enabled_until_due_nanos_untracked(nanos, &self.excluded);
+        self.elapsed += nanos;
+    }
+
+    fn exclude(&mut self, entity: Entity) {
+        // its entries in the heap become stale
+        self.due.remove(&entity);
+        self.excluded.push(entity);
+    }
+
+    fn end_exclusions(&mut self, table: &RealtimeComponentTable<T>) {
+        for entity in std::mem::take(&mut self.excluded) {
+            if let Some(scheduled) = table.get_with_schedule(entity) {
+                self.reschedule(entity, scheduled);
+            }
+        }
+    }
+
+    fn reschedule(&mut self, entity: Entity, scheduled: &ScheduledRealtimeComponent<T>) {
+        self.excluded.retain(|&excluded| excluded != entity);
+        if scheduled.is_enabled() {
+            let due = self.elapsed + scheduled.until_next_tick_nanos();
+            self.due.insert(entity, due);
+            self.heap.push(Reverse((due, entity)));
+        } else {
+            self.due.remove(&entity);
+        }
+        if self.heap.len() > 2 * self.due.len().max(16) {
+            self.rebuild_heap();
+        }
+    }
+}
+
+/// Simulate every component in the table returned by `table` for the duration of a frame,
+/// applying each event to the context as it occurs, in the order the components tick across
+/// the whole table. Then advance the table's simulated time. The context's frame hooks apply to
+/// each entity as in `process_entity_frame`. Entities whose frames the context changes (e.g.
+/// because they're dormant, their time is scaled or the catch-up mode limits them) are left out
+/// of the shared pass and simulated afterwards, one at a time, so their events follow those of
+/// the rest of the table. Each entity's `after_entity_frame` hook is called once the whole
+/// table's frame is complete. The table's event cap and maximum period apply as usual.
+pub fn process_table_frame<C, T>(
+    frame_duration: Duration,
+    context: &mut C,
+    scheduler: &mut impl Scheduler<T>,
+    table: impl Fn(&mut C) -> &mut RealtimeComponentTable<T>,
+) where
+    C: RealtimeFrameHooks,
+    T: RealtimeComponentApplyEvent<C>,
+{
+    crate::realtime_profile_scope!("process_table_frame");
+    let entities = table(context).entities().collect::<Vec<_>>();
+    let mut separate = Vec::new();
+    for &entity in &entities {
+        if let Some(entity_frame) =
+            begin_table_entity_frame(entity, frame_duration, context, scheduler, &table)
+        {
+            if entity_frame != frame_duration {
+                separate.push((entity, entity_frame));
+            }
+        }
+    }
+    for &(entity, _) in &separate {
+        scheduler.exclude(entity);
+    }
+    let mut frame_remaining = frame_duration;
+    let mut capped = false;
+    while let Some((entity, until_due)) = scheduler.next_due(table(context), frame_remaining) {
+        if table(context).event_cap_reached() {
+            table(context).report_capped(entity);
+            capped = true;
+            break;
+        }
+        scheduler.advance(table(context), until_due);
+        frame_remaining -= until_due;
+        let scheduled = match table(context).get_with_schedule_mut_untracked(entity) {
+            Some(scheduled) => scheduled,
+            None => continue,
+        };
+        let event = scheduled.tick(entity);
+        if let Some(scheduled) = table(context).after_tick_untracked(entity) {
+            scheduler.reschedule(entity, scheduled);
+        }
+        context.before_tick(entity);
+        T::apply_event(event, entity, context);
+    }
+    if capped {
+        // components held back by the event cap are left due rather than falling behind
+        scheduler.advance_until_due(table(context), frame_remaining);
+    } else {
+        scheduler.advance(table(context), frame_remaining);
+    }
+    for (entity, entity_frame) in separate {
+        if entity_frame.is_zero() {
+            continue;
+        }
+        process_separate_entity_frame(entity, entity_frame, context, scheduler, &table);
+    }
+    scheduler.end_exclusions(table(context));
+    for entity in entities {
+        context.after_entity_frame(entity);
+    }
+    table(context).advance_simulated_time(frame_duration);
+}
+
+/// Prepare to process the frame of an entity in the table, returning the duration of the frame
+/// to simulate, or `None` if its component is disabled. See `begin_entity_frame`.
+fn begin_table_entity_frame<C, T>(
+    entity: Entity,
+    frame_duration: Duration,
+    context: &mut C,
+    scheduler: &mut impl Scheduler<T>,
+    table: impl Fn(&mut C) -> &mut RealtimeComponentTable<T>,
+) -> Option<Duration>
+where
+    C: RealtimeFrameHooks,
+    T: RealtimeComponentApplyEvent<C>,
+{
+    let period = table(context)
+        .get_with_schedule(entity)
+        .filter(|scheduled| scheduled.is_enabled())?
+        .period();
+    let frame_duration = crate::entity_frame_duration(entity, frame_duration, context);
+    if frame_duration.is_zero() {
+        return Some(frame_duration);
+    }
+    let frame_duration = context
+        .realtime_catch_up_mode(entity)
+        .clamp(frame_duration, || {
+            Some(period).filter(|period| !period.is_zero())
+        });
+    let scheduled = table(context).get_with_schedule_mut_untracked(entity)?;
+    if let Some(event) = scheduled.drop_excess_ticks(frame_duration) {
+        scheduler.reschedule(entity, scheduled);
+        T::apply_event(event, entity, context);
+    }
+    Some(frame_duration)
+}
+
+/// Simulate the component of a single entity for the duration of its frame, notifying the
+/// scheduler of each new schedule
+fn process_separate_entity_frame<C, T>(
+    entity: Entity,
+    frame_duration: Duration,
+    context: &mut C,
+    scheduler: &mut impl Scheduler<T>,
+    table: impl Fn(&mut C) -> &mut RealtimeComponentTable<T>,
+) where
+    C: RealtimeFrameHooks,
+    T: RealtimeComponentApplyEvent<C>,
+{
+    let mut frame_remaining = duration_to_nanos(frame_duration);
+    loop {
+        let capped = table(context).event_cap_reached();
+        let scheduled = match table(context)
+            .get_with_schedule_mut_untracked(entity)
+            .filter(|scheduled| scheduled.is_enabled())
+        {
+            Some(scheduled) => scheduled,
+            None => break,
+        };
+        let due = scheduled.until_next_tick_nanos() <= frame_remaining;
+        if !due || capped {
+            // components held back by the event cap are left due
+            scheduled.advance_nanos(scheduled.until_next_tick_nanos().min(frame_remaining));
+            scheduler.reschedule(entity, scheduled);
+            if due {
+                table(context).report_capped(entity);
+            }
+            break;
+        }
+        frame_remaining -= scheduled.until_next_tick_nanos();
+        let event = scheduled.tick(entity);
+        if let Some(scheduled) = table(context).after_tick_untracked(entity) {
+            scheduler.reschedule(entity, scheduled);
+        }
+        context.before_tick(entity);
+        T::apply_event(event, entity, context);
+    }
+}
@@ -0,0 +1,271 @@
+//! Realtime components whose behaviour is defined by [rhai](https://rhai.rs) scripts, allowing
+//! new timed effects to be added without recompiling the game.
+//!
+//! A script must define a `tick` function, called with the component's state bound to `this`,
+//! which returns an array of the event and the number of milliseconds until the next tick. It
+//! may also define an `apply` function which takes an event and returns the commands (a single
+//! value, or an array of values) for the context to carry out. Scripts only affect the game
+//! through these commands, so the context decides what scripts are allowed to do.
+//!
+//! ```rhai
+//! fn tick() {
+//!     this.brightness = if this.brightness > 0 { 0 } else { 10 };
+//!     [this.brightness, 150]
+//! }
+//!
+//! fn apply(brightness) {
+//!     #{ set_light: brightness }
+//! }
+//! ```
+
+use crate::{Entity, RealtimeComponent, RealtimeComponentApplyEvent};
+/// Re-export rhai so scripted components can be used without depending on it directly
+pub use rhai;
+use rhai::{Array, CallFnOptions, Dynamic, Engine, Scope, AST};
+use std::error::Error;
+use std::fmt;
+use std::rc::Rc;
+use std::time::Duration;
+
+/// Limit on the number of operations a single call into a script may perform, so a script
+/// stuck in a loop can't hang the game
+const MAX_OPERATIONS: u64 = 100_000;
+
+#[derive(Debug)]
+pub enum ScriptError {
+    Parse(rhai::ParseError),
+    MissingTick,
+}
+
+impl fmt::Display for ScriptError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Parse(error) => write!(f, "failed to parse script: {}", error),
+            Self::MissingTick => write!(f, "script doesn't define a `tick` function"),
+        }
+    }
+}
+
+impl Error for ScriptError {}
+
+/// A compiled script defining the behaviour of a `ScriptedRealtimeComponent`
+pub struct Script {
+    name: String,
+    source: String,
+    engine: Engine,
+    ast: AST,
+    has_apply: bool,
+}
+
+impl Script {
+    pub fn compile(
+        name: impl Into<String>,
+        source: impl Into<String>,
+    ) -> Result<Self, ScriptError> {
+        let name = name.into();
+        let source = source.into();
+        let mut engine = Engine::new();
+        engine.set_max_operations(MAX_OPERATIONS);
+        let ast = engine.compile(&source).map_err(ScriptError::Parse)?;
+        let has_function = |name: &str| ast.iter_functions().any(|function| function.name == name);
+        if !has_function("tick") {
+            return Err(ScriptError::MissingTick);
+        }
+        let has_apply = has_function("apply");
+        Ok(Self {
+            name,
+            source,
+            engine,
+            ast,
+            has_apply,
+        })
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// Panics if the script raises an error, since there's no other way for an error to escape
+    /// from `tick` or `apply_event`
+    fn call(&self, function: &str, this: Option<&mut Dynamic>, args: Vec<Dynamic>) -> Dynamic {
+        let mut options = CallFnOptions::new().eval_ast(false);
+        if let Some(this) = this {
+            options = options.bind_this_ptr(this);
+        }
+        self.engine
+            .call_fn_with_options(options, &mut Scope::new(), &self.ast, function, args)
+            .unwrap_or_else(|error| panic!("error in script {}: {}", self.name, error))
+    }
+}
+
+impl fmt::Debug for Script {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Script")
+            .field("name", &self.name)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Event produced by a `ScriptedRealtimeComponent`
+#[derive(Clone)]
+pub struct ScriptedEvent {
+    script: Rc<Script>,
+    pub value: Dynamic,
+}
+
+impl fmt::Debug for ScriptedEvent {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ScriptedEvent")
+            .field("script", &self.script.name)
+            .field("value", &self.value)
+            .finish()
+    }
+}
+
+/// A context which can carry out the commands returned by scripts
+pub trait ScriptContext {
+    fn script_command(&mut self, entity: Entity, command: Dynamic);
+}
+
+/// Realtime component whose `tick` and `apply_event` call into a script
+#[derive(Clone)]
+pub struct ScriptedRealtimeComponent {
+    script: Rc<Script>,
+    state: Dynamic,
+}
+
+impl ScriptedRealtimeComponent {
+    /// `state` is bound to `this` when the script's `tick` function is called
+    pub fn new(script: Rc<Script>, state: Dynamic) -> Self {
+        Self { script, state }
+    }
+
+    pub fn script(&self) -> &Rc<Script> {
+        &self.script
+    }
+
+    pub fn state(&self) -> &Dynamic {
+        &self.state
+    }
+
+    pub fn state_mut(&mut self) -> &mut Dynamic {
+        &mut self.state
+    }
+}
+
+impl fmt::Debug for ScriptedRealtimeComponent {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ScriptedRealtimeComponent")
+            .field("script", &self.script.name)
+            .field("state", &self.state)
+            .finish()
+    }
+}
+
+impl RealtimeComponent for ScriptedRealtimeComponent {
+    type Event = ScriptedEvent;
+
+    fn tick(&mut self) -> (Self::Event, Duration) {
+        let result = self.script.call("tick", Some(&mut self.state), Vec::new());
+        let mut result = result.try_cast::<Array>().unwrap_or_else(|| {
+            panic!(
+                "`tick` in script {} must return [event, milliseconds]",
+                self.script.name
+            )
+        });
+        let until_next_tick = result
+            .pop()
+            .and_then(|ms| {
+                ms.as_int()
+                    .map(|ms| Duration::from_millis(ms.max(0) as u64))
+                    .or_else(|_| {
+                        ms.as_float()
+                            .map(|ms| Duration::from_secs_f64(ms.max(0.) / 1000.))
+                    })
+                    .ok()
+            })
+            .unwrap_or_else(|| {
+                panic!(
+                    "`tick` in script {} must return [event, milliseconds]",
+                    self.script.name
+                )
+            });
+        let value = result.pop().unwrap_or_default();
+        let event = ScriptedEvent {
+            script: self.script.clone(),
+            value,
+        };
+        (event, until_next_tick)
+    }
+}
+
+impl<C: ScriptContext> RealtimeComponentApplyEvent<C> for ScriptedRealtimeComponent {
+    fn apply_event(event: ScriptedEvent, entity: Entity, context: &mut C) {
+        if !event.script.has_apply {
+            return;
+        }
+        let commands = event.script.call("apply", None, vec![event.value]);
+        if commands.is_unit() {
+            return;
+        }
+        match commands.try_cast_result::<Array>() {
+            Ok(commands) => {
+                for command in commands {
+                    context.script_command(entity, command);
+                }
+            }
+            Err(command) => context.script_command(entity, command),
+        }
+    }
+}
+
+/// Scripted components are serialized along with the source of their script, which is
+/// recompiled when they are deserialized
+#[cfg(feature = "serialize")]
+mod serialize {
+    use super::{Script, ScriptedRealtimeComponent};
+    use rhai::Dynamic;
+    use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+    use std::rc::Rc;
+
+    #[derive(Serialize)]
+    struct SerializeScriptedRealtimeComponent<'a> {
+        name: &'a str,
+        source: &'a str,
+        state: &'a Dynamic,
+    }
+
+    #[derive(Deserialize)]
+    struct DeserializeScriptedRealtimeComponent {
+        name: String,
+        source: String,
+        state: Dynamic,
+    }
+
+    impl Serialize for ScriptedRealtimeComponent {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            SerializeScriptedRealtimeComponent {
+                name: &self.script.name,
+                source: &self.script.source,
+                state: &self.state,
+            }
+            .serialize(serializer)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for ScriptedRealtimeComponent {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let DeserializeScriptedRealtimeComponent {
+                name,
+                source,
+                state,
+            } = DeserializeScriptedRealtimeComponent::deserialize(deserializer)?;
+            let script = Script::compile(name, source).map_err(de::Error::custom)?;
+            Ok(ScriptedRealtimeComponent::new(Rc::new(script), state))
+        }
+    }
+}
@@ -0,0 +1,81 @@
+//! Variants of the scheduling and frame processing functions which take durations as `f64`
+//! seconds, for game frameworks which report the time between frames as a float. Seconds are
+//! rounded to the nearest nanosecond, whereas `Duration::from_secs_f64` truncates and panics on
+//! negative deltas.
+
+use crate::scheduler::EntityScheduler;
+use crate::{
+    process_entity_frame, AnimationContext, ContextContainsRealtimeComponents, Entity,
+    RealtimeComponent, RealtimeComponentTable, ScheduledRealtimeComponent,
+};
+use std::time::Duration;
+
+/// Convert `secs` to a `Duration`, rounded to the nearest nanosecond. Negative and NaN values
+/// become zero, and values too large to represent become `Duration::MAX`.
+pub fn duration_from_secs(secs: f64) -> Duration {
+    if secs.is_nan() || secs <= 0.0 {
+        return Duration::ZERO;
+    }
+    let nanos = (secs * 1e9).round();
+    if nanos >= u64::MAX as f64 {
+        Duration::MAX
+    } else {
+        Duration::from_nanos(nanos as u64)
+    }
+}
+
+/// Like `process_entity_frame` but the frame duration is in seconds
+pub fn process_entity_frame_secs<C: ContextContainsRealtimeComponents>(
+    entity: Entity,
+    frame_secs: f64,
+    context: &mut C,
+) -> bool {
+    process_entity_frame(entity, duration_from_secs(frame_secs), context)
+}
+
+impl<S> AnimationContext<S> {
+    /// Like `tick` but the frame duration is in seconds
+    pub fn tick_secs<C: ContextContainsRealtimeComponents>(&mut self, context: C, frame_secs: f64)
+    where
+        S: EntityScheduler<C>,
+    {
+        self.tick(context, duration_from_secs(frame_secs));
+    }
+}
+
+impl<T: RealtimeComponent> ScheduledRealtimeComponent<T> {
+    /// Like `new` but the time until the first tick is in seconds
+    pub fn new_secs(component: T, until_next_tick_secs: f64) -> Self {
+        Self::new(component, duration_from_secs(until_next_tick_secs))
+    }
+
+    pub fn until_next_tick_secs(&self) -> f64 {
+        self.until_next_tick().as_secs_f64()
+    }
+
+    pub fn set_until_next_tick_secs(&mut self, until_next_tick_secs: f64) {
+        self.set_until_next_tick(duration_from_secs(until_next_tick_secs));
+    }
+}
+
+impl<T: RealtimeComponent> RealtimeComponentTable<T> {
+    /// Insert a component which first ticks after `until_next_tick_secs` seconds. Returns the
+    /// component which was replaced, if any.
+    pub fn insert_with_delay_secs(
+        &mut self,
+        entity: Entity,
+        data: T,
+        until_next_tick_secs: f64,
+    ) -> Option<T> {
+        self.insert_with_schedule(
+            entity,
+            ScheduledRealtimeComponent::new_secs(data, until_next_tick_secs),
+        )
+        .map(|c| c.component)
+    }
+
+    /// Total simulated time the table has been advanced by, in seconds
+    pub fn simulated_secs(&self) -> f64 {
+        self.simulated_time().as_secs_f64()
+    }
+}
@@ -0,0 +1,181 @@
+//! A thread-safe realtime component table, split into shards which are locked independently so
+//! that other threads (e.g. render extraction jobs) can read components while the main thread
+//! ticks other shards
+
+use crate::{
+    Entity, EventCapExceeded, OnEventCapExceeded, OnPeriodClamped, PeriodClamped,
+    RealtimeComponent, RealtimeComponentTable, SchedulePolicy,
+};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::sync::{Arc, PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard};
+use std::time::Duration;
+
+/// A `RealtimeComponentTable` split into shards, each behind its own lock. Entities are
+/// assigned to shards by hash, so an entity stays in the same shard for as long as it's live.
+/// Locks poisoned by a panic on another thread are ignored.
+#[derive(Debug)]
+pub struct ShardedRealtimeComponentTable<T: RealtimeComponent> {
+    shards: Vec<RwLock<RealtimeComponentTable<T>>>,
+}
+
+impl<T: RealtimeComponent> ShardedRealtimeComponentTable<T> {
+    /// Panics if `num_shards` is zero
+    pub fn new(num_shards: usize) -> Self {
+        assert!(num_shards > 0, "num_shards must be positive");
+        Self {
+            shards: (0..num_shards).map(|_| Default::default()).collect(),
+        }
+    }
+
+    /// Split an existing table into `num_shards` shards. Panics if `num_shards` is zero.
+    pub fn from_table(mut table: RealtimeComponentTable<T>, num_shards: usize) -> Self {
+        let mut sharded = Self::new(num_shards);
+        for shard in sharded.shards_mut() {
+            shard.simulated_time = table.simulated_time;
+            shard.limits = table.limits.clone();
+            shard.schedule_policy = table.schedule_policy;
+        }
+        let entities = table.entities().collect::<Vec<_>>();
+        for entity in entities {
+            if let Some(scheduled) = table.remove_with_schedule(entity) {
+                sharded
+                    .write_shard(entity)
+                    .insert_with_schedule(entity, scheduled);
+            }
+        }
+        sharded
+    }
+
+    pub fn num_shards(&self) -> usize {
+        self.shards.len()
+    }
+
+    /// The index of the shard containing the component of an entity
+    pub fn shard_index(&self, entity: Entity) -> usize {
+        let mut hasher = DefaultHasher::new();
+        entity.hash(&mut hasher);
+        (hasher.finish() % self.shards.len() as u64) as usize
+    }
+
+    /// Lock the shard containing the component of an entity for reading
+    pub fn read_shard(&self, entity: Entity) -> RwLockReadGuard<'_, RealtimeComponentTable<T>> {
+        self.shards[self.shard_index(entity)]
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Lock the shard containing the component of an entity for writing
+    pub fn write_shard(&self, entity: Entity) -> RwLockWriteGuard<'_, RealtimeComponentTable<T>> {
+        self.shards[self.shard_index(entity)]
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Call `f` with the component of an entity while its shard is locked for reading
+    pub fn with<R>(&self, entity: Entity, f: impl FnOnce(&T) -> R) -> Option<R> {
+        self.read_shard(entity).get(entity).map(f)
+    }
+
+    /// Call `f` with the component of an entity while its shard is locked for writing
+    pub fn with_mut<R>(&self, entity: Entity, f: impl FnOnce(&mut T) -> R) -> Option<R> {
+        self.write_shard(entity).get_mut(entity).map(f)
+    }
+
+    pub fn insert(&self, entity: Entity, data: T) -> Option<T> {
+        self.write_shard(entity).insert(entity, data)
+    }
+
+    pub fn remove(&self, entity: Entity) -> Option<T> {
+        self.write_shard(entity).remove(entity)
+    }
+
+    pub fn contains(&self, entity: Entity) -> bool {
+        self.read_shard(entity).contains(entity)
+    }
+
+    /// Call `f` with each shard in turn, locking each for writing only while `f` is called
+    /// with it, e.g. to tick the components in each shard while other threads read the rest
+    pub fn for_each_shard_mut(&self, mut f: impl FnMut(&mut RealtimeComponentTable<T>)) {
+        for shard in &self.shards {
+            f(&mut shard.write().unwrap_or_else(PoisonError::into_inner));
+        }
+    }
+
+    /// Access every shard without locking
+    pub fn shards_mut(&mut self) -> impl '_ + Iterator<Item = &mut RealtimeComponentTable<T>> {
+        self.shards
+            .iter_mut()
+            .map(|shard| shard.get_mut().unwrap_or_else(PoisonError::into_inner))
+    }
+
+    /// Set the schedule policy of every shard
+    pub fn set_schedule_policy(&self, schedule_policy: SchedulePolicy) {
+        self.for_each_shard_mut(|shard| shard.set_schedule_policy(schedule_policy));
+    }
+
+    /// Set the event cap of every shard. Each shard counts its own events, so up to
+    /// `max_events_per_frame` events may be produced per shard.
+    pub fn set_event_cap(
+        &self,
+        max_events_per_frame: u32,
+        on_overflow: impl Fn(EventCapExceeded) + Send + Sync + 'static,
+    ) {
+        let on_overflow: OnEventCapExceeded = Arc::new(on_overflow);
+        self.for_each_shard_mut(|shard| {
+            shard
+                .limits
+                .set_event_cap(max_events_per_frame, Arc::clone(&on_overflow))
+        });
+    }
+
+    pub fn clear_event_cap(&self) {
+        self.for_each_shard_mut(|shard| shard.clear_event_cap());
+    }
+
+    /// Set the maximum period of every shard
+    pub fn set_max_period(
+        &self,
+        max_period: Duration,
+        on_clamped: impl Fn(PeriodClamped) + Send + Sync + 'static,
+    ) {
+        let on_clamped: OnPeriodClamped = Arc::new(on_clamped);
+        self.for_each_shard_mut(|shard| {
+            shard
+                .limits
+                .set_max_period(max_period, Arc::clone(&on_clamped))
+        });
+    }
+
+    pub fn clear_max_period(&self) {
+        self.for_each_shard_mut(|shard| shard.clear_max_period());
+    }
+
+    /// Merge the shards back into a single table. Its simulated time is the greatest of the
+    /// shards' simulated times. Its event cap, maximum period and schedule policy are those of
+    /// the first shard, so shards should be configured together with e.g. `set_schedule_policy`
+    /// rather than individually. Panics in builds with debug assertions if the shards' schedule
+    /// policies differ.
+    pub fn into_table(self) -> RealtimeComponentTable<T> {
+        let mut table = RealtimeComponentTable::default();
+        for (index, shard) in self.shards.into_iter().enumerate() {
+            let mut shard = shard.into_inner().unwrap_or_else(PoisonError::into_inner);
+            table.simulated_time = table.simulated_time.max(shard.simulated_time);
+            if index == 0 {
+                table.limits = shard.limits.clone();
+                table.schedule_policy = shard.schedule_policy;
+            }
+            debug_assert_eq!(
+                table.schedule_policy, shard.schedule_policy,
+                "shards have different schedule policies"
+            );
+            let entities = shard.entities().collect::<Vec<_>>();
+            for entity in entities {
+                if let Some(scheduled) = shard.remove_with_schedule(entity) {
+                    table.insert_with_schedule(entity, scheduled);
+                }
+            }
+        }
+        table
+    }
+}
@@ -0,0 +1,146 @@
+//! Applying events to contexts which are shared rather than mutably borrowed, for games whose
+//! state lives in `RefCell`s or ECS resource cells. Components apply their events to a
+//! `SharedContext` of the game's context type, which only holds a shared reference to it:
+//!
+//! ```ignore
+//! impl<'a> RealtimeComponentApplyEvent<SharedContext<'a, Game>> for Flicker {
+//!     fn apply_event(event: FlickerEvent, entity: Entity, game: &mut SharedContext<'a, Game>) {
+//!         game.lights.borrow_mut().set_intensity(entity, event.intensity);
+//!     }
+//! }
+//!
+//! declare_realtime_entity_module! {
+//!     components<'a>[SharedContext<'a, Game>] {
+//!         flicker: Flicker,
+//!     }
+//! }
+//!
+//! impl ContextContainsRealtimeComponentsShared for Game {
+//!     type Components = components::RealtimeComponents;
+//!     fn with_realtime_components<R>(
+//!         &self,
+//!         f: impl FnOnce(&mut Self::Components) -> R,
+//!     ) -> R {
+//!         f(&mut self.realtime_components.borrow_mut())
+//!     }
+//! }
+//! ```
+
+use crate::{CatchUpMode, Entity, RealtimeComponents, RealtimeEntityEvents, RealtimeFrameHooks};
+use std::ops::Deref;
+use std::time::Duration;
+
+/// A shared reference to a context, for use as the context type of
+/// `declare_realtime_entity_module!` and `RealtimeComponentApplyEvent` when events are applied
+/// without mutably borrowing the context. Dereferences to the context.
+#[derive(Debug)]
+pub struct SharedContext<'a, C: ?Sized>(pub &'a C);
+
+impl<'a, C: ?Sized> Deref for SharedContext<'a, C> {
+    type Target = C;
+
+    fn deref(&self) -> &C {
+        self.0
+    }
+}
+
+/// Counterpart of `ContextContainsRealtimeComponents` for shared contexts
+pub trait ContextContainsRealtimeComponentsShared {
+    type Components: for<'a> RealtimeComponents<SharedContext<'a, Self>>;
+
+    /// Call `f` with mutable access to the realtime components, e.g. by borrowing them from a
+    /// `RefCell`. The components aren't borrowed while events are applied, so event handlers
+    /// may access them.
+    fn with_realtime_components<R>(&self, f: impl FnOnce(&mut Self::Components) -> R) -> R;
+
+    /// See `ContextContainsRealtimeComponents::realtime_max_frame_duration`
+    fn realtime_max_frame_duration(&self, _entity: Entity) -> Option<Duration> {
+        None
+    }
+
+    /// See `ContextContainsRealtimeComponents::realtime_time_scale`
+    fn realtime_time_scale(&self, _entity: Entity) -> f64 {
+        1.0
+    }
+
+    /// See `ContextContainsRealtimeComponents::realtime_dormant`
+    fn realtime_dormant(&self, _entity: Entity) -> bool {
+        false
+    }
+
+    /// See `ContextContainsRealtimeComponents::realtime_catch_up_mode`
+    fn realtime_catch_up_mode(&self, _entity: Entity) -> CatchUpMode {
+        CatchUpMode::ReplayAll
+    }
+}
+
+/// The hooks of a shared context. Since the context is shared, `before_tick` and
+/// `after_entity_frame` are never called.
+struct SharedFrameHooks<'a, C: ?Sized>(&'a C);
+
+impl<'a, C: ContextContainsRealtimeComponentsShared + ?Sized> RealtimeFrameHooks
+    for SharedFrameHooks<'a, C>
+{
+    fn realtime_max_frame_duration(&self, entity: Entity) -> Option<Duration> {
+        self.0.realtime_max_frame_duration(entity)
+    }
+
+    fn realtime_time_scale(&self, entity: Entity) -> f64 {
+        self.0.realtime_time_scale(entity)
+    }
+
+    fn realtime_dormant(&self, entity: Entity) -> bool {
+        self.0.realtime_dormant(entity)
+    }
+
+    fn realtime_catch_up_mode(&self, entity: Entity) -> CatchUpMode {
+        self.0.realtime_catch_up_mode(entity)
+    }
+}
+
+/// Like `process_entity_frame` but for a shared context. The realtime components are only
+/// borrowed while ticking, not while applying events. The context's dormancy, time scale,
+/// maximum frame duration and catch-up mode apply as usual, but since the context is shared its
+/// `before_tick` and `after_entity_frame` hooks aren't called.
+pub fn process_entity_frame_shared<C: ContextContainsRealtimeComponentsShared>(
+    entity: Entity,
+    frame_duration: Duration,
+    context: &C,
+) -> bool {
+    crate::realtime_profile_scope!("process_entity_frame_shared");
+    if !context.with_realtime_components(|components| components.contains_entity(entity)) {
+        return false;
+    }
+    let mut frame_remaining = begin_entity_frame_shared(entity, frame_duration, context);
+    while frame_remaining > Duration::from_micros(0) {
+        let (events, until_next_tick) = context
+            .with_realtime_components(|components| components.tick_entity(entity, frame_remaining));
+        events.apply(entity, &mut SharedContext(context));
+        frame_remaining -= until_next_tick;
+    }
+    true
+}
+
+/// Prepare to process an entity's frame, returning the duration of the frame to simulate. See
+/// `begin_entity_frame`.
+fn begin_entity_frame_shared<C: ContextContainsRealtimeComponentsShared>(
+    entity: Entity,
+    frame_duration: Duration,
+    context: &C,
+) -> Duration {
+    let frame_duration =
+        crate::entity_frame_duration(entity, frame_duration, &SharedFrameHooks(context));
+    if frame_duration.is_zero() {
+        return frame_duration;
+    }
+    let frame_duration = context
+        .realtime_catch_up_mode(entity)
+        .clamp(frame_duration, || {
+            context.with_realtime_components(|components| components.min_period(entity))
+        });
+    let events = context.with_realtime_components(|components| {
+        components.drop_excess_ticks(entity, frame_duration)
+    });
+    events.apply(entity, &mut SharedContext(context));
+    frame_duration
+}
@@ -0,0 +1,171 @@
+//! A realtime component which moves through a sequence of states, dwelling in each for a fixed
+//! duration, for multi-phase effects (e.g. charge, then flash, then fade)
+
+use crate::combinators::FiniteRealtimeComponent;
+use crate::RealtimeComponent;
+#[cfg(feature = "serialize")]
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::Hash;
+use std::time::Duration;
+
+/// Event of a `TimedStateMachine`
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TimedStateEvent<S> {
+    /// The machine entered a state
+    Entered(S),
+    /// The machine finished dwelling in a state with no next state. The component should be
+    /// removed when applying this event, otherwise it repeats the event every time the final
+    /// state's dwell duration passes.
+    Finished(S),
+}
+
+/// How long to dwell in a state, and which state to enter next
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TimedStateTransition<S> {
+    pub dwell: Duration,
+    /// `None` if the machine should finish after this state
+    pub next: Option<S>,
+}
+
+/// A `TimedStateMachine` couldn't be created because it could enter a state with no entry in
+/// its transition table
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UnknownTimedState<S>(pub S);
+
+impl<S: fmt::Debug> fmt::Display for UnknownTimedState<S> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "state {:?} has no entry in the timed state machine's transition table",
+            self.0
+        )
+    }
+}
+
+impl<S: fmt::Debug> std::error::Error for UnknownTimedState<S> {}
+
+/// Enters its initial state on its first tick, then after each state's dwell duration enters
+/// the next state according to its transition table
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    feature = "serialize",
+    serde(
+        try_from = "UncheckedTimedStateMachine<S>",
+        bound(
+            serialize = "S: Serialize",
+            deserialize = "S: Deserialize<'de> + Clone + fmt::Debug"
+        )
+    )
+)]
+#[derive(Debug, Clone)]
+pub struct TimedStateMachine<S: Eq + Hash> {
+    transitions: HashMap<S, TimedStateTransition<S>>,
+    current: Option<S>,
+    next: Option<S>,
+}
+
+impl<S: Clone + Eq + Hash> TimedStateMachine<S> {
+    /// Returns an error if the initial state, or the next state of any transition, has no
+    /// entry in `transitions`. Later entries for a state replace earlier ones.
+    pub fn new(
+        initial: S,
+        transitions: impl IntoIterator<Item = (S, TimedStateTransition<S>)>,
+    ) -> Result<Self, UnknownTimedState<S>> {
+        Self {
+            transitions: transitions.into_iter().collect(),
+            current: None,
+            next: Some(initial),
+        }
+        .validate()
+    }
+
+    fn validate(self) -> Result<Self, UnknownTimedState<S>> {
+        let unknown = self
+            .current
+            .iter()
+            .chain(self.next.iter())
+            .chain(
+                self.transitions
+                    .values()
+                    .filter_map(|transition| transition.next.as_ref()),
+            )
+            .find(|state| !self.transitions.contains_key(state));
+        match unknown {
+            Some(state) => Err(UnknownTimedState(state.clone())),
+            None => Ok(self),
+        }
+    }
+
+    /// The state most recently entered, or `None` before the first tick
+    pub fn current(&self) -> Option<&S> {
+        self.current.as_ref()
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.current.is_some() && self.next.is_none()
+    }
+
+    fn transition(&self, state: &S) -> &TimedStateTransition<S> {
+        self.transitions
+            .get(state)
+            .expect("transitions are validated when the machine is created")
+    }
+}
+
+#[cfg(feature = "serialize")]
+#[derive(Deserialize)]
+struct UncheckedTimedStateMachine<S: Eq + Hash> {
+    transitions: HashMap<S, TimedStateTransition<S>>,
+    current: Option<S>,
+    next: Option<S>,
+}
+
+#[cfg(feature = "serialize")]
+impl<S: Clone + Eq + Hash> TryFrom<UncheckedTimedStateMachine<S>> for TimedStateMachine<S> {
+    type Error = UnknownTimedState<S>;
+
+    fn try_from(machine: UncheckedTimedStateMachine<S>) -> Result<Self, Self::Error> {
+        Self {
+            transitions: machine.transitions,
+            current: machine.current,
+            next: machine.next,
+        }
+        .validate()
+    }
+}
+
+impl<S: Clone + Eq + Hash> RealtimeComponent for TimedStateMachine<S> {
+    type Event = TimedStateEvent<S>;
+
+    fn tick(&mut self) -> (Self::Event, Duration) {
+        match self.next.take() {
+            Some(state) => {
+                let transition = self.transition(&state);
+                let dwell = transition.dwell;
+                self.next = transition.next.clone();
+                self.current = Some(state.clone());
+                (TimedStateEvent::Entered(state), dwell)
+            }
+            None => {
+                let state = self
+                    .current
+                    .clone()
+                    .expect("machine has neither a current nor a next state");
+                let dwell = self.transition(&state).dwell;
+                (TimedStateEvent::Finished(state), dwell)
+            }
+        }
+    }
+}
+
+/// Finished once the final state has been entered, so the `Finished` event is never emitted
+/// when used with combinators
+impl<S: Clone + Eq + Hash> FiniteRealtimeComponent for TimedStateMachine<S> {
+    fn is_finished(&self) -> bool {
+        TimedStateMachine::is_finished(self)
+    }
+}
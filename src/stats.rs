@@ -0,0 +1,112 @@
+//! Counters of the work done while processing frames, enabled by the `stats` feature. Without
+//! the feature the counters are never incremented.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+/// Work done on the current thread since the counters were last taken
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FrameStats {
+    /// Number of times the schedules of an entity's components were scanned to find the next
+    /// component to tick
+    pub schedule_scans: u64,
+    /// Number of component schedules visited during scans
+    pub schedules_visited: u64,
+    /// Number of times a component was ticked
+    pub ticks: u64,
+}
+
+/// Counters of the work done by a single component since it was inserted. Not serialized.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ComponentStats {
+    /// Number of times the component ticked
+    pub ticks: u64,
+    /// Total simulated time the component's schedule has advanced by
+    pub simulated_time: Duration,
+    /// Wall-clock time at which the component last ticked
+    pub last_tick: Option<Instant>,
+}
+
+impl ComponentStats {
+    /// The mean simulated time between ticks, or `None` if the component hasn't ticked
+    pub fn mean_period(&self) -> Option<Duration> {
+        if self.ticks == 0 {
+            None
+        } else {
+            Some(Duration::from_nanos(
+                (self.simulated_time.as_nanos() / self.ticks as u128) as u64,
+            ))
+        }
+    }
+}
+
+thread_local! {
+    static FRAME_STATS: Cell<FrameStats> = Cell::new(FrameStats::default());
+}
+
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+
+/// Return the counters for the current thread, resetting them to zero
+pub fn take() -> FrameStats {
+    FRAME_STATS.with(|stats| stats.take())
+}
+
+#[cfg(feature = "stats")]
+fn update(f: impl FnOnce(&mut FrameStats)) {
+    FRAME_STATS.with(|stats| {
+        let mut current = stats.get();
+        f(&mut current);
+        stats.set(current);
+    });
+}
+
+#[doc(hidden)]
+#[inline]
+pub fn record_schedule_scan(_num_schedules: usize) {
+    #[cfg(feature = "stats")]
+    update(|stats| {
+        stats.schedule_scans += 1;
+        stats.schedules_visited += _num_schedules as u64;
+    });
+}
+
+#[doc(hidden)]
+#[inline]
+pub fn record_tick() {
+    #[cfg(feature = "stats")]
+    update(|stats| stats.ticks += 1);
+}
+
+/// Global allocator which counts allocations, for measuring the allocations made per frame.
+/// Install it with `#[global_allocator]` and read the count with `take_allocations`.
+#[derive(Debug, Default)]
+pub struct CountingAllocator<A = System>(pub A);
+
+unsafe impl<A: GlobalAlloc> GlobalAlloc for CountingAllocator<A> {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        self.0.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        self.0.dealloc(ptr, layout)
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        self.0.alloc_zeroed(layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        self.0.realloc(ptr, layout, new_size)
+    }
+}
+
+/// Return the number of allocations made on any thread through a `CountingAllocator`,
+/// resetting the count to zero
+pub fn take_allocations() -> u64 {
+    ALLOCATIONS.swap(0, Ordering::Relaxed)
+}
@@ -0,0 +1,197 @@
+//! Utilities for testing realtime components
+
+use crate::{
+    begin_entity_frame, ContextContainsRealtimeComponents, Entity, RealtimeComponent,
+    RealtimeComponents, RealtimeEntityEvents, ScheduledRealtimeComponent,
+};
+use std::fmt::{Debug, Write};
+use std::time::Duration;
+
+/// An event applied during a headless simulation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SimulationLogEntry {
+    /// Simulated time since the start of the simulation at which the event was applied
+    pub time: Duration,
+    pub entity: Entity,
+    /// Name of the field of the component which produced the event
+    pub component: &'static str,
+}
+
+/// Run a context's realtime components for `duration` of simulated time, in frames of
+/// `frame_duration` (the final frame may be shorter), without a game loop. The context's frame
+/// hooks apply as in `process_entity_frame`. Returns every event in the order it was applied.
+pub fn simulate<C: ContextContainsRealtimeComponents>(
+    context: &mut C,
+    duration: Duration,
+    frame_duration: Duration,
+) -> Vec<SimulationLogEntry> {
+    assert!(
+        frame_duration > Duration::from_millis(0),
+        "frame duration must be non-zero"
+    );
+    let component_names =
+        <<C::Components as RealtimeComponents<C>>::EntityEvents as RealtimeEntityEvents<C>>::COMPONENT_NAMES;
+    let mut log = Vec::new();
+    let mut entities = Vec::new();
+    let mut frame_start = Duration::from_millis(0);
+    while frame_start < duration {
+        let frame_duration = frame_duration.min(duration - frame_start);
+        entities.extend(context.realtime_entities());
+        for entity in entities.drain(..) {
+            if !context.components_mut().contains_entity(entity) {
+                continue;
+            }
+            let entity_frame_duration = begin_entity_frame(entity, frame_duration, context);
+            let mut frame_remaining = entity_frame_duration;
+            while frame_remaining > Duration::from_millis(0) {
+                let (events, until_next_tick) = context
+                    .components_mut()
+                    .tick_entity(entity, frame_remaining);
+                frame_remaining -= until_next_tick;
+                let time = frame_start + (entity_frame_duration - frame_remaining);
+                log.extend(events.ticked().iter().map(|index| SimulationLogEntry {
+                    time,
+                    entity,
+                    component: component_names[index],
+                }));
+                if !events.is_empty() {
+                    context.before_tick(entity);
+                }
+                events.apply(entity, context);
+            }
+            context.after_entity_frame(entity);
+        }
+        frame_start += frame_duration;
+    }
+    log
+}
+
+/// Run a single component for `duration` of simulated time, starting `initial_delay` before
+/// its first tick. Returns each event along with the simulated time at which it occurred,
+/// including events which occur exactly at the end of the duration. The component is ticked on
+/// behalf of a newly allocated entity.
+pub fn run_component<T: RealtimeComponent>(
+    component: T,
+    initial_delay: Duration,
+    duration: Duration,
+) -> Vec<(Duration, T::Event)> {
+    let entity = entity_table::EntityAllocator::default().alloc();
+    let mut scheduled = ScheduledRealtimeComponent::new(component, initial_delay);
+    let mut timeline = Vec::new();
+    let mut time = Duration::from_millis(0);
+    while scheduled.until_next_tick() <= duration - time {
+        time += scheduled.until_next_tick();
+        timeline.push((time, scheduled.tick(entity)));
+    }
+    timeline
+}
+
+/// Assert that a sequence of (time, event) pairs (e.g. from `run_component`) matches the
+/// expected sequence exactly. On mismatch, panics with a line-by-line diff in which missing
+/// entries are prefixed with `-` and unexpected entries with `+`.
+#[track_caller]
+pub fn assert_timeline<E: PartialEq + Debug>(actual: &[(Duration, E)], expected: &[(Duration, E)]) {
+    if actual != expected {
+        panic!(
+            "timeline doesn't match expectation:\n{}",
+            timeline_diff(actual, expected)
+        );
+    }
+}
+
+/// Longest-common-subsequence diff of the entries of two timelines
+fn timeline_diff<E: PartialEq + Debug>(
+    actual: &[(Duration, E)],
+    expected: &[(Duration, E)],
+) -> String {
+    let (n, m) = (expected.len(), actual.len());
+    // lcs[i][j] is the length of the longest common subsequence of expected[i..] and actual[j..]
+    let mut lcs = vec![vec![0usize; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[i][j] = if expected[i] == actual[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+    let mut diff = String::new();
+    let (mut i, mut j) = (0, 0);
+    while i < n || j < m {
+        if i < n && j < m && expected[i] == actual[j] {
+            let _ = writeln!(diff, "  {:?}: {:?}", expected[i].0, expected[i].1);
+            i += 1;
+            j += 1;
+        } else if j == m || (i < n && lcs[i + 1][j] >= lcs[i][j + 1]) {
+            let _ = writeln!(diff, "- {:?}: {:?}", expected[i].0, expected[i].1);
+            i += 1;
+        } else {
+            let _ = writeln!(diff, "+ {:?}: {:?}", actual[j].0, actual[j].1);
+            j += 1;
+        }
+    }
+    diff
+}
+
+/// `proptest` strategies for generating schedules, tables and frame durations, for
+/// property-testing realtime components
+#[cfg(feature = "proptest")]
+pub mod strategies {
+    use crate::{RealtimeComponent, RealtimeComponentTable, ScheduledRealtimeComponent};
+    use entity_table::EntityAllocator;
+    use proptest::collection::{self, SizeRange};
+    use proptest::prelude::*;
+    use std::fmt::Debug;
+    use std::time::Duration;
+
+    /// Durations between zero and `max` inclusive
+    pub fn duration(max: Duration) -> impl Strategy<Value = Duration> {
+        (0..=max.as_nanos().min(u64::MAX as u128) as u64).prop_map(Duration::from_nanos)
+    }
+
+    /// Sequences of frame durations, each between zero and `max_frame_duration` inclusive
+    pub fn frame_durations(
+        max_frame_duration: Duration,
+        len: impl Into<SizeRange>,
+    ) -> impl Strategy<Value = Vec<Duration>> {
+        collection::vec(duration(max_frame_duration), len)
+    }
+
+    /// Components from `component` with a time until their next tick between zero and
+    /// `max_until_next_tick` inclusive
+    pub fn scheduled_component<T, S>(
+        component: S,
+        max_until_next_tick: Duration,
+    ) -> impl Strategy<Value = ScheduledRealtimeComponent<T>>
+    where
+        T: RealtimeComponent + Debug,
+        S: Strategy<Value = T>,
+    {
+        (component, duration(max_until_next_tick)).prop_map(|(component, until_next_tick)| {
+            ScheduledRealtimeComponent::new(component, until_next_tick)
+        })
+    }
+
+    /// Tables of scheduled components, each associated with a distinct entity
+    pub fn table<T, S>(
+        component: S,
+        max_until_next_tick: Duration,
+        len: impl Into<SizeRange>,
+    ) -> impl Strategy<Value = RealtimeComponentTable<T>>
+    where
+        T: RealtimeComponent + Debug,
+        S: Strategy<Value = T>,
+    {
+        collection::vec(scheduled_component(component, max_until_next_tick), len).prop_map(
+            |scheduled_components| {
+                let mut entity_allocator = EntityAllocator::default();
+                let mut table = RealtimeComponentTable::default();
+                for scheduled in scheduled_components {
+                    table.insert_with_schedule(entity_allocator.alloc(), scheduled);
+                }
+                table
+            },
+        )
+    }
+}
@@ -0,0 +1,233 @@
+//! A realtime component which emits a list of payloads at fixed offsets from when it starts,
+//! for scripted effects such as cutscenes and screen shakes
+
+use crate::combinators::FiniteRealtimeComponent;
+use crate::{RealtimeComponent, ScheduledRealtimeComponent};
+#[cfg(feature = "serialize")]
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Event of a `Timeline`
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TimelineEvent<P> {
+    /// The payload of a keyframe whose offset has been reached
+    Keyframe(P),
+    /// The end of the timeline was reached for the last time. The component should be removed
+    /// when applying this event, otherwise it repeats the event every time the timeline's
+    /// length passes.
+    Finished,
+}
+
+/// How many times a `Timeline` plays
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TimelineLoop {
+    Once,
+    /// Play the given number of times in total. `Times(0)` plays once.
+    Times(u32),
+    /// Never finish
+    Forever,
+}
+
+/// Emits the payload of each keyframe at its offset, and finishes once its length has passed.
+/// Keyframes with the same offset are emitted in the order they were given, by consecutive
+/// ticks with no time between them. Insert the result of `scheduled` so the first keyframe is
+/// emitted at its offset rather than immediately.
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Timeline<P> {
+    keyframes: Vec<(Duration, P)>,
+    length: Duration,
+    looping: TimelineLoop,
+    /// Index of the next keyframe to emit, or the number of keyframes if the next tick finishes
+    /// the timeline
+    next: usize,
+    /// Number of times the timeline has started playing
+    plays: u32,
+}
+
+impl<P: Clone> Timeline<P> {
+    /// The length of the timeline defaults to the offset of its last keyframe
+    pub fn new(mut keyframes: Vec<(Duration, P)>) -> Self {
+        keyframes.sort_by_key(|&(offset, _)| offset);
+        let length = keyframes
+            .last()
+            .map(|&(offset, _)| offset)
+            .unwrap_or_default();
+        Self {
+            keyframes,
+            length,
+            looping: TimelineLoop::Once,
+            next: 0,
+            plays: 1,
+        }
+    }
+
+    /// Set the time after the start at which the timeline finishes, or restarts if it loops.
+    /// Lengths before the last keyframe are extended to it.
+    pub fn with_length(self, length: Duration) -> Self {
+        let last_offset = self
+            .keyframes
+            .last()
+            .map(|&(offset, _)| offset)
+            .unwrap_or_default();
+        Self {
+            length: length.max(last_offset),
+            ..self
+        }
+    }
+
+    /// Timelines without keyframes or with zero length play once regardless of `looping`
+    pub fn with_loop(self, looping: TimelineLoop) -> Self {
+        Self { looping, ..self }
+    }
+
+    /// Schedule the timeline such that it starts when inserted
+    pub fn scheduled(self) -> ScheduledRealtimeComponent<Self> {
+        let until_first_tick = self.offset_of(self.next);
+        ScheduledRealtimeComponent::new(self, until_first_tick)
+    }
+
+    pub fn length(&self) -> Duration {
+        self.length
+    }
+
+    pub fn keyframes(&self) -> &[(Duration, P)] {
+        &self.keyframes
+    }
+
+    fn offset_of(&self, index: usize) -> Duration {
+        self.keyframes
+            .get(index)
+            .map(|&(offset, _)| offset)
+            .unwrap_or(self.length)
+    }
+
+    fn restarts(&self) -> bool {
+        // restarting a zero-length timeline would emit keyframes forever without time passing
+        !self.keyframes.is_empty()
+            && !self.length.is_zero()
+            && match self.looping {
+                TimelineLoop::Once => false,
+                TimelineLoop::Times(times) => self.plays < times,
+                TimelineLoop::Forever => true,
+            }
+    }
+}
+
+impl<P: Clone> RealtimeComponent for Timeline<P> {
+    type Event = TimelineEvent<P>;
+
+    fn tick(&mut self) -> (Self::Event, Duration) {
+        let (offset, payload) = match self.keyframes.get(self.next) {
+            Some((offset, payload)) => (*offset, payload.clone()),
+            None => {
+                return (
+                    TimelineEvent::Finished,
+                    self.length.max(Duration::from_nanos(1)),
+                )
+            }
+        };
+        self.next += 1;
+        let until_next_tick = if self.next == self.keyframes.len() && self.restarts() {
+            self.next = 0;
+            self.plays = self.plays.saturating_add(1);
+            self.length - offset + self.offset_of(0)
+        } else {
+            self.offset_of(self.next) - offset
+        };
+        (TimelineEvent::Keyframe(payload), until_next_tick)
+    }
+}
+
+/// Finished once the last keyframe of the last play has been emitted, so the `Finished` event
+/// is never emitted when used with combinators
+impl<P: Clone> FiniteRealtimeComponent for Timeline<P> {
+    fn is_finished(&self) -> bool {
+        self.next == self.keyframes.len()
+    }
+}
+
+/// Builds a `Timeline` from individual keyframes and repeating runs of keyframes. See also the
+/// `timeline!` macro.
+#[derive(Debug, Clone)]
+pub struct TimelineBuilder<P> {
+    keyframes: Vec<(Duration, P)>,
+}
+
+impl<P> Default for TimelineBuilder<P> {
+    fn default() -> Self {
+        Self {
+            keyframes: Vec::new(),
+        }
+    }
+}
+
+impl<P: Clone> TimelineBuilder<P> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a keyframe at `offset`
+    pub fn at(mut self, offset: Duration, payload: P) -> Self {
+        self.keyframes.push((offset, payload));
+        self
+    }
+
+    /// Add a keyframe every `period` from `start` until `duration` after `start`, not including
+    /// the end. Panics if `period` is zero.
+    pub fn every(
+        mut self,
+        start: Duration,
+        period: Duration,
+        duration: Duration,
+        payload: P,
+    ) -> Self {
+        assert!(!period.is_zero(), "period must be non-zero");
+        let mut elapsed = Duration::from_nanos(0);
+        while elapsed < duration {
+            self.keyframes.push((start + elapsed, payload.clone()));
+            elapsed += period;
+        }
+        self
+    }
+
+    pub fn build(self) -> Timeline<P> {
+        Timeline::new(self.keyframes)
+    }
+}
+
+/// Build a `Timeline` from a list of entries separated by commas. Each entry is either a
+/// keyframe at an offset, or a keyframe repeated every period for a duration, optionally
+/// starting from an offset other than zero. Durations are written as integers followed by one
+/// of the units `ns`, `us`, `ms` or `s`.
+///
+/// ```ignore
+/// let explosion = timeline! {
+///     at 0ms => Effect::Flash,
+///     at 150ms => Effect::Boom,
+///     every 50ms for 1s => Effect::Spark,
+///     every 100ms for 500ms from 1s => Effect::Smoke,
+/// };
+/// ```
+#[macro_export]
+macro_rules! timeline {
+    (@entries $builder:expr;) => {
+        $builder.build()
+    };
+    (@entries $builder:expr; at $offset:literal => $payload:expr $(, $($rest:tt)*)?) => {
+        $crate::timeline!(@entries $builder.at($crate::realtime_initial_delay!($offset), $payload); $($($rest)*)?)
+    };
+    (@entries $builder:expr; every $period:literal for $duration:literal $(from $start:literal)? => $payload:expr $(, $($rest:tt)*)?) => {
+        $crate::timeline!(@entries $builder.every(
+            $crate::realtime_initial_delay!($($start)?),
+            $crate::realtime_initial_delay!($period),
+            $crate::realtime_initial_delay!($duration),
+            $payload,
+        ); $($($rest)*)?)
+    };
+    { $($entries:tt)* } => {
+        $crate::timeline!(@entries $crate::timeline::TimelineBuilder::new(); $($entries)*)
+    };
+}
@@ -0,0 +1,130 @@
+//! Wall-clock watchdog which reports individual component ticks and event applications that
+//! take longer than a threshold, enabled by the `watchdog` feature. Without the feature nothing
+//! is measured and installing a callback does nothing.
+
+use entity_table::Entity;
+#[cfg(feature = "watchdog")]
+use std::cell::RefCell;
+use std::time::Duration;
+
+/// The part of a component's work which was measured
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TickPhase {
+    /// `RealtimeComponent::tick`
+    Tick,
+    /// `RealtimeComponentApplyEvent::apply_event`
+    ApplyEvent,
+}
+
+/// A single tick or event application which exceeded the watchdog's threshold
+#[derive(Debug, Clone, Copy)]
+pub struct SlowTick {
+    /// Type name of the component
+    pub component: &'static str,
+    pub phase: TickPhase,
+    /// The entity whose component was ticked or whose event was being applied. This is `None`
+    /// for components ticked outside of a table.
+    pub entity: Option<Entity>,
+    /// Wall-clock time taken
+    pub duration: Duration,
+}
+
+#[cfg(feature = "watchdog")]
+struct Watchdog {
+    threshold: Duration,
+    callback: Box<dyn FnMut(&SlowTick)>,
+}
+
+#[cfg(feature = "watchdog")]
+thread_local! {
+    static WATCHDOG: RefCell<Option<Watchdog>> = const { RefCell::new(None) };
+}
+
+/// Call `callback` for each tick or event application on the current thread which takes longer
+/// than `threshold`, replacing any previously installed callback
+pub fn install(threshold: Duration, callback: impl FnMut(&SlowTick) + 'static) {
+    #[cfg(feature = "watchdog")]
+    WATCHDOG.with(|watchdog| {
+        *watchdog.borrow_mut() = Some(Watchdog {
+            threshold,
+            callback: Box::new(callback),
+        })
+    });
+    #[cfg(not(feature = "watchdog"))]
+    let _ = (threshold, callback);
+}
+
+/// Stop watching ticks on the current thread
+pub fn uninstall() {
+    #[cfg(feature = "watchdog")]
+    WATCHDOG.with(|watchdog| *watchdog.borrow_mut() = None);
+}
+
+/// Callback for `install` which logs slow ticks as warnings
+#[cfg(feature = "log")]
+pub fn log_slow_tick(slow_tick: &SlowTick) {
+    match slow_tick.entity {
+        Some(entity) => log::warn!(
+            "{:?} of {} for {:?} took {:?}",
+            slow_tick.phase,
+            slow_tick.component,
+            entity,
+            slow_tick.duration
+        ),
+        None => log::warn!(
+            "{:?} of {} took {:?}",
+            slow_tick.phase,
+            slow_tick.component,
+            slow_tick.duration
+        ),
+    }
+}
+
+#[cfg(feature = "watchdog")]
+fn threshold() -> Option<Duration> {
+    WATCHDOG.with(|watchdog| {
+        watchdog
+            .borrow()
+            .as_ref()
+            .map(|watchdog| watchdog.threshold)
+    })
+}
+
+#[cfg(feature = "watchdog")]
+fn report(slow_tick: SlowTick) {
+    // The callback is taken out for the duration of the call so it may install a replacement
+    let watchdog = WATCHDOG.with(|watchdog| watchdog.borrow_mut().take());
+    if let Some(mut watchdog) = watchdog {
+        (watchdog.callback)(&slow_tick);
+        WATCHDOG.with(|current| {
+            current.borrow_mut().get_or_insert(watchdog);
+        });
+    }
+}
+
+#[doc(hidden)]
+#[inline]
+pub fn watch<R>(
+    phase: TickPhase,
+    component: &'static str,
+    entity: Option<Entity>,
+    f: impl FnOnce() -> R,
+) -> R {
+    #[cfg(feature = "watchdog")]
+    if let Some(threshold) = threshold() {
+        let start = std::time::Instant::now();
+        let result = f();
+        let duration = start.elapsed();
+        if duration > threshold {
+            report(SlowTick {
+                component,
+                phase,
+                entity,
+                duration,
+            });
+        }
+        return result;
+    }
+    let _ = (phase, component, entity);
+    f()
+}
@@ -0,0 +1,119 @@
+use entity_table::{Entity, EntityAllocator};
+use entity_table_realtime::combinators::{
+    Alternate, Chain, CombinedEvent, FiniteRealtimeComponent, Repeat,
+};
+use entity_table_realtime::composite::Composite;
+use entity_table_realtime::rate_limit::{Debounce, Throttle};
+use entity_table_realtime::{RealtimeComponent, ScheduledRealtimeComponent, TickCtx};
+use std::time::Duration;
+
+/// Produces the entity it was ticked for, which is only known when ticked with `tick_with`
+#[derive(Debug, Clone)]
+struct NeedsEntity {
+    ticks_remaining: u32,
+}
+
+impl NeedsEntity {
+    fn new(ticks: u32) -> Self {
+        Self {
+            ticks_remaining: ticks,
+        }
+    }
+}
+
+impl RealtimeComponent for NeedsEntity {
+    type Event = Option<Entity>;
+
+    fn tick(&mut self) -> (Self::Event, Duration) {
+        self.ticks_remaining -= 1;
+        (None, Duration::from_millis(10))
+    }
+
+    fn tick_with(&mut self, context: &TickCtx) -> (Self::Event, Duration) {
+        self.ticks_remaining -= 1;
+        (Some(context.entity), Duration::from_millis(10))
+    }
+}
+
+impl FiniteRealtimeComponent for NeedsEntity {
+    fn is_finished(&self) -> bool {
+        self.ticks_remaining == 0
+    }
+}
+
+fn tick_scheduled<T: RealtimeComponent>(
+    component: T,
+    entity: Entity,
+    num_ticks: usize,
+) -> Vec<T::Event> {
+    let mut scheduled = ScheduledRealtimeComponent::new(component, Duration::ZERO);
+    (0..num_ticks)
+        .map(|_| {
+            let event = scheduled.tick(entity);
+            scheduled.advance_nanos(scheduled.until_next_tick_nanos());
+            event
+        })
+        .collect()
+}
+
+#[test]
+fn combinators_forward_tick_with() {
+    let entity = EntityAllocator::default().alloc();
+    let chain = tick_scheduled(
+        Chain::new(NeedsEntity::new(1), NeedsEntity::new(1)),
+        entity,
+        2,
+    );
+    assert_eq!(
+        chain,
+        vec![
+            CombinedEvent::First(Some(entity)),
+            CombinedEvent::Second(Some(entity))
+        ]
+    );
+    let repeat = tick_scheduled(Repeat::new(NeedsEntity::new(1), 2), entity, 2);
+    assert_eq!(repeat, vec![Some(entity), Some(entity)]);
+    let alternate = tick_scheduled(
+        Alternate::new(NeedsEntity::new(1), NeedsEntity::new(1)),
+        entity,
+        3,
+    );
+    assert_eq!(
+        alternate,
+        vec![
+            CombinedEvent::First(Some(entity)),
+            CombinedEvent::Second(Some(entity)),
+            CombinedEvent::First(Some(entity)),
+        ]
+    );
+}
+
+#[test]
+fn composite_forwards_tick_with() {
+    let entity = EntityAllocator::default().alloc();
+    let mut composite = Composite::scheduled(vec![
+        (Duration::ZERO, NeedsEntity::new(u32::MAX)),
+        (Duration::from_millis(5), NeedsEntity::new(u32::MAX)),
+    ]);
+    for _ in 0..4 {
+        assert_eq!(composite.tick(entity), Some(entity));
+        composite.advance_nanos(composite.until_next_tick_nanos());
+    }
+}
+
+#[test]
+fn rate_limiters_forward_tick_with() {
+    let entity = EntityAllocator::default().alloc();
+    let throttle = tick_scheduled(
+        Throttle::new(NeedsEntity::new(u32::MAX), Duration::ZERO),
+        entity,
+        2,
+    );
+    assert_eq!(throttle, vec![Some(Some(entity)), Some(Some(entity))]);
+    let debounce = tick_scheduled(
+        Debounce::new(NeedsEntity::new(1), Duration::from_millis(5)),
+        entity,
+        2,
+    );
+    assert_eq!(debounce, vec![None, Some(Some(entity))]);
+}
@@ -0,0 +1,118 @@
+use entity_table::{Entity, EntityAllocator};
+use entity_table_realtime::{
+    RealtimeComponent, RealtimeComponentApplyEvent, RealtimeComponentTable,
+    ScheduledRealtimeComponent,
+};
+use std::time::Duration;
+
+/// Counts its ticks, catching up by ticking as `RealtimeComponent::skip` does by default
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Default)]
+struct Counter(u32);
+
+impl RealtimeComponent for Counter {
+    type Event = u32;
+
+    fn tick(&mut self) -> (Self::Event, Duration) {
+        self.0 += 1;
+        (self.0, Duration::from_millis(10))
+    }
+}
+
+impl RealtimeComponentApplyEvent<()> for Counter {
+    fn apply_event(_event: u32, _entity: Entity, _context: &mut ()) {}
+}
+
+/// Records the time it was told it missed rather than catching up by ticking
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Default)]
+struct Ambient {
+    missed: Vec<Duration>,
+    num_ticks: u32,
+}
+
+impl RealtimeComponent for Ambient {
+    type Event = ();
+
+    fn tick(&mut self) -> (Self::Event, Duration) {
+        self.num_ticks += 1;
+        ((), Duration::from_millis(10))
+    }
+
+    fn skip(&mut self, missed: Duration) -> Duration {
+        self.missed.push(missed);
+        Duration::from_millis(3)
+    }
+}
+
+impl RealtimeComponentApplyEvent<()> for Ambient {
+    fn apply_event(_event: (), _entity: Entity, _context: &mut ()) {}
+}
+
+entity_table_realtime::declare_realtime_entity_module! {
+    components[()] {
+        counter: Counter,
+        ambient: Ambient,
+    }
+}
+
+#[test]
+fn components_are_told_how_much_time_they_missed() {
+    let entity = EntityAllocator::default().alloc();
+    let mut table = RealtimeComponentTable::default();
+    table.insert_with_schedule(
+        entity,
+        ScheduledRealtimeComponent::new(Ambient::default(), Duration::from_millis(4)),
+    );
+    // not yet due, so nothing is missed
+    table.advance_silently(Duration::from_millis(3));
+    let scheduled = table.get_with_schedule(entity).unwrap();
+    assert!(scheduled.component.missed.is_empty());
+    assert_eq!(scheduled.until_next_tick(), Duration::from_millis(1));
+    table.advance_silently(Duration::from_millis(250));
+    let scheduled = table.get_with_schedule(entity).unwrap();
+    assert_eq!(scheduled.component.missed, vec![Duration::from_millis(249)]);
+    assert_eq!(scheduled.component.num_ticks, 0);
+    assert_eq!(scheduled.until_next_tick(), Duration::from_millis(3));
+    assert_eq!(table.simulated_time(), Duration::from_millis(253));
+}
+
+#[test]
+fn default_skip_catches_up_by_discarding_ticks() {
+    let entity = EntityAllocator::default().alloc();
+    let mut table = RealtimeComponentTable::default();
+    table.insert(entity, Counter::default());
+    table.advance_silently(Duration::from_millis(35));
+    let scheduled = table.get_with_schedule(entity).unwrap();
+    // ticks at 0ms, 10ms, 20ms and 30ms
+    assert_eq!(scheduled.component.0, 4);
+    assert_eq!(scheduled.until_next_tick(), Duration::from_millis(5));
+}
+
+#[test]
+fn module_advances_every_table_silently() {
+    let mut allocator = EntityAllocator::default();
+    let (e1, e2) = (allocator.alloc(), allocator.alloc());
+    let mut components = components::RealtimeComponents::default();
+    components.counter.insert(e1, Counter::default());
+    components.ambient.insert(e1, Ambient::default());
+    components.ambient.insert(e2, Ambient::default());
+    components
+        .ambient
+        .get_with_schedule_mut(e2)
+        .unwrap()
+        .set_enabled(false);
+    components.advance_silently(Duration::from_millis(20));
+    assert_eq!(components.counter.get(e1).unwrap().0, 3);
+    assert_eq!(
+        components.ambient.get(e1).unwrap().missed,
+        vec![Duration::from_millis(20)]
+    );
+    // disabled components don't miss anything
+    assert!(components.ambient.get(e2).unwrap().missed.is_empty());
+    // the next frame carries on from the skipped schedules without replaying them
+    let events = components.fast_forward(Duration::from_millis(10));
+    assert_eq!(events.len(), 2);
+    assert_eq!(events[0].0, Duration::from_millis(3));
+    assert_eq!(events[1].0, Duration::from_millis(10));
+}
@@ -0,0 +1,133 @@
+use entity_table::{ComponentTable, Entities, Entity, EntityAllocator};
+use entity_table_realtime::audio::{process_entity_frame_with_audio_cues, AudioCue};
+use entity_table_realtime::{
+    ContextContainsRealtimeComponents, RealtimeComponent, RealtimeComponentApplyEvent,
+};
+use std::time::Duration;
+
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Sound {
+    Footstep,
+}
+
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone)]
+struct Periodic(Duration);
+
+impl RealtimeComponent for Periodic {
+    type Event = ();
+
+    fn tick(&mut self) -> (Self::Event, Duration) {
+        ((), self.0)
+    }
+}
+
+impl RealtimeComponentApplyEvent<Context> for Periodic {
+    fn apply_event(_event: (), _entity: Entity, context: &mut Context) {
+        context.num_events += 1;
+    }
+}
+
+entity_table_realtime::declare_realtime_entity_module! {
+    components[Context] {
+        footsteps: Periodic,
+        breathing: Periodic,
+    }
+}
+
+#[derive(Default)]
+struct Context {
+    components: components::RealtimeComponents,
+    entities: ComponentTable<()>,
+    num_events: usize,
+}
+
+impl ContextContainsRealtimeComponents for Context {
+    type Components = components::RealtimeComponents;
+    fn components_mut(&mut self) -> &mut Self::Components {
+        &mut self.components
+    }
+    fn realtime_entities(&self) -> Entities<'_> {
+        self.entities.entities()
+    }
+}
+
+fn context(entity: Entity) -> Context {
+    let mut context = Context::default();
+    context.entities.insert(entity, ());
+    context
+        .components
+        .footsteps
+        .insert(entity, Periodic(Duration::from_millis(10)));
+    context
+        .components
+        .breathing
+        .insert(entity, Periodic(Duration::from_millis(40)));
+    context
+}
+
+/// Only footsteps make a sound
+fn footstep_cue(event: &components::RealtimeEntityEvent) -> Option<Sound> {
+    match event {
+        components::RealtimeEntityEvent::footsteps(()) => Some(Sound::Footstep),
+        components::RealtimeEntityEvent::breathing(()) => None,
+    }
+}
+
+#[test]
+fn cues_are_stamped_with_the_time_of_their_event_plus_latency() {
+    let entity = EntityAllocator::default().alloc();
+    let mut context = context(entity);
+    let mut cues = Vec::new();
+    let ms = Duration::from_millis;
+    process_entity_frame_with_audio_cues(
+        entity,
+        ms(25),
+        ms(50),
+        &mut context,
+        footstep_cue,
+        &mut cues,
+    );
+    let cue = |play_at| AudioCue {
+        entity,
+        cue: Sound::Footstep,
+        play_at,
+    };
+    // footsteps at 0ms, 10ms and 20ms into a frame which finished at 25ms
+    assert_eq!(cues, vec![cue(ms(25)), cue(ms(35)), cue(ms(45))]);
+    // events without cues are still applied
+    assert_eq!(context.num_events, 4);
+    // the spacing of cues doesn't depend on how time is divided into frames
+    cues.clear();
+    for _ in 0..3 {
+        process_entity_frame_with_audio_cues(
+            entity,
+            ms(5),
+            ms(50),
+            &mut context,
+            footstep_cue,
+            &mut cues,
+        );
+    }
+    // footsteps at 30ms and 40ms, in frames finishing at 30ms and 40ms
+    assert_eq!(cues, vec![cue(ms(50)), cue(ms(50))]);
+}
+
+#[test]
+fn cues_whose_time_has_passed_play_immediately() {
+    let entity = EntityAllocator::default().alloc();
+    let mut context = context(entity);
+    let mut cues = Vec::new();
+    let ms = Duration::from_millis;
+    process_entity_frame_with_audio_cues(
+        entity,
+        ms(25),
+        ms(10),
+        &mut context,
+        footstep_cue,
+        &mut cues,
+    );
+    let play_at = cues.iter().map(|cue| cue.play_at).collect::<Vec<_>>();
+    assert_eq!(play_at, vec![ms(0), ms(0), ms(5)]);
+}
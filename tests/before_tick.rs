@@ -0,0 +1,125 @@
+use entity_table::{ComponentTable, Entities, Entity, EntityAllocator};
+use entity_table_realtime::{
+    process_entity_frame, ContextContainsRealtimeComponents, RealtimeComponent,
+    RealtimeComponentApplyEvent,
+};
+use std::time::Duration;
+
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy)]
+enum Effect {
+    Flicker,
+    Poison,
+}
+
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone)]
+struct Periodic {
+    effect: Effect,
+    period: Duration,
+}
+
+impl RealtimeComponent for Periodic {
+    type Event = Effect;
+
+    fn tick(&mut self) -> (Self::Event, Duration) {
+        (self.effect, self.period)
+    }
+}
+
+impl RealtimeComponentApplyEvent<Context> for Periodic {
+    fn apply_event(effect: Effect, _entity: Entity, context: &mut Context) {
+        let position = context.position.expect("before_tick wasn't called");
+        context.log.push(format!("{effect:?} at {position}"));
+    }
+}
+
+entity_table_realtime::declare_realtime_entity_module! {
+    components[Context] {
+        flicker: Periodic,
+        poison: Periodic,
+    }
+}
+
+#[derive(Default)]
+struct Context {
+    components: components::RealtimeComponents,
+    entities: ComponentTable<()>,
+    positions: ComponentTable<u32>,
+    /// The position of the entity whose components are ticking, looked up once per tick
+    position: Option<u32>,
+    log: Vec<String>,
+}
+
+impl ContextContainsRealtimeComponents for Context {
+    type Components = components::RealtimeComponents;
+    fn components_mut(&mut self) -> &mut Self::Components {
+        &mut self.components
+    }
+    fn realtime_entities(&self) -> Entities<'_> {
+        self.entities.entities()
+    }
+    fn before_tick(&mut self, entity: Entity) {
+        self.position = self.positions.get(entity).copied();
+        self.log.push("before_tick".to_string());
+    }
+}
+
+fn periodic(effect: Effect, millis: u64) -> Periodic {
+    Periodic {
+        effect,
+        period: Duration::from_millis(millis),
+    }
+}
+
+#[test]
+fn before_tick_is_called_once_per_tick_before_events_are_applied() {
+    let mut allocator = EntityAllocator::default();
+    let (e1, e2) = (allocator.alloc(), allocator.alloc());
+    let mut context = Context::default();
+    for (entity, position) in [(e1, 1), (e2, 2)] {
+        context.entities.insert(entity, ());
+        context.positions.insert(entity, position);
+        context
+            .components
+            .flicker
+            .insert(entity, periodic(Effect::Flicker, 10));
+    }
+    context
+        .components
+        .poison
+        .insert(e1, periodic(Effect::Poison, 15));
+    // both components of e1 tick at 0ms, then flicker at 10ms, then poison at 15ms
+    process_entity_frame(e1, Duration::from_millis(15), &mut context);
+    assert_eq!(
+        context.log,
+        vec![
+            "before_tick",
+            "Flicker at 1",
+            "Poison at 1",
+            "before_tick",
+            "Flicker at 1",
+            "before_tick",
+            "Poison at 1",
+        ]
+    );
+    context.log.clear();
+    process_entity_frame(e2, Duration::from_millis(5), &mut context);
+    assert_eq!(context.log, vec!["before_tick", "Flicker at 2"]);
+}
+
+#[test]
+fn before_tick_is_not_called_for_frames_without_ticks() {
+    let entity = EntityAllocator::default().alloc();
+    let mut context = Context::default();
+    context.entities.insert(entity, ());
+    context.positions.insert(entity, 0);
+    context
+        .components
+        .flicker
+        .insert(entity, periodic(Effect::Flicker, 10));
+    process_entity_frame(entity, Duration::from_millis(1), &mut context);
+    context.log.clear();
+    process_entity_frame(entity, Duration::from_millis(5), &mut context);
+    assert!(context.log.is_empty());
+}
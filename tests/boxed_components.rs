@@ -0,0 +1,138 @@
+// With the serialize feature, the generated types can only hold boxed trait objects which are
+// serialized with typetag
+#![cfg(any(not(feature = "serialize"), feature = "typetag"))]
+
+use entity_table::{Entity, EntityAllocator};
+#[cfg(feature = "typetag")]
+use entity_table_realtime::typetag;
+use entity_table_realtime::{RealtimeComponent, RealtimeComponentApplyEvent};
+use std::fmt::Debug;
+use std::time::Duration;
+
+/// A family of components which all produce the same type of event, stored in a single table
+#[cfg_attr(feature = "typetag", typetag::serde)]
+trait Effect: RealtimeComponent<Event = u32> + Debug {
+    fn clone_box(&self) -> Box<dyn Effect>;
+}
+
+impl Clone for Box<dyn Effect> {
+    fn clone(&self) -> Self {
+        self.clone_box()
+    }
+}
+
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone)]
+struct Flicker;
+
+impl RealtimeComponent for Flicker {
+    type Event = u32;
+
+    fn tick(&mut self) -> (Self::Event, Duration) {
+        (1, Duration::from_millis(10))
+    }
+}
+
+#[cfg_attr(feature = "typetag", typetag::serde)]
+impl Effect for Flicker {
+    fn clone_box(&self) -> Box<dyn Effect> {
+        Box::new(self.clone())
+    }
+}
+
+/// Only ever ticks once, skipping straight to the end of whatever time it misses
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone)]
+struct Burn {
+    damage: u32,
+}
+
+impl RealtimeComponent for Burn {
+    type Event = u32;
+
+    fn tick(&mut self) -> (Self::Event, Duration) {
+        (self.damage, Duration::from_millis(25))
+    }
+
+    fn skip(&mut self, _missed: Duration) -> Duration {
+        self.damage = 0;
+        Duration::from_millis(1)
+    }
+}
+
+#[cfg_attr(feature = "typetag", typetag::serde)]
+impl Effect for Burn {
+    fn clone_box(&self) -> Box<dyn Effect> {
+        Box::new(self.clone())
+    }
+}
+
+impl RealtimeComponentApplyEvent<Vec<u32>> for Box<dyn Effect> {
+    fn apply_event(event: u32, _entity: Entity, context: &mut Vec<u32>) {
+        context.push(event);
+    }
+}
+
+entity_table_realtime::declare_realtime_entity_module! {
+    components[Vec<u32>] {
+        effect: Box<dyn Effect>,
+    }
+}
+
+fn components(e1: Entity, e2: Entity) -> components::RealtimeComponents {
+    let mut components = components::RealtimeComponents::default();
+    components.effect.insert(e1, Box::new(Flicker));
+    components.effect.insert(e2, Box::new(Burn { damage: 7 }));
+    components
+}
+
+#[test]
+fn boxed_components_of_different_types_share_a_table() {
+    let mut allocator = EntityAllocator::default();
+    let (e1, e2) = (allocator.alloc(), allocator.alloc());
+    let mut components = components(e1, e2);
+    let mut events = components
+        .fast_forward(Duration::from_millis(30))
+        .into_iter()
+        .map(|(time, entity, events)| (time, entity, events.effect.unwrap()))
+        .collect::<Vec<_>>();
+    events.sort_by_key(|&(time, entity, _)| (time, entity != e1));
+    let ms = Duration::from_millis;
+    assert_eq!(
+        events,
+        vec![
+            (ms(0), e1, 1),
+            (ms(0), e2, 7),
+            (ms(10), e1, 1),
+            (ms(20), e1, 1),
+            (ms(25), e2, 7),
+            (ms(30), e1, 1),
+        ]
+    );
+}
+
+#[test]
+fn boxed_components_forward_skip() {
+    let mut allocator = EntityAllocator::default();
+    let (e1, e2) = (allocator.alloc(), allocator.alloc());
+    let mut components = components(e1, e2);
+    components.advance_silently(Duration::from_millis(30));
+    assert_eq!(
+        components.effect.time_to_due(e2),
+        Some(Duration::from_millis(1))
+    );
+    let mut applied = Vec::new();
+    let (events, _) = components.tick_entity(e2, Duration::from_millis(1));
+    events.apply(e2, &mut applied);
+    assert_eq!(applied, vec![0]);
+    // cloning the table clones each boxed component
+    let cloned = components.clone();
+    assert_eq!(format!("{:?}", cloned.effect.get(e1)), "Some(Flicker)");
+}
+
+#[cfg(feature = "typetag")]
+#[test]
+fn boxed_components_are_serializable_with_typetag() {
+    fn assert_serde<T: serde::Serialize + for<'de> serde::Deserialize<'de>>() {}
+    assert_serde::<components::RealtimeComponents>();
+}
@@ -0,0 +1,73 @@
+use entity_table::{ComponentTable, Entities, Entity, EntityAllocator};
+use entity_table_realtime::{
+    process_entity_frame_catch_unwind, ContextContainsRealtimeComponents, RealtimeComponent,
+    RealtimeComponentApplyEvent,
+};
+use std::time::Duration;
+
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone)]
+struct Faulty;
+
+impl RealtimeComponent for Faulty {
+    type Event = ();
+
+    fn tick(&mut self) -> (Self::Event, Duration) {
+        ((), Duration::from_millis(10))
+    }
+}
+
+impl RealtimeComponentApplyEvent<Context> for Faulty {
+    fn apply_event(_event: (), _entity: Entity, _context: &mut Context) {
+        panic!("faulty component");
+    }
+}
+
+entity_table_realtime::declare_realtime_entity_module! {
+    components[Context] {
+        faulty: Faulty,
+    }
+}
+
+#[derive(Default)]
+struct Context {
+    components: components::RealtimeComponents,
+    entities: ComponentTable<()>,
+    frames_ended: Vec<Entity>,
+}
+
+impl ContextContainsRealtimeComponents for Context {
+    type Components = components::RealtimeComponents;
+    fn components_mut(&mut self) -> &mut Self::Components {
+        &mut self.components
+    }
+    fn realtime_entities(&self) -> Entities<'_> {
+        self.entities.entities()
+    }
+
+    fn after_entity_frame(&mut self, entity: Entity) {
+        self.frames_ended.push(entity);
+    }
+}
+
+#[test]
+fn entity_without_components_is_skipped() {
+    let entity = EntityAllocator::default().alloc();
+    let mut context = Context::default();
+    let processed =
+        process_entity_frame_catch_unwind(entity, Duration::from_millis(10), &mut context);
+    assert!(matches!(processed, Ok(false)));
+    assert!(context.frames_ended.is_empty());
+}
+
+#[test]
+fn frame_is_ended_after_a_panic() {
+    let entity = EntityAllocator::default().alloc();
+    let mut context = Context::default();
+    context.components.faulty.insert(entity, Faulty);
+    let panic = process_entity_frame_catch_unwind(entity, Duration::from_millis(10), &mut context)
+        .unwrap_err();
+    assert_eq!(panic.entity, entity);
+    assert_eq!(panic.component, "faulty");
+    assert_eq!(context.frames_ended, vec![entity]);
+}
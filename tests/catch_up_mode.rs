@@ -0,0 +1,113 @@
+use entity_table::{ComponentTable, Entities, Entity, EntityAllocator};
+use entity_table_realtime::scheduler::{process_table_frame, LinearScan};
+use entity_table_realtime::{
+    process_entity_frame, CatchUpMode, ContextContainsRealtimeComponents, RealtimeComponent,
+    RealtimeComponentApplyEvent, RealtimeComponentTable, RealtimeCoordinator,
+};
+use std::time::Duration;
+
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone)]
+struct Periodic;
+
+impl RealtimeComponent for Periodic {
+    type Event = ();
+
+    fn tick(&mut self) -> (Self::Event, Duration) {
+        ((), Duration::from_millis(10))
+    }
+}
+
+impl RealtimeComponentApplyEvent<Context> for Periodic {
+    fn apply_event(_event: (), _entity: Entity, context: &mut Context) {
+        context.num_events += 1;
+    }
+}
+
+entity_table_realtime::declare_realtime_entity_module! {
+    components[Context] {
+        periodic: Periodic,
+    }
+}
+
+struct Context {
+    components: components::RealtimeComponents,
+    table: RealtimeComponentTable<Periodic>,
+    entities: ComponentTable<()>,
+    catch_up_mode: CatchUpMode,
+    num_events: u32,
+}
+
+impl Context {
+    fn new(entity: Entity, catch_up_mode: CatchUpMode) -> Self {
+        let mut context = Self {
+            components: Default::default(),
+            table: Default::default(),
+            entities: Default::default(),
+            catch_up_mode,
+            num_events: 0,
+        };
+        context.entities.insert(entity, ());
+        context.components.periodic.insert(entity, Periodic);
+        context.table.insert(entity, Periodic);
+        context
+    }
+}
+
+impl ContextContainsRealtimeComponents for Context {
+    type Components = components::RealtimeComponents;
+    fn components_mut(&mut self) -> &mut Self::Components {
+        &mut self.components
+    }
+    fn realtime_entities(&self) -> Entities<'_> {
+        self.entities.entities()
+    }
+
+    fn realtime_catch_up_mode(&self, _entity: Entity) -> CatchUpMode {
+        self.catch_up_mode
+    }
+}
+
+const FRAME: Duration = Duration::from_millis(100);
+const CLAMP_TOTAL: CatchUpMode = CatchUpMode::ClampTotal(Duration::from_millis(25));
+
+#[test]
+fn catch_up_mode_is_chosen_by_each_context() {
+    let entity = EntityAllocator::default().alloc();
+    let mut replay_all = Context::new(entity, CatchUpMode::ReplayAll);
+    let mut clamp_total = Context::new(entity, CLAMP_TOTAL);
+    process_entity_frame(entity, FRAME, &mut clamp_total);
+    process_entity_frame(entity, FRAME, &mut replay_all);
+    assert_eq!(clamp_total.num_events, 3);
+    assert_eq!(replay_all.num_events, 11);
+}
+
+#[test]
+fn coordinator_applies_catch_up_mode() {
+    let entity = EntityAllocator::default().alloc();
+    let mut context = Context::new(entity, CLAMP_TOTAL);
+    let mut coordinator = RealtimeCoordinator::<Context>::new();
+    coordinator.register(std::mem::take(&mut context.components));
+    assert!(coordinator.process_entity_frame(entity, FRAME, &mut context));
+    assert_eq!(context.num_events, 3);
+    let mut context = Context::new(entity, CLAMP_TOTAL);
+    let mut coordinator = RealtimeCoordinator::<Context>::new();
+    coordinator.register(std::mem::take(&mut context.components));
+    coordinator.process_entity_frame_with_budget(entity, FRAME, &mut context, 100);
+    assert_eq!(context.num_events, 3);
+}
+
+#[test]
+fn table_frame_applies_catch_up_mode_per_entity() {
+    let entity = EntityAllocator::default().alloc();
+    let mut context = Context::new(entity, CLAMP_TOTAL);
+    process_table_frame(FRAME, &mut context, &mut LinearScan::new(), |context| {
+        &mut context.table
+    });
+    assert_eq!(context.num_events, 3);
+    assert!(context
+        .table
+        .get_with_schedule(entity)
+        .unwrap()
+        .is_enabled());
+}
@@ -0,0 +1,111 @@
+use entity_table::{ComponentTable, Entities, Entity, EntityAllocator};
+use entity_table_realtime::{
+    process_entity_frame_with_catch_up, CatchUpPolicy, ContextContainsRealtimeComponents,
+    RealtimeComponent, RealtimeComponentApplyEvent,
+};
+use std::time::Duration;
+
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone)]
+struct Periodic(usize);
+
+impl RealtimeComponent for Periodic {
+    type Event = usize;
+
+    fn tick(&mut self) -> (Self::Event, Duration) {
+        (self.0, Duration::from_millis(10))
+    }
+}
+
+impl RealtimeComponentApplyEvent<Context> for Periodic {
+    fn apply_event(index: usize, _entity: Entity, context: &mut Context) {
+        context.num_events[index] += 1;
+    }
+}
+
+const BURST: usize = 0;
+const SKIP: usize = 1;
+
+entity_table_realtime::declare_realtime_entity_module! {
+    components[Context] {
+        #[catch_up(burst)]
+        burst: Periodic,
+        #[catch_up(skip)]
+        skip: Periodic,
+    }
+}
+
+#[derive(Default)]
+struct Context {
+    components: components::RealtimeComponents,
+    entities: ComponentTable<()>,
+    num_events: [u32; 2],
+}
+
+impl Context {
+    fn new(entity: Entity) -> Self {
+        let mut context = Self::default();
+        context.entities.insert(entity, ());
+        context.components.burst.insert(entity, Periodic(BURST));
+        context.components.skip.insert(entity, Periodic(SKIP));
+        context
+    }
+}
+
+impl ContextContainsRealtimeComponents for Context {
+    type Components = components::RealtimeComponents;
+    fn components_mut(&mut self) -> &mut Self::Components {
+        &mut self.components
+    }
+    fn realtime_entities(&self) -> Entities<'_> {
+        self.entities.entities()
+    }
+}
+
+#[test]
+fn policies_are_declared_per_component() {
+    assert_eq!(components::catch_up_policy::burst, CatchUpPolicy::Burst);
+    assert_eq!(components::catch_up_policy::skip, CatchUpPolicy::Skip);
+}
+
+#[test]
+fn fast_forward_only_replays_bursting_components() {
+    let entity = EntityAllocator::default().alloc();
+    let mut context = Context::new(entity);
+    let events = context.components.fast_forward(Duration::from_millis(95));
+    // ticks at 0ms, 10ms, ..., 90ms
+    assert_eq!(events.len(), 10);
+    assert!(events.iter().all(|(_, _, events)| events.skip.is_none()));
+    // both components carry on from the same point in their schedules
+    assert_eq!(
+        context.components.burst.time_to_due(entity),
+        Some(Duration::from_millis(5))
+    );
+    assert_eq!(
+        context.components.skip.time_to_due(entity),
+        Some(Duration::from_millis(5))
+    );
+}
+
+#[test]
+fn long_frames_are_skipped_by_skipping_components() {
+    let entity = EntityAllocator::default().alloc();
+    let mut context = Context::new(entity);
+    process_entity_frame_with_catch_up(
+        entity,
+        Duration::from_millis(100),
+        Duration::from_millis(25),
+        &mut context,
+    );
+    // bursting ticks at 0ms, 10ms, ..., 100ms, while skipping only ticks at 80ms, 90ms and
+    // 100ms, in the final 25ms of the frame
+    assert_eq!(context.num_events, [11, 3]);
+    let mut context = Context::new(entity);
+    process_entity_frame_with_catch_up(
+        entity,
+        Duration::from_millis(20),
+        Duration::from_millis(25),
+        &mut context,
+    );
+    assert_eq!(context.num_events, [3, 3]);
+}
@@ -0,0 +1,123 @@
+use entity_table_realtime::combinators::{
+    Alternate, Chain, CombinedEvent, FiniteRealtimeComponent, Repeat,
+};
+use entity_table_realtime::movement::{RealtimeMovement, RealtimeMovementEvent, Speed};
+use entity_table_realtime::testing::{assert_timeline, run_component};
+use entity_table_realtime::timeline::{Timeline, TimelineEvent};
+use entity_table_realtime::RealtimeComponent;
+use std::time::Duration;
+
+/// Blinks a fixed number of times, then finishes
+#[derive(Debug, Clone, PartialEq)]
+struct Blink {
+    remaining: u32,
+    period: Duration,
+}
+
+impl Blink {
+    fn new(times: u32, period_ms: u64) -> Self {
+        Self {
+            remaining: times,
+            period: Duration::from_millis(period_ms),
+        }
+    }
+}
+
+impl RealtimeComponent for Blink {
+    type Event = u32;
+
+    fn tick(&mut self) -> (Self::Event, Duration) {
+        assert!(!self.is_finished(), "finished components mustn't tick");
+        self.remaining -= 1;
+        (self.remaining, self.period)
+    }
+}
+
+impl FiniteRealtimeComponent for Blink {
+    fn is_finished(&self) -> bool {
+        self.remaining == 0
+    }
+}
+
+const fn ms(millis: u64) -> Duration {
+    Duration::from_millis(millis)
+}
+
+use CombinedEvent::{First, Second};
+
+#[test]
+fn chains_run_the_second_component_once_the_first_finishes() {
+    let mut chain = Chain::new(Blink::new(2, 10), Blink::new(2, 5));
+    assert_timeline(
+        // finished components mustn't be ticked, so stop before the chain's next tick at 30ms
+        &run_component(chain.clone(), ms(0), ms(29)),
+        &[
+            (ms(0), First(1)),
+            (ms(10), First(0)),
+            (ms(20), Second(1)),
+            (ms(25), Second(0)),
+        ],
+    );
+    for _ in 0..3 {
+        assert!(!chain.is_finished());
+        chain.tick();
+    }
+    chain.tick();
+    assert!(chain.is_finished());
+}
+
+#[test]
+fn repeats_restart_from_the_original_component() {
+    let repeat = Repeat::new(Blink::new(2, 10), 3);
+    assert_eq!(repeat.repetitions_remaining(), 2);
+    let events = run_component(repeat.clone(), ms(0), ms(59))
+        .into_iter()
+        .map(|(_, event)| event)
+        .collect::<Vec<_>>();
+    assert_eq!(events, vec![1, 0, 1, 0, 1, 0]);
+    // zero repetitions run the component once
+    let once = Repeat::new(Blink::new(2, 10), 0);
+    assert_eq!(run_component(once, ms(0), ms(19)).len(), 2);
+}
+
+#[test]
+fn alternates_switch_between_components_forever() {
+    let alternate = Alternate::new(Blink::new(1, 10), Blink::new(2, 5));
+    let events = run_component(alternate, ms(0), ms(39))
+        .into_iter()
+        .map(|(_, event)| event)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        events,
+        vec![
+            First(0),
+            Second(1),
+            Second(0),
+            First(0),
+            Second(1),
+            Second(0),
+        ]
+    );
+}
+
+#[test]
+fn timelines_and_movements_can_be_chained() {
+    let timeline = Timeline::new(vec![(ms(0), 'a'), (ms(10), 'b')]);
+    let movement = RealtimeMovement::new(vec![(1, 0), (2, 0)], Speed::cells_per_second(100.0))
+        .with_max_steps(1);
+    let mut chain = Chain::new(timeline, movement);
+    // the timeline's Finished event isn't emitted, and neither is anything after the movement
+    // expires, once the chain finishes
+    let mut events = Vec::new();
+    while !chain.is_finished() {
+        events.push(chain.tick().0);
+    }
+    assert_eq!(
+        events,
+        vec![
+            First(TimelineEvent::Keyframe('a')),
+            First(TimelineEvent::Keyframe('b')),
+            Second(RealtimeMovementEvent::Expired((1, 0))),
+        ]
+    );
+}
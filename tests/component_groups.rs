@@ -0,0 +1,133 @@
+use entity_table::{ComponentTable, Entities, Entity, EntityAllocator};
+use entity_table_realtime::{
+    process_entity_frame_excluding_group, CatchUpPolicy, ContextContainsRealtimeComponents,
+    RealtimeComponent, RealtimeComponentApplyEvent,
+};
+use std::time::Duration;
+
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Name {
+    Flicker,
+    Sparkle,
+    Poison,
+}
+
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone)]
+struct Periodic(Name);
+
+impl RealtimeComponent for Periodic {
+    type Event = Name;
+
+    fn tick(&mut self) -> (Self::Event, Duration) {
+        (self.0, Duration::from_millis(10))
+    }
+}
+
+impl RealtimeComponentApplyEvent<Context> for Periodic {
+    fn apply_event(name: Name, _entity: Entity, context: &mut Context) {
+        context.log.push(name);
+    }
+}
+
+entity_table_realtime::declare_realtime_entity_module! {
+    components[Context] {
+        #[group(render)]
+        flicker: Periodic,
+        #[catch_up(skip)]
+        #[group(render)]
+        sparkle: Periodic,
+        poison: Periodic,
+    }
+}
+
+#[derive(Default)]
+struct Context {
+    components: components::RealtimeComponents,
+    entities: ComponentTable<()>,
+    log: Vec<Name>,
+}
+
+impl Context {
+    fn new(entity: Entity) -> Self {
+        let mut context = Self::default();
+        context.entities.insert(entity, ());
+        context
+            .components
+            .flicker
+            .insert(entity, Periodic(Name::Flicker));
+        context
+            .components
+            .sparkle
+            .insert(entity, Periodic(Name::Sparkle));
+        context
+            .components
+            .poison
+            .insert(entity, Periodic(Name::Poison));
+        context
+    }
+}
+
+impl ContextContainsRealtimeComponents for Context {
+    type Components = components::RealtimeComponents;
+    fn components_mut(&mut self) -> &mut Self::Components {
+        &mut self.components
+    }
+    fn realtime_entities(&self) -> Entities<'_> {
+        self.entities.entities()
+    }
+}
+
+const FRAME: Duration = Duration::from_millis(25);
+
+#[test]
+fn groups_are_declared_per_component() {
+    assert_eq!(components::group::flicker, Some("render"));
+    assert_eq!(components::group::sparkle, Some("render"));
+    assert_eq!(components::group::poison, None);
+    assert_eq!(components::catch_up_policy::sparkle, CatchUpPolicy::Skip);
+}
+
+#[test]
+fn excluded_groups_are_left_untouched() {
+    let entity = EntityAllocator::default().alloc();
+    let mut context = Context::new(entity);
+    process_entity_frame_excluding_group(entity, FRAME, "render", &mut context);
+    assert_eq!(context.log, vec![Name::Poison; 3]);
+    assert_eq!(
+        context.components.flicker.time_to_due(entity),
+        Some(Duration::ZERO)
+    );
+    assert_eq!(
+        context.components.sparkle.time_to_due(entity),
+        Some(Duration::ZERO)
+    );
+    assert_eq!(
+        context.components.poison.time_to_due(entity),
+        Some(Duration::from_millis(5))
+    );
+    // excluding a group no component belongs to processes every component
+    process_entity_frame_excluding_group(entity, FRAME, "audio", &mut context);
+    assert_eq!(context.log.len(), 3 + 3 * 3);
+}
+
+#[test]
+fn groups_can_be_ticked_on_their_own() {
+    let entity = EntityAllocator::default().alloc();
+    let mut context = Context::new(entity);
+    let mut elapsed = Duration::ZERO;
+    while elapsed < FRAME {
+        let (events, until_next_tick) =
+            context
+                .components
+                .tick_entity_group("render", entity, FRAME - elapsed);
+        events.apply(entity, &mut context);
+        elapsed += until_next_tick;
+    }
+    assert_eq!(context.log, [Name::Flicker, Name::Sparkle].repeat(3));
+    assert_eq!(
+        context.components.poison.time_to_due(entity),
+        Some(Duration::ZERO)
+    );
+}
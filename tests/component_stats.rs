@@ -0,0 +1,50 @@
+use entity_table::EntityAllocator;
+use entity_table_realtime::stats::ComponentStats;
+use entity_table_realtime::{RealtimeComponent, RealtimeComponentTable};
+use std::time::Duration;
+
+#[derive(Debug, Clone)]
+struct Periodic;
+
+impl RealtimeComponent for Periodic {
+    type Event = ();
+
+    fn tick(&mut self) -> (Self::Event, Duration) {
+        ((), Duration::from_millis(10))
+    }
+}
+
+#[cfg(feature = "stats")]
+#[test]
+fn components_count_their_ticks_and_simulated_time() {
+    let mut allocator = EntityAllocator::default();
+    let (e1, e2) = (allocator.alloc(), allocator.alloc());
+    let mut table = RealtimeComponentTable::default();
+    table.insert(e1, Periodic);
+    assert_eq!(table.component_stats(e1), Some(ComponentStats::default()));
+    assert_eq!(table.component_stats(e2), None);
+    assert_eq!(table.component_stats(e1).unwrap().mean_period(), None);
+    table.fast_forward(Duration::from_millis(40));
+    let stats = table.component_stats(e1).unwrap();
+    // ticks at 0ms, 10ms, 20ms, 30ms and 40ms
+    assert_eq!(stats.ticks, 5);
+    assert_eq!(stats.simulated_time, Duration::from_millis(40));
+    assert_eq!(stats.mean_period(), Some(Duration::from_millis(8)));
+    assert!(stats.last_tick.is_some());
+    // inserting a component resets its stats
+    table.insert(e1, Periodic);
+    assert_eq!(table.component_stats(e1), Some(ComponentStats::default()));
+}
+
+#[cfg(not(feature = "stats"))]
+#[test]
+fn component_stats_are_zero_without_the_feature() {
+    let entity = EntityAllocator::default().alloc();
+    let mut table = RealtimeComponentTable::default();
+    table.insert(entity, Periodic);
+    table.fast_forward(Duration::from_millis(40));
+    assert_eq!(
+        table.component_stats(entity),
+        Some(ComponentStats::default())
+    );
+}
@@ -0,0 +1,67 @@
+use entity_table::{ComponentTable, Entities, Entity, EntityAllocator};
+use entity_table_realtime::{
+    process_entity_frame, ContextContainsRealtimeComponents, RealtimeComponent,
+    RealtimeComponentApplyEvent,
+};
+use std::time::Duration;
+
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone)]
+struct Spread {
+    index: usize,
+}
+
+impl RealtimeComponent for Spread {
+    type Event = usize;
+
+    fn tick(&mut self) -> (Self::Event, Duration) {
+        let index = self.index;
+        self.index += 1;
+        (index, Duration::from_millis(10))
+    }
+}
+
+impl<'a, const W: usize, const H: usize> RealtimeComponentApplyEvent<Grid<'a, W, H>> for Spread {
+    fn apply_event(index: usize, _entity: Entity, context: &mut Grid<'a, W, H>) {
+        context.cells[index % H][index % W] += 1;
+    }
+}
+
+entity_table_realtime::declare_realtime_entity_module! {
+    components<'a, const W: usize, const H: usize>[Grid<'a, W, H>] {
+        spread: Spread,
+    }
+}
+
+struct Grid<'a, const W: usize, const H: usize> {
+    components: &'a mut components::RealtimeComponents,
+    entities: &'a ComponentTable<()>,
+    cells: [[u32; W]; H],
+}
+
+impl<'a, const W: usize, const H: usize> ContextContainsRealtimeComponents for Grid<'a, W, H> {
+    type Components = components::RealtimeComponents;
+    fn components_mut(&mut self) -> &mut Self::Components {
+        self.components
+    }
+    fn realtime_entities(&self) -> Entities<'_> {
+        self.entities.entities()
+    }
+}
+
+#[test]
+fn const_generic_context() {
+    let mut entity_allocator = EntityAllocator::default();
+    let mut components = components::RealtimeComponents::default();
+    let mut entities = ComponentTable::default();
+    let entity = entity_allocator.alloc();
+    entities.insert(entity, ());
+    components.spread.insert(entity, Spread { index: 0 });
+    let mut grid = Grid::<3, 2> {
+        components: &mut components,
+        entities: &entities,
+        cells: [[0; 3]; 2],
+    };
+    process_entity_frame(entity, Duration::from_millis(35), &mut grid);
+    assert_eq!(grid.cells, [[1, 0, 1], [1, 1, 0]]);
+}
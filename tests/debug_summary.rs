@@ -0,0 +1,89 @@
+use entity_table::{Entity, EntityAllocator};
+use entity_table_realtime::{RealtimeComponent, RealtimeComponentApplyEvent};
+use std::time::Duration;
+
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone)]
+struct Periodic;
+
+impl RealtimeComponent for Periodic {
+    type Event = ();
+
+    fn tick(&mut self) -> (Self::Event, Duration) {
+        ((), Duration::from_millis(10))
+    }
+}
+
+impl RealtimeComponentApplyEvent<()> for Periodic {
+    fn apply_event(_event: (), _entity: Entity, _context: &mut ()) {}
+}
+
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone)]
+struct Slow;
+
+impl RealtimeComponent for Slow {
+    type Event = ();
+
+    fn tick(&mut self) -> (Self::Event, Duration) {
+        ((), Duration::from_millis(25))
+    }
+}
+
+impl RealtimeComponentApplyEvent<()> for Slow {
+    fn apply_event(_event: (), _entity: Entity, _context: &mut ()) {}
+}
+
+entity_table_realtime::declare_realtime_entity_module! {
+    components[()] {
+        periodic: Periodic,
+        slow: Slow,
+    }
+}
+
+#[test]
+fn debug_summary_groups_schedules_by_entity() {
+    let mut allocator = EntityAllocator::default();
+    let (e1, e2, e3) = (allocator.alloc(), allocator.alloc(), allocator.alloc());
+    let mut components = components::RealtimeComponents::default();
+    components.periodic.insert(e1, Periodic);
+    components.slow.insert(e1, Slow);
+    components.slow.insert(e2, Slow);
+    for entity in [e1, e2] {
+        components.tick_entity(entity, Duration::from_millis(5));
+    }
+    let summary = components.debug_summary();
+    assert_eq!(summary.entities.len(), 2);
+    let e1_summary = summary.get(e1).unwrap();
+    assert_eq!(e1_summary.components.len(), 2);
+    let periodic = e1_summary.get("periodic").unwrap();
+    assert_eq!(periodic.until_next_tick, Duration::from_millis(10));
+    assert_eq!(periodic.period, Duration::from_millis(10));
+    let slow = e1_summary.get("slow").unwrap();
+    assert_eq!(slow.until_next_tick, Duration::from_millis(25));
+    assert_eq!(slow.period, Duration::from_millis(25));
+    let e2_summary = summary.get(e2).unwrap();
+    assert!(e2_summary.get("periodic").is_none());
+    assert_eq!(
+        e2_summary.get("slow").unwrap().period,
+        Duration::from_millis(25)
+    );
+    assert!(summary.get(e3).is_none());
+}
+
+#[test]
+fn debug_summary_reports_whether_components_are_enabled() {
+    let entity = EntityAllocator::default().alloc();
+    let mut components = components::RealtimeComponents::default();
+    components.periodic.insert(entity, Periodic);
+    let enabled = |components: &components::RealtimeComponents| {
+        components
+            .debug_summary()
+            .get(entity)
+            .and_then(|summary| summary.get("periodic"))
+            .map(|summary| summary.enabled)
+    };
+    assert_eq!(enabled(&components), Some(true));
+    assert_eq!(components.toggle_enabled(entity, "periodic"), Some(false));
+    assert_eq!(enabled(&components), Some(false));
+}
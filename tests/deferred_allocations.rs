@@ -0,0 +1,84 @@
+use entity_table::{ComponentTable, Entities, Entity, EntityAllocator};
+use entity_table_realtime::stats::{self, CountingAllocator};
+use entity_table_realtime::{
+    defer_entity_frame, ContextContainsRealtimeComponents, DeferredRealtimeEvents,
+    RealtimeComponent, RealtimeComponentApplyEvent,
+};
+use std::time::Duration;
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator(std::alloc::System);
+
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone)]
+struct Periodic(Duration);
+
+impl RealtimeComponent for Periodic {
+    type Event = u32;
+
+    fn tick(&mut self) -> (Self::Event, Duration) {
+        (1, self.0)
+    }
+}
+
+impl RealtimeComponentApplyEvent<Context> for Periodic {
+    fn apply_event(event: u32, _entity: Entity, context: &mut Context) {
+        context.total += event;
+    }
+}
+
+entity_table_realtime::declare_realtime_entity_module! {
+    components[Context] {
+        fast: Periodic,
+        slow: Periodic,
+    }
+}
+
+struct Context {
+    components: components::RealtimeComponents,
+    entities: ComponentTable<()>,
+    total: u32,
+}
+
+impl ContextContainsRealtimeComponents for Context {
+    type Components = components::RealtimeComponents;
+    fn components_mut(&mut self) -> &mut Self::Components {
+        &mut self.components
+    }
+    fn realtime_entities(&self) -> Entities<'_> {
+        self.entities.entities()
+    }
+}
+
+#[test]
+fn deferred_frame_with_few_events_does_not_allocate() {
+    let mut entity_allocator = EntityAllocator::default();
+    let mut context = Context {
+        components: Default::default(),
+        entities: Default::default(),
+        total: 0,
+    };
+    let entity = entity_allocator.alloc();
+    context.entities.insert(entity, ());
+    context
+        .components
+        .fast
+        .insert(entity, Periodic(Duration::from_millis(10)));
+    context
+        .components
+        .slow
+        .insert(entity, Periodic(Duration::from_millis(30)));
+    let mut deferred = DeferredRealtimeEvents::<_, 8>::new();
+    stats::take_allocations();
+    defer_entity_frame(
+        entity,
+        Duration::from_millis(35),
+        &mut context,
+        &mut deferred,
+    );
+    let num_events = deferred.len();
+    deferred.apply(&mut context);
+    assert_eq!(stats::take_allocations(), 0);
+    assert_eq!(num_events, 6);
+    assert_eq!(context.total, 6);
+}
@@ -0,0 +1,93 @@
+use entity_table::{Entity, EntityAllocator};
+use entity_table_realtime::{
+    DeterminismAudit, Divergence, RealtimeComponent, RealtimeComponentApplyEvent,
+};
+use std::time::Duration;
+
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone)]
+struct Periodic(u64);
+
+impl RealtimeComponent for Periodic {
+    type Event = ();
+
+    fn tick(&mut self) -> (Self::Event, Duration) {
+        ((), Duration::from_millis(self.0))
+    }
+}
+
+impl RealtimeComponentApplyEvent<()> for Periodic {
+    fn apply_event(_event: (), _entity: Entity, _context: &mut ()) {}
+}
+
+entity_table_realtime::declare_realtime_entity_module! {
+    components[()] {
+        periodic: Periodic,
+    }
+}
+
+const FRAMES: [Duration; 4] = [
+    Duration::from_millis(16),
+    Duration::from_millis(17),
+    Duration::from_millis(5),
+    Duration::from_millis(33),
+];
+
+/// Record a live run of the components, returning the audit and the state at its start
+fn record(entity: Entity) -> (DeterminismAudit, components::RealtimeComponents) {
+    let mut components = components::RealtimeComponents::default();
+    components.periodic.insert(entity, Periodic(7));
+    let start = components.clone();
+    let mut audit = DeterminismAudit::new();
+    for frame_duration in FRAMES {
+        components.fast_forward(frame_duration);
+        audit.record(frame_duration, components.debug_summary().schedule_hash());
+    }
+    (audit, start)
+}
+
+#[test]
+fn resimulating_the_same_frames_matches_the_recording() {
+    let entity = EntityAllocator::default().alloc();
+    let (audit, mut components) = record(entity);
+    assert_eq!(audit.frames().len(), FRAMES.len());
+    assert_eq!(audit.frames()[2].frame_duration, FRAMES[2]);
+    let divergence = audit.audit(|frame_duration| {
+        components.fast_forward(frame_duration);
+        components.debug_summary().schedule_hash()
+    });
+    assert_eq!(divergence, None);
+}
+
+#[test]
+fn audit_reports_the_first_frame_which_diverges() {
+    let entity = EntityAllocator::default().alloc();
+    let (audit, mut components) = record(entity);
+    let mut frame_index = 0;
+    let divergence = audit.audit(|frame_duration| {
+        if frame_index == 2 {
+            // a change the recording didn't see
+            components.periodic.get_mut(entity).unwrap().0 = 8;
+        }
+        frame_index += 1;
+        components.fast_forward(frame_duration);
+        components.debug_summary().schedule_hash()
+    });
+    let Divergence {
+        frame_index,
+        expected_hash,
+        actual_hash,
+    } = divergence.unwrap();
+    assert_eq!(frame_index, 2);
+    assert_eq!(expected_hash, audit.frames()[2].hash);
+    assert_ne!(actual_hash, expected_hash);
+}
+
+#[test]
+fn cleared_audit_has_nothing_to_diverge_from() {
+    let entity = EntityAllocator::default().alloc();
+    let (mut audit, _) = record(entity);
+    audit.clear();
+    assert!(audit.frames().is_empty());
+    assert_eq!(audit.audit(|_| 0), None);
+}
@@ -0,0 +1,177 @@
+use entity_table::{Entity, EntityAllocator};
+use entity_table_realtime::erased::{
+    process_entity_frame_erased, ContextContainsErasedRealtimeComponents, ErasedRealtimeComponents,
+};
+use entity_table_realtime::scheduler::{process_table_frame, LinearScan};
+use entity_table_realtime::{
+    process_entity_frame_dyn, RealtimeComponent, RealtimeComponentApplyEvent,
+    RealtimeComponentTable, RealtimeComponents, RealtimeCoordinator, RealtimeFrameHooks,
+    ScheduledRealtimeComponent,
+};
+use std::time::Duration;
+
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone)]
+struct Periodic(u32);
+
+impl RealtimeComponent for Periodic {
+    type Event = u32;
+
+    fn tick(&mut self) -> (Self::Event, Duration) {
+        (self.0, Duration::from_millis(10))
+    }
+}
+
+impl RealtimeComponentApplyEvent<Events> for Periodic {
+    fn apply_event(event: u32, _entity: Entity, context: &mut Events) {
+        context.0.push(event);
+    }
+}
+
+impl RealtimeComponentApplyEvent<Erased> for Periodic {
+    fn apply_event(event: u32, _entity: Entity, context: &mut Erased) {
+        context.events.push(event);
+    }
+}
+
+impl RealtimeComponentApplyEvent<Table> for Periodic {
+    fn apply_event(event: u32, _entity: Entity, context: &mut Table) {
+        context.events.push(event);
+    }
+}
+
+entity_table_realtime::declare_realtime_entity_module! {
+    components[Events] {
+        enabled: Periodic,
+        disabled: Periodic,
+    }
+}
+
+#[derive(Default)]
+struct Events(Vec<u32>);
+
+impl RealtimeFrameHooks for Events {}
+
+struct Erased {
+    components: ErasedRealtimeComponents<Erased>,
+    events: Vec<u32>,
+}
+
+impl RealtimeFrameHooks for Erased {}
+
+impl ContextContainsErasedRealtimeComponents for Erased {
+    fn erased_components_mut(&mut self) -> &mut ErasedRealtimeComponents<Self> {
+        &mut self.components
+    }
+}
+
+#[derive(Default)]
+struct Table {
+    table: RealtimeComponentTable<Periodic>,
+    events: Vec<u32>,
+}
+
+impl RealtimeFrameHooks for Table {}
+
+/// A disabled component which was due when it was disabled
+fn disabled_due_now() -> ScheduledRealtimeComponent<Periodic> {
+    let mut scheduled = ScheduledRealtimeComponent::new(Periodic(2), Duration::ZERO);
+    scheduled.set_enabled(false);
+    scheduled
+}
+
+fn components_with_disabled_due_now(entity: Entity) -> components::RealtimeComponents {
+    let mut components = components::RealtimeComponents::default();
+    components.enabled.insert(entity, Periodic(1));
+    components
+        .disabled
+        .insert_with_schedule(entity, disabled_due_now());
+    components
+}
+
+#[test]
+fn until_next_tick_ignores_disabled_components() {
+    let entity = EntityAllocator::default().alloc();
+    let mut components = components::RealtimeComponents::default();
+    components
+        .disabled
+        .insert_with_schedule(entity, disabled_due_now());
+    assert_eq!(
+        RealtimeComponents::<Events>::until_next_tick(&components, entity),
+        None
+    );
+    assert!(RealtimeComponents::<Events>::contains_entity(
+        &components,
+        entity
+    ));
+    components.enabled.insert_with_schedule(
+        entity,
+        ScheduledRealtimeComponent::new(Periodic(1), Duration::from_millis(3)),
+    );
+    assert_eq!(
+        components.until_next_tick(entity),
+        Some(Duration::from_millis(3))
+    );
+}
+
+#[test]
+fn coordinator_frame_terminates_with_disabled_component_due_now() {
+    let entity = EntityAllocator::default().alloc();
+    let mut coordinator = RealtimeCoordinator::<Events>::new();
+    coordinator.register(components_with_disabled_due_now(entity));
+    let mut events = Events::default();
+    assert!(coordinator.process_entity_frame(entity, Duration::from_millis(25), &mut events));
+    assert_eq!(events.0, vec![1, 1, 1]);
+}
+
+#[test]
+fn erased_frame_terminates_with_disabled_component_due_now() {
+    let entity = EntityAllocator::default().alloc();
+    let mut context = Erased {
+        components: ErasedRealtimeComponents::default(),
+        events: Vec::new(),
+    };
+    let mut enabled = RealtimeComponentTable::default();
+    enabled.insert(entity, Periodic(1));
+    let mut disabled = RealtimeComponentTable::default();
+    disabled.insert_with_schedule(entity, disabled_due_now());
+    context.components.register(enabled);
+    context.components.register(disabled);
+    process_entity_frame_erased(entity, Duration::from_millis(25), &mut context);
+    assert_eq!(context.events, vec![1, 1, 1]);
+}
+
+#[test]
+fn dyn_frame_terminates_with_disabled_component_due_now() {
+    let entity = EntityAllocator::default().alloc();
+    let mut components = components_with_disabled_due_now(entity);
+    let mut events = Events::default();
+    process_entity_frame_dyn(
+        entity,
+        Duration::from_millis(25),
+        &mut components,
+        &mut events,
+    );
+    assert_eq!(events.0, vec![1, 1, 1]);
+}
+
+#[test]
+fn table_frame_leaves_disabled_components_disabled() {
+    let mut entity_allocator = EntityAllocator::default();
+    let mut context = Table::default();
+    context.table.insert(entity_allocator.alloc(), Periodic(1));
+    let disabled = entity_allocator.alloc();
+    context
+        .table
+        .insert_with_schedule(disabled, disabled_due_now());
+    process_table_frame(
+        Duration::from_millis(25),
+        &mut context,
+        &mut LinearScan::new(),
+        |context| &mut context.table,
+    );
+    assert_eq!(context.events, vec![1, 1, 1]);
+    let disabled = context.table.get_with_schedule(disabled).unwrap();
+    assert!(!disabled.is_enabled());
+    assert_eq!(disabled.until_next_tick(), Duration::ZERO);
+}
@@ -0,0 +1,113 @@
+use entity_table::{ComponentTable, Entities, Entity, EntityAllocator};
+use entity_table_realtime::{
+    process_entity_frame, ContextContainsRealtimeComponents, RealtimeComponent,
+    RealtimeComponentApplyEvent,
+};
+use std::time::Duration;
+
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone)]
+struct Periodic;
+
+impl RealtimeComponent for Periodic {
+    type Event = ();
+
+    fn tick(&mut self) -> (Self::Event, Duration) {
+        ((), ms(10))
+    }
+}
+
+impl RealtimeComponentApplyEvent<Context> for Periodic {
+    fn apply_event(_event: (), entity: Entity, context: &mut Context) {
+        context.applied.push(entity);
+    }
+}
+
+entity_table_realtime::declare_realtime_entity_module! {
+    components[Context] {
+        periodic: Periodic,
+    }
+}
+
+#[derive(Default)]
+struct Context {
+    components: components::RealtimeComponents,
+    entities: ComponentTable<()>,
+    /// Entities which are off-screen
+    off_screen: ComponentTable<()>,
+    applied: Vec<Entity>,
+}
+
+impl ContextContainsRealtimeComponents for Context {
+    type Components = components::RealtimeComponents;
+    fn components_mut(&mut self) -> &mut Self::Components {
+        &mut self.components
+    }
+    fn realtime_entities(&self) -> Entities<'_> {
+        self.entities.entities()
+    }
+    fn realtime_dormant(&self, entity: Entity) -> bool {
+        self.off_screen.contains(entity)
+    }
+}
+
+const fn ms(millis: u64) -> Duration {
+    Duration::from_millis(millis)
+}
+
+fn process_frame(context: &mut Context, frame_duration: Duration) {
+    let entities = context.entities.entities().collect::<Vec<_>>();
+    for entity in entities {
+        process_entity_frame(entity, frame_duration, context);
+    }
+}
+
+#[test]
+fn dormant_entities_freeze_and_thaw_without_catching_up() {
+    let mut allocator = EntityAllocator::default();
+    let (e1, e2) = (allocator.alloc(), allocator.alloc());
+    let mut context = Context::default();
+    for entity in [e1, e2] {
+        context.entities.insert(entity, ());
+        context.components.periodic.insert(entity, Periodic);
+    }
+    // ticks at 0ms, leaving 6ms until the next tick
+    process_frame(&mut context, ms(4));
+    assert_eq!(context.applied, vec![e1, e2]);
+    context.applied.clear();
+    context.off_screen.insert(e1, ());
+    for _ in 0..10 {
+        process_frame(&mut context, ms(25));
+    }
+    // only the entity which isn't dormant is simulated, and its schedule is frozen
+    assert!(context.applied.iter().all(|&entity| entity == e2));
+    assert_eq!(context.applied.len(), 25);
+    assert_eq!(context.components.periodic.time_to_due(e1), Some(ms(6)));
+    context.applied.clear();
+    context.off_screen.remove(e1);
+    // the thawed entity picks up where it left off rather than fast-forwarding
+    process_entity_frame(e1, ms(20), &mut context);
+    assert_eq!(context.applied, vec![e1, e1]);
+    assert_eq!(context.components.periodic.time_to_due(e1), Some(ms(6)));
+}
+
+#[test]
+fn dormancy_is_queried_each_frame() {
+    let entity = EntityAllocator::default().alloc();
+    let mut context = Context::default();
+    context.entities.insert(entity, ());
+    context.components.periodic.insert(entity, Periodic);
+    let mut num_ticks_per_frame = Vec::new();
+    for frame in 0..6 {
+        if frame % 2 == 1 {
+            context.off_screen.insert(entity, ());
+        } else {
+            context.off_screen.remove(entity);
+        }
+        process_entity_frame(entity, ms(10), &mut context);
+        num_ticks_per_frame.push(context.applied.len());
+        context.applied.clear();
+    }
+    // frames while dormant are dropped entirely
+    assert_eq!(num_ticks_per_frame, vec![2, 0, 1, 0, 1, 0]);
+}
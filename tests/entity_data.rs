@@ -0,0 +1,102 @@
+use entity_table::{Entity, EntityAllocator};
+use entity_table_realtime::{RealtimeComponent, RealtimeComponentApplyEvent};
+use std::time::Duration;
+
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+struct Periodic(u64);
+
+impl RealtimeComponent for Periodic {
+    type Event = ();
+
+    fn tick(&mut self) -> (Self::Event, Duration) {
+        ((), Duration::from_millis(self.0))
+    }
+}
+
+impl RealtimeComponentApplyEvent<()> for Periodic {
+    fn apply_event(_event: (), _entity: Entity, _context: &mut ()) {}
+}
+
+entity_table_realtime::declare_realtime_entity_module! {
+    components[()] {
+        flicker: Periodic,
+        particle: Periodic,
+        poison: Periodic,
+    }
+}
+
+use components::RealtimeEntityData;
+
+#[test]
+fn entity_data_is_empty_without_components() {
+    assert!(RealtimeEntityData::default().is_empty());
+    let data = RealtimeEntityData {
+        poison: Some(Periodic(10)),
+        ..Default::default()
+    };
+    assert!(!data.is_empty());
+}
+
+#[test]
+fn merged_entity_data_prefers_the_other_components() {
+    let base = RealtimeEntityData {
+        flicker: Some(Periodic(10)),
+        particle: Some(Periodic(20)),
+        ..Default::default()
+    };
+    let variant = RealtimeEntityData {
+        particle: Some(Periodic(5)),
+        poison: Some(Periodic(30)),
+        ..Default::default()
+    };
+    let merged = base.clone().merge(variant);
+    assert_eq!(merged.flicker, Some(Periodic(10)));
+    assert_eq!(merged.particle, Some(Periodic(5)));
+    assert_eq!(merged.poison, Some(Periodic(30)));
+    let unchanged = base.clone().merge(RealtimeEntityData::default());
+    assert_eq!(unchanged.flicker, base.flicker);
+    assert_eq!(unchanged.particle, base.particle);
+    assert!(unchanged.poison.is_none());
+}
+
+#[test]
+fn inserting_entity_data_returns_the_replaced_components() {
+    let entity = EntityAllocator::default().alloc();
+    let mut components = components::RealtimeComponents::default();
+    let replaced = components.insert_entity_data(
+        entity,
+        RealtimeEntityData {
+            flicker: Some(Periodic(10)),
+            particle: Some(Periodic(20)),
+            ..Default::default()
+        },
+    );
+    assert!(replaced.is_empty());
+    let replaced = components.insert_entity_data(
+        entity,
+        RealtimeEntityData {
+            particle: Some(Periodic(5)),
+            poison: Some(Periodic(30)),
+            ..Default::default()
+        },
+    );
+    assert_eq!(replaced.flicker, None);
+    assert_eq!(replaced.particle, Some(Periodic(20)));
+    assert_eq!(replaced.poison, None);
+    // components absent from the inserted data are left in place
+    assert_eq!(components.flicker.get(entity), Some(&Periodic(10)));
+    assert_eq!(components.particle.get(entity), Some(&Periodic(5)));
+    assert_eq!(components.poison.get(entity), Some(&Periodic(30)));
+}
+
+#[cfg(feature = "serialize")]
+#[test]
+fn deserialized_entity_data_may_omit_absent_components() {
+    let data: RealtimeEntityData = serde_json::from_str(r#"{ "poison": 30 }"#).unwrap();
+    assert_eq!(data.flicker, None);
+    assert_eq!(data.particle, None);
+    assert_eq!(data.poison, Some(Periodic(30)));
+    let data: RealtimeEntityData = serde_json::from_str("{}").unwrap();
+    assert!(data.is_empty());
+}
@@ -0,0 +1,53 @@
+use entity_table::Entity;
+use entity_table_realtime::{RealtimeComponent, RealtimeComponentApplyEvent};
+use std::time::Duration;
+
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+struct Periodic(u64);
+
+impl RealtimeComponent for Periodic {
+    type Event = ();
+
+    fn tick(&mut self) -> (Self::Event, Duration) {
+        ((), Duration::from_millis(self.0))
+    }
+}
+
+impl RealtimeComponentApplyEvent<()> for Periodic {
+    fn apply_event(_event: (), _entity: Entity, _context: &mut ()) {}
+}
+
+entity_table_realtime::declare_realtime_entity_module! {
+    components[()] {
+        flicker: Periodic,
+        particle: Periodic,
+    }
+}
+
+mod spawn {
+    use crate::Periodic;
+    use entity_table_realtime::realtime_entity_data;
+
+    /// Unrelated type with the same name as the generated one, which the macro mustn't use
+    #[allow(dead_code)]
+    #[derive(Default)]
+    struct RealtimeEntityData {
+        flicker: Option<u8>,
+    }
+
+    pub(crate) fn flicker() -> crate::components::RealtimeEntityData {
+        realtime_entity_data! {
+            crate::components::RealtimeEntityData {
+                flicker: Periodic(10),
+            }
+        }
+    }
+}
+
+#[test]
+fn entity_data_macro_builds_the_named_type_from_another_module() {
+    let data = spawn::flicker();
+    assert_eq!(data.flicker, Some(Periodic(10)));
+    assert_eq!(data.particle, None);
+}
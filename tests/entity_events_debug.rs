@@ -0,0 +1,83 @@
+use entity_table::{Entity, EntityAllocator};
+use entity_table_realtime::{
+    RealtimeComponent, RealtimeComponentApplyEvent, ScheduledRealtimeComponent,
+};
+use std::time::Duration;
+
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone)]
+struct Counter(u32);
+
+impl RealtimeComponent for Counter {
+    type Event = u32;
+
+    fn tick(&mut self) -> (Self::Event, Duration) {
+        self.0 += 1;
+        (self.0, Duration::from_millis(10))
+    }
+}
+
+impl RealtimeComponentApplyEvent<()> for Counter {
+    fn apply_event(_event: u32, _entity: Entity, _context: &mut ()) {}
+}
+
+entity_table_realtime::declare_realtime_entity_module! {
+    default_names[()] {
+        counter: Counter,
+        idle: Counter,
+    }
+}
+
+entity_table_realtime::declare_realtime_entity_module! {
+    #[names(
+        RealtimeComponents = RenamedComponents,
+        RealtimeEntityData = RenamedEntityData,
+        RealtimeEntityEvents = RenamedEntityEvents,
+        RealtimeEntityEvent = RenamedEntityEvent,
+        RealtimeEntityViewMut = RenamedEntityViewMut,
+    )]
+    renamed[()] {
+        counter: Counter,
+    }
+}
+
+#[test]
+fn entity_events_debug_uses_type_name_and_ticked_fields() {
+    let entity = EntityAllocator::default().alloc();
+    let mut components = default_names::RealtimeComponents::default();
+    components.counter.insert(entity, Counter(0));
+    components.idle.insert_with_schedule(
+        entity,
+        ScheduledRealtimeComponent::new(Counter(0), Duration::from_secs(1)),
+    );
+    let (events, _) = components.tick_entity(entity, Duration::from_millis(5));
+    assert_eq!(
+        format!("{:?}", events),
+        "RealtimeEntityEvents { counter: 1 }"
+    );
+}
+
+#[test]
+fn renamed_entity_events_debug_uses_new_name() {
+    let entity = EntityAllocator::default().alloc();
+    let mut components = renamed::RenamedComponents::default();
+    components.counter.insert(entity, Counter(0));
+    let (events, _) = components.tick_entity(entity, Duration::from_millis(5));
+    assert_eq!(
+        format!("{:?}", events),
+        "RenamedEntityEvents { counter: 1 }"
+    );
+}
+
+#[test]
+fn renamed_entity_view_mut_uses_new_name() {
+    let entity = EntityAllocator::default().alloc();
+    let mut components = renamed::RenamedComponents::default();
+    components.counter.insert(entity, Counter(0));
+    let view: renamed::RenamedEntityViewMut<'_> = components.entity_view_mut(entity);
+    if let Some(counter) = view.counter {
+        counter.0 = 5;
+    }
+    let (events, _) = components.tick_entity(entity, Duration::from_millis(5));
+    assert_eq!(events.counter, Some(6));
+}
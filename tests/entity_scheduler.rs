@@ -0,0 +1,146 @@
+use entity_table::{ComponentTable, Entities, Entity, EntityAllocator};
+use entity_table_realtime::scheduler::{EntityScheduler, LinearScan};
+use entity_table_realtime::{
+    process_entity_frame_with_scheduler, AnimationContext, ContextContainsRealtimeComponents,
+    RealtimeComponent, RealtimeComponentApplyEvent, RealtimeComponents,
+};
+use std::time::Duration;
+
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone)]
+struct Periodic;
+
+impl RealtimeComponent for Periodic {
+    type Event = ();
+
+    fn tick(&mut self) -> (Self::Event, Duration) {
+        ((), Duration::from_millis(10))
+    }
+}
+
+impl<'a> RealtimeComponentApplyEvent<Context<'a>> for Periodic {
+    fn apply_event(_event: (), _entity: Entity, context: &mut Context<'a>) {
+        *context.num_events += 1;
+    }
+}
+
+entity_table_realtime::declare_realtime_entity_module! {
+    components<'a>[Context<'a>] {
+        periodic: Periodic,
+    }
+}
+
+struct Context<'a> {
+    components: &'a mut components::RealtimeComponents,
+    entities: &'a ComponentTable<()>,
+    num_events: &'a mut usize,
+}
+
+impl<'a> ContextContainsRealtimeComponents for Context<'a> {
+    type Components = components::RealtimeComponents;
+    fn components_mut(&mut self) -> &mut Self::Components {
+        self.components
+    }
+    fn realtime_entities(&self) -> Entities<'_> {
+        self.entities.entities()
+    }
+}
+
+/// Takes the same steps as `LinearScan`, counting them
+#[derive(Default)]
+struct CountingScheduler {
+    steps: usize,
+}
+
+impl<C: ContextContainsRealtimeComponents + ?Sized> EntityScheduler<C> for CountingScheduler {
+    fn step(
+        &mut self,
+        entity: Entity,
+        components: &mut C::Components,
+        frame_remaining: Duration,
+    ) -> (
+        <C::Components as RealtimeComponents<C>>::EntityEvents,
+        Duration,
+    ) {
+        self.steps += 1;
+        EntityScheduler::<C>::step(&mut LinearScan::new(), entity, components, frame_remaining)
+    }
+}
+
+const FRAME: Duration = Duration::from_millis(25);
+
+#[test]
+fn animation_context_steps_with_its_scheduler() {
+    let entity = EntityAllocator::default().alloc();
+    let mut components = components::RealtimeComponents::default();
+    let mut entities = ComponentTable::default();
+    entities.insert(entity, ());
+    components.periodic.insert(entity, Periodic);
+    let mut num_events = 0;
+    let mut animation_context = AnimationContext::with_scheduler(CountingScheduler::default());
+    animation_context.tick(
+        Context {
+            components: &mut components,
+            entities: &entities,
+            num_events: &mut num_events,
+        },
+        FRAME,
+    );
+    // ticks at 0ms, 10ms and 20ms, then a step to the end of the frame
+    assert_eq!(num_events, 3);
+    assert_eq!(animation_context.scheduler().steps, 4);
+    animation_context.scheduler_mut().steps = 0;
+    animation_context.tick_with_budget(
+        Context {
+            components: &mut components,
+            entities: &entities,
+            num_events: &mut num_events,
+        },
+        FRAME,
+        1,
+    );
+    assert_eq!(num_events, 4);
+    assert_eq!(animation_context.scheduler().steps, 1);
+}
+
+#[test]
+fn process_entity_frame_steps_with_the_given_scheduler() {
+    let entity = EntityAllocator::default().alloc();
+    let mut components = components::RealtimeComponents::default();
+    let entities = ComponentTable::default();
+    components.periodic.insert(entity, Periodic);
+    let mut num_events = 0;
+    let mut scheduler = CountingScheduler::default();
+    let mut context = Context {
+        components: &mut components,
+        entities: &entities,
+        num_events: &mut num_events,
+    };
+    assert!(process_entity_frame_with_scheduler(
+        entity,
+        FRAME,
+        &mut context,
+        &mut scheduler
+    ));
+    assert_eq!(scheduler.steps, 4);
+    assert_eq!(num_events, 3);
+}
+
+#[test]
+fn animation_context_scans_by_default() {
+    let entity = EntityAllocator::default().alloc();
+    let mut components = components::RealtimeComponents::default();
+    let mut entities = ComponentTable::default();
+    entities.insert(entity, ());
+    components.periodic.insert(entity, Periodic);
+    let mut num_events = 0;
+    AnimationContext::new().tick(
+        Context {
+            components: &mut components,
+            entities: &entities,
+            num_events: &mut num_events,
+        },
+        FRAME,
+    );
+    assert_eq!(num_events, 3);
+}
@@ -0,0 +1,245 @@
+use entity_table::{ComponentTable, Entities, Entity, EntityAllocator};
+use entity_table_realtime::erased::{
+    process_entity_frame_erased, ContextContainsErasedRealtimeComponents, ErasedRealtimeComponents,
+};
+use entity_table_realtime::fixed::{process_fixed_entity_frame, FixedRealtimeComponentTable};
+use entity_table_realtime::scheduler::{process_table_frame, LinearScan};
+use entity_table_realtime::{
+    process_entity_frame, ContextContainsRealtimeComponents, RealtimeComponent,
+    RealtimeComponentApplyEvent, RealtimeComponentTable, RealtimeCoordinator,
+};
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone)]
+struct Periodic;
+
+impl RealtimeComponent for Periodic {
+    type Event = ();
+
+    fn tick(&mut self) -> (Self::Event, Duration) {
+        ((), Duration::from_millis(10))
+    }
+}
+
+impl RealtimeComponentApplyEvent<Context> for Periodic {
+    fn apply_event(_event: (), _entity: Entity, context: &mut Context) {
+        context.num_events += 1;
+    }
+}
+
+entity_table_realtime::declare_realtime_entity_module! {
+    components[Context] {
+        a: Periodic,
+    }
+}
+
+#[derive(Default)]
+struct Context {
+    components: components::RealtimeComponents,
+    table: RealtimeComponentTable<Periodic>,
+    fixed: FixedRealtimeComponentTable<Periodic, 4>,
+    erased: ErasedRealtimeComponents<Context>,
+    entities: ComponentTable<()>,
+    num_events: usize,
+}
+
+impl ContextContainsRealtimeComponents for Context {
+    type Components = components::RealtimeComponents;
+    fn components_mut(&mut self) -> &mut Self::Components {
+        &mut self.components
+    }
+    fn realtime_entities(&self) -> Entities<'_> {
+        self.entities.entities()
+    }
+}
+
+impl ContextContainsErasedRealtimeComponents for Context {
+    fn erased_components_mut(&mut self) -> &mut ErasedRealtimeComponents<Self> {
+        &mut self.erased
+    }
+}
+
+#[test]
+fn coordinator_stops_at_capped_components() {
+    let mut allocator = EntityAllocator::default();
+    let (e1, e2) = (allocator.alloc(), allocator.alloc());
+    let mut context = Context::default();
+    let mut module = components::RealtimeComponents::default();
+    module.a.set_event_cap(1, |_| ());
+    module.a.insert(e1, Periodic);
+    module.a.insert(e2, Periodic);
+    let mut coordinator = RealtimeCoordinator::<Context>::new();
+    coordinator.register(module);
+    let frame = Duration::from_millis(5);
+    assert!(coordinator.process_entity_frame(e1, frame, &mut context));
+    // e2 is due now but the cap was used up by e1
+    assert!(coordinator.process_entity_frame(e2, frame, &mut context));
+    assert_eq!(context.num_events, 1);
+}
+
+#[test]
+fn capped_components_are_left_due_rather_than_falling_behind() {
+    let mut allocator = EntityAllocator::default();
+    let (e1, e2) = (allocator.alloc(), allocator.alloc());
+    let mut context = Context::default();
+    context.components.a.set_event_cap(1, |_| ());
+    context.components.a.insert(e1, Periodic);
+    context.components.a.insert(e2, Periodic);
+    context
+        .components
+        .a
+        .get_with_schedule_mut(e2)
+        .unwrap()
+        .set_until_next_tick(Duration::from_millis(3));
+    let frame = Duration::from_millis(8);
+    process_entity_frame(e1, frame, &mut context);
+    process_entity_frame(e2, frame, &mut context);
+    assert_eq!(context.num_events, 1);
+    let table = &context.components.a;
+    assert_eq!(
+        table.get_with_schedule(e1).unwrap().until_next_tick(),
+        Duration::from_millis(2)
+    );
+    // e2 became due during the frame, and is due at the start of the next one
+    assert_eq!(
+        table.get_with_schedule(e2).unwrap().until_next_tick(),
+        Duration::ZERO
+    );
+}
+
+#[test]
+fn event_cap_accepts_closures_capturing_state() {
+    let mut allocator = EntityAllocator::default();
+    let (e1, e2) = (allocator.alloc(), allocator.alloc());
+    let mut context = Context::default();
+    let overflows = Arc::new(AtomicU32::new(0));
+    let counter = Arc::clone(&overflows);
+    context.components.a.set_event_cap(1, move |_| {
+        counter.fetch_add(1, Ordering::Relaxed);
+    });
+    context.components.a.insert(e1, Periodic);
+    context.components.a.insert(e2, Periodic);
+    let frame = Duration::from_millis(5);
+    process_entity_frame(e1, frame, &mut context);
+    process_entity_frame(e2, frame, &mut context);
+    assert_eq!(overflows.load(Ordering::Relaxed), 1);
+}
+
+#[test]
+fn process_table_frame_applies_limits() {
+    let mut allocator = EntityAllocator::default();
+    let (e1, e2) = (allocator.alloc(), allocator.alloc());
+    let mut context = Context::default();
+    context.table.set_event_cap(1, |_| ());
+    context.table.insert(e1, Periodic);
+    context.table.insert(e2, Periodic);
+    let frame = Duration::from_millis(8);
+    process_table_frame(frame, &mut context, &mut LinearScan::new(), |context| {
+        &mut context.table
+    });
+    assert_eq!(context.num_events, 1);
+    let due: Vec<_> = [e1, e2]
+        .iter()
+        .map(|&e| {
+            context
+                .table
+                .get_with_schedule(e)
+                .unwrap()
+                .until_next_tick()
+        })
+        .collect();
+    assert!(due.contains(&Duration::ZERO));
+    assert!(due.contains(&Duration::from_millis(2)));
+    let mut context = Context::default();
+    context
+        .table
+        .set_max_period(Duration::from_millis(5), |_| ());
+    context.table.insert(e1, Periodic);
+    process_table_frame(frame, &mut context, &mut LinearScan::new(), |context| {
+        &mut context.table
+    });
+    // ticks at 0ms and 5ms with the period clamped
+    assert_eq!(context.num_events, 2);
+}
+
+#[test]
+fn fixed_tables_apply_limits() {
+    let mut allocator = EntityAllocator::default();
+    let (e1, e2) = (allocator.alloc(), allocator.alloc());
+    let mut context = Context::default();
+    context.fixed.set_event_cap(1, |_| ());
+    context.fixed.insert(e1, Periodic).unwrap();
+    context.fixed.insert(e2, Periodic).unwrap();
+    let frame = Duration::from_millis(8);
+    for entity in [e1, e2] {
+        process_fixed_entity_frame(entity, frame, &mut context, |context| &mut context.fixed);
+    }
+    assert_eq!(context.num_events, 1);
+    assert_eq!(context.fixed.events_this_frame(), 1);
+    assert_eq!(
+        context
+            .fixed
+            .get_with_schedule(e2)
+            .unwrap()
+            .until_next_tick(),
+        Duration::ZERO
+    );
+    context.fixed.advance_simulated_time(frame);
+    assert_eq!(context.fixed.events_this_frame(), 0);
+}
+
+#[test]
+fn erased_tables_apply_limits() {
+    let mut allocator = EntityAllocator::default();
+    let (e1, e2) = (allocator.alloc(), allocator.alloc());
+    let mut context = Context::default();
+    let num_clamped = Arc::new(AtomicU32::new(0));
+    let mut table = RealtimeComponentTable::default();
+    table.set_event_cap(1, |_| ());
+    table.set_max_period(Duration::from_millis(4), {
+        let num_clamped = Arc::clone(&num_clamped);
+        move |_| {
+            num_clamped.fetch_add(1, Ordering::Relaxed);
+        }
+    });
+    table.insert(e1, Periodic);
+    table.insert(e2, Periodic);
+    let id = context.erased.register(table);
+    let frame = Duration::from_millis(2);
+    for entity in [e1, e2] {
+        process_entity_frame_erased(entity, frame, &mut context);
+    }
+    assert_eq!(context.num_events, 1);
+    assert_eq!(num_clamped.load(Ordering::Relaxed), 1);
+    let table = context.erased.get(id);
+    assert_eq!(table.events_this_frame(), 1);
+    assert_eq!(
+        table.get_with_schedule(e1).unwrap().until_next_tick(),
+        Duration::from_millis(2)
+    );
+    assert_eq!(
+        table.get_with_schedule(e2).unwrap().until_next_tick(),
+        Duration::ZERO
+    );
+    context.erased.advance_simulated_time(frame);
+    assert_eq!(context.erased.get(id).events_this_frame(), 0);
+    process_entity_frame_erased(e2, frame, &mut context);
+    assert_eq!(context.num_events, 2);
+}
+
+#[test]
+fn fast_forward_applies_event_cap() {
+    let entity = EntityAllocator::default().alloc();
+    let mut table = RealtimeComponentTable::default();
+    table.set_event_cap(3, |_| ());
+    table.insert(entity, Periodic);
+    let events = table.fast_forward(Duration::from_millis(100));
+    assert_eq!(events.len(), 3);
+    assert_eq!(
+        table.get_with_schedule(entity).unwrap().until_next_tick(),
+        Duration::ZERO
+    );
+}
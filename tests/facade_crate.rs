@@ -0,0 +1,66 @@
+/// Facade re-exporting this crate and serde, neither of which is imported where the macro is
+/// invoked
+mod engine {
+    pub use entity_table_realtime as realtime;
+    #[cfg(feature = "serialize")]
+    pub use serde;
+}
+
+mod game {
+    use crate::engine::realtime::{RealtimeComponent, RealtimeComponentApplyEvent};
+    use std::time::Duration;
+
+    #[cfg_attr(
+        feature = "serialize",
+        derive(crate::engine::serde::Serialize, crate::engine::serde::Deserialize)
+    )]
+    #[cfg_attr(feature = "serialize", serde(crate = "crate::engine::serde"))]
+    #[derive(Debug, Clone)]
+    pub struct Flicker;
+
+    impl RealtimeComponent for Flicker {
+        type Event = ();
+
+        fn tick(&mut self) -> (Self::Event, Duration) {
+            ((), Duration::from_millis(10))
+        }
+    }
+
+    impl RealtimeComponentApplyEvent<Context> for Flicker {
+        fn apply_event(_event: (), _entity: entity_table::Entity, context: &mut Context) {
+            context.num_flickers += 1;
+        }
+    }
+
+    #[derive(Default)]
+    pub struct Context {
+        pub num_flickers: usize,
+    }
+
+    crate::engine::realtime::declare_realtime_entity_module! {
+        #[serde(crate = "crate::engine::serde")]
+        components[Context] {
+            flicker: Flicker,
+        }
+    }
+
+    pub use components::RealtimeComponents;
+}
+
+#[test]
+fn macro_works_through_a_facade() {
+    let entity = entity_table::EntityAllocator::default().alloc();
+    let mut components = game::RealtimeComponents::default();
+    components.flicker.insert(entity, game::Flicker);
+    let mut context = game::Context::default();
+    let (events, _) = components.tick_entity(entity, std::time::Duration::from_millis(5));
+    events.apply(entity, &mut context);
+    assert_eq!(context.num_flickers, 1);
+}
+
+#[cfg(feature = "serialize")]
+#[test]
+fn generated_types_derive_serde_through_the_facade() {
+    fn assert_serde<T: engine::serde::Serialize + for<'de> engine::serde::Deserialize<'de>>() {}
+    assert_serde::<game::RealtimeComponents>();
+}
@@ -0,0 +1,125 @@
+use entity_table::{Entity, EntityAllocator};
+use entity_table_realtime::{
+    RealtimeComponent, RealtimeComponentApplyEvent, RealtimeComponentTable,
+    ScheduledRealtimeComponent, MAX_TICKS_PER_INSTANT,
+};
+use std::time::Duration;
+
+/// Keeps returning zero durations from `tick`
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone)]
+struct Stuck;
+
+impl RealtimeComponent for Stuck {
+    type Event = ();
+
+    fn tick(&mut self) -> (Self::Event, Duration) {
+        ((), Duration::ZERO)
+    }
+}
+
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone)]
+struct Periodic;
+
+impl RealtimeComponent for Periodic {
+    type Event = ();
+
+    fn tick(&mut self) -> (Self::Event, Duration) {
+        ((), Duration::from_millis(10))
+    }
+}
+
+impl RealtimeComponentApplyEvent<()> for Stuck {
+    fn apply_event(_event: (), _entity: Entity, _context: &mut ()) {}
+}
+
+impl RealtimeComponentApplyEvent<()> for Periodic {
+    fn apply_event(_event: (), _entity: Entity, _context: &mut ()) {}
+}
+
+entity_table_realtime::declare_realtime_entity_module! {
+    components[()] {
+        stuck: Stuck,
+        periodic: Periodic,
+    }
+}
+
+#[test]
+fn fast_forward_ends_when_components_tick_with_zero_durations() {
+    let entity = EntityAllocator::default().alloc();
+    let mut table = RealtimeComponentTable::default();
+    table.insert_with_schedule(
+        entity,
+        ScheduledRealtimeComponent::new(Stuck, Duration::from_millis(10)),
+    );
+    let events = table.fast_forward(Duration::from_millis(100));
+    assert_eq!(events.len(), MAX_TICKS_PER_INSTANT as usize);
+    assert!(events
+        .iter()
+        .all(|&(time, _, _)| time == Duration::from_millis(10)));
+    assert_eq!(
+        table.get_with_schedule(entity).unwrap().until_next_tick(),
+        Duration::ZERO
+    );
+}
+
+#[test]
+fn module_fast_forward_ends_when_components_tick_with_zero_durations() {
+    let entity = EntityAllocator::default().alloc();
+    let mut components = components::RealtimeComponents::default();
+    components.stuck.insert_with_schedule(
+        entity,
+        ScheduledRealtimeComponent::new(Stuck, Duration::from_millis(10)),
+    );
+    components.periodic.insert(entity, Periodic);
+    let events = components.fast_forward(Duration::from_millis(100));
+    // the periodic component ticks at 0ms, then both tick at 10ms until the limit is reached
+    assert_eq!(events.len(), 1 + MAX_TICKS_PER_INSTANT as usize);
+    assert!(events[1..]
+        .iter()
+        .all(|&(time, _, _)| time == Duration::from_millis(10)));
+    // the rest of the time is skipped, leaving every component due
+    assert_eq!(
+        components
+            .stuck
+            .get_with_schedule(entity)
+            .unwrap()
+            .until_next_tick(),
+        Duration::ZERO
+    );
+    assert_eq!(
+        components
+            .periodic
+            .get_with_schedule(entity)
+            .unwrap()
+            .until_next_tick(),
+        Duration::ZERO
+    );
+}
+
+#[test]
+fn module_fast_forward_leaves_capped_components_due() {
+    let mut allocator = EntityAllocator::default();
+    let (e1, e2) = (allocator.alloc(), allocator.alloc());
+    let mut components = components::RealtimeComponents::default();
+    components.periodic.set_event_cap(1, |_| ());
+    components.periodic.insert(e1, Periodic);
+    components.periodic.insert_with_schedule(
+        e2,
+        ScheduledRealtimeComponent::new(Periodic, Duration::from_millis(5)),
+    );
+    let events = components.fast_forward(Duration::from_millis(100));
+    assert_eq!(events.len(), 1);
+    // neither component falls behind while held back by the cap
+    for entity in [e1, e2] {
+        assert_eq!(
+            components
+                .periodic
+                .get_with_schedule(entity)
+                .unwrap()
+                .until_next_tick(),
+            Duration::ZERO
+        );
+    }
+}
@@ -0,0 +1,64 @@
+use entity_table::{Entity, EntityAllocator};
+use entity_table_realtime::fixed::{process_fixed_table_frame, FixedRealtimeComponentTable};
+use entity_table_realtime::stats::{self, CountingAllocator};
+use entity_table_realtime::{RealtimeComponent, RealtimeComponentApplyEvent, RealtimeFrameHooks};
+use std::time::Duration;
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator(std::alloc::System);
+
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone)]
+struct Periodic(Duration);
+
+impl RealtimeComponent for Periodic {
+    type Event = u32;
+
+    fn tick(&mut self) -> (Self::Event, Duration) {
+        (1, self.0)
+    }
+}
+
+impl RealtimeComponentApplyEvent<Context> for Periodic {
+    fn apply_event(event: u32, _entity: Entity, context: &mut Context) {
+        context.total += event;
+    }
+}
+
+struct Context {
+    periodic: FixedRealtimeComponentTable<Periodic, 4>,
+    total: u32,
+}
+
+impl RealtimeFrameHooks for Context {}
+
+#[test]
+fn fixed_table_frames_do_not_allocate() {
+    let mut entity_allocator = EntityAllocator::default();
+    let mut context = Context {
+        periodic: FixedRealtimeComponentTable::new(),
+        total: 0,
+    };
+    let fast = entity_allocator.alloc();
+    let slow = entity_allocator.alloc();
+    context
+        .periodic
+        .insert(fast, Periodic(Duration::from_millis(10)))
+        .unwrap();
+    context
+        .periodic
+        .insert(slow, Periodic(Duration::from_millis(30)))
+        .unwrap();
+    stats::take_allocations();
+    for _ in 0..10 {
+        process_fixed_table_frame(Duration::from_millis(35), &mut context, |context| {
+            &mut context.periodic
+        });
+    }
+    assert_eq!(stats::take_allocations(), 0);
+    assert_eq!(context.total, 36 + 12);
+    assert_eq!(
+        context.periodic.simulated_time(),
+        Duration::from_millis(350)
+    );
+}
@@ -0,0 +1,115 @@
+use entity_table::{Entity, EntityAllocator};
+use entity_table_realtime::fixed::{
+    process_fixed_entity_frame, process_fixed_table_frame, FixedRealtimeComponentTable,
+    FixedTableFull,
+};
+use entity_table_realtime::{RealtimeComponent, RealtimeComponentApplyEvent, RealtimeFrameHooks};
+use std::time::Duration;
+
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+struct Periodic(u64);
+
+impl RealtimeComponent for Periodic {
+    type Event = u64;
+
+    fn tick(&mut self) -> (Self::Event, Duration) {
+        (self.0, Duration::from_millis(self.0))
+    }
+}
+
+impl RealtimeComponentApplyEvent<Context> for Periodic {
+    fn apply_event(event: u64, entity: Entity, context: &mut Context) {
+        context.events.push((entity, event));
+    }
+}
+
+#[derive(Default)]
+struct Context {
+    periodic: FixedRealtimeComponentTable<Periodic, 2>,
+    events: Vec<(Entity, u64)>,
+}
+
+impl RealtimeFrameHooks for Context {}
+
+fn entities(n: usize) -> Vec<Entity> {
+    let mut allocator = EntityAllocator::default();
+    (0..n).map(|_| allocator.alloc()).collect()
+}
+
+#[test]
+fn inserting_into_a_full_table_returns_the_component() {
+    let e = entities(3);
+    let mut table = FixedRealtimeComponentTable::<Periodic, 2>::new();
+    assert_eq!(table.capacity(), 2);
+    assert!(table.is_empty());
+    assert_eq!(table.insert(e[0], Periodic(1)), Ok(None));
+    assert_eq!(table.insert(e[1], Periodic(2)), Ok(None));
+    assert!(table.is_full());
+    assert_eq!(
+        table.insert(e[2], Periodic(3)),
+        Err(FixedTableFull(Periodic(3)))
+    );
+    // replacing the component of an entity in the table is allowed when it's full
+    assert_eq!(table.insert(e[1], Periodic(4)), Ok(Some(Periodic(2))));
+    assert_eq!(table.get(e[1]), Some(&Periodic(4)));
+    assert_eq!(table.len(), 2);
+    assert_eq!(table.remove(e[0]), Some(Periodic(1)));
+    assert!(!table.contains(e[0]));
+    assert_eq!(table.insert(e[2], Periodic(3)), Ok(None));
+    let mut entities = table.entities().collect::<Vec<_>>();
+    entities.sort();
+    assert_eq!(entities, vec![e[1], e[2]]);
+}
+
+#[test]
+fn fixed_entity_frames_apply_events_in_order() {
+    let e = entities(2);
+    let mut context = Context::default();
+    context.periodic.insert(e[0], Periodic(10)).unwrap();
+    let frame = Duration::from_millis(25);
+    assert!(!process_fixed_entity_frame(
+        e[1],
+        frame,
+        &mut context,
+        |c| { &mut c.periodic }
+    ));
+    assert!(context.events.is_empty());
+    assert!(process_fixed_entity_frame(e[0], frame, &mut context, |c| {
+        &mut c.periodic
+    }));
+    // ticks at 0ms, 10ms and 20ms
+    assert_eq!(context.events, vec![(e[0], 10); 3]);
+}
+
+#[test]
+fn fixed_table_frames_process_every_entity_and_advance_time() {
+    let e = entities(2);
+    let mut context = Context::default();
+    context.periodic.insert(e[0], Periodic(10)).unwrap();
+    context.periodic.insert(e[1], Periodic(15)).unwrap();
+    process_fixed_table_frame(Duration::from_millis(20), &mut context, |c| &mut c.periodic);
+    // ticks at 0ms, 10ms and 20ms, and at 0ms and 15ms
+    assert_eq!(
+        context.events,
+        vec![(e[0], 10), (e[0], 10), (e[0], 10), (e[1], 15), (e[1], 15)]
+    );
+    assert_eq!(context.periodic.simulated_time(), Duration::from_millis(20));
+}
+
+#[cfg(feature = "serialize")]
+#[test]
+fn deserializing_more_entries_than_the_capacity_fails() {
+    let e = entities(3);
+    let mut table = FixedRealtimeComponentTable::<Periodic, 4>::new();
+    for (i, &entity) in e.iter().enumerate() {
+        table.insert(entity, Periodic(i as u64 + 1)).unwrap();
+    }
+    let json = serde_json::to_string(&table).unwrap();
+    let round_trip: FixedRealtimeComponentTable<Periodic, 4> = serde_json::from_str(&json).unwrap();
+    assert_eq!(round_trip.get(e[2]), Some(&Periodic(3)));
+    let error = serde_json::from_str::<FixedRealtimeComponentTable<Periodic, 2>>(&json)
+        .unwrap_err()
+        .to_string();
+    assert!(error.contains("full"), "{error}");
+}
@@ -0,0 +1,60 @@
+use entity_table::{Entity, EntityAllocator};
+use entity_table_realtime::{RealtimeComponent, RealtimeComponentApplyEvent};
+use std::time::Duration;
+
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone)]
+struct Level(u32);
+
+impl RealtimeComponent for Level {
+    type Event = u32;
+
+    fn tick(&mut self) -> (Self::Event, Duration) {
+        (self.0, Duration::from_millis(10))
+    }
+}
+
+impl RealtimeComponentApplyEvent<()> for Level {
+    fn apply_event(_event: u32, _entity: Entity, _context: &mut ()) {}
+}
+
+entity_table_realtime::declare_realtime_entity_module! {
+    components[()] {
+        light: Level,
+        sound: Level,
+    }
+}
+
+#[test]
+fn each_entity_is_visited_once_with_all_of_its_components() {
+    let mut allocator = EntityAllocator::default();
+    let (e1, e2, e3) = (allocator.alloc(), allocator.alloc(), allocator.alloc());
+    let mut components = components::RealtimeComponents::default();
+    components.light.insert(e1, Level(1));
+    components.sound.insert(e1, Level(2));
+    components.sound.insert(e2, Level(3));
+    components.light.insert(e3, Level(4));
+    let mut visited = Vec::new();
+    components.for_each_entity_mut(|entity, view| {
+        visited.push(entity);
+        // move the sound level into the light, where there's a light to move it into
+        if let (Some(light), Some(sound)) = (view.light, view.sound) {
+            light.0 += sound.0;
+            sound.0 = 0;
+        }
+    });
+    visited.sort_by_key(|entity| [e1, e2, e3].iter().position(|e| e == entity));
+    assert_eq!(visited, vec![e1, e2, e3]);
+    assert_eq!(components.light.get(e1).unwrap().0, 3);
+    assert_eq!(components.sound.get(e1).unwrap().0, 0);
+    assert_eq!(components.sound.get(e2).unwrap().0, 3);
+    assert_eq!(components.light.get(e3).unwrap().0, 4);
+}
+
+#[test]
+fn nothing_is_visited_without_components() {
+    let mut components = components::RealtimeComponents::default();
+    let mut num_visited = 0;
+    components.for_each_entity_mut(|_, _| num_visited += 1);
+    assert_eq!(num_visited, 0);
+}
@@ -0,0 +1,156 @@
+use entity_table::{ComponentTable, Entities, Entity, EntityAllocator};
+use entity_table_realtime::budget::{FrameCeiling, FrameCeilingExceeded};
+use entity_table_realtime::{
+    process_entity_frame_with_policy, AnimationContext, ContextContainsRealtimeComponents,
+    RealtimeComponent, RealtimeComponentApplyEvent,
+};
+use std::time::Duration;
+
+/// Ticks with the given period, which may be zero
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone)]
+struct Periodic(Duration);
+
+impl RealtimeComponent for Periodic {
+    type Event = ();
+
+    fn tick(&mut self) -> (Self::Event, Duration) {
+        ((), self.0)
+    }
+}
+
+impl<'a> RealtimeComponentApplyEvent<Context<'a>> for Periodic {
+    fn apply_event(_event: (), entity: Entity, context: &mut Context<'a>) {
+        context.applied.push(entity);
+    }
+}
+
+entity_table_realtime::declare_realtime_entity_module! {
+    components<'a>[Context<'a>] {
+        periodic: Periodic,
+    }
+}
+
+struct Context<'a> {
+    components: &'a mut components::RealtimeComponents,
+    entities: &'a ComponentTable<()>,
+    applied: &'a mut Vec<Entity>,
+}
+
+impl<'a> ContextContainsRealtimeComponents for Context<'a> {
+    type Components = components::RealtimeComponents;
+    fn components_mut(&mut self) -> &mut Self::Components {
+        self.components
+    }
+    fn realtime_entities(&self) -> Entities<'_> {
+        self.entities.entities()
+    }
+}
+
+const FRAME: Duration = Duration::from_millis(25);
+
+fn spawn(
+    periods: &[Duration],
+) -> (
+    Vec<Entity>,
+    components::RealtimeComponents,
+    ComponentTable<()>,
+) {
+    let mut allocator = EntityAllocator::default();
+    let mut components = components::RealtimeComponents::default();
+    let mut entities = ComponentTable::default();
+    let spawned = periods
+        .iter()
+        .map(|&period| {
+            let entity = allocator.alloc();
+            entities.insert(entity, ());
+            components.periodic.insert(entity, Periodic(period));
+            entity
+        })
+        .collect();
+    (spawned, components, entities)
+}
+
+#[test]
+fn frames_within_the_ceiling_are_processed_normally() {
+    let (e, mut components, entities) = spawn(&[Duration::from_millis(10); 2]);
+    let mut applied = Vec::new();
+    let result = AnimationContext::new().tick_with_ceiling(
+        Context {
+            components: &mut components,
+            entities: &entities,
+            applied: &mut applied,
+        },
+        FRAME,
+        100,
+    );
+    assert_eq!(result, Ok(()));
+    // ticks at 0ms, 10ms and 20ms
+    assert_eq!(applied, vec![e[0], e[0], e[0], e[1], e[1], e[1]]);
+    assert_eq!(components.periodic.simulated_time(), FRAME);
+}
+
+#[test]
+fn components_which_never_advance_are_stopped_by_the_ceiling() {
+    let (e, mut components, entities) = spawn(&[Duration::ZERO, Duration::from_millis(10)]);
+    let mut applied = Vec::new();
+    let result = AnimationContext::new().tick_with_ceiling(
+        Context {
+            components: &mut components,
+            entities: &entities,
+            applied: &mut applied,
+        },
+        FRAME,
+        50,
+    );
+    assert_eq!(
+        result,
+        Err(FrameCeilingExceeded {
+            max_steps: 50,
+            entity: e[0],
+        })
+    );
+    // the entity after the one which reached the ceiling isn't processed
+    assert_eq!(applied.len(), 50);
+    assert!(applied.iter().all(|&entity| entity == e[0]));
+    assert_eq!(components.periodic.time_to_due(e[1]), Some(Duration::ZERO));
+    // simulated time is advanced regardless
+    assert_eq!(components.periodic.simulated_time(), FRAME);
+}
+
+#[test]
+fn frame_ceilings_count_steps_across_entities_until_reset() {
+    let (e, mut components, entities) = spawn(&[Duration::from_millis(10); 2]);
+    let mut applied = Vec::new();
+    let mut context = Context {
+        components: &mut components,
+        entities: &entities,
+        applied: &mut applied,
+    };
+    let mut ceiling = FrameCeiling::new(6);
+    process_entity_frame_with_policy(e[0], FRAME, &mut context, &mut ceiling);
+    // a step for each tick, then a step to the end of the frame
+    assert_eq!(ceiling.steps(), 4);
+    assert_eq!(ceiling.exceeded(), None);
+    process_entity_frame_with_policy(e[1], FRAME, &mut context, &mut ceiling);
+    assert_eq!(ceiling.steps(), 6);
+    assert_eq!(
+        ceiling.exceeded(),
+        Some(FrameCeilingExceeded {
+            max_steps: 6,
+            entity: e[1],
+        })
+    );
+    assert_eq!(
+        ceiling.exceeded().unwrap().to_string(),
+        format!(
+            "realtime frame exceeded its ceiling of 6 steps while processing {:?}",
+            e[1]
+        )
+    );
+    ceiling.reset();
+    assert_eq!(ceiling.steps(), 0);
+    assert_eq!(ceiling.exceeded(), None);
+    // ticks of the second entity at 0ms and 10ms
+    assert_eq!(applied, vec![e[0], e[0], e[0], e[1], e[1]]);
+}
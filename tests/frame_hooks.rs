@@ -0,0 +1,155 @@
+use entity_table::{ComponentTable, Entities, Entity, EntityAllocator};
+use entity_table_realtime::scheduler::{process_table_frame, LinearScan};
+use entity_table_realtime::{
+    defer_entity_frame, process_entity_frame_dyn, testing, ContextContainsRealtimeComponents,
+    DeferredRealtimeEvents, RealtimeComponent, RealtimeComponentApplyEvent, RealtimeComponentTable,
+    RealtimeCoordinator,
+};
+use std::time::Duration;
+
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone)]
+struct Periodic;
+
+impl RealtimeComponent for Periodic {
+    type Event = ();
+
+    fn tick(&mut self) -> (Self::Event, Duration) {
+        ((), Duration::from_millis(10))
+    }
+}
+
+impl RealtimeComponentApplyEvent<Context> for Periodic {
+    fn apply_event(_event: (), _entity: Entity, context: &mut Context) {
+        context.num_events += 1;
+    }
+}
+
+entity_table_realtime::declare_realtime_entity_module! {
+    components[Context] {
+        periodic: Periodic,
+    }
+}
+
+struct Context {
+    components: components::RealtimeComponents,
+    table: RealtimeComponentTable<Periodic>,
+    entities: ComponentTable<()>,
+    dormant: bool,
+    time_scale: f64,
+    num_events: u32,
+    num_before_tick: u32,
+    num_after_frame: u32,
+}
+
+impl Context {
+    fn new(entity: Entity) -> Self {
+        let mut context = Self {
+            components: Default::default(),
+            table: Default::default(),
+            entities: Default::default(),
+            dormant: false,
+            time_scale: 1.0,
+            num_events: 0,
+            num_before_tick: 0,
+            num_after_frame: 0,
+        };
+        context.entities.insert(entity, ());
+        context.components.periodic.insert(entity, Periodic);
+        context.table.insert(entity, Periodic);
+        context
+    }
+}
+
+impl ContextContainsRealtimeComponents for Context {
+    type Components = components::RealtimeComponents;
+    fn components_mut(&mut self) -> &mut Self::Components {
+        &mut self.components
+    }
+    fn realtime_entities(&self) -> Entities<'_> {
+        self.entities.entities()
+    }
+
+    fn realtime_dormant(&self, _entity: Entity) -> bool {
+        self.dormant
+    }
+
+    fn realtime_time_scale(&self, _entity: Entity) -> f64 {
+        self.time_scale
+    }
+
+    fn before_tick(&mut self, _entity: Entity) {
+        self.num_before_tick += 1;
+    }
+
+    fn after_entity_frame(&mut self, _entity: Entity) {
+        self.num_after_frame += 1;
+    }
+}
+
+const FRAME: Duration = Duration::from_millis(100);
+
+/// Process a frame through each entry point which doesn't go through `process_entity_frame`,
+/// each with its own copy of the entity's components
+fn process_frame_of_every_entry_point(entity: Entity, context: &mut Context) {
+    let mut components = std::mem::take(&mut context.components);
+    let coordinated = components.clone();
+    process_entity_frame_dyn(entity, FRAME, &mut components, context);
+    let mut coordinator = RealtimeCoordinator::<Context>::new();
+    coordinator.register(coordinated);
+    coordinator.process_entity_frame(entity, FRAME, context);
+    process_table_frame(FRAME, context, &mut LinearScan::new(), |context| {
+        &mut context.table
+    });
+}
+
+#[test]
+fn dormant_entities_are_not_simulated_on_any_path() {
+    let entity = EntityAllocator::default().alloc();
+    let mut context = Context::new(entity);
+    context.dormant = true;
+    process_frame_of_every_entry_point(entity, &mut context);
+    assert_eq!(context.num_events, 0);
+    assert_eq!(context.num_after_frame, 3);
+    let mut context = Context::new(entity);
+    context.dormant = true;
+    assert!(testing::simulate(&mut context, FRAME, FRAME).is_empty());
+    assert_eq!(context.num_after_frame, 1);
+}
+
+#[test]
+fn time_scale_and_hooks_apply_on_every_path() {
+    let entity = EntityAllocator::default().alloc();
+    let mut context = Context::new(entity);
+    context.time_scale = 0.5;
+    process_frame_of_every_entry_point(entity, &mut context);
+    // ticks at 0ms, 10ms, ..., 50ms of each 50ms scaled frame
+    assert_eq!(context.num_events, 3 * 6);
+    assert_eq!(context.num_before_tick, 3 * 6);
+    assert_eq!(context.num_after_frame, 3);
+    let mut context = Context::new(entity);
+    context.time_scale = 0.5;
+    assert_eq!(testing::simulate(&mut context, FRAME, FRAME).len(), 6);
+    assert_eq!(context.num_after_frame, 1);
+}
+
+#[test]
+fn deferred_frames_call_hooks_as_events_are_deferred() {
+    let entity = EntityAllocator::default().alloc();
+    let mut context = Context::new(entity);
+    let mut deferred = DeferredRealtimeEvents::<_, 16>::new();
+    defer_entity_frame(entity, FRAME, &mut context, &mut deferred);
+    // ticks at 0ms, 10ms, ..., 100ms
+    assert_eq!(context.num_before_tick, 11);
+    assert_eq!(context.num_after_frame, 1);
+    assert_eq!(context.num_events, 0);
+    deferred.apply(&mut context);
+    assert_eq!(context.num_events, 11);
+    assert_eq!(context.num_before_tick, 11);
+    let mut context = Context::new(entity);
+    context.dormant = true;
+    defer_entity_frame(entity, FRAME, &mut context, &mut deferred);
+    assert!(deferred.is_empty());
+    assert_eq!(context.num_before_tick, 0);
+    assert_eq!(context.num_after_frame, 1);
+}
@@ -0,0 +1,49 @@
+use entity_table_realtime::{period_from_fps, period_from_hz, Frequency};
+use std::time::Duration;
+
+#[test]
+fn periods_are_rounded_to_the_nearest_nanosecond() {
+    assert_eq!(period_from_fps(60), Duration::from_nanos(16_666_667));
+    assert_eq!(period_from_fps(30), Duration::from_nanos(33_333_333));
+    assert_eq!(period_from_fps(1), Duration::from_secs(1));
+    assert_eq!(period_from_hz(60.0), period_from_fps(60));
+    assert_eq!(period_from_hz(0.5), Duration::from_secs(2));
+    assert_eq!(period_from_hz(3.0), Duration::from_nanos(333_333_333));
+}
+
+#[test]
+fn very_high_frequencies_have_a_non_zero_period() {
+    assert_eq!(period_from_hz(1e12), Duration::from_nanos(1));
+}
+
+#[test]
+fn frequencies_round_trip_through_periods() {
+    let frequency = Frequency::from_period(Duration::from_millis(250));
+    assert_eq!(frequency.hz(), 4.0);
+    assert_eq!(frequency.period(), Duration::from_millis(250));
+    assert!(Frequency::from_hz(10.0) > frequency);
+}
+
+#[test]
+#[should_panic(expected = "frequency must be positive and finite")]
+fn zero_frequencies_are_rejected() {
+    Frequency::from_hz(0.0);
+}
+
+#[test]
+#[should_panic(expected = "frequency must be positive and finite")]
+fn nan_frequencies_are_rejected() {
+    period_from_hz(f64::NAN);
+}
+
+#[test]
+#[should_panic(expected = "frame rate must be positive")]
+fn zero_frame_rates_are_rejected() {
+    period_from_fps(0);
+}
+
+#[test]
+#[should_panic(expected = "period must be non-zero")]
+fn zero_periods_are_rejected() {
+    Frequency::from_period(Duration::ZERO);
+}
@@ -0,0 +1,92 @@
+use entity_table::{ComponentTable, Entities, Entity, EntityAllocator};
+use entity_table_realtime::{
+    process_entity_frame, ContextContainsRealtimeComponents, RealtimeComponent,
+    RealtimeComponentApplyEvent,
+};
+use std::time::Duration;
+
+trait Sink {
+    fn record(&mut self, value: u32);
+}
+
+impl Sink for Vec<u32> {
+    fn record(&mut self, value: u32) {
+        self.push(value);
+    }
+}
+
+/// Keeps only the most recent value
+impl Sink for Option<u32> {
+    fn record(&mut self, value: u32) {
+        *self = Some(value);
+    }
+}
+
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone)]
+struct Counter(u32);
+
+impl RealtimeComponent for Counter {
+    type Event = u32;
+
+    fn tick(&mut self) -> (Self::Event, Duration) {
+        self.0 += 1;
+        (self.0, Duration::from_millis(10))
+    }
+}
+
+impl<'a, S> RealtimeComponentApplyEvent<Context<'a, S>> for Counter
+where
+    S: Sink,
+{
+    fn apply_event(value: u32, _entity: Entity, context: &mut Context<'a, S>) {
+        context.sink.record(value);
+    }
+}
+
+entity_table_realtime::declare_realtime_entity_module! {
+    components<'a, S>[Context<'a, S>] where S: Sink {
+        counter: Counter,
+    }
+}
+
+struct Context<'a, S> {
+    components: &'a mut components::RealtimeComponents,
+    entities: &'a ComponentTable<()>,
+    sink: S,
+}
+
+impl<'a, S: Sink> ContextContainsRealtimeComponents for Context<'a, S> {
+    type Components = components::RealtimeComponents;
+    fn components_mut(&mut self) -> &mut Self::Components {
+        self.components
+    }
+    fn realtime_entities(&self) -> Entities<'_> {
+        self.entities.entities()
+    }
+}
+
+#[test]
+fn one_module_serves_contexts_with_different_type_parameters() {
+    let entity = EntityAllocator::default().alloc();
+    let mut components = components::RealtimeComponents::default();
+    let mut entities = ComponentTable::default();
+    entities.insert(entity, ());
+    components.counter.insert(entity, Counter(0));
+    let frame = Duration::from_millis(25);
+    let mut all = Context {
+        components: &mut components,
+        entities: &entities,
+        sink: Vec::new(),
+    };
+    process_entity_frame(entity, frame, &mut all);
+    assert_eq!(all.sink, vec![1, 2, 3]);
+    let mut latest = Context {
+        components: &mut components,
+        entities: &entities,
+        sink: None,
+    };
+    process_entity_frame(entity, frame, &mut latest);
+    // ticks at 30ms, 40ms and 50ms
+    assert_eq!(latest.sink, Some(6));
+}
@@ -0,0 +1,216 @@
+use entity_table::{Entity, EntityAllocator};
+use entity_table_realtime::scheduler::{
+    process_table_frame, HeapScheduler, IndexedScheduler, LinearScan, Scheduler,
+};
+use entity_table_realtime::{
+    RealtimeComponent, RealtimeComponentApplyEvent, RealtimeComponentTable, RealtimeFrameHooks,
+};
+use std::time::Duration;
+
+#[derive(Debug, Clone)]
+struct Periodic(Duration);
+
+impl RealtimeComponent for Periodic {
+    type Event = ();
+
+    fn tick(&mut self) -> (Self::Event, Duration) {
+        ((), self.0)
+    }
+}
+
+impl RealtimeComponentApplyEvent<Context> for Periodic {
+    fn apply_event(_event: (), entity: Entity, context: &mut Context) {
+        context.ticked.push(entity);
+    }
+}
+
+#[derive(Default)]
+struct Context {
+    table: RealtimeComponentTable<Periodic>,
+    ticked: Vec<Entity>,
+    /// Entity whose time runs at half speed, so its frames are simulated separately
+    slow: Option<Entity>,
+}
+
+impl RealtimeFrameHooks for Context {
+    fn realtime_time_scale(&self, entity: Entity) -> f64 {
+        if self.slow == Some(entity) {
+            0.5
+        } else {
+            1.0
+        }
+    }
+}
+
+fn context_with_periods(entity_allocator: &mut EntityAllocator, periods_ms: &[u64]) -> Context {
+    let mut context = Context::default();
+    for &period in periods_ms {
+        context.table.insert(
+            entity_allocator.alloc(),
+            Periodic(Duration::from_millis(period)),
+        );
+    }
+    context
+}
+
+fn run_frames(context: &mut Context, scheduler: &mut impl Scheduler<Periodic>) {
+    for _ in 0..10 {
+        process_table_frame(Duration::from_millis(16), context, scheduler, |context| {
+            &mut context.table
+        });
+    }
+}
+
+#[test]
+fn indexed_scheduler_ticks_in_the_same_order_as_linear_scan() {
+    let mut entity_allocator = EntityAllocator::default();
+    let mut linear = context_with_periods(&mut entity_allocator, &[7, 10, 13, 30]);
+    let mut indexed = Context {
+        table: linear.table.clone(),
+        ticked: Vec::new(),
+        slow: None,
+    };
+    run_frames(&mut linear, &mut LinearScan::new());
+    let mut scheduler = IndexedScheduler::new();
+    run_frames(&mut indexed, &mut scheduler);
+    assert_eq!(indexed.ticked, linear.ticked);
+    assert_eq!(
+        scheduler.min_until_next_tick(&indexed.table),
+        linear.table.min_until_next_tick()
+    );
+}
+
+#[test]
+fn heap_scheduler_ticks_in_the_same_order_as_linear_scan() {
+    let mut entity_allocator = EntityAllocator::default();
+    let mut linear = context_with_periods(&mut entity_allocator, &[7, 10, 13, 30, 10, 2]);
+    let mut heap = Context {
+        table: linear.table.clone(),
+        ticked: Vec::new(),
+        slow: None,
+    };
+    run_frames(&mut linear, &mut LinearScan::new());
+    let mut scheduler = HeapScheduler::new();
+    run_frames(&mut heap, &mut scheduler);
+    assert_eq!(heap.ticked, linear.ticked);
+    assert_eq!(
+        scheduler.min_until_next_tick(&heap.table),
+        linear.table.min_until_next_tick()
+    );
+}
+
+#[test]
+fn indexed_scheduler_follows_changes_to_the_table() {
+    let mut entity_allocator = EntityAllocator::default();
+    let mut context = context_with_periods(&mut entity_allocator, &[10, 25]);
+    let mut scheduler = IndexedScheduler::new();
+    run_frames(&mut context, &mut scheduler);
+    assert_eq!(
+        scheduler.min_until_next_tick(&context.table),
+        context.table.min_until_next_tick()
+    );
+    let entity = entity_allocator.alloc();
+    context
+        .table
+        .insert(entity, Periodic(Duration::from_millis(1)));
+    assert_eq!(
+        scheduler.min_until_next_tick(&context.table),
+        Some(Duration::ZERO)
+    );
+    context.table.set_enabled(entity, false);
+    assert_eq!(
+        scheduler.min_until_next_tick(&context.table),
+        context.table.min_until_next_tick()
+    );
+    context.table.clear();
+    assert_eq!(scheduler.min_until_next_tick(&context.table), None);
+}
+
+#[test]
+fn iteration_by_schedule_is_soonest_first() {
+    let mut entity_allocator = EntityAllocator::default();
+    let mut context = context_with_periods(&mut entity_allocator, &[7, 10, 13, 30]);
+    let mut scheduler = IndexedScheduler::new();
+    run_frames(&mut context, &mut scheduler);
+    let sorted = context
+        .table
+        .sorted_by_schedule()
+        .into_iter()
+        .map(|(entity, scheduled)| (entity, scheduled.until_next_tick()))
+        .collect::<Vec<_>>();
+    assert!(sorted.windows(2).all(|pair| pair[0].1 <= pair[1].1));
+    let indexed = scheduler
+        .iter_by_schedule(&context.table)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        indexed.iter().map(|&(_, until)| until).collect::<Vec<_>>(),
+        sorted.iter().map(|&(_, until)| until).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn processing_table_frames_keeps_the_index_valid() {
+    let mut entity_allocator = EntityAllocator::default();
+    let mut context = context_with_periods(&mut entity_allocator, &[7, 10, 13, 30]);
+    let entity = entity_allocator.alloc();
+    context
+        .table
+        .insert(entity, Periodic(Duration::from_millis(3)));
+    context
+        .table
+        .get_with_schedule_mut(entity)
+        .unwrap()
+        .set_max_missed_periods(Some(1));
+    context.table.set_event_cap(4, |_| ());
+    let mut scheduler = IndexedScheduler::new();
+    let generation = context.table.schedule_generation();
+    run_frames(&mut context, &mut scheduler);
+    assert_eq!(context.table.schedule_generation(), generation);
+    assert_eq!(
+        scheduler.min_until_next_tick(&context.table),
+        context.table.min_until_next_tick()
+    );
+    let indexed = scheduler
+        .iter_by_schedule(&context.table)
+        .map(|(entity, until)| (entity, Some(until)))
+        .collect::<Vec<_>>();
+    for (entity, until) in indexed {
+        assert_eq!(context.table.time_to_due(entity), until);
+    }
+}
+
+#[test]
+fn entities_simulated_separately_are_excluded_by_the_scheduler() {
+    let mut entity_allocator = EntityAllocator::default();
+    let mut linear = context_with_periods(&mut entity_allocator, &[7, 10, 13, 30]);
+    linear.slow = linear.table.entities().nth(1);
+    let mut indexed = Context {
+        table: linear.table.clone(),
+        ticked: Vec::new(),
+        slow: linear.slow,
+    };
+    let mut heap = Context {
+        table: linear.table.clone(),
+        ticked: Vec::new(),
+        slow: linear.slow,
+    };
+    run_frames(&mut linear, &mut LinearScan::new());
+    let generation = indexed.table.schedule_generation();
+    let mut indexed_scheduler = IndexedScheduler::new();
+    run_frames(&mut indexed, &mut indexed_scheduler);
+    // the slow entity's component is never disabled, which would rebuild the index
+    assert_eq!(indexed.table.schedule_generation(), generation);
+    run_frames(&mut heap, &mut HeapScheduler::new());
+    assert_eq!(indexed.ticked, linear.ticked);
+    assert_eq!(heap.ticked, linear.ticked);
+    assert_eq!(
+        indexed_scheduler.min_until_next_tick(&indexed.table),
+        linear.table.min_until_next_tick()
+    );
+    for entity in linear.table.entities() {
+        assert_eq!(
+            indexed.table.time_to_due(entity),
+            linear.table.time_to_due(entity)
+        );
+    }
+}
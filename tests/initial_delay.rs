@@ -0,0 +1,112 @@
+use entity_table::{Entity, EntityAllocator};
+use entity_table_realtime::{RealtimeComponent, RealtimeComponentApplyEvent};
+use std::time::Duration;
+
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone)]
+struct Periodic;
+
+impl RealtimeComponent for Periodic {
+    type Event = ();
+
+    fn tick(&mut self) -> (Self::Event, Duration) {
+        ((), Duration::from_millis(10))
+    }
+}
+
+impl RealtimeComponentApplyEvent<()> for Periodic {
+    fn apply_event(_event: (), _entity: Entity, _context: &mut ()) {}
+}
+
+entity_table_realtime::declare_realtime_entity_module! {
+    components[()] {
+        immediate: Periodic,
+        nanos: Periodic = 250ns,
+        micros: Periodic = 500us,
+        millis: Periodic = 100ms,
+        seconds: Periodic = 2s,
+    }
+}
+
+fn entity_data() -> components::RealtimeEntityData {
+    components::RealtimeEntityData {
+        immediate: Some(Periodic),
+        nanos: Some(Periodic),
+        micros: Some(Periodic),
+        millis: Some(Periodic),
+        seconds: Some(Periodic),
+    }
+}
+
+fn initial_delays(
+    components: &components::RealtimeComponents,
+    entity: Entity,
+) -> [Option<Duration>; 5] {
+    [
+        components.immediate.time_to_due(entity),
+        components.nanos.time_to_due(entity),
+        components.micros.time_to_due(entity),
+        components.millis.time_to_due(entity),
+        components.seconds.time_to_due(entity),
+    ]
+}
+
+const EXPECTED: [Option<Duration>; 5] = [
+    Some(Duration::ZERO),
+    Some(Duration::from_nanos(250)),
+    Some(Duration::from_micros(500)),
+    Some(Duration::from_millis(100)),
+    Some(Duration::from_secs(2)),
+];
+
+#[test]
+fn initial_delays_are_declared_with_units() {
+    assert_eq!(components::initial_delay::immediate, Duration::ZERO);
+    assert_eq!(components::initial_delay::nanos, Duration::from_nanos(250));
+    assert_eq!(
+        components::initial_delay::micros,
+        Duration::from_micros(500)
+    );
+    assert_eq!(
+        components::initial_delay::millis,
+        Duration::from_millis(100)
+    );
+    assert_eq!(components::initial_delay::seconds, Duration::from_secs(2));
+}
+
+#[test]
+fn entity_data_is_inserted_with_initial_delays() {
+    let mut allocator = EntityAllocator::default();
+    let (e1, e2) = (allocator.alloc(), allocator.alloc());
+    let mut components = components::RealtimeComponents::default();
+    components.insert_entity_data(e1, entity_data());
+    assert_eq!(initial_delays(&components, e1), EXPECTED);
+    components.update_entity_data(e2, entity_data());
+    assert_eq!(initial_delays(&components, e2), EXPECTED);
+    // components inserted directly into a table are due immediately
+    components.millis.insert(e2, Periodic);
+    assert_eq!(components.millis.time_to_due(e2), Some(Duration::ZERO));
+}
+
+#[test]
+fn delayed_components_first_tick_after_their_delay() {
+    let entity = EntityAllocator::default().alloc();
+    let mut components = components::RealtimeComponents::default();
+    components.insert_entity_data(
+        entity,
+        components::RealtimeEntityData {
+            millis: Some(Periodic),
+            ..Default::default()
+        },
+    );
+    let events = components.fast_forward(Duration::from_millis(125));
+    let times: Vec<_> = events.iter().map(|&(time, _, _)| time).collect();
+    assert_eq!(
+        times,
+        vec![
+            Duration::from_millis(100),
+            Duration::from_millis(110),
+            Duration::from_millis(120)
+        ]
+    );
+}
@@ -0,0 +1,62 @@
+use entity_table::EntityAllocator;
+use entity_table_realtime::{RealtimeComponent, RealtimeComponentTable};
+use std::time::Duration;
+
+#[derive(Debug, Clone, PartialEq)]
+struct Periodic(u32);
+
+impl RealtimeComponent for Periodic {
+    type Event = u32;
+
+    fn tick(&mut self) -> (Self::Event, Duration) {
+        (self.0, Duration::from_millis(40))
+    }
+}
+
+const fn ms(millis: u64) -> Duration {
+    Duration::from_millis(millis)
+}
+
+#[test]
+fn first_ticks_are_spread_evenly_across_the_period() {
+    let mut allocator = EntityAllocator::default();
+    let entities = (0..4).map(|_| allocator.alloc()).collect::<Vec<_>>();
+    let mut table = RealtimeComponentTable::default();
+    table.insert_staggered(
+        entities
+            .iter()
+            .enumerate()
+            .map(|(i, &e)| (e, Periodic(i as u32))),
+        ms(40),
+    );
+    let delays = entities
+        .iter()
+        .map(|&entity| table.time_to_due(entity).unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(delays, vec![ms(0), ms(10), ms(20), ms(30)]);
+    // each 10ms frame, exactly one component ticks
+    for i in 0..8 {
+        let events = table.fast_forward(ms(if i == 0 { 0 } else { 10 }));
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].2, i % 4);
+    }
+}
+
+#[test]
+fn staggering_replaces_existing_components() {
+    let mut allocator = EntityAllocator::default();
+    let (e1, e2, e3) = (allocator.alloc(), allocator.alloc(), allocator.alloc());
+    let mut table = RealtimeComponentTable::default();
+    table.insert(e1, Periodic(0));
+    table.insert_staggered(
+        [(e1, Periodic(1)), (e2, Periodic(2)), (e3, Periodic(3))],
+        ms(10),
+    );
+    assert_eq!(table.get(e1), Some(&Periodic(1)));
+    assert_eq!(table.len(), 3);
+    // durations which don't divide evenly are rounded down to the nanosecond
+    assert_eq!(table.time_to_due(e2), Some(Duration::from_nanos(3_333_333)));
+    assert_eq!(table.time_to_due(e3), Some(Duration::from_nanos(6_666_666)));
+    table.insert_staggered(std::iter::empty::<(_, Periodic)>(), ms(10));
+    assert_eq!(table.len(), 3);
+}
@@ -0,0 +1,80 @@
+#![cfg(feature = "rand")]
+
+use entity_table::EntityAllocator;
+use entity_table_realtime::rand_core::{self, impls, RngCore};
+use entity_table_realtime::{RealtimeComponent, RealtimeComponentTable};
+use std::time::Duration;
+
+/// Always returns the same value
+struct Constant(u64);
+
+impl RngCore for Constant {
+    fn next_u32(&mut self) -> u32 {
+        (self.0 >> 32) as u32
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        impls::fill_bytes_via_next(self, dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand_core::Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Periodic(u32);
+
+impl RealtimeComponent for Periodic {
+    type Event = ();
+
+    fn tick(&mut self) -> (Self::Event, Duration) {
+        ((), Duration::from_millis(10))
+    }
+}
+
+const fn ms(millis: u64) -> Duration {
+    Duration::from_millis(millis)
+}
+
+#[test]
+fn first_ticks_are_delayed_by_a_random_duration_within_the_range() {
+    let mut allocator = EntityAllocator::default();
+    let entities = [allocator.alloc(), allocator.alloc(), allocator.alloc()];
+    let mut table = RealtimeComponentTable::default();
+    for (entity, random) in entities.into_iter().zip([0, u64::MAX / 2 + 1, u64::MAX]) {
+        table.insert_with_jitter(entity, Periodic(0), ms(5), ms(10), &mut Constant(random));
+    }
+    let delays = entities
+        .iter()
+        .map(|&entity| table.time_to_due(entity).unwrap())
+        .collect::<Vec<_>>();
+    // the jitter is always less than the range
+    assert_eq!(
+        delays,
+        vec![ms(5), ms(10), ms(15) - Duration::from_nanos(1)]
+    );
+}
+
+#[test]
+fn inserting_with_jitter_returns_the_replaced_component() {
+    let entity = EntityAllocator::default().alloc();
+    let mut table = RealtimeComponentTable::default();
+    let mut rng = Constant(0);
+    assert_eq!(
+        table.insert_with_jitter(entity, Periodic(1), ms(0), ms(10), &mut rng),
+        None
+    );
+    assert_eq!(
+        table.insert_with_jitter(entity, Periodic(2), ms(0), ms(10), &mut rng),
+        Some(Periodic(1))
+    );
+    // an empty range means no jitter
+    table.insert_with_jitter(entity, Periodic(3), ms(7), ms(0), &mut Constant(u64::MAX));
+    assert_eq!(table.time_to_due(entity), Some(ms(7)));
+}
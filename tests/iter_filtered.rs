@@ -0,0 +1,61 @@
+use entity_table::{Entity, EntityAllocator};
+use entity_table_realtime::{RealtimeComponent, RealtimeComponentTable};
+use std::time::Duration;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Light {
+    brightness: u8,
+}
+
+impl RealtimeComponent for Light {
+    type Event = u8;
+
+    fn tick(&mut self) -> (Self::Event, Duration) {
+        (self.brightness, Duration::from_millis(10))
+    }
+}
+
+fn table(entities: &[Entity]) -> RealtimeComponentTable<Light> {
+    let mut table = RealtimeComponentTable::default();
+    for (i, &entity) in entities.iter().enumerate() {
+        table.insert(
+            entity,
+            Light {
+                brightness: i as u8 * 10,
+            },
+        );
+    }
+    table
+}
+
+#[test]
+fn iter_filtered_yields_only_matching_components() {
+    let mut allocator = EntityAllocator::default();
+    let entities = [allocator.alloc(), allocator.alloc(), allocator.alloc()];
+    let table = table(&entities);
+    let lit = table
+        .iter_filtered(|_, light| light.brightness > 0)
+        .map(|(entity, light)| (entity, light.brightness))
+        .collect::<Vec<_>>();
+    assert_eq!(lit, vec![(entities[1], 10), (entities[2], 20)]);
+    let not_first = table
+        .iter_filtered(|entity, _| entity != entities[0])
+        .count();
+    assert_eq!(not_first, 2);
+    assert_eq!(table.iter_filtered(|_, _| false).count(), 0);
+}
+
+#[test]
+fn iter_filtered_mut_modifies_only_matching_components() {
+    let mut allocator = EntityAllocator::default();
+    let entities = [allocator.alloc(), allocator.alloc(), allocator.alloc()];
+    let mut table = table(&entities);
+    for (_, light) in table.iter_filtered_mut(|_, light| light.brightness < 20) {
+        light.brightness += 1;
+    }
+    let brightness = entities
+        .iter()
+        .map(|&entity| table.get(entity).unwrap().brightness)
+        .collect::<Vec<_>>();
+    assert_eq!(brightness, vec![1, 11, 20]);
+}
@@ -0,0 +1,60 @@
+use entity_table::EntityAllocator;
+use entity_table_realtime::{
+    RealtimeComponent, RealtimeComponentTable, ScheduledRealtimeComponent,
+};
+use std::time::Duration;
+
+#[derive(Debug, Clone)]
+struct Counter(u32);
+
+impl RealtimeComponent for Counter {
+    type Event = u32;
+
+    fn tick(&mut self) -> (Self::Event, Duration) {
+        self.0 += 1;
+        (self.0, Duration::from_millis(10))
+    }
+}
+
+const fn ms(millis: u64) -> Duration {
+    Duration::from_millis(millis)
+}
+
+#[test]
+fn iter_schedules_yields_the_time_until_each_components_next_tick() {
+    let mut allocator = EntityAllocator::default();
+    let (e1, e2) = (allocator.alloc(), allocator.alloc());
+    let mut table = RealtimeComponentTable::default();
+    table.insert(e1, Counter(0));
+    table.insert_with_schedule(e2, ScheduledRealtimeComponent::new(Counter(0), ms(7)));
+    assert_eq!(
+        table.iter_schedules().collect::<Vec<_>>(),
+        vec![(e1, ms(0)), (e2, ms(7))]
+    );
+    table.fast_forward(ms(5));
+    assert_eq!(
+        table.iter_schedules().collect::<Vec<_>>(),
+        vec![(e1, ms(5)), (e2, ms(2))]
+    );
+}
+
+#[test]
+fn iter_mut_with_until_next_tick_modifies_components_but_not_schedules() {
+    let mut allocator = EntityAllocator::default();
+    let (e1, e2) = (allocator.alloc(), allocator.alloc());
+    let mut table = RealtimeComponentTable::default();
+    table.insert(e1, Counter(0));
+    table.insert_with_schedule(e2, ScheduledRealtimeComponent::new(Counter(0), ms(7)));
+    for (_, counter, until_next_tick) in table.iter_mut_with_until_next_tick() {
+        // give components which are about to tick a head start
+        if until_next_tick < ms(5) {
+            counter.0 = 100;
+        }
+    }
+    assert_eq!(table.get(e1).unwrap().0, 100);
+    assert_eq!(table.get(e2).unwrap().0, 0);
+    assert_eq!(
+        table.iter_schedules().collect::<Vec<_>>(),
+        vec![(e1, ms(0)), (e2, ms(7))]
+    );
+}
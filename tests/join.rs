@@ -0,0 +1,132 @@
+use entity_table::{ComponentTable, Entity, EntityAllocator};
+use entity_table_realtime::join::{
+    iter_join, iter_join_component_table, iter_join_component_table_mut,
+    iter_mut_join_component_table,
+};
+use entity_table_realtime::{RealtimeComponent, RealtimeComponentTable};
+use std::time::Duration;
+
+#[derive(Debug, Clone, PartialEq)]
+struct Light(u32);
+
+impl RealtimeComponent for Light {
+    type Event = ();
+
+    fn tick(&mut self) -> (Self::Event, Duration) {
+        ((), Duration::from_millis(10))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Sound(u32);
+
+impl RealtimeComponent for Sound {
+    type Event = ();
+
+    fn tick(&mut self) -> (Self::Event, Duration) {
+        ((), Duration::from_millis(10))
+    }
+}
+
+fn entities(n: usize) -> Vec<Entity> {
+    let mut allocator = EntityAllocator::default();
+    (0..n).map(|_| allocator.alloc()).collect()
+}
+
+#[test]
+fn iter_join_yields_entities_in_both_tables() {
+    let e = entities(4);
+    let mut lights = RealtimeComponentTable::default();
+    let mut sounds = RealtimeComponentTable::default();
+    lights.insert(e[0], Light(0));
+    lights.insert(e[1], Light(1));
+    lights.insert(e[2], Light(2));
+    sounds.insert(e[1], Sound(10));
+    sounds.insert(e[2], Sound(20));
+    sounds.insert(e[3], Sound(30));
+    let mut joined = iter_join(&lights, &sounds)
+        .map(|(entity, light, sound)| (entity, light.clone(), sound.clone()))
+        .collect::<Vec<_>>();
+    joined.sort_by_key(|&(entity, _, _)| entity);
+    assert_eq!(
+        joined,
+        vec![(e[1], Light(1), Sound(10)), (e[2], Light(2), Sound(20))]
+    );
+    // the result doesn't depend on which table is smaller
+    sounds.remove(e[3]);
+    sounds.remove(e[2]);
+    let joined = iter_join(&lights, &sounds)
+        .map(|(entity, _, _)| entity)
+        .collect::<Vec<_>>();
+    assert_eq!(joined, vec![e[1]]);
+}
+
+#[test]
+fn iter_join_of_disjoint_tables_is_empty() {
+    let e = entities(2);
+    let mut lights = RealtimeComponentTable::default();
+    let mut sounds = RealtimeComponentTable::default();
+    lights.insert(e[0], Light(0));
+    sounds.insert(e[1], Sound(1));
+    assert_eq!(iter_join(&lights, &sounds).count(), 0);
+    assert_eq!(
+        iter_join(&lights, &RealtimeComponentTable::<Sound>::default()).count(),
+        0
+    );
+}
+
+#[test]
+fn iter_join_component_table_yields_entities_in_both_tables() {
+    let e = entities(3);
+    let mut lights = RealtimeComponentTable::default();
+    let mut positions = ComponentTable::default();
+    lights.insert(e[0], Light(0));
+    lights.insert(e[1], Light(1));
+    positions.insert(e[1], (1, 1));
+    positions.insert(e[2], (2, 2));
+    let joined = iter_join_component_table(&lights, &positions)
+        .map(|(entity, light, &position)| (entity, light.clone(), position))
+        .collect::<Vec<_>>();
+    assert_eq!(joined, vec![(e[1], Light(1), (1, 1))]);
+    positions.remove(e[2]);
+    positions.insert(e[0], (0, 0));
+    let mut joined = iter_join_component_table(&lights, &positions)
+        .map(|(entity, _, _)| entity)
+        .collect::<Vec<_>>();
+    joined.sort();
+    assert_eq!(joined, vec![e[0], e[1]]);
+}
+
+#[test]
+fn iter_join_component_table_mut_modifies_the_plain_table() {
+    let e = entities(3);
+    let mut lights = RealtimeComponentTable::default();
+    let mut brightness = ComponentTable::default();
+    lights.insert(e[0], Light(5));
+    lights.insert(e[1], Light(7));
+    brightness.insert(e[1], 0);
+    brightness.insert(e[2], 0);
+    for (_, light, brightness) in iter_join_component_table_mut(&lights, &mut brightness) {
+        *brightness = light.0;
+    }
+    assert_eq!(brightness.get(e[1]), Some(&7));
+    assert_eq!(brightness.get(e[2]), Some(&0));
+    assert_eq!(brightness.get(e[0]), None);
+}
+
+#[test]
+fn iter_mut_join_component_table_modifies_the_realtime_table() {
+    let e = entities(3);
+    let mut lights = RealtimeComponentTable::default();
+    let mut targets = ComponentTable::default();
+    lights.insert(e[0], Light(0));
+    lights.insert(e[1], Light(0));
+    targets.insert(e[1], 9);
+    targets.insert(e[2], 3);
+    for (_, light, &target) in iter_mut_join_component_table(&mut lights, &targets) {
+        light.0 = target;
+    }
+    assert_eq!(lights.get(e[0]), Some(&Light(0)));
+    assert_eq!(lights.get(e[1]), Some(&Light(9)));
+    assert!(!lights.contains(e[2]));
+}
@@ -0,0 +1,148 @@
+use entity_table::{ComponentTable, Entities, Entity, EntityAllocator};
+use entity_table_realtime::rate_limit::Throttle;
+use entity_table_realtime::{
+    insert_realtime_component, remove_realtime_entity, ContextContainsRealtimeComponents,
+    RealtimeComponent, RealtimeComponentApplyEvent,
+};
+use std::time::Duration;
+
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SoundEvent {
+    Start,
+    Play,
+    Stop,
+}
+
+/// A looping sound which must be started when inserted and stopped when removed
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+struct Sound;
+
+impl RealtimeComponent for Sound {
+    type Event = SoundEvent;
+
+    fn tick(&mut self) -> (Self::Event, Duration) {
+        (SoundEvent::Play, Duration::from_millis(100))
+    }
+
+    fn on_insert(&mut self) -> Option<Self::Event> {
+        Some(SoundEvent::Start)
+    }
+
+    fn on_remove(&mut self) -> Option<Self::Event> {
+        Some(SoundEvent::Stop)
+    }
+}
+
+impl RealtimeComponentApplyEvent<Context> for Sound {
+    fn apply_event(event: SoundEvent, entity: Entity, context: &mut Context) {
+        context.log.push((entity, event));
+    }
+}
+
+entity_table_realtime::declare_realtime_entity_module! {
+    components[Context] {
+        sound: Sound,
+        throttled: Throttle<Sound>,
+    }
+}
+
+#[derive(Default)]
+struct Context {
+    components: components::RealtimeComponents,
+    entities: ComponentTable<()>,
+    log: Vec<(Entity, SoundEvent)>,
+}
+
+impl ContextContainsRealtimeComponents for Context {
+    type Components = components::RealtimeComponents;
+    fn components_mut(&mut self) -> &mut Self::Components {
+        &mut self.components
+    }
+    fn realtime_entities(&self) -> Entities<'_> {
+        self.entities.entities()
+    }
+}
+
+#[test]
+fn removing_with_events_returns_the_on_remove_event() {
+    let entity = EntityAllocator::default().alloc();
+    let mut context = Context::default();
+    context.components.sound.insert(entity, Sound);
+    assert_eq!(
+        context.components.sound.remove_with_event(entity),
+        Some((Sound, Some(SoundEvent::Stop)))
+    );
+    assert_eq!(context.components.sound.remove_with_event(entity), None);
+    // plain removal discards the event
+    context.components.sound.insert(entity, Sound);
+    assert_eq!(context.components.sound.remove(entity), Some(Sound));
+}
+
+#[test]
+fn removing_an_entity_applies_the_on_remove_events() {
+    let mut allocator = EntityAllocator::default();
+    let (e1, e2) = (allocator.alloc(), allocator.alloc());
+    let mut context = Context::default();
+    context.components.sound.insert(e1, Sound);
+    context
+        .components
+        .throttled
+        .insert(e1, Throttle::new(Sound, Duration::from_secs(1)));
+    context.components.sound.insert(e2, Sound);
+    remove_realtime_entity(e1, &mut context);
+    // the throttle never drops its inner component's on_remove event
+    assert_eq!(
+        context.log,
+        vec![(e1, SoundEvent::Stop), (e1, SoundEvent::Stop)]
+    );
+    assert!(!context.components.contains_entity(e1));
+    assert!(context.components.sound.contains(e2));
+    let events = context.components.remove_entity_with_events(e2);
+    assert_eq!(events.sound, Some(SoundEvent::Stop));
+    assert_eq!(events.throttled, None);
+}
+
+#[test]
+fn inserting_with_events_returns_the_on_insert_event() {
+    let entity = EntityAllocator::default().alloc();
+    let mut context = Context::default();
+    assert_eq!(
+        context.components.sound.insert_with_event(entity, Sound),
+        (None, Some(SoundEvent::Start))
+    );
+    assert_eq!(
+        context.components.sound.insert_with_event(entity, Sound),
+        (Some(Sound), Some(SoundEvent::Start))
+    );
+    // plain insertion discards the event
+    context.components.sound.insert(entity, Sound);
+    assert!(context.log.is_empty());
+}
+
+#[test]
+fn inserting_a_component_applies_its_on_insert_event() {
+    let entity = EntityAllocator::default().alloc();
+    let mut context = Context::default();
+    let replaced =
+        insert_realtime_component(entity, Sound, &mut context, |c| &mut c.components.sound);
+    assert_eq!(replaced, None);
+    assert_eq!(context.log, vec![(entity, SoundEvent::Start)]);
+    // the event is applied before the component's first tick
+    assert_eq!(
+        context.components.sound.time_to_due(entity),
+        Some(Duration::ZERO)
+    );
+    let (replaced, events) = context.components.insert_entity_data_with_events(
+        entity,
+        components::RealtimeEntityData {
+            sound: Some(Sound),
+            throttled: Some(Throttle::new(Sound, Duration::from_secs(1))),
+        },
+    );
+    assert_eq!(replaced.sound, Some(Sound));
+    assert!(replaced.throttled.is_none());
+    assert_eq!(events.sound, Some(SoundEvent::Start));
+    assert_eq!(events.throttled, Some(Some(SoundEvent::Start)));
+}
@@ -0,0 +1,64 @@
+#![cfg(all(feature = "liveness", debug_assertions))]
+
+use entity_table::{Entity, EntityAllocator};
+use entity_table_realtime::{liveness, RealtimeComponent, RealtimeComponentTable};
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::Duration;
+
+#[derive(Debug, Clone)]
+struct Periodic;
+
+impl RealtimeComponent for Periodic {
+    type Event = ();
+
+    fn tick(&mut self) -> (Self::Event, Duration) {
+        ((), Duration::from_millis(10))
+    }
+}
+
+/// Allocate an entity, checking the liveness of accessed entities against the allocator
+fn checked_entity() -> (Rc<RefCell<EntityAllocator>>, Entity) {
+    let allocator = Rc::new(RefCell::new(EntityAllocator::default()));
+    let entity = allocator.borrow_mut().alloc();
+    let check = Rc::clone(&allocator);
+    liveness::install(move |entity| check.borrow().exists(entity));
+    (allocator, entity)
+}
+
+#[test]
+fn live_entities_can_be_accessed() {
+    let (_allocator, entity) = checked_entity();
+    let mut table = RealtimeComponentTable::default();
+    table.insert(entity, Periodic);
+    assert!(table.get(entity).is_some());
+    assert!(table.get_with_schedule_mut(entity).is_some());
+}
+
+#[test]
+#[should_panic(expected = "which is not live")]
+fn inserting_for_dead_entities_panics() {
+    let (allocator, entity) = checked_entity();
+    allocator.borrow_mut().free(entity);
+    RealtimeComponentTable::default().insert(entity, Periodic);
+}
+
+#[test]
+#[should_panic(expected = "which is not live")]
+fn getting_components_of_dead_entities_panics() {
+    let (allocator, entity) = checked_entity();
+    let mut table = RealtimeComponentTable::default();
+    table.insert(entity, Periodic);
+    allocator.borrow_mut().free(entity);
+    table.get(entity);
+}
+
+#[test]
+fn dead_entities_are_unchecked_once_uninstalled() {
+    let (allocator, entity) = checked_entity();
+    allocator.borrow_mut().free(entity);
+    liveness::uninstall();
+    let mut table = RealtimeComponentTable::default();
+    table.insert(entity, Periodic);
+    assert!(table.get(entity).is_some());
+}
@@ -0,0 +1,166 @@
+#![cfg(feature = "log")]
+
+use entity_table::{ComponentTable, Entities, Entity, EntityAllocator};
+use entity_table_realtime::{
+    process_entity_frame, process_entity_frame_with_budget, process_entity_frame_with_catch_up,
+    ContextContainsRealtimeComponents, RealtimeComponent, RealtimeComponentApplyEvent,
+};
+use std::cell::RefCell;
+use std::sync::Once;
+use std::time::Duration;
+
+/// Records warnings logged on the current thread, so tests running in parallel don't see each
+/// other's warnings
+struct CapturingLogger;
+
+thread_local! {
+    static WARNINGS: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+}
+
+impl log::Log for CapturingLogger {
+    fn enabled(&self, metadata: &log::Metadata) -> bool {
+        metadata.level() <= log::Level::Warn
+    }
+
+    fn log(&self, record: &log::Record) {
+        if self.enabled(record.metadata()) {
+            WARNINGS.with(|warnings| warnings.borrow_mut().push(record.args().to_string()));
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+fn take_warnings() -> Vec<String> {
+    static INIT: Once = Once::new();
+    INIT.call_once(|| {
+        log::set_logger(&CapturingLogger).unwrap();
+        log::set_max_level(log::LevelFilter::Warn);
+    });
+    WARNINGS.with(|warnings| warnings.take())
+}
+
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone)]
+struct Periodic(Duration);
+
+impl RealtimeComponent for Periodic {
+    type Event = ();
+
+    fn tick(&mut self) -> (Self::Event, Duration) {
+        ((), self.0)
+    }
+}
+
+impl RealtimeComponentApplyEvent<Context> for Periodic {
+    fn apply_event(_event: (), _entity: Entity, _context: &mut Context) {}
+}
+
+entity_table_realtime::declare_realtime_entity_module! {
+    components[Context] {
+        flicker: Periodic,
+    }
+}
+
+#[derive(Default)]
+struct Context {
+    components: components::RealtimeComponents,
+    entities: ComponentTable<()>,
+}
+
+impl Context {
+    fn new(entity: Entity, period: Duration) -> Self {
+        let mut context = Self::default();
+        context.entities.insert(entity, ());
+        context.components.flicker.insert(entity, Periodic(period));
+        context
+    }
+}
+
+impl ContextContainsRealtimeComponents for Context {
+    type Components = components::RealtimeComponents;
+    fn components_mut(&mut self) -> &mut Self::Components {
+        &mut self.components
+    }
+    fn realtime_entities(&self) -> Entities<'_> {
+        self.entities.entities()
+    }
+}
+
+const FRAME: Duration = Duration::from_millis(25);
+
+#[test]
+fn ordinary_frames_log_nothing() {
+    let entity = EntityAllocator::default().alloc();
+    let mut context = Context::new(entity, Duration::from_millis(10));
+    take_warnings();
+    process_entity_frame(entity, FRAME, &mut context);
+    assert_eq!(take_warnings(), Vec::<String>::new());
+}
+
+#[test]
+fn zero_duration_ticks_are_logged() {
+    let entity = EntityAllocator::default().alloc();
+    let mut context = Context::new(entity, Duration::ZERO);
+    take_warnings();
+    context.components.tick_entity(entity, FRAME);
+    let warnings = take_warnings();
+    assert_eq!(warnings.len(), 1);
+    assert!(warnings[0].contains("Periodic returned a zero duration from tick"));
+}
+
+#[test]
+fn removing_absent_components_is_logged() {
+    let entity = EntityAllocator::default().alloc();
+    let mut context = Context::new(entity, Duration::from_millis(10));
+    take_warnings();
+    assert!(context.components.flicker.remove(entity).is_some());
+    assert!(take_warnings().is_empty());
+    assert!(context.components.flicker.remove(entity).is_none());
+    let warnings = take_warnings();
+    assert_eq!(warnings.len(), 1);
+    assert!(warnings[0].starts_with("attempted to remove absent"));
+}
+
+#[test]
+fn processing_entities_without_components_is_logged() {
+    let mut allocator = EntityAllocator::default();
+    let (entity, other) = (allocator.alloc(), allocator.alloc());
+    let mut context = Context::new(entity, Duration::from_millis(10));
+    take_warnings();
+    process_entity_frame(other, FRAME, &mut context);
+    assert_eq!(
+        take_warnings(),
+        vec![format!(
+            "processing frame for {:?} which has no realtime components",
+            other
+        )]
+    );
+}
+
+#[test]
+fn exhausted_budgets_are_logged() {
+    let entity = EntityAllocator::default().alloc();
+    let mut context = Context::new(entity, Duration::from_millis(10));
+    take_warnings();
+    assert_eq!(
+        process_entity_frame_with_budget(entity, FRAME, &mut context, 2),
+        2
+    );
+    let warnings = take_warnings();
+    assert_eq!(warnings.len(), 1);
+    assert!(warnings[0].starts_with("tick budget of 2 exhausted"));
+}
+
+#[test]
+fn oversized_catch_up_frames_are_logged() {
+    let entity = EntityAllocator::default().alloc();
+    let mut context = Context::new(entity, Duration::from_millis(10));
+    take_warnings();
+    process_entity_frame_with_catch_up(entity, FRAME, FRAME, &mut context);
+    assert!(take_warnings().is_empty());
+    process_entity_frame_with_catch_up(entity, FRAME * 4, FRAME, &mut context);
+    let warnings = take_warnings();
+    assert_eq!(warnings.len(), 1);
+    assert!(warnings[0].starts_with("frame of 100ms"));
+}
@@ -0,0 +1,78 @@
+use entity_table::{Entity, EntityAllocator};
+use entity_table_realtime::{RealtimeComponent, RealtimeComponentApplyEvent};
+use std::time::Duration;
+
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone)]
+struct Periodic;
+
+impl RealtimeComponent for Periodic {
+    type Event = ();
+
+    fn tick(&mut self) -> (Self::Event, Duration) {
+        ((), ms(10))
+    }
+}
+
+impl RealtimeComponentApplyEvent<()> for Periodic {
+    fn apply_event(_event: (), _entity: Entity, _context: &mut ()) {}
+}
+
+entity_table_realtime::declare_realtime_entity_module! {
+    components[()] {
+        flicker: Periodic,
+        poison: Periodic,
+    }
+}
+
+const fn ms(millis: u64) -> Duration {
+    Duration::from_millis(millis)
+}
+
+fn until_next_tick(components: &components::RealtimeComponents, entity: Entity) -> [Duration; 2] {
+    [&components.flicker, &components.poison]
+        .map(|table| table.get_with_schedule(entity).unwrap().until_next_tick())
+}
+
+#[test]
+fn map_schedules_replaces_the_schedule_of_every_component() {
+    let mut allocator = EntityAllocator::default();
+    let (e1, e2) = (allocator.alloc(), allocator.alloc());
+    let mut components = components::RealtimeComponents::default();
+    for entity in [e1, e2] {
+        components.flicker.insert(entity, Periodic);
+        components.poison.insert(entity, Periodic);
+    }
+    // ticks at 0ms, leaving 5ms until the next tick
+    components.fast_forward(ms(5));
+    components.poison.set_enabled(e2, false);
+    // slow time for every component of one entity
+    components.map_schedules(|entity, until_next_tick| {
+        if entity == e2 {
+            until_next_tick * 2
+        } else {
+            until_next_tick
+        }
+    });
+    assert_eq!(until_next_tick(&components, e1), [ms(5), ms(5)]);
+    // disabled components are included
+    assert_eq!(until_next_tick(&components, e2), [ms(10), ms(10)]);
+}
+
+#[test]
+fn map_schedules_on_a_table_only_affects_that_table() {
+    let entity = EntityAllocator::default().alloc();
+    let mut components = components::RealtimeComponents::default();
+    components.flicker.insert(entity, Periodic);
+    components.poison.insert(entity, Periodic);
+    components
+        .flicker
+        .map_schedules(|_, until_next_tick| until_next_tick + ms(7));
+    assert_eq!(until_next_tick(&components, entity), [ms(7), ms(0)]);
+    let events = components.fast_forward(ms(7));
+    let times = events
+        .iter()
+        .map(|(time, _, events)| (*time, events.flicker.is_some(), events.poison.is_some()))
+        .collect::<Vec<_>>();
+    assert_eq!(times, vec![(ms(0), false, true), (ms(7), true, false)]);
+}
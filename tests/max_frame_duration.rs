@@ -0,0 +1,111 @@
+use entity_table::{ComponentTable, Entities, Entity, EntityAllocator};
+use entity_table_realtime::{
+    process_entity_frame, process_entity_frame_with_budget, ContextContainsRealtimeComponents,
+    RealtimeComponent, RealtimeComponentApplyEvent,
+};
+use std::collections::HashMap;
+use std::time::Duration;
+
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone)]
+struct Periodic;
+
+impl RealtimeComponent for Periodic {
+    type Event = ();
+
+    fn tick(&mut self) -> (Self::Event, Duration) {
+        ((), Duration::from_millis(10))
+    }
+}
+
+impl RealtimeComponentApplyEvent<Context> for Periodic {
+    fn apply_event(_event: (), entity: Entity, context: &mut Context) {
+        *context.num_events.entry(entity).or_default() += 1;
+    }
+}
+
+entity_table_realtime::declare_realtime_entity_module! {
+    components[Context] {
+        flicker: Periodic,
+    }
+}
+
+#[derive(Default)]
+struct Context {
+    components: components::RealtimeComponents,
+    entities: ComponentTable<()>,
+    max_frame_durations: HashMap<Entity, Duration>,
+    num_events: HashMap<Entity, usize>,
+}
+
+impl Context {
+    fn new(entities: &[Entity]) -> Self {
+        let mut context = Self::default();
+        for &entity in entities {
+            context.entities.insert(entity, ());
+            context.components.flicker.insert(entity, Periodic);
+        }
+        context
+    }
+}
+
+impl ContextContainsRealtimeComponents for Context {
+    type Components = components::RealtimeComponents;
+    fn components_mut(&mut self) -> &mut Self::Components {
+        &mut self.components
+    }
+    fn realtime_entities(&self) -> Entities<'_> {
+        self.entities.entities()
+    }
+    fn realtime_max_frame_duration(&self, entity: Entity) -> Option<Duration> {
+        self.max_frame_durations.get(&entity).copied()
+    }
+}
+
+const FRAME: Duration = Duration::from_millis(100);
+const MAX: Duration = Duration::from_millis(25);
+
+#[test]
+fn frames_are_clamped_to_the_entitys_max_frame_duration() {
+    let mut allocator = EntityAllocator::default();
+    let (capped, uncapped) = (allocator.alloc(), allocator.alloc());
+    let mut context = Context::new(&[capped, uncapped]);
+    context.max_frame_durations.insert(capped, MAX);
+    process_entity_frame(capped, FRAME, &mut context);
+    process_entity_frame(uncapped, FRAME, &mut context);
+    // ticks at 0ms, 10ms and 20ms, and the rest of the frame is dropped
+    assert_eq!(context.num_events[&capped], 3);
+    assert_eq!(
+        context.components.flicker.time_to_due(capped),
+        Some(Duration::from_millis(5))
+    );
+    assert_eq!(context.num_events[&uncapped], 11);
+    assert_eq!(
+        context.components.flicker.time_to_due(uncapped),
+        Some(Duration::from_millis(10))
+    );
+}
+
+#[test]
+fn frames_shorter_than_the_max_are_unaffected() {
+    let entity = EntityAllocator::default().alloc();
+    let mut context = Context::new(&[entity]);
+    context.max_frame_durations.insert(entity, MAX);
+    process_entity_frame(entity, Duration::from_millis(15), &mut context);
+    assert_eq!(context.num_events[&entity], 2);
+    assert_eq!(
+        context.components.flicker.time_to_due(entity),
+        Some(Duration::from_millis(5))
+    );
+}
+
+#[test]
+fn budgeted_frames_are_clamped_too() {
+    let entity = EntityAllocator::default().alloc();
+    let mut context = Context::new(&[entity]);
+    context.max_frame_durations.insert(entity, MAX);
+    assert_eq!(
+        process_entity_frame_with_budget(entity, FRAME, &mut context, 100),
+        3
+    );
+}
@@ -0,0 +1,129 @@
+use entity_table::{ComponentTable, Entities, Entity, EntityAllocator};
+use entity_table_realtime::{
+    process_entity_frame, ContextContainsRealtimeComponents, RealtimeComponent,
+    RealtimeComponentApplyEvent, ScheduledRealtimeComponent,
+};
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SparkEvent {
+    Spark,
+    Burst(u64),
+}
+
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone)]
+struct Spark;
+
+impl RealtimeComponent for Spark {
+    type Event = SparkEvent;
+
+    fn tick(&mut self) -> (Self::Event, Duration) {
+        (SparkEvent::Spark, ms(10))
+    }
+
+    fn on_ticks_dropped(&mut self, dropped: u64) -> Option<Self::Event> {
+        Some(SparkEvent::Burst(dropped))
+    }
+}
+
+impl RealtimeComponentApplyEvent<Context> for Spark {
+    fn apply_event(event: SparkEvent, _entity: Entity, context: &mut Context) {
+        context.events.push(event);
+    }
+}
+
+entity_table_realtime::declare_realtime_entity_module! {
+    components[Context] {
+        spark: Spark,
+    }
+}
+
+#[derive(Default)]
+struct Context {
+    components: components::RealtimeComponents,
+    entities: ComponentTable<()>,
+    events: Vec<SparkEvent>,
+}
+
+impl ContextContainsRealtimeComponents for Context {
+    type Components = components::RealtimeComponents;
+    fn components_mut(&mut self) -> &mut Self::Components {
+        &mut self.components
+    }
+    fn realtime_entities(&self) -> Entities<'_> {
+        self.entities.entities()
+    }
+}
+
+const fn ms(millis: u64) -> Duration {
+    Duration::from_millis(millis)
+}
+
+fn context_with_spark(entity: Entity, max_missed_periods: Option<u64>) -> Context {
+    let mut context = Context::default();
+    context.entities.insert(entity, ());
+    let mut scheduled = ScheduledRealtimeComponent::new(Spark, ms(0));
+    scheduled.set_max_missed_periods(max_missed_periods);
+    context
+        .components
+        .spark
+        .insert_with_schedule(entity, scheduled);
+    context
+}
+
+#[test]
+fn ticks_beyond_the_maximum_missed_periods_are_dropped() {
+    let entity = EntityAllocator::default().alloc();
+    let mut context = context_with_spark(entity, Some(2));
+    // the first tick establishes the period
+    process_entity_frame(entity, ms(5), &mut context);
+    assert_eq!(context.events, vec![SparkEvent::Spark]);
+    context.events.clear();
+    // 95ms behind is 9 periods, 7 more than allowed, leaving ticks at 75ms, 85ms and 95ms
+    process_entity_frame(entity, ms(100), &mut context);
+    assert_eq!(
+        context.events,
+        vec![
+            SparkEvent::Burst(7),
+            SparkEvent::Spark,
+            SparkEvent::Spark,
+            SparkEvent::Spark
+        ]
+    );
+    assert_eq!(context.components.spark.time_to_due(entity), Some(ms(5)));
+}
+
+#[test]
+fn ticks_within_the_maximum_missed_periods_are_kept() {
+    let entity = EntityAllocator::default().alloc();
+    let mut context = context_with_spark(entity, Some(2));
+    process_entity_frame(entity, ms(5), &mut context);
+    context.events.clear();
+    process_entity_frame(entity, ms(30), &mut context);
+    assert_eq!(context.events, vec![SparkEvent::Spark; 3]);
+}
+
+#[test]
+fn components_without_a_maximum_never_drop_ticks() {
+    let entity = EntityAllocator::default().alloc();
+    let mut context = context_with_spark(entity, None);
+    process_entity_frame(entity, ms(5), &mut context);
+    context.events.clear();
+    process_entity_frame(entity, ms(100), &mut context);
+    assert_eq!(context.events, vec![SparkEvent::Spark; 10]);
+}
+
+#[test]
+fn drop_excess_ticks_leaves_components_which_have_not_ticked() {
+    let mut scheduled = ScheduledRealtimeComponent::new(Spark, ms(0)).with_max_missed_periods(0);
+    assert_eq!(scheduled.max_missed_periods(), Some(0));
+    assert_eq!(scheduled.drop_excess_ticks(ms(100)), None);
+    scheduled.tick(EntityAllocator::default().alloc());
+    // 25ms behind at the end of the frame is 2 periods
+    assert_eq!(
+        scheduled.drop_excess_ticks(ms(35)),
+        Some(SparkEvent::Burst(2))
+    );
+    assert_eq!(scheduled.until_next_tick(), ms(30));
+}
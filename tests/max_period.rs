@@ -0,0 +1,96 @@
+use entity_table::{ComponentTable, Entities, Entity, EntityAllocator};
+use entity_table_realtime::{
+    process_entity_frame, ContextContainsRealtimeComponents, PeriodClamped, RealtimeComponent,
+    RealtimeComponentApplyEvent,
+};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Mistakenly asks never to be ticked again
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone)]
+struct Forever;
+
+impl RealtimeComponent for Forever {
+    type Event = ();
+
+    fn tick(&mut self) -> (Self::Event, Duration) {
+        ((), Duration::MAX)
+    }
+}
+
+impl RealtimeComponentApplyEvent<Context> for Forever {
+    fn apply_event(_event: (), _entity: Entity, context: &mut Context) {
+        context.applied += 1;
+    }
+}
+
+entity_table_realtime::declare_realtime_entity_module! {
+    components[Context] {
+        forever: Forever,
+    }
+}
+
+#[derive(Default)]
+struct Context {
+    components: components::RealtimeComponents,
+    entities: ComponentTable<()>,
+    applied: usize,
+}
+
+impl ContextContainsRealtimeComponents for Context {
+    type Components = components::RealtimeComponents;
+    fn components_mut(&mut self) -> &mut Self::Components {
+        &mut self.components
+    }
+    fn realtime_entities(&self) -> Entities<'_> {
+        self.entities.entities()
+    }
+}
+
+const fn ms(millis: u64) -> Duration {
+    Duration::from_millis(millis)
+}
+
+fn context_with_forever(entity: Entity) -> Context {
+    let mut context = Context::default();
+    context.entities.insert(entity, ());
+    context.components.forever.insert(entity, Forever);
+    context
+}
+
+#[test]
+fn periods_longer_than_the_maximum_are_clamped_and_reported() {
+    let entity = EntityAllocator::default().alloc();
+    let mut context = context_with_forever(entity);
+    let clamped = Arc::new(Mutex::new(Vec::<PeriodClamped>::new()));
+    context.components.forever.set_max_period(ms(10), {
+        let clamped = Arc::clone(&clamped);
+        move |period_clamped| clamped.lock().unwrap().push(period_clamped)
+    });
+    assert_eq!(context.components.forever.max_period(), Some(ms(10)));
+    process_entity_frame(entity, ms(25), &mut context);
+    // ticks at 0ms, 10ms and 20ms
+    assert_eq!(context.applied, 3);
+    assert_eq!(context.components.forever.time_to_due(entity), Some(ms(5)));
+    let clamped = clamped.lock().unwrap();
+    assert_eq!(clamped.len(), 3);
+    assert!(clamped[0].component.ends_with("Forever"));
+    assert_eq!(clamped[0].entity, entity);
+    // schedules are stored in nanoseconds, saturating at the largest representable duration
+    assert_eq!(clamped[0].returned, Duration::from_nanos(u64::MAX));
+    assert_eq!(clamped[0].max_period, ms(10));
+}
+
+#[test]
+fn components_are_not_clamped_without_a_maximum() {
+    let entity = EntityAllocator::default().alloc();
+    let mut context = context_with_forever(entity);
+    context.components.forever.set_max_period(ms(10), |_| ());
+    context.components.forever.clear_max_period();
+    assert_eq!(context.components.forever.max_period(), None);
+    process_entity_frame(entity, ms(25), &mut context);
+    assert_eq!(context.applied, 1);
+    process_entity_frame(entity, ms(1000), &mut context);
+    assert_eq!(context.applied, 1);
+}
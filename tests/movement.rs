@@ -0,0 +1,126 @@
+use entity_table::EntityAllocator;
+use entity_table_realtime::movement::{
+    GridMovement, RealtimeMovement, RealtimeMovementEvent, Speed,
+};
+use entity_table_realtime::testing::{assert_timeline, run_component};
+use entity_table_realtime::RealtimeComponentTable;
+use std::time::Duration;
+
+const fn ns(nanos: u64) -> Duration {
+    Duration::from_nanos(nanos)
+}
+
+#[test]
+fn cell_periods_are_rounded_without_accumulating_error() {
+    let speed = Speed::cells_per_second(3.0);
+    assert_eq!(speed.as_cells_per_second(), 3.0);
+    assert_eq!(speed.cell_period(), ns(333_333_333));
+    let periods = (0..3).map(|i| speed.period_of_cell(i)).collect::<Vec<_>>();
+    assert_eq!(
+        periods,
+        vec![ns(333_333_333), ns(333_333_334), ns(333_333_333)]
+    );
+    let total = (0..300).map(|i| speed.period_of_cell(i)).sum::<Duration>();
+    assert_eq!(total, Duration::from_secs(100));
+    assert_eq!(speed.duration_of_cells(300), Duration::from_secs(100));
+    assert_eq!(
+        Speed::from_cell_period(Duration::from_millis(250)).as_cells_per_second(),
+        4.0
+    );
+}
+
+#[test]
+fn grid_movement_steps_once_per_cell() {
+    let entity = EntityAllocator::default().alloc();
+    let mut table = RealtimeComponentTable::default();
+    let movement = GridMovement::new((1, 0), Speed::cells_per_second(3.0));
+    table.insert_with_schedule(entity, movement.scheduled());
+    let events = table
+        .fast_forward(Duration::from_secs(1))
+        .into_iter()
+        .map(|(time, _, step)| (time, step))
+        .collect::<Vec<_>>();
+    // the first step happens once the first cell has been crossed
+    assert_timeline(
+        &events,
+        &[
+            (ns(333_333_333), (1, 0)),
+            (ns(666_666_667), (1, 0)),
+            (ns(1_000_000_000), (1, 0)),
+        ],
+    );
+}
+
+#[test]
+fn grid_movement_changes_take_effect_after_the_scheduled_step() {
+    let mut movement = GridMovement::new((0, 1), Speed::cells_per_second(10.0));
+    movement.set_step((0, -1));
+    movement.set_speed(Speed::cells_per_second(20.0));
+    assert_eq!(movement.step(), (0, -1));
+    assert_eq!(movement.speed(), Speed::cells_per_second(20.0));
+    let ms = Duration::from_millis;
+    // the first tick is immediate when not scheduled with `scheduled`
+    assert_timeline(
+        &run_component(movement, ms(0), ms(100)),
+        &[(ms(0), (0, -1)), (ms(50), (0, -1)), (ms(100), (0, -1))],
+    );
+}
+
+#[test]
+fn realtime_movement_follows_its_path_and_then_arrives() {
+    let path = vec![(1, 0), (2, 0), (2, 1)];
+    let movement = RealtimeMovement::new(path.clone(), Speed::cells_per_second(10.0));
+    assert_eq!(movement.path(), &path[..]);
+    let entity = EntityAllocator::default().alloc();
+    let mut table = RealtimeComponentTable::default();
+    table.insert_with_schedule(entity, movement.scheduled());
+    let events = table
+        .fast_forward(Duration::from_millis(400))
+        .into_iter()
+        .map(|(time, _, event)| (time, event))
+        .collect::<Vec<_>>();
+    let ms = Duration::from_millis;
+    // the terminal step repeats until the component is removed
+    assert_timeline(
+        &events,
+        &[
+            (ms(100), RealtimeMovementEvent::MovedTo((1, 0))),
+            (ms(200), RealtimeMovementEvent::MovedTo((2, 0))),
+            (ms(300), RealtimeMovementEvent::Arrived((2, 1))),
+            (ms(400), RealtimeMovementEvent::Arrived((2, 1))),
+        ],
+    );
+    assert_eq!(table.get(entity).unwrap().steps_taken(), 3);
+    assert!(events[2].1.is_terminal());
+    assert!(!events[1].1.is_terminal());
+    assert_eq!(events[2].1.cell(), (2, 1));
+}
+
+#[test]
+fn realtime_movement_expires_after_max_steps() {
+    let path = (1..=10).map(|x| (x, 0)).collect();
+    let movement = RealtimeMovement::new(path, Speed::cells_per_second(10.0)).with_max_steps(2);
+    let events = run_component(movement, Duration::ZERO, Duration::from_millis(200))
+        .into_iter()
+        .map(|(_, event)| event)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        events,
+        vec![
+            RealtimeMovementEvent::MovedTo((1, 0)),
+            RealtimeMovementEvent::Expired((2, 0)),
+            RealtimeMovementEvent::Expired((2, 0)),
+        ]
+    );
+    // reaching the end of the path on the last step counts as arriving
+    let movement =
+        RealtimeMovement::new(vec![(0, 1)], Speed::cells_per_second(10.0)).with_max_steps(1);
+    let events = run_component(movement, Duration::ZERO, Duration::ZERO);
+    assert_eq!(events[0].1, RealtimeMovementEvent::Arrived((0, 1)));
+}
+
+#[test]
+#[should_panic(expected = "path must not be empty")]
+fn realtime_movement_needs_a_path() {
+    RealtimeMovement::new(Vec::new(), Speed::cells_per_second(1.0));
+}
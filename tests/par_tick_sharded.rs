@@ -0,0 +1,119 @@
+#![cfg(feature = "rayon")]
+
+use entity_table::{Entity, EntityAllocator};
+use entity_table_realtime::{
+    apply_buffered_events, RealtimeComponent, RealtimeComponentApplyEvent,
+};
+use std::time::Duration;
+
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone)]
+struct Counter {
+    count: u32,
+    period: Duration,
+}
+
+impl RealtimeComponent for Counter {
+    type Event = u32;
+
+    fn tick(&mut self) -> (Self::Event, Duration) {
+        self.count += 1;
+        (self.count, self.period)
+    }
+}
+
+impl RealtimeComponentApplyEvent<Vec<(Entity, u32)>> for Counter {
+    fn apply_event(count: u32, entity: Entity, context: &mut Vec<(Entity, u32)>) {
+        context.push((entity, count));
+    }
+}
+
+entity_table_realtime::declare_realtime_entity_module! {
+    components[Vec<(Entity, u32)>] {
+        flicker: Counter,
+        poison: Counter,
+    }
+}
+
+fn counter(millis: u64) -> Counter {
+    Counter {
+        count: 0,
+        period: Duration::from_millis(millis),
+    }
+}
+
+/// Entities are inserted in reverse so that table order differs from entity order
+fn spawn(n: usize) -> (Vec<Entity>, components::RealtimeComponents) {
+    let mut allocator = EntityAllocator::default();
+    let entities = (0..n).map(|_| allocator.alloc()).collect::<Vec<_>>();
+    let mut components = components::RealtimeComponents::default();
+    for (i, &entity) in entities.iter().enumerate().rev() {
+        components.flicker.insert(entity, counter(10 + i as u64));
+        if i % 3 == 0 {
+            components.poison.insert(entity, counter(7));
+        }
+    }
+    (entities, components)
+}
+
+fn applied(events: Vec<(Entity, components::RealtimeEntityEvents)>) -> Vec<(Entity, u32)> {
+    let mut context = Vec::new();
+    apply_buffered_events(events, &mut context);
+    context
+}
+
+#[test]
+fn sharded_events_are_in_entity_order_for_any_number_of_shards() {
+    let (entities, mut components) = spawn(10);
+    let events = components.par_tick_all_sharded(Duration::from_millis(30), 3);
+    let event_entities = events.iter().map(|&(entity, _)| entity).collect::<Vec<_>>();
+    let mut sorted = event_entities.clone();
+    sorted.sort();
+    assert_eq!(event_entities, sorted);
+    assert_eq!(event_entities.first(), Some(&entities[0]));
+    let expected = applied(events);
+    for num_shards in [1, 2, 4, 10, 64] {
+        let (_, mut components) = spawn(10);
+        let events = components.par_tick_all_sharded(Duration::from_millis(30), num_shards);
+        assert_eq!(applied(events), expected, "{num_shards} shards");
+    }
+}
+
+#[test]
+fn sharded_ticks_simulate_each_entity_like_par_tick_all() {
+    let (_, mut sharded) = spawn(10);
+    let (_, mut unsharded) = spawn(10);
+    for _ in 0..3 {
+        let frame = Duration::from_millis(17);
+        let mut sharded_events = applied(sharded.par_tick_all_sharded(frame, 4));
+        let mut unsharded_events = applied(unsharded.par_tick_all(frame));
+        // only the order of entities may differ
+        sharded_events.sort_by_key(|&(entity, _)| entity);
+        unsharded_events.sort_by_key(|&(entity, _)| entity);
+        assert_eq!(sharded_events, unsharded_events);
+    }
+}
+
+#[test]
+fn buffered_events_of_an_entity_are_applied_in_order() {
+    let (entities, mut components) = spawn(1);
+    // flicker ticks at 0ms and 10ms, poison at 0ms, 7ms and 14ms
+    let events = components.par_tick_all_sharded(Duration::from_millis(14), 2);
+    assert_eq!(
+        applied(events),
+        vec![
+            (entities[0], 1),
+            (entities[0], 1),
+            (entities[0], 2),
+            (entities[0], 2),
+            (entities[0], 3)
+        ]
+    );
+}
+
+#[test]
+#[should_panic(expected = "num_shards must be positive")]
+fn zero_shards_panics() {
+    let (_, mut components) = spawn(2);
+    components.par_tick_all_sharded(Duration::from_millis(10), 0);
+}
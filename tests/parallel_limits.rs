@@ -0,0 +1,104 @@
+#![cfg(feature = "rayon")]
+
+use entity_table::{Entity, EntityAllocator};
+use entity_table_realtime::{
+    RealtimeComponent, RealtimeComponentApplyEvent, ScheduledRealtimeComponent,
+};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone)]
+struct Periodic(Duration);
+
+impl RealtimeComponent for Periodic {
+    type Event = ();
+
+    fn tick(&mut self) -> (Self::Event, Duration) {
+        ((), self.0)
+    }
+}
+
+impl RealtimeComponentApplyEvent<()> for Periodic {
+    fn apply_event(_event: (), _entity: Entity, _context: &mut ()) {}
+}
+
+entity_table_realtime::declare_realtime_entity_module! {
+    components[()] {
+        periodic: Periodic,
+    }
+}
+
+static NUM_OVERFLOWS: AtomicUsize = AtomicUsize::new(0);
+static NUM_CLAMPED: AtomicUsize = AtomicUsize::new(0);
+
+fn components_with_limits(period: Duration) -> components::RealtimeComponents {
+    let mut entity_allocator = EntityAllocator::default();
+    let mut components = components::RealtimeComponents::default();
+    components.periodic.set_event_cap(5, |_| {
+        NUM_OVERFLOWS.fetch_add(1, Ordering::Relaxed);
+    });
+    components
+        .periodic
+        .set_max_period(Duration::from_secs(1), |_| {
+            NUM_CLAMPED.fetch_add(1, Ordering::Relaxed);
+        });
+    for _ in 0..10 {
+        components
+            .periodic
+            .insert(entity_allocator.alloc(), Periodic(period));
+    }
+    components
+}
+
+#[test]
+fn parallel_ticks_apply_the_table_limits() {
+    let mut components = components_with_limits(Duration::from_millis(10));
+    let events = components.par_tick_all(Duration::from_millis(5));
+    assert_eq!(events.len(), 5);
+    assert_eq!(components.periodic.events_this_frame(), 5);
+    let mut components = components_with_limits(Duration::from_millis(10));
+    let events = components.par_tick_all_sharded(Duration::from_millis(5), 3);
+    assert_eq!(events.len(), 5);
+    assert_eq!(components.periodic.events_this_frame(), 5);
+    assert_eq!(NUM_OVERFLOWS.load(Ordering::Relaxed), 2);
+    let mut components = components_with_limits(Duration::MAX);
+    components.par_tick_all(Duration::from_millis(5));
+    let num_clamped = components
+        .periodic
+        .iter_with_schedule()
+        .filter(|(_, scheduled)| scheduled.period() == Duration::from_secs(1))
+        .count();
+    assert_eq!(num_clamped, 5);
+    assert_eq!(NUM_CLAMPED.load(Ordering::Relaxed), 5);
+}
+
+#[test]
+fn parallel_deferred_components_are_left_due() {
+    let mut entity_allocator = EntityAllocator::default();
+    let (e1, e2) = (entity_allocator.alloc(), entity_allocator.alloc());
+    let mut components = components::RealtimeComponents::default();
+    components.periodic.set_event_cap(1, |_| ());
+    components
+        .periodic
+        .insert(e1, Periodic(Duration::from_millis(10)));
+    components.periodic.insert_with_schedule(
+        e2,
+        ScheduledRealtimeComponent::new(
+            Periodic(Duration::from_millis(10)),
+            Duration::from_millis(3),
+        ),
+    );
+    // a single shard ticks the entities in order, so e1 uses up the cap
+    let events = components.par_tick_all_sharded(Duration::from_millis(8), 1);
+    assert_eq!(events.len(), 1);
+    let until_next_tick = |entity| {
+        components
+            .periodic
+            .get_with_schedule(entity)
+            .unwrap()
+            .until_next_tick()
+    };
+    assert_eq!(until_next_tick(e1), Duration::from_millis(2));
+    assert_eq!(until_next_tick(e2), Duration::ZERO);
+}
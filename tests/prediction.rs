@@ -0,0 +1,89 @@
+use entity_table::{ComponentTable, Entities, Entity, EntityAllocator};
+use entity_table_realtime::prediction::RealtimePrediction;
+use entity_table_realtime::{
+    process_entity_frame, ContextContainsRealtimeComponents, RealtimeComponent,
+    RealtimeComponentApplyEvent, RealtimeComponentTable, SchedulePolicy,
+};
+use std::time::Duration;
+
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone)]
+struct Periodic;
+
+impl RealtimeComponent for Periodic {
+    type Event = ();
+
+    fn tick(&mut self) -> (Self::Event, Duration) {
+        ((), Duration::from_millis(10))
+    }
+}
+
+impl RealtimeComponentApplyEvent<Context> for Periodic {
+    fn apply_event(_event: (), _entity: Entity, context: &mut Context) {
+        context.num_events += 1;
+    }
+}
+
+entity_table_realtime::declare_realtime_entity_module! {
+    components[Context] {
+        a: Periodic,
+    }
+}
+
+#[derive(Default)]
+struct Context {
+    components: components::RealtimeComponents,
+    entities: ComponentTable<()>,
+    num_events: usize,
+}
+
+impl ContextContainsRealtimeComponents for Context {
+    type Components = components::RealtimeComponents;
+    fn components_mut(&mut self) -> &mut Self::Components {
+        &mut self.components
+    }
+    fn realtime_entities(&self) -> Entities<'_> {
+        self.entities.entities()
+    }
+}
+
+const DELAY: Duration = Duration::from_millis(7);
+
+#[test]
+fn reconcile_keeps_local_configuration() {
+    let mut allocator = EntityAllocator::default();
+    let (e1, e2, e3) = (allocator.alloc(), allocator.alloc(), allocator.alloc());
+    let mut context = Context::default();
+    context.components.a.set_event_cap(1, |_| ());
+    context
+        .components
+        .a
+        .set_schedule_policy(SchedulePolicy::Delay(DELAY));
+    let mut authoritative = RealtimeComponentTable::default();
+    authoritative.insert(e1, Periodic);
+    authoritative.insert(e2, Periodic);
+    let mut prediction = RealtimePrediction::new();
+    let generation = context.components.a.schedule_generation();
+    prediction.reconcile(&mut context.components.a, authoritative, 0);
+    assert!(context.components.a.schedule_generation() > generation);
+    assert_eq!(context.components.a.event_cap(), Some(1));
+    assert_eq!(
+        context.components.a.schedule_policy(),
+        SchedulePolicy::Delay(DELAY)
+    );
+    // both components are due now, but only one may tick this frame
+    let frame = Duration::from_millis(5);
+    process_entity_frame(e1, frame, &mut context);
+    process_entity_frame(e2, frame, &mut context);
+    assert_eq!(context.num_events, 1);
+    context.components.a.insert(e3, Periodic);
+    assert_eq!(
+        context
+            .components
+            .a
+            .get_with_schedule(e3)
+            .unwrap()
+            .until_next_tick(),
+        DELAY
+    );
+}
@@ -0,0 +1,116 @@
+use entity_table::{ComponentTable, Entities, Entity, EntityAllocator};
+use entity_table_realtime::{
+    process_entities_frame, process_entities_frame_dedup, process_entity_frame,
+    process_entity_frame_dyn, ContextContainsRealtimeComponents, RealtimeComponent,
+    RealtimeComponentApplyEvent, RealtimeCoordinator,
+};
+use std::time::Duration;
+
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone)]
+struct Periodic;
+
+impl RealtimeComponent for Periodic {
+    type Event = ();
+
+    fn tick(&mut self) -> (Self::Event, Duration) {
+        ((), Duration::from_millis(10))
+    }
+}
+
+impl RealtimeComponentApplyEvent<Context> for Periodic {
+    fn apply_event(_event: (), _entity: Entity, context: &mut Context) {
+        context.num_events += 1;
+    }
+}
+
+entity_table_realtime::declare_realtime_entity_module! {
+    components[Context] {
+        flicker: Periodic,
+    }
+}
+
+#[derive(Default)]
+struct Context {
+    components: components::RealtimeComponents,
+    entities: ComponentTable<()>,
+    num_events: usize,
+}
+
+impl ContextContainsRealtimeComponents for Context {
+    type Components = components::RealtimeComponents;
+    fn components_mut(&mut self) -> &mut Self::Components {
+        &mut self.components
+    }
+    fn realtime_entities(&self) -> Entities<'_> {
+        self.entities.entities()
+    }
+}
+
+const FRAME: Duration = Duration::from_millis(25);
+
+#[test]
+fn entities_without_components_can_be_pruned() {
+    let mut allocator = EntityAllocator::default();
+    let (e1, e2) = (allocator.alloc(), allocator.alloc());
+    let mut context = Context::default();
+    context.components.flicker.insert(e1, Periodic);
+    let mut entities = vec![e1, e2];
+    entities.retain(|&entity| process_entity_frame(entity, FRAME, &mut context));
+    assert_eq!(entities, vec![e1]);
+    assert_eq!(context.num_events, 3);
+    context.components.flicker.remove(e1);
+    assert!(!process_entity_frame(e1, FRAME, &mut context));
+    assert_eq!(context.num_events, 3);
+}
+
+#[test]
+fn dyn_and_coordinator_frames_report_whether_anything_was_processed() {
+    let mut allocator = EntityAllocator::default();
+    let (e1, e2) = (allocator.alloc(), allocator.alloc());
+    let mut context = Context::default();
+    let mut components = components::RealtimeComponents::default();
+    components.flicker.insert(e1, Periodic);
+    assert!(process_entity_frame_dyn(
+        e1,
+        FRAME,
+        &mut components,
+        &mut context
+    ));
+    assert!(!process_entity_frame_dyn(
+        e2,
+        FRAME,
+        &mut components,
+        &mut context
+    ));
+    assert_eq!(context.num_events, 3);
+    let mut coordinator = RealtimeCoordinator::<Context>::new();
+    coordinator.register(components);
+    assert!(coordinator.process_entity_frame(e1, FRAME, &mut context));
+    assert!(!coordinator.process_entity_frame(e2, FRAME, &mut context));
+    assert_eq!(context.num_events, 6);
+}
+
+#[test]
+fn batches_of_entities_are_processed_and_counted() {
+    let mut allocator = EntityAllocator::default();
+    let (e1, e2, e3) = (allocator.alloc(), allocator.alloc(), allocator.alloc());
+    let mut context = Context::default();
+    context.components.flicker.insert(e1, Periodic);
+    context.components.flicker.insert(e2, Periodic);
+    // e3 has no components, and e1 appears twice
+    let batch = [e1, e2, e3, e1];
+    assert_eq!(process_entities_frame(&batch, FRAME, &mut context), 3);
+    // e1 ticks at 0ms, 10ms and 20ms in its first frame, then at 30ms, 40ms and 50ms in its
+    // second
+    assert_eq!(context.num_events, 3 + 3 + 3);
+    assert_eq!(
+        context.components.flicker.time_to_due(e1),
+        Some(Duration::from_millis(10))
+    );
+    let mut context = Context::default();
+    context.components.flicker.insert(e1, Periodic);
+    context.components.flicker.insert(e2, Periodic);
+    assert_eq!(process_entities_frame_dedup(&batch, FRAME, &mut context), 2);
+    assert_eq!(context.num_events, 3 + 3);
+}
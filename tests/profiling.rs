@@ -0,0 +1,83 @@
+#![cfg(feature = "puffin")]
+
+use entity_table::{ComponentTable, Entities, Entity, EntityAllocator};
+use entity_table_realtime::puffin::{self, GlobalProfiler};
+use entity_table_realtime::{
+    process_entity_frame, ContextContainsRealtimeComponents, RealtimeComponent,
+    RealtimeComponentApplyEvent,
+};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone)]
+struct Periodic;
+
+impl RealtimeComponent for Periodic {
+    type Event = ();
+
+    fn tick(&mut self) -> (Self::Event, Duration) {
+        ((), Duration::from_millis(10))
+    }
+}
+
+impl RealtimeComponentApplyEvent<Context> for Periodic {
+    fn apply_event(_event: (), _entity: Entity, context: &mut Context) {
+        context.applied += 1;
+    }
+}
+
+entity_table_realtime::declare_realtime_entity_module! {
+    components[Context] {
+        flicker: Periodic,
+    }
+}
+
+#[derive(Default)]
+struct Context {
+    components: components::RealtimeComponents,
+    entities: ComponentTable<()>,
+    applied: usize,
+}
+
+impl ContextContainsRealtimeComponents for Context {
+    type Components = components::RealtimeComponents;
+    fn components_mut(&mut self) -> &mut Self::Components {
+        &mut self.components
+    }
+    fn realtime_entities(&self) -> Entities<'_> {
+        self.entities.entities()
+    }
+}
+
+#[test]
+fn processing_a_frame_reports_scopes_to_puffin() {
+    let scope_names = Arc::new(Mutex::new(Vec::new()));
+    let sink_scope_names = Arc::clone(&scope_names);
+    let sink = GlobalProfiler::lock().add_sink(Box::new(move |frame| {
+        let mut names = sink_scope_names.lock().unwrap();
+        names.extend(
+            frame
+                .scope_delta
+                .iter()
+                .map(|scope| scope.name().to_string()),
+        );
+    }));
+    puffin::set_scopes_on(true);
+    let entity = EntityAllocator::default().alloc();
+    let mut context = Context::default();
+    context.entities.insert(entity, ());
+    context.components.flicker.insert(entity, Periodic);
+    process_entity_frame(entity, Duration::from_millis(25), &mut context);
+    GlobalProfiler::lock().new_frame();
+    puffin::set_scopes_on(false);
+    GlobalProfiler::lock().remove_sink(sink);
+    assert_eq!(context.applied, 3);
+    let scope_names = scope_names.lock().unwrap();
+    for expected in ["process_entity_frame", "tick", "apply_event"] {
+        assert!(
+            scope_names.iter().any(|name| name == expected),
+            "missing scope {expected} in {scope_names:?}"
+        );
+    }
+}
@@ -0,0 +1,93 @@
+use entity_table::EntityAllocator;
+use std::time::Duration;
+
+mod effects {
+    use entity_table::Entity;
+    use entity_table_realtime::{RealtimeComponent, RealtimeComponentApplyEvent};
+    use std::time::Duration;
+
+    #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+    #[derive(Debug, Clone)]
+    pub struct Flicker;
+
+    impl RealtimeComponent for Flicker {
+        type Event = u8;
+
+        fn tick(&mut self) -> (Self::Event, Duration) {
+            (1, Duration::from_millis(10))
+        }
+    }
+
+    impl RealtimeComponentApplyEvent<crate::world::Context> for Flicker {
+        fn apply_event(event: u8, _entity: Entity, context: &mut crate::world::Context) {
+            context.log.push(event);
+        }
+    }
+
+    pub mod particles {
+        use entity_table::Entity;
+        use entity_table_realtime::{RealtimeComponent, RealtimeComponentApplyEvent};
+        use std::time::Duration;
+
+        #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+        #[derive(Debug, Clone)]
+        pub struct Emitter;
+
+        impl RealtimeComponent for Emitter {
+            type Event = u8;
+
+            fn tick(&mut self) -> (Self::Event, Duration) {
+                (2, Duration::from_millis(25))
+            }
+        }
+
+        impl RealtimeComponentApplyEvent<crate::world::Context> for Emitter {
+            fn apply_event(event: u8, _entity: Entity, context: &mut crate::world::Context) {
+                context.log.push(event);
+            }
+        }
+    }
+}
+
+mod world {
+    #[derive(Default)]
+    pub struct Context {
+        pub log: Vec<u8>,
+    }
+}
+
+/// Nothing is imported here, so every type the generated code refers to must be resolved
+/// through the paths it was given
+mod spawn {
+    entity_table_realtime::declare_realtime_entity_module! {
+        components[crate::world::Context] {
+            flicker: crate::effects::Flicker,
+            emitter: super::effects::particles::Emitter,
+        }
+    }
+
+    pub use components::{RealtimeComponents, RealtimeEntityData};
+}
+
+#[test]
+fn path_qualified_types_resolve_where_the_macro_is_invoked() {
+    let entity = EntityAllocator::default().alloc();
+    let mut components = spawn::RealtimeComponents::default();
+    components.insert_entity_data(
+        entity,
+        spawn::RealtimeEntityData {
+            flicker: Some(effects::Flicker),
+            emitter: Some(effects::particles::Emitter),
+        },
+    );
+    let mut context = world::Context::default();
+    let mut elapsed = Duration::ZERO;
+    let frame = Duration::from_millis(30);
+    while elapsed < frame {
+        let (events, until_next_tick) = components.tick_entity(entity, frame - elapsed);
+        events.apply(entity, &mut context);
+        elapsed += until_next_tick;
+    }
+    // flicker at 0ms, 10ms, 20ms and 30ms, emitter at 0ms and 25ms
+    assert_eq!(context.log, vec![1, 2, 1, 1, 2, 1]);
+}
@@ -0,0 +1,98 @@
+use entity_table::{Entity, EntityAllocator};
+use entity_table_realtime::rate_limit::{Debounce, Throttle};
+use entity_table_realtime::testing::{assert_timeline, run_component};
+use entity_table_realtime::{RealtimeComponent, RealtimeComponentApplyEvent};
+use std::time::Duration;
+
+/// Ticks in bursts of four ticks 2ms apart, with 10ms between bursts. Each event is the number
+/// of the tick, counting from zero.
+#[derive(Debug, Clone, Default)]
+struct Bursty {
+    ticks: u32,
+}
+
+impl RealtimeComponent for Bursty {
+    type Event = u32;
+
+    fn tick(&mut self) -> (Self::Event, Duration) {
+        let event = self.ticks;
+        self.ticks += 1;
+        let period = if self.ticks.is_multiple_of(4) { 10 } else { 2 };
+        (event, Duration::from_millis(period))
+    }
+}
+
+impl RealtimeComponentApplyEvent<Vec<u32>> for Bursty {
+    fn apply_event(event: u32, _entity: Entity, context: &mut Vec<u32>) {
+        context.push(event);
+    }
+}
+
+const fn ms(millis: u64) -> Duration {
+    Duration::from_millis(millis)
+}
+
+/// The events which weren't dropped, with the times they occurred
+fn emitted<E>(timeline: Vec<(Duration, Option<E>)>) -> Vec<(Duration, E)> {
+    timeline
+        .into_iter()
+        .filter_map(|(time, event)| event.map(|event| (time, event)))
+        .collect()
+}
+
+#[test]
+fn throttle_drops_events_within_the_min_interval_of_the_last_emitted_event() {
+    let throttle = Throttle::new(Bursty::default(), ms(5));
+    assert_eq!(throttle.min_interval(), ms(5));
+    let timeline = run_component(throttle, ms(0), ms(40));
+    // the inner component still ticks at its own rate
+    let times = timeline.iter().map(|&(time, _)| time).collect::<Vec<_>>();
+    assert_eq!(times, [0, 2, 4, 6, 16, 18, 20, 22, 32, 34, 36, 38].map(ms));
+    assert_timeline(
+        &emitted(timeline),
+        &[
+            (ms(0), 0),
+            (ms(6), 3),
+            (ms(16), 4),
+            (ms(22), 7),
+            (ms(32), 8),
+            (ms(38), 11),
+        ],
+    );
+}
+
+#[test]
+fn throttle_with_no_min_interval_emits_every_event() {
+    let timeline = run_component(Throttle::new(Bursty::default(), ms(0)), ms(0), ms(20));
+    assert!(timeline.iter().all(|(_, event)| event.is_some()));
+}
+
+#[test]
+fn debounce_emits_the_last_event_of_each_burst_once_quiet() {
+    let debounce = Debounce::new(Bursty::default(), ms(5));
+    assert_eq!(debounce.quiet(), ms(5));
+    let timeline = run_component(debounce, ms(0), ms(50));
+    assert_timeline(
+        &emitted(timeline),
+        &[(ms(11), 3), (ms(27), 7), (ms(43), 11)],
+    );
+}
+
+#[test]
+fn debounce_never_emits_for_components_which_are_never_quiet() {
+    let debounce = Debounce::new(Bursty::default(), ms(20));
+    let timeline = run_component(debounce, ms(0), ms(200));
+    assert!(!timeline.is_empty());
+    assert!(emitted(timeline).is_empty());
+}
+
+#[test]
+fn only_emitted_events_are_applied() {
+    let entity = EntityAllocator::default().alloc();
+    let mut applied = Vec::new();
+    Throttle::<Bursty>::apply_event(None, entity, &mut applied);
+    Throttle::<Bursty>::apply_event(Some(1), entity, &mut applied);
+    Debounce::<Bursty>::apply_event(None, entity, &mut applied);
+    Debounce::<Bursty>::apply_event(Some(2), entity, &mut applied);
+    assert_eq!(applied, vec![1, 2]);
+}
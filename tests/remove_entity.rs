@@ -0,0 +1,74 @@
+use entity_table::{Entity, EntityAllocator};
+use entity_table_realtime::{RealtimeComponent, RealtimeComponentApplyEvent, RealtimeComponents};
+use std::time::Duration;
+
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone)]
+struct Periodic;
+
+impl RealtimeComponent for Periodic {
+    type Event = ();
+
+    fn tick(&mut self) -> (Self::Event, Duration) {
+        ((), Duration::from_millis(10))
+    }
+}
+
+impl RealtimeComponentApplyEvent<()> for Periodic {
+    fn apply_event(_event: (), _entity: Entity, _context: &mut ()) {}
+}
+
+entity_table_realtime::declare_realtime_entity_module! {
+    components[()] {
+        flicker: Periodic,
+        poison: Periodic,
+    }
+}
+
+/// Despawn an entity from any collection of realtime components
+fn despawn<R: RealtimeComponents<()>>(components: &mut R, entity: Entity) {
+    components.remove_entity(entity);
+}
+
+/// Reset any collection of realtime components, e.g. when loading a new level
+fn reset<R: RealtimeComponents<()>>(components: &mut R) {
+    components.clear();
+}
+
+fn components_of(entities: &[Entity]) -> components::RealtimeComponents {
+    let mut components = components::RealtimeComponents::default();
+    for &entity in entities {
+        components.flicker.insert(entity, Periodic);
+        components.poison.insert(entity, Periodic);
+    }
+    components
+}
+
+#[test]
+fn remove_entity_removes_every_component_of_only_that_entity() {
+    let mut allocator = EntityAllocator::default();
+    let (e1, e2) = (allocator.alloc(), allocator.alloc());
+    let mut components = components_of(&[e1, e2]);
+    despawn(&mut components, e1);
+    assert!(!components.contains_entity(e1));
+    assert!(!components.flicker.contains(e1));
+    assert!(!components.poison.contains(e1));
+    assert!(components.flicker.contains(e2));
+    assert!(components.poison.contains(e2));
+    // removing an entity with no components does nothing
+    despawn(&mut components, e1);
+    assert_eq!(components.flicker.len(), 1);
+}
+
+#[test]
+fn clear_removes_every_component() {
+    let mut allocator = EntityAllocator::default();
+    let (e1, e2) = (allocator.alloc(), allocator.alloc());
+    let mut components = components_of(&[e1, e2]);
+    reset(&mut components);
+    assert!(components.flicker.is_empty());
+    assert!(components.poison.is_empty());
+    assert!(components
+        .fast_forward(Duration::from_millis(100))
+        .is_empty());
+}
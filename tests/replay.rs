@@ -0,0 +1,117 @@
+use entity_table::{ComponentTable, Entities, Entity, EntityAllocator};
+use entity_table_realtime::replay::{
+    process_entity_frame_recorded, ReplayEvent, ReplayLog, ReplayWriter, REPLAY_FORMAT_VERSION,
+};
+use entity_table_realtime::{
+    ContextContainsRealtimeComponents, RealtimeComponent, RealtimeComponentApplyEvent,
+};
+use std::time::Duration;
+
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone)]
+struct Counter(u32);
+
+impl RealtimeComponent for Counter {
+    type Event = u32;
+
+    fn tick(&mut self) -> (Self::Event, Duration) {
+        self.0 += 1;
+        (self.0, Duration::from_millis(10))
+    }
+}
+
+impl RealtimeComponentApplyEvent<Context> for Counter {
+    fn apply_event(_count: u32, _entity: Entity, context: &mut Context) {
+        context.num_events += 1;
+    }
+}
+
+entity_table_realtime::declare_realtime_entity_module! {
+    components[Context] {
+        counter: Counter,
+    }
+}
+
+#[derive(Default)]
+struct Context {
+    components: components::RealtimeComponents,
+    entities: ComponentTable<()>,
+    num_events: usize,
+}
+
+impl ContextContainsRealtimeComponents for Context {
+    type Components = components::RealtimeComponents;
+    fn components_mut(&mut self) -> &mut Self::Components {
+        &mut self.components
+    }
+    fn realtime_entities(&self) -> Entities<'_> {
+        self.entities.entities()
+    }
+}
+
+/// Records even counts only
+fn record(event: &components::RealtimeEntityEvent) -> Option<u32> {
+    match *event {
+        components::RealtimeEntityEvent::counter(count) => (count % 2 == 0).then_some(count),
+    }
+}
+
+fn record_frames(entity: Entity, frames: &[Duration]) -> (ReplayLog<u32>, Context) {
+    let mut context = Context::default();
+    context.entities.insert(entity, ());
+    context.components.counter.insert(entity, Counter(0));
+    let mut writer = ReplayWriter::new();
+    for &frame_duration in frames {
+        writer.begin_frame(frame_duration);
+        process_entity_frame_recorded(entity, frame_duration, &mut context, record, &mut writer);
+    }
+    (writer.finish(), context)
+}
+
+#[test]
+fn frames_and_recorded_events_are_logged() {
+    let entity = EntityAllocator::default().alloc();
+    let ms = Duration::from_millis;
+    let (log, context) = record_frames(entity, &[ms(25), ms(5), ms(20)]);
+    assert_eq!(log.version(), REPLAY_FORMAT_VERSION);
+    assert_eq!(log.len(), 3);
+    assert_eq!(
+        log.frame_durations().collect::<Vec<_>>(),
+        vec![ms(25), ms(5), ms(20)]
+    );
+    let event = |offset, event| ReplayEvent {
+        offset,
+        entity,
+        event,
+    };
+    // ticks at 0ms, 10ms and 20ms in the first frame, 30ms at the end of the second frame, and
+    // 40ms and 50ms in the third
+    assert_eq!(log.frames()[0].events, vec![event(ms(10), 2)]);
+    assert_eq!(log.frames()[1].events, vec![event(ms(5), 4)]);
+    assert_eq!(log.frames()[2].events, vec![event(ms(20), 6)]);
+    // events which aren't recorded are still applied
+    assert_eq!(context.num_events, 6);
+}
+
+#[test]
+#[should_panic(expected = "no frame has begun")]
+fn events_must_be_recorded_in_a_frame() {
+    let entity = EntityAllocator::default().alloc();
+    ReplayWriter::new().record_event(Duration::ZERO, entity, ());
+}
+
+#[cfg(feature = "serialize")]
+#[test]
+fn logs_round_trip_and_newer_versions_are_rejected() {
+    let entity = EntityAllocator::default().alloc();
+    let (log, _) = record_frames(entity, &[Duration::from_millis(25)]);
+    let mut json = serde_json::to_value(&log).unwrap();
+    assert_eq!(json["version"], REPLAY_FORMAT_VERSION);
+    let round_tripped: ReplayLog<u32> = serde_json::from_value(json.clone()).unwrap();
+    assert_eq!(round_tripped, log);
+    json["version"] = (REPLAY_FORMAT_VERSION + 1).into();
+    let error = serde_json::from_value::<ReplayLog<u32>>(json).unwrap_err();
+    assert!(error
+        .to_string()
+        .contains("unsupported replay format version 2"));
+}
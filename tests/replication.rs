@@ -0,0 +1,63 @@
+use entity_table::{Entity, EntityAllocator};
+use entity_table_realtime::replication::ScheduleState;
+use entity_table_realtime::{RealtimeComponent, RealtimeComponentApplyEvent};
+use std::time::Duration;
+
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+struct Periodic;
+
+impl RealtimeComponent for Periodic {
+    type Event = ();
+
+    fn tick(&mut self) -> (Self::Event, Duration) {
+        ((), Duration::from_millis(10))
+    }
+}
+
+impl RealtimeComponentApplyEvent<()> for Periodic {
+    fn apply_event(_event: (), _entity: Entity, _context: &mut ()) {}
+}
+
+entity_table_realtime::declare_realtime_entity_module! {
+    components[()] {
+        periodic: Periodic,
+        other: Periodic,
+    }
+}
+
+fn schedules(components: &components::RealtimeComponents) -> Vec<(Entity, ScheduleState)> {
+    components
+        .periodic
+        .iter_with_schedule()
+        .chain(components.other.iter_with_schedule())
+        .map(|(entity, scheduled)| (entity, ScheduleState::of(scheduled)))
+        .collect()
+}
+
+#[test]
+fn module_delta_replicates_whole_schedules() {
+    let mut entity_allocator = EntityAllocator::default();
+    let entity = entity_allocator.alloc();
+    let mut authority = components::RealtimeComponents::default();
+    authority.periodic.insert(entity, Periodic);
+    authority.other.insert(entity, Periodic);
+    let mut client = authority.clone();
+    let last_sync = authority.clone();
+    let mut frame_remaining = Duration::from_millis(25);
+    while !frame_remaining.is_zero() {
+        let (_, until_next_tick) = authority.tick_entity(entity, frame_remaining);
+        frame_remaining -= until_next_tick;
+    }
+    let removed = entity_allocator.alloc();
+    authority.other.insert(removed, Periodic);
+    let delta = authority.delta_from(&last_sync);
+    assert_eq!(delta.periodic.rescheduled.len(), 1);
+    let schedule = delta.periodic.rescheduled[0].1;
+    assert_eq!(schedule.period, Duration::from_millis(10));
+    assert_eq!(schedule.age, Duration::from_millis(25));
+    assert_eq!(schedule.since_last_tick, Duration::from_millis(5));
+    client.apply_delta(delta);
+    assert_eq!(schedules(&client), schedules(&authority));
+    assert!(authority.delta_from(&client).is_empty());
+}
@@ -0,0 +1,129 @@
+use entity_table::{ComponentTable, Entities, Entity, EntityAllocator};
+use entity_table_realtime::{
+    process_entity_frame, resimulate, ContextContainsRealtimeComponents, RealtimeComponent,
+    RealtimeComponentApplyEvent,
+};
+use std::time::Duration;
+
+/// Ticks at pseudo-random intervals which aren't whole milliseconds
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone)]
+struct Jitter(u64);
+
+impl RealtimeComponent for Jitter {
+    type Event = u64;
+
+    fn tick(&mut self) -> (Self::Event, Duration) {
+        self.0 = self
+            .0
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        let period = Duration::from_nanos(1_000_000 + (self.0 >> 33) % 9_000_000);
+        (self.0, period)
+    }
+}
+
+impl RealtimeComponentApplyEvent<Context> for Jitter {
+    fn apply_event(value: u64, entity: Entity, context: &mut Context) {
+        context.log.push((entity, value));
+    }
+}
+
+entity_table_realtime::declare_realtime_entity_module! {
+    components[Context] {
+        jitter: Jitter,
+        flicker: Jitter,
+    }
+}
+
+#[derive(Default)]
+struct Context {
+    components: components::RealtimeComponents,
+    entities: ComponentTable<()>,
+    log: Vec<(Entity, u64)>,
+}
+
+impl ContextContainsRealtimeComponents for Context {
+    type Components = components::RealtimeComponents;
+    fn components_mut(&mut self) -> &mut Self::Components {
+        &mut self.components
+    }
+    fn realtime_entities(&self) -> Entities<'_> {
+        self.entities.entities()
+    }
+}
+
+fn context(entities: &[Entity]) -> Context {
+    let mut context = Context::default();
+    for (i, &entity) in entities.iter().enumerate() {
+        context.entities.insert(entity, ());
+        context.components.jitter.insert(entity, Jitter(i as u64));
+        context
+            .components
+            .flicker
+            .insert(entity, Jitter(i as u64 + 100));
+    }
+    context
+}
+
+fn frames() -> Vec<Duration> {
+    let mut frames = vec![Duration::from_nanos(16_666_667); 30];
+    frames[10] = Duration::from_nanos(33_333_333);
+    frames[20] = Duration::from_micros(250);
+    frames
+}
+
+fn schedules(context: &Context, entities: &[Entity]) -> Vec<Option<Duration>> {
+    entities
+        .iter()
+        .flat_map(|&entity| {
+            [
+                context.components.jitter.time_to_due(entity),
+                context.components.flicker.time_to_due(entity),
+            ]
+        })
+        .collect()
+}
+
+#[test]
+fn resimulating_matches_the_original_simulation() {
+    let mut allocator = EntityAllocator::default();
+    let entities = [allocator.alloc(), allocator.alloc(), allocator.alloc()];
+    let mut context = context(&entities);
+    let snapshot = context.components.clone();
+    for frame_duration in frames() {
+        for entity in entities {
+            process_entity_frame(entity, frame_duration, &mut context);
+        }
+        context.components.advance_simulated_time(frame_duration);
+    }
+    let log = std::mem::take(&mut context.log);
+    let schedules_after = schedules(&context, &entities);
+    assert!(log.len() > 100);
+    resimulate(&snapshot, &frames(), &mut context);
+    assert_eq!(context.log, log);
+    assert_eq!(schedules(&context, &entities), schedules_after);
+    assert_eq!(
+        context.components.jitter.simulated_time(),
+        frames().iter().sum::<Duration>()
+    );
+}
+
+#[test]
+fn resimulating_is_deterministic_regardless_of_the_current_state() {
+    let mut allocator = EntityAllocator::default();
+    let entities = [allocator.alloc(), allocator.alloc()];
+    let mut context = context(&entities);
+    let snapshot = context.components.clone();
+    resimulate(&snapshot, &frames(), &mut context);
+    let log = std::mem::take(&mut context.log);
+    let schedules_after = schedules(&context, &entities);
+    // diverge from the snapshot before rolling back to it
+    context.components.remove_entity(entities[0]);
+    context.components.jitter.insert(entities[1], Jitter(7));
+    resimulate(&snapshot, &frames()[..5], &mut context);
+    context.log.clear();
+    resimulate(&snapshot, &frames(), &mut context);
+    assert_eq!(context.log, log);
+    assert_eq!(schedules(&context, &entities), schedules_after);
+}
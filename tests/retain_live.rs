@@ -0,0 +1,61 @@
+use entity_table::{Entity, EntityAllocator};
+use entity_table_realtime::{RealtimeComponent, RealtimeComponentApplyEvent};
+use std::time::Duration;
+
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone)]
+struct Periodic;
+
+impl RealtimeComponent for Periodic {
+    type Event = ();
+
+    fn tick(&mut self) -> (Self::Event, Duration) {
+        ((), Duration::from_millis(10))
+    }
+}
+
+impl RealtimeComponentApplyEvent<()> for Periodic {
+    fn apply_event(_event: (), _entity: Entity, _context: &mut ()) {}
+}
+
+entity_table_realtime::declare_realtime_entity_module! {
+    components[()] {
+        flicker: Periodic,
+        poison: Periodic,
+    }
+}
+
+#[test]
+fn components_of_freed_entities_are_removed() {
+    let mut allocator = EntityAllocator::default();
+    let (e1, e2, e3) = (allocator.alloc(), allocator.alloc(), allocator.alloc());
+    let mut components = components::RealtimeComponents::default();
+    for entity in [e1, e2, e3] {
+        components.flicker.insert(entity, Periodic);
+    }
+    components.poison.insert(e2, Periodic);
+    components.poison.insert(e3, Periodic);
+    // despawned without removing its realtime components
+    allocator.free(e2);
+    components.retain_live(&allocator);
+    assert!(!components.contains_entity(e2));
+    assert!(components.flicker.contains(e1));
+    assert!(components.flicker.contains(e3));
+    assert!(components.poison.contains(e3));
+    // a new entity reusing the freed slot starts with no components
+    let e4 = allocator.alloc();
+    assert!(!components.contains_entity(e4));
+}
+
+#[test]
+fn tables_retain_only_live_entities() {
+    let mut allocator = EntityAllocator::default();
+    let (e1, e2) = (allocator.alloc(), allocator.alloc());
+    let mut components = components::RealtimeComponents::default();
+    components.flicker.insert(e1, Periodic);
+    components.flicker.insert(e2, Periodic);
+    allocator.free(e1);
+    allocator.free(e2);
+    components.flicker.retain_live(&allocator);
+    assert!(components.flicker.is_empty());
+}
@@ -0,0 +1,89 @@
+use entity_table::{Entity, EntityAllocator};
+use entity_table_realtime::{RealtimeComponent, RealtimeComponentApplyEvent, ScheduleHistory};
+use std::time::Duration;
+
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone)]
+struct Periodic;
+
+impl RealtimeComponent for Periodic {
+    type Event = ();
+
+    fn tick(&mut self) -> (Self::Event, Duration) {
+        ((), Duration::from_millis(10))
+    }
+}
+
+impl RealtimeComponentApplyEvent<()> for Periodic {
+    fn apply_event(_event: (), _entity: Entity, _context: &mut ()) {}
+}
+
+entity_table_realtime::declare_realtime_entity_module! {
+    components[()] {
+        periodic: Periodic,
+    }
+}
+
+const FRAME: Duration = Duration::from_millis(3);
+
+/// Record the schedules after each of `num_frames` frames
+fn record_frames(num_frames: usize, capacity: usize) -> (Entity, ScheduleHistory) {
+    let entity = EntityAllocator::default().alloc();
+    let mut components = components::RealtimeComponents::default();
+    components.periodic.insert(entity, Periodic);
+    let mut history = ScheduleHistory::new(capacity);
+    for _ in 0..num_frames {
+        components.fast_forward(FRAME);
+        history.record(components.debug_summary());
+    }
+    (entity, history)
+}
+
+fn until_next_tick(history: &ScheduleHistory, frames_ago: usize, entity: Entity) -> Duration {
+    history
+        .component(frames_ago, entity, "periodic")
+        .unwrap()
+        .until_next_tick
+}
+
+#[test]
+fn history_looks_back_from_the_most_recent_frame() {
+    let (entity, history) = record_frames(3, 8);
+    assert_eq!(history.len(), 3);
+    // ticks at 0ms, then 3ms, 6ms and 9ms into its 10ms period
+    assert_eq!(
+        until_next_tick(&history, 0, entity),
+        Duration::from_millis(1)
+    );
+    assert_eq!(
+        until_next_tick(&history, 1, entity),
+        Duration::from_millis(4)
+    );
+    assert_eq!(
+        until_next_tick(&history, 2, entity),
+        Duration::from_millis(7)
+    );
+    assert!(history.frames_ago(3).is_none());
+    assert!(history.component(0, entity, "missing").is_none());
+}
+
+#[test]
+fn history_discards_the_oldest_frame_when_full() {
+    let (entity, mut history) = record_frames(3, 2);
+    assert_eq!(history.capacity(), 2);
+    assert_eq!(history.len(), 2);
+    assert_eq!(
+        until_next_tick(&history, 1, entity),
+        Duration::from_millis(4)
+    );
+    assert!(history.frames_ago(2).is_none());
+    history.clear();
+    assert!(history.is_empty());
+}
+
+#[test]
+fn history_with_no_capacity_records_nothing() {
+    let (_, history) = record_frames(3, 0);
+    assert!(history.is_empty());
+    assert!(history.frames_ago(0).is_none());
+}
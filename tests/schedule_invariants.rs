@@ -0,0 +1,43 @@
+#![cfg(debug_assertions)]
+
+use entity_table::{Entity, EntityAllocator};
+use entity_table_realtime::debug_assert_schedule_step;
+
+fn entity() -> Entity {
+    EntityAllocator::default().alloc()
+}
+
+#[test]
+fn consistent_steps_are_accepted() {
+    let entity = entity();
+    // ticked exactly when due, and rescheduled
+    debug_assert_schedule_step("flicker", entity, Some(10), Some(7), true, 10);
+    // advanced by the step
+    debug_assert_schedule_step("flicker", entity, Some(10), Some(4), false, 6);
+    // components which weren't present aren't checked
+    debug_assert_schedule_step("flicker", entity, None, Some(3), false, 6);
+}
+
+#[test]
+#[should_panic(expected = "schedule of flicker of")]
+fn stepping_past_the_next_tick_panics() {
+    debug_assert_schedule_step("flicker", entity(), Some(5), Some(0), false, 6);
+}
+
+#[test]
+#[should_panic(expected = "ticked with 4ns until its next tick")]
+fn ticking_early_panics() {
+    debug_assert_schedule_step("flicker", entity(), Some(10), Some(10), true, 6);
+}
+
+#[test]
+#[should_panic(expected = "was due but didn't tick")]
+fn not_ticking_when_due_panics() {
+    debug_assert_schedule_step("flicker", entity(), Some(6), Some(0), false, 6);
+}
+
+#[test]
+#[should_panic(expected = "went from 10ns to Some(5)ns over a 6ns step")]
+fn schedules_drifting_panics() {
+    debug_assert_schedule_step("flicker", entity(), Some(10), Some(5), false, 6);
+}
@@ -0,0 +1,74 @@
+use entity_table::{Entity, EntityAllocator};
+use entity_table_realtime::{RealtimeComponent, RealtimeComponentTable, SchedulePolicy};
+use std::time::Duration;
+
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone)]
+struct Periodic;
+
+impl RealtimeComponent for Periodic {
+    type Event = ();
+
+    fn tick(&mut self) -> (Self::Event, Duration) {
+        ((), Duration::from_millis(100))
+    }
+}
+
+fn entities(n: usize) -> Vec<Entity> {
+    let mut allocator = EntityAllocator::default();
+    (0..n).map(|_| allocator.alloc()).collect()
+}
+
+#[test]
+fn inserted_components_are_immediate_by_default() {
+    let e = entities(1);
+    let mut table = RealtimeComponentTable::default();
+    assert_eq!(table.schedule_policy(), SchedulePolicy::Immediate);
+    table.insert(e[0], Periodic);
+    assert_eq!(table.time_to_due(e[0]), Some(Duration::ZERO));
+}
+
+#[test]
+fn delayed_components_first_tick_after_the_delay() {
+    let e = entities(2);
+    let mut table = RealtimeComponentTable::default()
+        .with_schedule_policy(SchedulePolicy::Delay(Duration::from_millis(30)));
+    table.insert(e[0], Periodic);
+    assert_eq!(table.time_to_due(e[0]), Some(Duration::from_millis(30)));
+    // changing the policy only affects components inserted afterwards
+    table.set_schedule_policy(SchedulePolicy::Immediate);
+    table.insert(e[1], Periodic);
+    assert_eq!(table.time_to_due(e[0]), Some(Duration::from_millis(30)));
+    assert_eq!(table.time_to_due(e[1]), Some(Duration::ZERO));
+    let times = table
+        .fast_forward(Duration::from_millis(30))
+        .into_iter()
+        .map(|(time, entity, ())| (time, entity))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        times,
+        vec![(Duration::ZERO, e[1]), (Duration::from_millis(30), e[0])]
+    );
+}
+
+#[test]
+fn spread_components_first_tick_within_the_range_deterministically() {
+    let range = Duration::from_millis(100);
+    let policy = SchedulePolicy::Spread(range);
+    let e = entities(32);
+    let delays = e
+        .iter()
+        .map(|&entity| policy.until_first_tick(entity))
+        .collect::<Vec<_>>();
+    assert!(delays.iter().all(|&delay| delay < range));
+    assert!(delays.iter().any(|&delay| delay != delays[0]));
+    let mut table = RealtimeComponentTable::default().with_schedule_policy(policy);
+    for (&entity, &delay) in e.iter().zip(&delays) {
+        table.insert(entity, Periodic);
+        assert_eq!(table.time_to_due(entity), Some(delay));
+    }
+    assert_eq!(
+        SchedulePolicy::Spread(Duration::ZERO).until_first_tick(e[0]),
+        Duration::ZERO
+    );
+}
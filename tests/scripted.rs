@@ -0,0 +1,124 @@
+#![cfg(feature = "rhai")]
+
+use entity_table::{Entity, EntityAllocator};
+use entity_table_realtime::scripted::rhai::{Dynamic, Engine};
+use entity_table_realtime::scripted::{
+    Script, ScriptContext, ScriptError, ScriptedRealtimeComponent,
+};
+use entity_table_realtime::{
+    RealtimeComponent, RealtimeComponentApplyEvent, RealtimeComponentTable,
+};
+use std::rc::Rc;
+use std::time::Duration;
+
+const FLICKER: &str = r#"
+fn tick() {
+    this.brightness = if this.brightness > 0 { 0 } else { 10 };
+    [this.brightness, 150]
+}
+
+fn apply(brightness) {
+    if brightness > 0 {
+        [#{ set_light: brightness }, #{ play: "buzz" }]
+    } else {
+        #{ set_light: brightness }
+    }
+}
+"#;
+
+#[derive(Default)]
+struct Context {
+    commands: Vec<(Entity, String)>,
+}
+
+impl ScriptContext for Context {
+    fn script_command(&mut self, entity: Entity, command: Dynamic) {
+        self.commands.push((entity, command.to_string()));
+    }
+}
+
+fn state(source: &str) -> Dynamic {
+    Engine::new().eval(source).unwrap()
+}
+
+fn component(name: &str, source: &str, state_source: &str) -> ScriptedRealtimeComponent {
+    let script = Script::compile(name, source).unwrap();
+    ScriptedRealtimeComponent::new(Rc::new(script), state(state_source))
+}
+
+#[test]
+fn scripts_must_parse_and_define_tick() {
+    assert!(matches!(
+        Script::compile("broken", "fn tick( {"),
+        Err(ScriptError::Parse(_))
+    ));
+    assert!(matches!(
+        Script::compile("no_tick", "fn apply(x) { x }"),
+        Err(ScriptError::MissingTick)
+    ));
+    let script = Script::compile("flicker", FLICKER).unwrap();
+    assert_eq!(script.name(), "flicker");
+    assert_eq!(script.source(), FLICKER);
+}
+
+#[test]
+fn scripts_tick_with_their_state_and_return_commands() {
+    let entity = EntityAllocator::default().alloc();
+    let mut table = RealtimeComponentTable::default();
+    table.insert(entity, component("flicker", FLICKER, "#{ brightness: 0 }"));
+    let mut context = Context::default();
+    for (_, entity, event) in table.fast_forward(Duration::from_millis(150)) {
+        ScriptedRealtimeComponent::apply_event(event, entity, &mut context);
+    }
+    assert_eq!(
+        context.commands,
+        vec![
+            (entity, "#{\"set_light\": 10}".to_string()),
+            (entity, "#{\"play\": \"buzz\"}".to_string()),
+            (entity, "#{\"set_light\": 0}".to_string()),
+        ]
+    );
+    let state = table.get(entity).unwrap().state().clone();
+    assert_eq!(state.to_string(), "#{\"brightness\": 0}");
+}
+
+#[test]
+fn scripts_without_apply_produce_no_commands() {
+    let entity = EntityAllocator::default().alloc();
+    let mut table = RealtimeComponentTable::default();
+    table.insert(
+        entity,
+        component("counter", "fn tick() { this += 1; [this, 2.5] }", "0"),
+    );
+    let mut context = Context::default();
+    let events = table.fast_forward(Duration::from_millis(5));
+    // fractional milliseconds are accepted
+    let times: Vec<_> = events.iter().map(|&(time, _, _)| time).collect();
+    assert_eq!(
+        times,
+        vec![
+            Duration::ZERO,
+            Duration::from_micros(2500),
+            Duration::from_millis(5)
+        ]
+    );
+    assert_eq!(events[2].2.value.as_int(), Ok(3));
+    for (_, entity, event) in events {
+        ScriptedRealtimeComponent::apply_event(event, entity, &mut context);
+    }
+    assert!(context.commands.is_empty());
+}
+
+#[test]
+#[should_panic(expected = "error in script spin")]
+fn scripts_stuck_in_a_loop_are_stopped() {
+    let mut component = component("spin", "fn tick() { loop {} }", "()");
+    component.tick();
+}
+
+#[cfg(feature = "serialize")]
+#[test]
+fn scripted_components_serialize_with_their_source() {
+    fn assert_serde<T: serde::Serialize + for<'de> serde::Deserialize<'de>>() {}
+    assert_serde::<ScriptedRealtimeComponent>();
+}
@@ -0,0 +1,114 @@
+use entity_table::{ComponentTable, Entities, Entity, EntityAllocator};
+use entity_table_realtime::seconds::{duration_from_secs, process_entity_frame_secs};
+use entity_table_realtime::{
+    AnimationContext, ContextContainsRealtimeComponents, RealtimeComponent,
+    RealtimeComponentApplyEvent, ScheduledRealtimeComponent,
+};
+use std::time::Duration;
+
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone)]
+struct Periodic;
+
+impl RealtimeComponent for Periodic {
+    type Event = ();
+
+    fn tick(&mut self) -> (Self::Event, Duration) {
+        ((), Duration::from_millis(100))
+    }
+}
+
+impl<'a> RealtimeComponentApplyEvent<Context<'a>> for Periodic {
+    fn apply_event(_event: (), _entity: Entity, context: &mut Context<'a>) {
+        *context.applied += 1;
+    }
+}
+
+entity_table_realtime::declare_realtime_entity_module! {
+    components<'a>[Context<'a>] {
+        periodic: Periodic,
+    }
+}
+
+struct Context<'a> {
+    components: &'a mut components::RealtimeComponents,
+    entities: &'a ComponentTable<()>,
+    applied: &'a mut usize,
+}
+
+impl<'a> ContextContainsRealtimeComponents for Context<'a> {
+    type Components = components::RealtimeComponents;
+    fn components_mut(&mut self) -> &mut Self::Components {
+        self.components
+    }
+    fn realtime_entities(&self) -> Entities<'_> {
+        self.entities.entities()
+    }
+}
+
+#[test]
+fn seconds_are_rounded_to_the_nearest_nanosecond() {
+    // 0.3 is slightly less than 300ms as an f64, which `from_secs_f64` truncates
+    assert_eq!(duration_from_secs(0.3), Duration::from_millis(300));
+    assert_eq!(duration_from_secs(1.5e-9), Duration::from_nanos(2));
+    assert_eq!(duration_from_secs(-0.016), Duration::ZERO);
+    assert_eq!(duration_from_secs(f64::NAN), Duration::ZERO);
+    assert_eq!(duration_from_secs(f64::INFINITY), Duration::MAX);
+    assert_eq!(duration_from_secs(1e30), Duration::MAX);
+}
+
+#[test]
+fn frames_and_schedules_in_seconds() {
+    let entity = EntityAllocator::default().alloc();
+    let mut components = components::RealtimeComponents::default();
+    let mut entities = ComponentTable::default();
+    entities.insert(entity, ());
+    components
+        .periodic
+        .insert_with_delay_secs(entity, Periodic, 0.05);
+    let mut applied = 0;
+    let mut context = Context {
+        components: &mut components,
+        entities: &entities,
+        applied: &mut applied,
+    };
+    // ticks at 50ms and 150ms
+    assert!(process_entity_frame_secs(entity, 0.2, &mut context));
+    // a negative frame duration simulates nothing
+    assert!(process_entity_frame_secs(entity, -1.0, &mut context));
+    assert_eq!(applied, 2);
+    assert_eq!(
+        components.periodic.time_to_due(entity),
+        Some(Duration::from_millis(50))
+    );
+}
+
+#[test]
+fn animation_context_ticks_in_seconds() {
+    let entity = EntityAllocator::default().alloc();
+    let mut components = components::RealtimeComponents::default();
+    let mut entities = ComponentTable::default();
+    entities.insert(entity, ());
+    components.periodic.insert(entity, Periodic);
+    let mut applied = 0;
+    AnimationContext::new().tick_secs(
+        Context {
+            components: &mut components,
+            entities: &entities,
+            applied: &mut applied,
+        },
+        0.25,
+    );
+    // ticks at 0ms, 100ms and 200ms
+    assert_eq!(applied, 3);
+    assert_eq!(components.periodic.simulated_secs(), 0.25);
+}
+
+#[test]
+fn scheduled_components_in_seconds() {
+    let mut scheduled = ScheduledRealtimeComponent::new_secs(Periodic, 0.25);
+    assert_eq!(scheduled.until_next_tick(), Duration::from_millis(250));
+    assert_eq!(scheduled.until_next_tick_secs(), 0.25);
+    scheduled.set_until_next_tick_secs(0.1);
+    assert_eq!(scheduled.until_next_tick(), Duration::from_millis(100));
+}
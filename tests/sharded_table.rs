@@ -0,0 +1,139 @@
+use entity_table::EntityAllocator;
+use entity_table_realtime::sharded::ShardedRealtimeComponentTable;
+use entity_table_realtime::{RealtimeComponent, RealtimeComponentTable, SchedulePolicy};
+use std::time::Duration;
+
+#[derive(Debug, Clone, PartialEq)]
+struct Periodic;
+
+#[derive(Debug, Clone, PartialEq)]
+struct Counter(u32);
+
+impl RealtimeComponent for Counter {
+    type Event = u32;
+
+    fn tick(&mut self) -> (Self::Event, Duration) {
+        self.0 += 1;
+        (self.0, Duration::from_millis(10))
+    }
+}
+
+impl RealtimeComponent for Periodic {
+    type Event = ();
+
+    fn tick(&mut self) -> (Self::Event, Duration) {
+        ((), Duration::from_millis(10))
+    }
+}
+
+#[test]
+fn components_are_accessed_through_their_shard_from_many_threads() {
+    let mut entity_allocator = EntityAllocator::default();
+    let entities = (0..100)
+        .map(|_| entity_allocator.alloc())
+        .collect::<Vec<_>>();
+    let sharded = ShardedRealtimeComponentTable::new(4);
+    assert_eq!(sharded.num_shards(), 4);
+    std::thread::scope(|scope| {
+        for chunk in entities.chunks(25) {
+            let sharded = &sharded;
+            scope.spawn(move || {
+                for &entity in chunk {
+                    assert_eq!(sharded.insert(entity, Counter(0)), None);
+                    sharded.with_mut(entity, |counter| counter.0 = 1);
+                }
+            });
+        }
+    });
+    for &entity in &entities {
+        assert!(sharded.contains(entity));
+        assert_eq!(sharded.with(entity, |counter| counter.0), Some(1));
+        assert!(sharded.read_shard(entity).contains(entity));
+        assert!(sharded.shard_index(entity) < 4);
+    }
+    // entities are spread across the shards
+    let mut num_ticked = Vec::new();
+    sharded.for_each_shard_mut(|shard| {
+        num_ticked.push(shard.fast_forward(Duration::from_millis(10)).len());
+    });
+    assert_eq!(num_ticked.iter().sum::<usize>(), 200);
+    assert!(num_ticked.iter().all(|&n| n > 0));
+    assert_eq!(sharded.remove(entities[0]), Some(Counter(3)));
+    assert!(!sharded.contains(entities[0]));
+    assert_eq!(sharded.with(entities[0], |counter| counter.0), None);
+}
+
+#[test]
+fn sharding_preserves_schedules() {
+    let mut entity_allocator = EntityAllocator::default();
+    let mut table = RealtimeComponentTable::default();
+    let entities = (0..10)
+        .map(|_| entity_allocator.alloc())
+        .collect::<Vec<_>>();
+    for &entity in &entities {
+        table.insert(entity, Counter(0));
+    }
+    table.fast_forward(Duration::from_millis(3));
+    let mut sharded = ShardedRealtimeComponentTable::from_table(table, 3);
+    for shard in sharded.shards_mut() {
+        shard.fast_forward(Duration::from_millis(4));
+    }
+    let table = sharded.into_table();
+    assert_eq!(table.len(), 10);
+    for &entity in &entities {
+        assert_eq!(table.time_to_due(entity), Some(Duration::from_millis(3)));
+        assert_eq!(table.get(entity), Some(&Counter(1)));
+    }
+}
+
+#[test]
+#[should_panic(expected = "num_shards must be positive")]
+fn sharded_tables_need_a_shard() {
+    ShardedRealtimeComponentTable::<Periodic>::new(0);
+}
+
+#[test]
+fn sharding_preserves_limits_and_schedule_policy() {
+    let mut entity_allocator = EntityAllocator::default();
+    let mut table = RealtimeComponentTable::default()
+        .with_schedule_policy(SchedulePolicy::Delay(Duration::from_millis(5)));
+    table.set_event_cap(3, |_| ());
+    table.set_max_period(Duration::from_secs(1), |_| ());
+    for _ in 0..10 {
+        table.insert(entity_allocator.alloc(), Periodic);
+    }
+    let mut sharded = ShardedRealtimeComponentTable::from_table(table, 4);
+    for shard in sharded.shards_mut() {
+        assert_eq!(shard.event_cap(), Some(3));
+        assert_eq!(shard.max_period(), Some(Duration::from_secs(1)));
+    }
+    let table = sharded.into_table();
+    assert_eq!(table.len(), 10);
+    assert_eq!(table.event_cap(), Some(3));
+    assert_eq!(table.max_period(), Some(Duration::from_secs(1)));
+    assert_eq!(
+        table.schedule_policy(),
+        SchedulePolicy::Delay(Duration::from_millis(5))
+    );
+}
+
+#[test]
+fn configuring_a_sharded_table_configures_every_shard() {
+    let mut sharded = ShardedRealtimeComponentTable::<Periodic>::new(3);
+    sharded.set_event_cap(2, |_| ());
+    sharded.set_max_period(Duration::from_secs(1), |_| ());
+    sharded.set_schedule_policy(SchedulePolicy::Spread(Duration::from_millis(100)));
+    for shard in sharded.shards_mut() {
+        assert_eq!(shard.event_cap(), Some(2));
+        assert_eq!(shard.max_period(), Some(Duration::from_secs(1)));
+        assert_eq!(
+            shard.schedule_policy(),
+            SchedulePolicy::Spread(Duration::from_millis(100))
+        );
+    }
+    sharded.clear_event_cap();
+    sharded.clear_max_period();
+    let table = sharded.into_table();
+    assert_eq!(table.event_cap(), None);
+    assert_eq!(table.max_period(), None);
+}
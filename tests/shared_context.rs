@@ -0,0 +1,86 @@
+use entity_table::{Entity, EntityAllocator};
+use entity_table_realtime::shared::{
+    process_entity_frame_shared, ContextContainsRealtimeComponentsShared, SharedContext,
+};
+use entity_table_realtime::{RealtimeComponent, RealtimeComponentApplyEvent};
+use std::cell::RefCell;
+use std::time::Duration;
+
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone)]
+struct Counter(u32);
+
+impl RealtimeComponent for Counter {
+    type Event = u32;
+
+    fn tick(&mut self) -> (Self::Event, Duration) {
+        self.0 += 1;
+        (self.0, Duration::from_millis(10))
+    }
+}
+
+impl<'a> RealtimeComponentApplyEvent<SharedContext<'a, Game>> for Counter {
+    fn apply_event(count: u32, entity: Entity, game: &mut SharedContext<'a, Game>) {
+        // the components aren't borrowed while events are applied
+        let total = game.components.borrow().counter.get(entity).unwrap().0;
+        game.log.borrow_mut().push((count, total));
+    }
+}
+
+entity_table_realtime::declare_realtime_entity_module! {
+    components<'a>[SharedContext<'a, Game>] {
+        counter: Counter,
+    }
+}
+
+#[derive(Default)]
+struct Game {
+    components: RefCell<components::RealtimeComponents>,
+    log: RefCell<Vec<(u32, u32)>>,
+    max_frame_duration: Option<Duration>,
+}
+
+impl ContextContainsRealtimeComponentsShared for Game {
+    type Components = components::RealtimeComponents;
+    fn with_realtime_components<R>(&self, f: impl FnOnce(&mut Self::Components) -> R) -> R {
+        f(&mut self.components.borrow_mut())
+    }
+    fn realtime_max_frame_duration(&self, _entity: Entity) -> Option<Duration> {
+        self.max_frame_duration
+    }
+}
+
+#[test]
+fn events_are_applied_to_a_shared_context() {
+    let mut allocator = EntityAllocator::default();
+    let (e1, e2) = (allocator.alloc(), allocator.alloc());
+    let game = Game::default();
+    game.components.borrow_mut().counter.insert(e1, Counter(0));
+    assert!(process_entity_frame_shared(
+        e1,
+        Duration::from_millis(25),
+        &game
+    ));
+    assert_eq!(*game.log.borrow(), vec![(1, 1), (2, 2), (3, 3)]);
+    assert!(!process_entity_frame_shared(
+        e2,
+        Duration::from_millis(25),
+        &game
+    ));
+    assert_eq!(game.log.borrow().len(), 3);
+}
+
+#[test]
+fn shared_contexts_limit_frame_durations() {
+    let entity = EntityAllocator::default().alloc();
+    let game = Game {
+        max_frame_duration: Some(Duration::from_millis(15)),
+        ..Default::default()
+    };
+    game.components
+        .borrow_mut()
+        .counter
+        .insert(entity, Counter(0));
+    process_entity_frame_shared(entity, Duration::from_millis(100), &game);
+    assert_eq!(game.log.borrow().len(), 2);
+}
@@ -0,0 +1,63 @@
+use entity_table::EntityAllocator;
+use entity_table_realtime::state_machine::{
+    TimedStateEvent, TimedStateMachine, TimedStateTransition, UnknownTimedState,
+};
+use entity_table_realtime::ScheduledRealtimeComponent;
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Phase {
+    Charge,
+    Flash,
+    Fade,
+}
+
+fn transition(dwell_ms: u64, next: Option<Phase>) -> TimedStateTransition<Phase> {
+    TimedStateTransition {
+        dwell: Duration::from_millis(dwell_ms),
+        next,
+    }
+}
+
+#[test]
+fn transitions_to_unknown_states_are_rejected() {
+    let missing_next = TimedStateMachine::new(
+        Phase::Charge,
+        [(Phase::Charge, transition(10, Some(Phase::Flash)))],
+    );
+    assert_eq!(missing_next.unwrap_err(), UnknownTimedState(Phase::Flash));
+    let missing_initial =
+        TimedStateMachine::new(Phase::Fade, [(Phase::Charge, transition(10, None))]);
+    assert_eq!(missing_initial.unwrap_err(), UnknownTimedState(Phase::Fade));
+}
+
+#[test]
+fn machine_enters_each_state_then_finishes() {
+    let entity = EntityAllocator::default().alloc();
+    let machine = TimedStateMachine::new(
+        Phase::Charge,
+        [
+            (Phase::Charge, transition(10, Some(Phase::Flash))),
+            (Phase::Flash, transition(5, Some(Phase::Fade))),
+            (Phase::Fade, transition(20, None)),
+        ],
+    )
+    .unwrap();
+    let mut scheduled = ScheduledRealtimeComponent::new(machine, Duration::ZERO);
+    let events = (0..4)
+        .map(|_| {
+            let event = scheduled.tick(entity);
+            scheduled.advance_nanos(scheduled.until_next_tick_nanos());
+            event
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        events,
+        vec![
+            TimedStateEvent::Entered(Phase::Charge),
+            TimedStateEvent::Entered(Phase::Flash),
+            TimedStateEvent::Entered(Phase::Fade),
+            TimedStateEvent::Finished(Phase::Fade),
+        ]
+    );
+}
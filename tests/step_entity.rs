@@ -0,0 +1,194 @@
+use entity_table::{ComponentTable, Entities, Entity, EntityAllocator};
+use entity_table_realtime::{
+    step_entity, step_entity_inspect, testing, ContextContainsRealtimeComponents,
+    RealtimeComponent, RealtimeComponentApplyEvent, RealtimeEntityEvents,
+    ScheduledRealtimeComponent,
+};
+use std::time::Duration;
+
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone)]
+struct Periodic(u64);
+
+impl RealtimeComponent for Periodic {
+    type Event = u64;
+
+    fn tick(&mut self) -> (Self::Event, Duration) {
+        (self.0, Duration::from_millis(self.0))
+    }
+}
+
+impl RealtimeComponentApplyEvent<Context> for Periodic {
+    fn apply_event(period: u64, _entity: Entity, context: &mut Context) {
+        context.log.push(format!("tick {}", period));
+    }
+}
+
+entity_table_realtime::declare_realtime_entity_module! {
+    components[Context] {
+        fast: Periodic,
+        slow: Periodic,
+    }
+}
+
+struct Context {
+    components: components::RealtimeComponents,
+    entities: ComponentTable<()>,
+    log: Vec<String>,
+    dormant: bool,
+    time_scale: f64,
+}
+
+impl Context {
+    fn new(entity: Entity) -> Self {
+        let mut context = Self {
+            components: Default::default(),
+            entities: Default::default(),
+            log: Vec::new(),
+            dormant: false,
+            time_scale: 1.0,
+        };
+        context.entities.insert(entity, ());
+        context.components.fast.insert(entity, Periodic(10));
+        context.components.slow.insert_with_schedule(
+            entity,
+            ScheduledRealtimeComponent::new(Periodic(20), Duration::from_millis(10)),
+        );
+        context
+    }
+}
+
+impl ContextContainsRealtimeComponents for Context {
+    type Components = components::RealtimeComponents;
+    fn components_mut(&mut self) -> &mut Self::Components {
+        &mut self.components
+    }
+    fn realtime_entities(&self) -> Entities<'_> {
+        self.entities.entities()
+    }
+
+    fn realtime_dormant(&self, _entity: Entity) -> bool {
+        self.dormant
+    }
+
+    fn realtime_time_scale(&self, _entity: Entity) -> f64 {
+        self.time_scale
+    }
+
+    fn before_tick(&mut self, _entity: Entity) {
+        self.log.push("before_tick".to_string());
+    }
+
+    fn after_entity_frame(&mut self, _entity: Entity) {
+        self.log.push("after_entity_frame".to_string());
+    }
+}
+
+#[test]
+fn step_entity_reports_the_names_of_ticked_components() {
+    let entity = EntityAllocator::default().alloc();
+    let mut context = Context::new(entity);
+    let step = step_entity(entity, &mut context).unwrap();
+    assert_eq!(step.ticked_names().collect::<Vec<_>>(), vec!["fast"]);
+    let step = step_entity(entity, &mut context).unwrap();
+    assert_eq!(step.elapsed, Duration::from_millis(10));
+    assert_eq!(step.ticked.len(), 2);
+    assert_eq!(
+        step.ticked_names().collect::<Vec<_>>(),
+        vec!["fast", "slow"]
+    );
+}
+
+#[test]
+fn simulation_log_names_ticked_components() {
+    let entity = EntityAllocator::default().alloc();
+    let mut context = Context::new(entity);
+    let log = testing::simulate(
+        &mut context,
+        Duration::from_millis(15),
+        Duration::from_millis(15),
+    );
+    let components = log
+        .iter()
+        .map(|entry| (entry.time, entry.component))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        components,
+        vec![
+            (Duration::ZERO, "fast"),
+            (Duration::from_millis(10), "fast"),
+            (Duration::from_millis(10), "slow"),
+        ]
+    );
+}
+
+#[test]
+fn step_entity_calls_hooks_around_events() {
+    let entity = EntityAllocator::default().alloc();
+    let mut context = Context::new(entity);
+    step_entity(entity, &mut context).unwrap();
+    assert_eq!(
+        context.log,
+        vec!["before_tick", "tick 10", "after_entity_frame"]
+    );
+    context.log.clear();
+    step_entity(entity, &mut context).unwrap();
+    assert_eq!(
+        context.log,
+        vec!["before_tick", "tick 10", "tick 20", "after_entity_frame"]
+    );
+}
+
+#[test]
+fn step_entity_inspects_events_before_applying_them() {
+    let entity = EntityAllocator::default().alloc();
+    let mut context = Context::new(entity);
+    let mut inspected = None;
+    let step = step_entity_inspect(entity, &mut context, |events, context| {
+        inspected = Some((events.ticked(), context.log.clone()));
+    })
+    .unwrap();
+    assert_eq!(
+        inspected,
+        Some((step.ticked, vec!["before_tick".to_string()]))
+    );
+}
+
+#[test]
+fn dormant_entities_do_not_step() {
+    let entity = EntityAllocator::default().alloc();
+    let mut context = Context::new(entity);
+    context.dormant = true;
+    let step = step_entity(entity, &mut context).unwrap();
+    assert!(step.ticked.is_empty());
+    assert_eq!(step.elapsed, Duration::ZERO);
+    assert_eq!(context.log, vec!["after_entity_frame"]);
+    context.dormant = false;
+    assert_eq!(step_entity(entity, &mut context).unwrap().ticked.len(), 1);
+}
+
+#[test]
+fn steps_are_measured_in_the_time_of_the_context() {
+    let entity = EntityAllocator::default().alloc();
+    let mut context = Context::new(entity);
+    context.time_scale = 0.5;
+    step_entity(entity, &mut context).unwrap();
+    let step = step_entity(entity, &mut context).unwrap();
+    assert_eq!(step.elapsed, Duration::from_millis(10));
+    assert_eq!(step.frame_duration, Duration::from_millis(20));
+}
+
+#[test]
+fn event_caps_are_reset_after_each_step() {
+    let mut allocator = EntityAllocator::default();
+    let (e1, e2) = (allocator.alloc(), allocator.alloc());
+    let mut context = Context::new(e1);
+    context.components.fast.insert(e2, Periodic(10));
+    context.components.fast.set_event_cap(1, |_| ());
+    assert_eq!(step_entity(e1, &mut context).unwrap().ticked.len(), 1);
+    assert_eq!(step_entity(e2, &mut context).unwrap().ticked.len(), 1);
+    assert_eq!(
+        context.log.iter().filter(|line| *line == "tick 10").count(),
+        2
+    );
+}
@@ -0,0 +1,100 @@
+use entity_table::{ComponentTable, Entities, Entity, EntityAllocator};
+use entity_table_realtime::{
+    process_entity_frame_with_budget, ContextContainsRealtimeComponents, RealtimeComponent,
+    RealtimeComponentApplyEvent, RealtimeCoordinator,
+};
+use std::time::Duration;
+
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone)]
+struct Periodic;
+
+impl RealtimeComponent for Periodic {
+    type Event = ();
+
+    fn tick(&mut self) -> (Self::Event, Duration) {
+        ((), Duration::from_millis(10))
+    }
+}
+
+impl RealtimeComponentApplyEvent<Context> for Periodic {
+    fn apply_event(_event: (), _entity: Entity, context: &mut Context) {
+        context.num_events += 1;
+    }
+}
+
+entity_table_realtime::declare_realtime_entity_module! {
+    components[Context] {
+        a: Periodic,
+        b: Periodic,
+    }
+}
+
+struct Context {
+    components: components::RealtimeComponents,
+    entities: ComponentTable<()>,
+    num_events: usize,
+}
+
+impl Context {
+    fn new(entity: Entity) -> Self {
+        let mut context = Self {
+            components: Default::default(),
+            entities: Default::default(),
+            num_events: 0,
+        };
+        context.entities.insert(entity, ());
+        context.components.a.insert(entity, Periodic);
+        context.components.b.insert(entity, Periodic);
+        context
+    }
+}
+
+impl ContextContainsRealtimeComponents for Context {
+    type Components = components::RealtimeComponents;
+    fn components_mut(&mut self) -> &mut Self::Components {
+        &mut self.components
+    }
+    fn realtime_entities(&self) -> Entities<'_> {
+        self.entities.entities()
+    }
+}
+
+const FRAME: Duration = Duration::from_millis(105);
+
+#[test]
+fn budget_counts_ticked_components() {
+    let entity = EntityAllocator::default().alloc();
+    let mut context = Context::new(entity);
+    assert_eq!(
+        process_entity_frame_with_budget(entity, FRAME, &mut context, 4),
+        4
+    );
+    assert_eq!(context.num_events, 4);
+    let mut context = Context::new(entity);
+    // both components tick at 0ms, 10ms, ..., 100ms, and the step to the end of the frame
+    // doesn't count
+    assert_eq!(
+        process_entity_frame_with_budget(entity, FRAME, &mut context, 100),
+        22
+    );
+    assert_eq!(context.num_events, 22);
+}
+
+#[test]
+fn coordinator_budget_counts_ticked_components() {
+    let entity = EntityAllocator::default().alloc();
+    let mut context = Context::new(entity);
+    let mut coordinator = RealtimeCoordinator::<Context>::new();
+    coordinator.register(std::mem::take(&mut context.components));
+    assert_eq!(
+        coordinator.process_entity_frame_with_budget(entity, FRAME, &mut context, 4),
+        4
+    );
+    assert_eq!(context.num_events, 4);
+    assert_eq!(
+        coordinator.process_entity_frame_with_budget(entity, FRAME, &mut context, 100),
+        20
+    );
+    assert_eq!(context.num_events, 24);
+}
@@ -0,0 +1,75 @@
+use entity_table::{Entity, EntityAllocator};
+use entity_table_realtime::{
+    RealtimeComponent, RealtimeComponentTable, ScheduledRealtimeComponent, TickCtx,
+};
+use std::time::Duration;
+
+/// Reports the context it was ticked with
+#[derive(Debug, Clone)]
+struct Observer;
+
+impl RealtimeComponent for Observer {
+    type Event = (Entity, Duration, Duration);
+
+    fn tick(&mut self) -> (Self::Event, Duration) {
+        unreachable!("tables tick components with tick_with")
+    }
+
+    fn tick_with(&mut self, context: &TickCtx) -> (Self::Event, Duration) {
+        let event = (
+            context.entity,
+            context.elapsed_since_last_tick,
+            context.total_simulated_time,
+        );
+        (event, Duration::from_millis(10))
+    }
+}
+
+const fn ms(millis: u64) -> Duration {
+    Duration::from_millis(millis)
+}
+
+#[test]
+fn components_are_ticked_with_their_entity_and_elapsed_time() {
+    let mut allocator = EntityAllocator::default();
+    let (e1, e2) = (allocator.alloc(), allocator.alloc());
+    let mut table = RealtimeComponentTable::default();
+    table.insert(e1, Observer);
+    table.insert_with_schedule(e2, ScheduledRealtimeComponent::new(Observer, ms(5)));
+    let mut events = table
+        .fast_forward(ms(20))
+        .into_iter()
+        .map(|(_, _, event)| event)
+        .collect::<Vec<_>>();
+    events.sort_by_key(|&(entity, _, total)| (total, entity != e1));
+    assert_eq!(
+        events,
+        vec![
+            (e1, ms(0), ms(0)),
+            (e2, ms(5), ms(5)),
+            (e1, ms(10), ms(10)),
+            (e2, ms(10), ms(15)),
+            (e1, ms(10), ms(20)),
+        ]
+    );
+}
+
+#[test]
+fn elapsed_time_reflects_rescheduling_and_excludes_time_disabled() {
+    let entity = EntityAllocator::default().alloc();
+    let mut table = RealtimeComponentTable::default();
+    table.insert(entity, Observer);
+    table.fast_forward(ms(5));
+    // rescheduled to tick at 8ms rather than 10ms
+    table
+        .get_with_schedule_mut(entity)
+        .unwrap()
+        .set_until_next_tick(ms(3));
+    let events = table.fast_forward(ms(3));
+    assert_eq!(events[0].2, (entity, ms(8), ms(8)));
+    table.set_enabled(entity, false);
+    table.fast_forward(ms(100));
+    table.set_enabled(entity, true);
+    let events = table.fast_forward(ms(10));
+    assert_eq!(events[0].2, (entity, ms(10), ms(18)));
+}
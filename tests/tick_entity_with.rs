@@ -0,0 +1,149 @@
+use entity_table::{Entity, EntityAllocator};
+use entity_table_realtime::{
+    RealtimeComponent, RealtimeComponentApplyEvent, RealtimeComponents, RealtimeEntityEvents,
+    ScheduledRealtimeComponent,
+};
+use std::time::Duration;
+
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone)]
+struct Counter {
+    count: u32,
+    period: Duration,
+}
+
+impl Counter {
+    fn new(period_ms: u64) -> Self {
+        Self {
+            count: 0,
+            period: Duration::from_millis(period_ms),
+        }
+    }
+}
+
+impl RealtimeComponent for Counter {
+    type Event = u32;
+
+    fn tick(&mut self) -> (Self::Event, Duration) {
+        self.count += 1;
+        (self.count, self.period)
+    }
+}
+
+impl RealtimeComponentApplyEvent<()> for Counter {
+    fn apply_event(_event: u32, _entity: Entity, _context: &mut ()) {}
+}
+
+entity_table_realtime::declare_realtime_entity_module! {
+    components[()] {
+        fast: Counter,
+        slow: Counter,
+        paused: Counter,
+    }
+}
+
+type Log = Vec<(Duration, &'static str, u32)>;
+
+fn log_event(log: &mut Log, time: Duration, event: components::RealtimeEntityEvent) {
+    log.push(match event {
+        components::RealtimeEntityEvent::fast(count) => (time, "fast", count),
+        components::RealtimeEntityEvent::slow(count) => (time, "slow", count),
+        components::RealtimeEntityEvent::paused(count) => (time, "paused", count),
+    });
+}
+
+fn components(entity: Entity) -> components::RealtimeComponents {
+    let mut components = components::RealtimeComponents::default();
+    components.fast.insert(entity, Counter::new(10));
+    components.slow.insert_with_schedule(
+        entity,
+        ScheduledRealtimeComponent::new(Counter::new(25), Duration::from_millis(5)),
+    );
+    components.paused.insert(entity, Counter::new(1));
+    components
+        .paused
+        .get_with_schedule_mut(entity)
+        .unwrap()
+        .set_enabled(false);
+    components
+}
+
+const FRAME: Duration = Duration::from_millis(50);
+
+/// Run a frame through `tick_entity`, logging each event with the time at which it occurred
+fn collected_log(entity: Entity, components: &mut components::RealtimeComponents) -> Log {
+    let mut log = Vec::new();
+    let mut elapsed = Duration::ZERO;
+    while elapsed < FRAME {
+        let (events, until_next_tick) = components.tick_entity(entity, FRAME - elapsed);
+        elapsed += until_next_tick;
+        RealtimeEntityEvents::<()>::for_each_event(events, |event| {
+            log_event(&mut log, elapsed, event)
+        });
+    }
+    log
+}
+
+/// Run a frame through `RealtimeComponents::tick_entity_with`, as a frame processor generic
+/// over the components would
+fn visited_log<R: RealtimeComponents<(), EntityEvents = components::RealtimeEntityEvents>>(
+    entity: Entity,
+    components: &mut R,
+) -> Log {
+    let mut log = Vec::new();
+    let mut elapsed = Duration::ZERO;
+    while elapsed < FRAME {
+        let mut events = Vec::new();
+        let push = |event| events.push(event);
+        elapsed += components.tick_entity_with(entity, FRAME - elapsed, push);
+        for event in events {
+            log_event(&mut log, elapsed, event);
+        }
+    }
+    log
+}
+
+#[test]
+fn visited_events_match_collected_events() {
+    let entity = EntityAllocator::default().alloc();
+    let mut collected = components(entity);
+    let mut visited = components(entity);
+    let log = collected_log(entity, &mut collected);
+    assert_eq!(log, visited_log(entity, &mut visited));
+    assert_eq!(
+        log,
+        vec![
+            (Duration::ZERO, "fast", 1),
+            (Duration::from_millis(5), "slow", 1),
+            (Duration::from_millis(10), "fast", 2),
+            (Duration::from_millis(20), "fast", 3),
+            (Duration::from_millis(30), "fast", 4),
+            (Duration::from_millis(30), "slow", 2),
+            (Duration::from_millis(40), "fast", 5),
+            (Duration::from_millis(50), "fast", 6),
+        ]
+    );
+    for (a, b) in [
+        (&collected.fast, &visited.fast),
+        (&collected.slow, &visited.slow),
+        (&collected.paused, &visited.paused),
+    ] {
+        let (a, b) = (
+            a.get_with_schedule(entity).unwrap(),
+            b.get_with_schedule(entity).unwrap(),
+        );
+        assert_eq!(a.until_next_tick(), b.until_next_tick());
+        assert_eq!(a.component.count, b.component.count);
+    }
+}
+
+#[test]
+fn entities_without_components_step_to_the_end_of_the_frame() {
+    let mut allocator = EntityAllocator::default();
+    let (entity, other) = (allocator.alloc(), allocator.alloc());
+    let mut components = components(entity);
+    let mut num_events = 0;
+    let until_next_tick = components.tick_entity_with(other, FRAME, |_| num_events += 1);
+    assert_eq!(until_next_tick, FRAME);
+    assert_eq!(num_events, 0);
+}
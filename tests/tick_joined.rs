@@ -0,0 +1,107 @@
+use entity_table::{ComponentTable, Entities, Entity, EntityAllocator};
+use entity_table_realtime::{
+    AnimationContext, ContextContainsRealtimeComponents, RealtimeComponent,
+    RealtimeComponentApplyEvent,
+};
+use std::time::Duration;
+
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone)]
+struct Periodic;
+
+impl RealtimeComponent for Periodic {
+    type Event = ();
+
+    fn tick(&mut self) -> (Self::Event, Duration) {
+        ((), Duration::from_millis(10))
+    }
+}
+
+impl<'a> RealtimeComponentApplyEvent<Context<'a>> for Periodic {
+    fn apply_event(_event: (), entity: Entity, context: &mut Context<'a>) {
+        context.applied.push(entity);
+    }
+}
+
+entity_table_realtime::declare_realtime_entity_module! {
+    components<'a>[Context<'a>] {
+        periodic: Periodic,
+    }
+}
+
+struct Context<'a> {
+    components: &'a mut components::RealtimeComponents,
+    entities: &'a ComponentTable<()>,
+    visible: &'a ComponentTable<()>,
+    applied: &'a mut Vec<Entity>,
+}
+
+impl<'a> ContextContainsRealtimeComponents for Context<'a> {
+    type Components = components::RealtimeComponents;
+    fn components_mut(&mut self) -> &mut Self::Components {
+        self.components
+    }
+    fn realtime_entities(&self) -> Entities<'_> {
+        self.entities.entities()
+    }
+}
+
+#[test]
+fn tick_joined_only_processes_entities_in_the_table() {
+    let mut allocator = EntityAllocator::default();
+    let (e1, e2) = (allocator.alloc(), allocator.alloc());
+    let mut components = components::RealtimeComponents::default();
+    let mut entities = ComponentTable::default();
+    let mut visible = ComponentTable::default();
+    for entity in [e1, e2] {
+        entities.insert(entity, ());
+        components.periodic.insert(entity, Periodic);
+    }
+    visible.insert(e2, ());
+    let mut applied = Vec::new();
+    let mut animation_context = AnimationContext::new();
+    animation_context.tick_joined(
+        Context {
+            components: &mut components,
+            entities: &entities,
+            visible: &visible,
+            applied: &mut applied,
+        },
+        Duration::from_millis(25),
+        |context| context.visible,
+    );
+    // ticks at 0ms, 10ms and 20ms
+    assert_eq!(applied, vec![e2, e2, e2]);
+    // the entity which wasn't processed is still due immediately
+    assert_eq!(components.periodic.time_to_due(e1), Some(Duration::ZERO));
+    assert_eq!(
+        components.periodic.time_to_due(e2),
+        Some(Duration::from_millis(5))
+    );
+}
+
+#[test]
+fn tick_joined_ignores_entities_without_realtime_components() {
+    let mut allocator = EntityAllocator::default();
+    let (e1, e2) = (allocator.alloc(), allocator.alloc());
+    let mut components = components::RealtimeComponents::default();
+    let mut entities = ComponentTable::default();
+    let mut visible = ComponentTable::default();
+    entities.insert(e1, ());
+    components.periodic.insert(e1, Periodic);
+    visible.insert(e1, ());
+    // visible but not animated
+    visible.insert(e2, ());
+    let mut applied = Vec::new();
+    AnimationContext::new().tick_joined(
+        Context {
+            components: &mut components,
+            entities: &entities,
+            visible: &visible,
+            applied: &mut applied,
+        },
+        Duration::from_millis(5),
+        |context| context.visible,
+    );
+    assert_eq!(applied, vec![e1]);
+}
@@ -0,0 +1,160 @@
+#![cfg(feature = "rand")]
+
+use entity_table::{ComponentTable, Entities, Entity, EntityAllocator};
+use entity_table_realtime::rand_core::{self, impls, RngCore};
+use entity_table_realtime::{
+    process_entity_frame, process_entity_frame_with_rng, ContextContainsRealtimeComponents,
+    RealtimeComponent, RealtimeComponentApplyEvent, TickCtx,
+};
+use std::time::Duration;
+
+/// Small deterministic generator for tests
+struct Xorshift(u64);
+
+impl RngCore for Xorshift {
+    fn next_u32(&mut self) -> u32 {
+        self.next_u64() as u32
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        impls::fill_bytes_via_next(self, dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand_core::Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+/// Ticks after a random delay of 1 to 10 milliseconds, or every 10 milliseconds if no generator
+/// is provided
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone)]
+struct Wander;
+
+impl RealtimeComponent for Wander {
+    type Event = u64;
+
+    fn tick(&mut self) -> (Self::Event, Duration) {
+        (0, Duration::from_millis(10))
+    }
+
+    fn tick_with_rng(
+        &mut self,
+        _context: &TickCtx,
+        rng: &mut dyn RngCore,
+    ) -> (Self::Event, Duration) {
+        let millis = 1 + rng.next_u64() % 10;
+        (millis, Duration::from_millis(millis))
+    }
+}
+
+/// Ignores the generator
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone)]
+struct Steady;
+
+impl RealtimeComponent for Steady {
+    type Event = u64;
+
+    fn tick(&mut self) -> (Self::Event, Duration) {
+        (10, Duration::from_millis(10))
+    }
+}
+
+impl RealtimeComponentApplyEvent<Context> for Wander {
+    fn apply_event(millis: u64, _entity: Entity, context: &mut Context) {
+        context.wander.push(millis);
+    }
+}
+
+impl RealtimeComponentApplyEvent<Context> for Steady {
+    fn apply_event(millis: u64, _entity: Entity, context: &mut Context) {
+        context.steady.push(millis);
+    }
+}
+
+entity_table_realtime::declare_realtime_entity_module! {
+    components[Context] {
+        wander: Wander,
+        steady: Steady,
+    }
+}
+
+#[derive(Default)]
+struct Context {
+    components: components::RealtimeComponents,
+    entities: ComponentTable<()>,
+    wander: Vec<u64>,
+    steady: Vec<u64>,
+}
+
+impl Context {
+    fn new(entity: Entity) -> Self {
+        let mut context = Self::default();
+        context.entities.insert(entity, ());
+        context.components.wander.insert(entity, Wander);
+        context.components.steady.insert(entity, Steady);
+        context
+    }
+}
+
+impl ContextContainsRealtimeComponents for Context {
+    type Components = components::RealtimeComponents;
+    fn components_mut(&mut self) -> &mut Self::Components {
+        &mut self.components
+    }
+    fn realtime_entities(&self) -> Entities<'_> {
+        self.entities.entities()
+    }
+}
+
+const FRAME: Duration = Duration::from_millis(100);
+
+fn run(entity: Entity, seed: u64) -> Context {
+    let mut context = Context::new(entity);
+    let mut rng = Xorshift(seed);
+    process_entity_frame_with_rng(entity, FRAME, &mut context, &mut rng);
+    context
+}
+
+#[test]
+fn components_draw_from_the_provided_generator() {
+    let entity = EntityAllocator::default().alloc();
+    let context = run(entity, 1);
+    assert!(context.wander.len() > 10);
+    assert!(context
+        .wander
+        .iter()
+        .all(|millis| (1..=10).contains(millis)));
+    assert!(context.wander.iter().any(|&millis| millis != 10));
+    // the delays before each tick fill the frame
+    let total = context.wander[..context.wander.len() - 1]
+        .iter()
+        .sum::<u64>();
+    assert!(total <= 100 && total + context.wander.last().unwrap() > 100);
+    // components which don't override tick_with_rng are unaffected
+    assert_eq!(context.steady, vec![10; 11]);
+}
+
+#[test]
+fn the_same_seed_produces_the_same_events() {
+    let entity = EntityAllocator::default().alloc();
+    assert_eq!(run(entity, 1).wander, run(entity, 1).wander);
+    assert_ne!(run(entity, 1).wander, run(entity, 2).wander);
+}
+
+#[test]
+fn frames_without_a_generator_call_tick() {
+    let entity = EntityAllocator::default().alloc();
+    let mut context = Context::new(entity);
+    process_entity_frame(entity, FRAME, &mut context);
+    assert_eq!(context.wander, vec![0; 11]);
+}
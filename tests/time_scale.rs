@@ -0,0 +1,109 @@
+use entity_table::{ComponentTable, Entities, Entity, EntityAllocator};
+use entity_table_realtime::{
+    process_entity_frame, process_entity_frame_with_catch_up, ContextContainsRealtimeComponents,
+    RealtimeComponent, RealtimeComponentApplyEvent,
+};
+use std::time::Duration;
+
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone)]
+struct Periodic;
+
+impl RealtimeComponent for Periodic {
+    type Event = ();
+
+    fn tick(&mut self) -> (Self::Event, Duration) {
+        ((), Duration::from_millis(10))
+    }
+}
+
+impl RealtimeComponentApplyEvent<Context> for Periodic {
+    fn apply_event(_event: (), _entity: Entity, context: &mut Context) {
+        context.num_events += 1;
+    }
+}
+
+entity_table_realtime::declare_realtime_entity_module! {
+    components[Context] {
+        periodic: Periodic,
+    }
+}
+
+struct Context {
+    components: components::RealtimeComponents,
+    entities: ComponentTable<()>,
+    time_scale: f64,
+    num_events: u32,
+    num_frames: u32,
+}
+
+impl Context {
+    fn new(entity: Entity, time_scale: f64) -> Self {
+        let mut context = Self {
+            components: Default::default(),
+            entities: Default::default(),
+            time_scale,
+            num_events: 0,
+            num_frames: 0,
+        };
+        context.entities.insert(entity, ());
+        context.components.periodic.insert(entity, Periodic);
+        context
+    }
+}
+
+impl ContextContainsRealtimeComponents for Context {
+    type Components = components::RealtimeComponents;
+    fn components_mut(&mut self) -> &mut Self::Components {
+        &mut self.components
+    }
+    fn realtime_entities(&self) -> Entities<'_> {
+        self.entities.entities()
+    }
+
+    fn realtime_time_scale(&self, _entity: Entity) -> f64 {
+        self.time_scale
+    }
+
+    fn after_entity_frame(&mut self, _entity: Entity) {
+        self.num_frames += 1;
+    }
+}
+
+#[test]
+fn time_scale_slows_entity_frames() {
+    let entity = EntityAllocator::default().alloc();
+    let mut context = Context::new(entity, 0.5);
+    process_entity_frame(entity, Duration::from_millis(100), &mut context);
+    // ticks at 0ms, 10ms, ..., 50ms of scaled time
+    assert_eq!(context.num_events, 6);
+}
+
+#[test]
+fn catch_up_applies_time_scale_once() {
+    let entity = EntityAllocator::default().alloc();
+    let mut context = Context::new(entity, 0.5);
+    process_entity_frame_with_catch_up(
+        entity,
+        Duration::from_millis(100),
+        Duration::from_secs(1),
+        &mut context,
+    );
+    assert_eq!(context.num_events, 6);
+    assert_eq!(context.num_frames, 1);
+}
+
+#[test]
+fn catch_up_applies_time_scale_once_when_splitting_the_frame() {
+    let entity = EntityAllocator::default().alloc();
+    let mut context = Context::new(entity, 0.5);
+    process_entity_frame_with_catch_up(
+        entity,
+        Duration::from_millis(100),
+        Duration::from_millis(20),
+        &mut context,
+    );
+    // the periodic component bursts through the excess 30ms, then simulates the remaining 20ms
+    assert_eq!(context.num_events, 6);
+    assert_eq!(context.num_frames, 1);
+}
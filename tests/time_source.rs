@@ -0,0 +1,68 @@
+use entity_table::{Entity, EntityAllocator};
+use entity_table_realtime::{
+    ManualTimeSource, RealtimeClock, RealtimeComponent, RealtimeComponentApplyEvent,
+    SystemTimeSource, TimeSource,
+};
+use std::time::Duration;
+
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone)]
+struct Periodic;
+
+impl RealtimeComponent for Periodic {
+    type Event = ();
+
+    fn tick(&mut self) -> (Self::Event, Duration) {
+        ((), Duration::from_millis(10))
+    }
+}
+
+impl RealtimeComponentApplyEvent<()> for Periodic {
+    fn apply_event(_event: (), _entity: Entity, _context: &mut ()) {}
+}
+
+entity_table_realtime::declare_realtime_entity_module! {
+    components[()] {
+        periodic: Periodic,
+    }
+}
+
+#[test]
+fn clock_measures_frames_of_a_manual_time_source() {
+    let mut time_source = ManualTimeSource::new();
+    time_source.advance(Duration::from_secs(5));
+    let mut clock = RealtimeClock::new(time_source);
+    assert_eq!(clock.frame_duration(), Duration::ZERO);
+    clock.time_source_mut().advance(Duration::from_millis(16));
+    assert_eq!(clock.frame_duration(), Duration::from_millis(16));
+    clock.time_source_mut().advance(Duration::from_millis(7));
+    clock.time_source_mut().advance(Duration::from_millis(10));
+    assert_eq!(clock.frame_duration(), Duration::from_millis(17));
+    assert_eq!(clock.frame_duration(), Duration::ZERO);
+    assert_eq!(clock.time_source().now(), Duration::from_millis(5033));
+}
+
+#[test]
+fn manual_time_source_drives_frames_deterministically() {
+    let entity = EntityAllocator::default().alloc();
+    let mut clock = RealtimeClock::new(ManualTimeSource::new());
+    let mut components = components::RealtimeComponents::default();
+    components.periodic.insert(entity, Periodic);
+    let mut num_events = 0;
+    for frame_ms in [16, 17, 16, 17, 16, 17] {
+        clock
+            .time_source_mut()
+            .advance(Duration::from_millis(frame_ms));
+        num_events += components.fast_forward(clock.frame_duration()).len();
+    }
+    // ticks at 0ms, 10ms, ..., 90ms of the 99ms run
+    assert_eq!(num_events, 10);
+}
+
+#[test]
+fn system_time_source_never_goes_backwards() {
+    let mut clock = RealtimeClock::<SystemTimeSource>::default();
+    let before = clock.time_source().now();
+    clock.frame_duration();
+    assert!(clock.time_source().now() >= before);
+}
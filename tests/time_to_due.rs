@@ -0,0 +1,75 @@
+use entity_table::{Entity, EntityAllocator};
+use entity_table_realtime::{
+    RealtimeComponent, RealtimeComponentApplyEvent, RealtimeComponentTable,
+    ScheduledRealtimeComponent,
+};
+use std::time::Duration;
+
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone)]
+struct Periodic(u64);
+
+impl RealtimeComponent for Periodic {
+    type Event = ();
+
+    fn tick(&mut self) -> (Self::Event, Duration) {
+        ((), ms(self.0))
+    }
+}
+
+impl RealtimeComponentApplyEvent<()> for Periodic {
+    fn apply_event(_event: (), _entity: Entity, _context: &mut ()) {}
+}
+
+entity_table_realtime::declare_realtime_entity_module! {
+    components[()] {
+        flicker: Periodic,
+        poison: Periodic,
+    }
+}
+
+const fn ms(millis: u64) -> Duration {
+    Duration::from_millis(millis)
+}
+
+fn insert_with_delay(
+    table: &mut RealtimeComponentTable<Periodic>,
+    entity: Entity,
+    delay: Duration,
+) {
+    table.insert_with_schedule(entity, ScheduledRealtimeComponent::new(Periodic(10), delay));
+}
+
+#[test]
+fn tables_report_when_components_are_due() {
+    let mut allocator = EntityAllocator::default();
+    let (e1, e2) = (allocator.alloc(), allocator.alloc());
+    let mut table = RealtimeComponentTable::default();
+    insert_with_delay(&mut table, e1, ms(15));
+    assert_eq!(table.time_to_due(e1), Some(ms(15)));
+    assert_eq!(table.time_to_due(e2), None);
+    assert!(!table.is_due(e1, ms(14)));
+    // a tick at exactly the end of the frame is part of the frame
+    assert!(table.is_due(e1, ms(15)));
+    assert!(!table.is_due(e2, ms(100)));
+    table.set_enabled(e1, false);
+    assert_eq!(table.time_to_due(e1), None);
+    assert!(!table.is_due(e1, ms(100)));
+}
+
+#[test]
+fn modules_report_the_soonest_component() {
+    let mut allocator = EntityAllocator::default();
+    let (e1, e2) = (allocator.alloc(), allocator.alloc());
+    let mut components = components::RealtimeComponents::default();
+    insert_with_delay(&mut components.flicker, e1, ms(20));
+    insert_with_delay(&mut components.poison, e1, ms(5));
+    assert_eq!(components.time_to_due(e1), Some(ms(5)));
+    assert!(components.is_due(e1, ms(5)));
+    assert!(!components.is_due(e1, ms(4)));
+    assert_eq!(components.time_to_due(e2), None);
+    assert!(!components.is_due(e2, ms(100)));
+    components.poison.set_enabled(e1, false);
+    assert_eq!(components.time_to_due(e1), Some(ms(20)));
+    assert!(!components.is_due(e1, ms(5)));
+}
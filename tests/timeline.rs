@@ -0,0 +1,41 @@
+use entity_table_realtime::testing::{assert_timeline, run_component};
+use entity_table_realtime::RealtimeComponent;
+use std::time::Duration;
+
+#[derive(Debug, Clone)]
+struct Counter(u32);
+
+impl RealtimeComponent for Counter {
+    type Event = u32;
+
+    fn tick(&mut self) -> (Self::Event, Duration) {
+        self.0 += 1;
+        (self.0, Duration::from_millis(10))
+    }
+}
+
+const fn ms(millis: u64) -> Duration {
+    Duration::from_millis(millis)
+}
+
+#[test]
+fn timelines_start_after_the_initial_delay_and_include_the_end() {
+    let timeline = run_component(Counter(0), ms(5), ms(25));
+    assert_timeline(&timeline, &[(ms(5), 1), (ms(15), 2), (ms(25), 3)]);
+    let timeline = run_component(Counter(0), ms(0), ms(20));
+    assert_timeline(&timeline, &[(ms(0), 1), (ms(10), 2), (ms(20), 3)]);
+    assert!(run_component(Counter(0), ms(30), ms(25)).is_empty());
+}
+
+#[test]
+#[should_panic(expected = "timeline doesn't match expectation:
+  0ns: 1
+- 10ms: 7
++ 10ms: 2
+  20ms: 3
++ 30ms: 4
+")]
+fn mismatched_timelines_panic_with_a_diff() {
+    let timeline = run_component(Counter(0), ms(0), ms(30));
+    assert_timeline(&timeline, &[(ms(0), 1), (ms(10), 7), (ms(20), 3)]);
+}
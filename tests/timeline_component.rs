@@ -0,0 +1,155 @@
+use entity_table::EntityAllocator;
+use entity_table_realtime::testing::{assert_timeline, run_component};
+use entity_table_realtime::timeline::{Timeline, TimelineBuilder, TimelineEvent, TimelineLoop};
+use entity_table_realtime::RealtimeComponentTable;
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Shake {
+    Start,
+    Peak,
+    Flash,
+    Settle,
+}
+
+use Shake::*;
+use TimelineEvent::{Finished, Keyframe};
+
+const fn ms(millis: u64) -> Duration {
+    Duration::from_millis(millis)
+}
+
+fn shake() -> Timeline<Shake> {
+    // keyframes needn't be given in order
+    Timeline::new(vec![
+        (ms(30), Settle),
+        (ms(5), Start),
+        (ms(10), Peak),
+        (ms(10), Flash),
+    ])
+}
+
+/// Run the timeline from when it's inserted
+fn run(timeline: Timeline<Shake>, duration: Duration) -> Vec<(Duration, TimelineEvent<Shake>)> {
+    let entity = EntityAllocator::default().alloc();
+    let mut table = RealtimeComponentTable::default();
+    table.insert_with_schedule(entity, timeline.scheduled());
+    table
+        .fast_forward(duration)
+        .into_iter()
+        .map(|(time, _, event)| (time, event))
+        .collect()
+}
+
+#[test]
+fn keyframes_are_emitted_at_their_offsets_then_the_timeline_finishes() {
+    let timeline = shake();
+    assert_eq!(timeline.length(), ms(30));
+    assert_eq!(timeline.keyframes()[0], (ms(5), Start));
+    assert_timeline(
+        &run(timeline, ms(60)),
+        &[
+            (ms(5), Keyframe(Start)),
+            (ms(10), Keyframe(Peak)),
+            (ms(10), Keyframe(Flash)),
+            (ms(30), Keyframe(Settle)),
+            (ms(30), Finished),
+            // repeated until the component is removed
+            (ms(60), Finished),
+        ],
+    );
+}
+
+#[test]
+fn the_length_can_extend_past_the_last_keyframe() {
+    let timeline = shake().with_length(ms(50));
+    assert_eq!(timeline.length(), ms(50));
+    assert_eq!(shake().with_length(ms(20)).length(), ms(30));
+    let events = run(timeline, ms(50));
+    assert_eq!(events.last(), Some(&(ms(50), Finished)));
+    assert_eq!(events.len(), 5);
+}
+
+#[test]
+fn looping_timelines_restart_after_their_length() {
+    let timeline = shake()
+        .with_length(ms(40))
+        .with_loop(TimelineLoop::Times(2));
+    assert_timeline(
+        &run(timeline, ms(80)),
+        &[
+            (ms(5), Keyframe(Start)),
+            (ms(10), Keyframe(Peak)),
+            (ms(10), Keyframe(Flash)),
+            (ms(30), Keyframe(Settle)),
+            (ms(45), Keyframe(Start)),
+            (ms(50), Keyframe(Peak)),
+            (ms(50), Keyframe(Flash)),
+            (ms(70), Keyframe(Settle)),
+            (ms(80), Finished),
+        ],
+    );
+    let timeline = shake().with_loop(TimelineLoop::Forever);
+    let events = run(timeline, Duration::from_secs(3));
+    assert_eq!(events.len(), 400);
+    assert!(events.iter().all(|(_, event)| *event != Finished));
+}
+
+#[test]
+fn empty_and_zero_length_timelines_play_once() {
+    let empty = Timeline::<Shake>::new(Vec::new()).with_loop(TimelineLoop::Forever);
+    assert_timeline(&run(empty, ms(0)), &[(ms(0), Finished)]);
+    let instant = Timeline::new(vec![(ms(0), Flash)]).with_loop(TimelineLoop::Forever);
+    assert_timeline(
+        &run_component(instant, ms(0), Duration::from_nanos(2)),
+        &[
+            (ms(0), Keyframe(Flash)),
+            (ms(0), Finished),
+            (Duration::from_nanos(1), Finished),
+            (Duration::from_nanos(2), Finished),
+        ],
+    );
+}
+
+#[test]
+fn builders_add_single_and_repeating_keyframes() {
+    let timeline = TimelineBuilder::new()
+        .at(ms(30), Settle)
+        .every(ms(5), ms(10), ms(25), Peak)
+        .build();
+    // the end of a repeating run is excluded
+    assert_eq!(
+        timeline.keyframes(),
+        vec![
+            (ms(5), Peak),
+            (ms(15), Peak),
+            (ms(25), Peak),
+            (ms(30), Settle)
+        ]
+    );
+}
+
+#[test]
+fn the_timeline_macro_matches_the_builder() {
+    let timeline = entity_table_realtime::timeline! {
+        at 0ms => Start,
+        at 150ms => Flash,
+        every 500us for 2ms => Peak,
+        every 1s for 2s from 1s => Settle,
+    };
+    let expected = TimelineBuilder::new()
+        .at(ms(0), Start)
+        .at(ms(150), Flash)
+        .every(ms(0), Duration::from_micros(500), ms(2), Peak)
+        .every(ms(1000), ms(1000), ms(2000), Settle)
+        .build();
+    assert_eq!(timeline, expected);
+    assert_eq!(timeline.keyframes().len(), 8);
+    assert_eq!(timeline.length(), ms(2000));
+}
+
+#[test]
+#[should_panic(expected = "period must be non-zero")]
+fn repeating_keyframes_need_a_period() {
+    TimelineBuilder::new().every(ms(0), ms(0), ms(10), Peak);
+}
@@ -0,0 +1,64 @@
+use entity_table::{ComponentTable, Entities, Entity, EntityAllocator};
+use entity_table_realtime::budget::WallClockDeadline;
+use entity_table_realtime::{
+    process_entity_frame_with_policy, ContextContainsRealtimeComponents, ManualTimeSource,
+    RealtimeComponent, RealtimeComponentApplyEvent,
+};
+use std::time::Duration;
+
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone)]
+struct Periodic;
+
+impl RealtimeComponent for Periodic {
+    type Event = ();
+
+    fn tick(&mut self) -> (Self::Event, Duration) {
+        ((), Duration::from_millis(10))
+    }
+}
+
+impl RealtimeComponentApplyEvent<Context> for Periodic {
+    fn apply_event(_event: (), _entity: Entity, context: &mut Context) {
+        context.num_events += 1;
+    }
+}
+
+entity_table_realtime::declare_realtime_entity_module! {
+    components[Context] {
+        periodic: Periodic,
+    }
+}
+
+#[derive(Default)]
+struct Context {
+    components: components::RealtimeComponents,
+    entities: ComponentTable<()>,
+    num_events: u32,
+}
+
+impl ContextContainsRealtimeComponents for Context {
+    type Components = components::RealtimeComponents;
+    fn components_mut(&mut self) -> &mut Self::Components {
+        &mut self.components
+    }
+    fn realtime_entities(&self) -> Entities<'_> {
+        self.entities.entities()
+    }
+}
+
+#[test]
+fn wall_clock_deadline_reads_its_time_source() {
+    let entity = EntityAllocator::default().alloc();
+    let mut context = Context::default();
+    context.entities.insert(entity, ());
+    context.components.periodic.insert(entity, Periodic);
+    let mut deadline = WallClockDeadline::after(ManualTimeSource::new(), Duration::from_millis(5));
+    assert_eq!(deadline.deadline(), Duration::from_millis(5));
+    let frame_duration = Duration::from_millis(100);
+    process_entity_frame_with_policy(entity, frame_duration, &mut context, &mut deadline);
+    assert_eq!(context.num_events, 11);
+    deadline.time_source_mut().advance(Duration::from_millis(5));
+    process_entity_frame_with_policy(entity, frame_duration, &mut context, &mut deadline);
+    assert_eq!(context.num_events, 11);
+}
@@ -0,0 +1,125 @@
+#![cfg(feature = "watchdog")]
+
+use entity_table::{ComponentTable, Entities, Entity, EntityAllocator};
+use entity_table_realtime::watchdog::{self, SlowTick, TickPhase};
+use entity_table_realtime::{
+    process_entity_frame, ContextContainsRealtimeComponents, RealtimeComponent,
+    RealtimeComponentApplyEvent,
+};
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::Duration;
+
+const SLOW: Duration = Duration::from_millis(20);
+const THRESHOLD: Duration = Duration::from_millis(5);
+
+/// Sleeps while ticking, or while its event is applied
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone)]
+struct Sleepy {
+    slow_tick: bool,
+}
+
+impl RealtimeComponent for Sleepy {
+    type Event = bool;
+
+    fn tick(&mut self) -> (Self::Event, Duration) {
+        if self.slow_tick {
+            std::thread::sleep(SLOW);
+        }
+        (!self.slow_tick, Duration::from_millis(10))
+    }
+}
+
+impl RealtimeComponentApplyEvent<Context> for Sleepy {
+    fn apply_event(slow_apply: bool, _entity: Entity, _context: &mut Context) {
+        if slow_apply {
+            std::thread::sleep(SLOW);
+        }
+    }
+}
+
+entity_table_realtime::declare_realtime_entity_module! {
+    components[Context] {
+        sleepy: Sleepy,
+    }
+}
+
+#[derive(Default)]
+struct Context {
+    components: components::RealtimeComponents,
+    entities: ComponentTable<()>,
+}
+
+impl Context {
+    fn new(entity: Entity, slow_tick: bool) -> Self {
+        let mut context = Self::default();
+        context.entities.insert(entity, ());
+        context
+            .components
+            .sleepy
+            .insert(entity, Sleepy { slow_tick });
+        context
+    }
+}
+
+impl ContextContainsRealtimeComponents for Context {
+    type Components = components::RealtimeComponents;
+    fn components_mut(&mut self) -> &mut Self::Components {
+        &mut self.components
+    }
+    fn realtime_entities(&self) -> Entities<'_> {
+        self.entities.entities()
+    }
+}
+
+fn install_recorder() -> Rc<RefCell<Vec<SlowTick>>> {
+    let slow_ticks = Rc::new(RefCell::new(Vec::new()));
+    let recorder = Rc::clone(&slow_ticks);
+    watchdog::install(THRESHOLD, move |slow_tick| {
+        recorder.borrow_mut().push(*slow_tick)
+    });
+    slow_ticks
+}
+
+#[test]
+fn slow_ticks_are_reported_with_their_entity() {
+    let entity = EntityAllocator::default().alloc();
+    let mut context = Context::new(entity, true);
+    let slow_ticks = install_recorder();
+    // ticks at 0ms and 10ms
+    process_entity_frame(entity, Duration::from_millis(15), &mut context);
+    watchdog::uninstall();
+    let slow_ticks = slow_ticks.borrow();
+    assert_eq!(slow_ticks.len(), 2);
+    for slow_tick in slow_ticks.iter() {
+        assert_eq!(slow_tick.phase, TickPhase::Tick);
+        assert_eq!(slow_tick.entity, Some(entity));
+        assert!(slow_tick.component.ends_with("Sleepy"));
+        assert!(slow_tick.duration >= SLOW);
+    }
+}
+
+#[test]
+fn slow_event_applications_are_reported() {
+    let entity = EntityAllocator::default().alloc();
+    let mut context = Context::new(entity, false);
+    let slow_ticks = install_recorder();
+    process_entity_frame(entity, Duration::from_millis(5), &mut context);
+    watchdog::uninstall();
+    let slow_ticks = slow_ticks.borrow();
+    assert_eq!(slow_ticks.len(), 1);
+    assert_eq!(slow_ticks[0].phase, TickPhase::ApplyEvent);
+    assert_eq!(slow_ticks[0].entity, Some(entity));
+    assert!(slow_ticks[0].component.ends_with("Sleepy"));
+}
+
+#[test]
+fn nothing_is_reported_once_uninstalled() {
+    let entity = EntityAllocator::default().alloc();
+    let mut context = Context::new(entity, true);
+    let slow_ticks = install_recorder();
+    watchdog::uninstall();
+    process_entity_frame(entity, Duration::from_millis(5), &mut context);
+    assert!(slow_ticks.borrow().is_empty());
+}